@@ -27,6 +27,8 @@ const NODE_TYPE_COLORS: Record<NodeType, string> = {
     external_service: "#fb923c",
     decision: "#e879f9",
     process: "#94a3b8",
+    entity: "#2dd4bf",
+    attribute: "#cbd5e1",
     unknown: "#6b7280",
 };
 
@@ -39,6 +41,8 @@ const NODE_TYPE_ICONS: Record<NodeType, string> = {
     external_service: "☁️",
     decision: "◆",
     process: "⚙️",
+    entity: "▦",
+    attribute: "•",
     unknown: "❓",
 };
 
@@ -427,7 +431,7 @@ const EdgesTab: React.FC<{ result: AnalysisResult }> = ({ result }) => {
                                 marginTop: 2,
                             }}
                         >
-                            {edge.relationship_type} {edge.label ? `• "${edge.label}"` : ""}
+                            {edge.relationship_type} • {edge.relation_kind.replace("_", " ")} {edge.label ? `• "${edge.label}"` : ""}
                         </div>
                     </div>
                 );
@@ -227,13 +227,18 @@ const FieldRow: React.FC<FieldRowProps> = ({ field, modelId }) => {
                 return "text-green-400";
             case "int":
             case "float":
+            case "decimal":
                 return "text-blue-400";
             case "boolean":
                 return "text-yellow-400";
             case "datetime":
                 return "text-purple-400";
+            case "file":
+                return "text-cyan-400";
             case "uuid":
                 return "text-pink-400";
+            case "enum":
+                return "text-orange-400";
             default:
                 return "text-[var(--ide-text-muted)]";
         }
@@ -15,7 +15,7 @@
 
 import React, { useEffect, useState } from "react";
 import { useProjectStore } from "../../hooks/useProjectStore";
-import { setActivePage, installProjectDependencies, clearInstallStatus, toggleTerminal } from "../../stores/projectStore";
+import { setActivePage, installProjectDependencies, cancelProjectInstall, clearInstallStatus, toggleTerminal, overwriteSyncConflicts, dismissSyncConflicts } from "../../stores/projectStore";
 import type { FeaturePage } from "../../stores/projectStore";
 
 
@@ -58,7 +58,7 @@ const FEATURE_PAGES: FeaturePageDef[] = [
  * Main IDE Layout — Feature Page Architecture
  */
 const IDELayout: React.FC = () => {
-    const { project, activePage, loading, loadingMessage, installLog, installError, terminalOpen, builderActive } = useProjectStore();
+    const { project, activePage, loading, loadingMessage, installLog, installError, terminalOpen, builderActive, syncConflicts } = useProjectStore();
     const [settingsOpen, setSettingsOpen] = useState(false);
 
     // Global keyboard shortcut: toggle terminal
@@ -175,6 +175,36 @@ const IDELayout: React.FC = () => {
 
                 {/* ===== CENTER: Page Content + Terminal ===== */}
                 <div className="flex-1 flex flex-col overflow-hidden">
+                    {/* Sync Conflicts: pages edited on disk and in the editor, left unwritten */}
+                    {syncConflicts.length > 0 && (
+                        <div className="px-4 py-2 border-b border-amber-500/30 bg-amber-500/10 text-xs text-amber-200 flex items-start gap-3">
+                            <div className="flex-1">
+                                <div className="font-medium">
+                                    {syncConflicts.length} page{syncConflicts.length === 1 ? "" : "s"} changed on disk and in the editor; not overwritten
+                                </div>
+                                <ul className="mt-1 space-y-0.5 text-amber-200/80">
+                                    {syncConflicts.map(conflict => (
+                                        <li key={conflict.page_id}>
+                                            <span className="font-mono">{conflict.file}</span> — {conflict.message}
+                                        </li>
+                                    ))}
+                                </ul>
+                            </div>
+                            <button
+                                onClick={() => dismissSyncConflicts()}
+                                className="px-3 py-1 rounded bg-[var(--ide-bg-elevated)] hover:bg-[var(--ide-bg-sidebar)] text-[var(--ide-text)]"
+                            >
+                                Keep Disk Files
+                            </button>
+                            <button
+                                onClick={() => overwriteSyncConflicts().catch(console.error)}
+                                className="px-3 py-1 rounded bg-[var(--ide-primary)] hover:bg-[var(--ide-primary-hover)] text-white"
+                            >
+                                Overwrite
+                            </button>
+                        </div>
+                    )}
+
                     {/* Page Content */}
                     <div className={`flex-1 relative overflow-hidden ${terminalOpen ? "h-[60%]" : ""}`}>
                         {loading && (
@@ -217,6 +247,16 @@ const IDELayout: React.FC = () => {
                                 <div className="h-full bg-[var(--ide-primary)] animate-pulse" style={{ width: '70%' }}></div>
                             </div>
                         )}
+                        {!installError && loadingMessage.startsWith("Installing dependencies") && (
+                            <div className="mt-4 flex items-center justify-end">
+                                <button
+                                    onClick={() => cancelProjectInstall()}
+                                    className="px-3 py-1.5 text-sm rounded bg-[var(--ide-bg-elevated)] hover:bg-[var(--ide-bg-sidebar)] text-[var(--ide-text)]"
+                                >
+                                    Cancel
+                                </button>
+                            </div>
+                        )}
                         {installLog && (
                             <pre className="mt-4 max-h-64 overflow-auto text-xs bg-[var(--ide-bg-elevated)] text-[var(--ide-text-secondary)] p-3 rounded border border-[var(--ide-border)] whitespace-pre-wrap">
                                 {installLog}
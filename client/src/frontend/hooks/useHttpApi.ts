@@ -11,8 +11,25 @@ import {
   GitStatus,
   FileEntry,
   ProjectSettings,
+  FieldInput,
+  ImportRepairReport,
+  ProjectTemplateSummary,
   FieldSchema,
   RelationSchema,
+  Severity,
+  DiagramModelsResult,
+  ProjectIssue,
+  SearchHit,
+  SyncConflict,
+  ProjectChange,
+  GeneratedFileEntry,
+  GenerationReport,
+  InstallResult,
+  ProcessOutputLine,
+  DevServerStatus,
+  ArchivedSummary,
+  BulkBlockResult,
+  PageLayout,
 } from "../types/api";
 import type { UiBuilderGenerateRequest, UiBuilderGenerateResponse } from "../types/uiBuilder";
 
@@ -32,7 +49,19 @@ export const httpApi = {
     const res = await client.get("/workspace");
     return res.data;
   },
-  setWorkspacePath: async (_path: string) => true,
+  setWorkspacePath: async (path: string) => {
+    await client.put("/workspace", { workspace_path: path });
+    return true;
+  },
+  pinProject: async (id: string, pinned: boolean) => {
+    const res = await client.put(`/workspace/projects/${id}/pin`, { pinned });
+    return res.data;
+  },
+  /** Move a project to the top of the recent list */
+  markProjectOpened: async (id: string) => {
+    const res = await client.post(`/workspace/projects/${id}/open`);
+    return res.data;
+  },
   pickFolder: async () => null,
   loadProjectById: async (id: string): Promise<ProjectSchema> => {
     const res = await client.get(`/project/${id}`);
@@ -58,16 +87,22 @@ export const httpApi = {
   createProject: async (
     name: string,
     description?: string,
+    template?: string,
   ): Promise<ProjectSchema> => {
     const res = await client.post("/project", {
       name,
       description: description || "",
+      template,
     });
     if (res.data && res.data.id) {
       activeProjectId = res.data.id;
     }
     return res.data;
   },
+  listTemplates: async (): Promise<ProjectTemplateSummary[]> => {
+    const res = await client.get("/templates");
+    return res.data;
+  },
   renameProject: async (name: string, projectId?: string): Promise<ProjectSchema> => {
     const targetId = projectId || activeProjectId;
     if (!targetId) throw new Error("Rename requires a project ID");
@@ -112,28 +147,98 @@ export const httpApi = {
     const res = await client.get(`/project/${activeProjectId}`);
     return res.data;
   },
-  importProjectJson: async (_json: string): Promise<ProjectSchema> => {
-    throw new Error("Import not implemented");
+  importProjectJson: async (
+    json: string,
+  ): Promise<{ project: ProjectSchema; report: ImportRepairReport }> => {
+    const res = await client.post("/import/project", { project: json });
+    const project = await httpApi.loadProjectById(res.data.project_id);
+    return { project, report: res.data.report };
   },
-  exportProjectJson: async () => "{}",
-  setProjectRoot: async (_path: string) => true,
-  syncToDisk: async () => {
-    if (!activeProjectId) return false;
-    await client.post("/codegen/sync", { projectId: activeProjectId });
+  /** The server pretty-prints the export unless `minify` is set; the text is returned as sent */
+  exportProjectJson: async (minify?: boolean): Promise<string> => {
+    if (!activeProjectId) throw new Error("No active project");
+    const res = await client.get(`/project/${activeProjectId}/export`, {
+      params: minify ? { minify: true } : undefined,
+      responseType: "text",
+      transformResponse: (data) => data,
+    });
+    return res.data;
+  },
+  /** `force` rewrites the starter files even when the folder already has them */
+  setProjectRoot: async (path: string, force?: boolean) => {
+    if (!activeProjectId) throw new Error("No active project");
+    await client.put(`/project/${activeProjectId}/root`, { rootPath: path, force });
     return true;
   },
-  syncDiskToProject: async () => true,
+  /** Pages edited both on disk and in the editor are left alone and returned; `force` overwrites them */
+  syncToDisk: async (force?: boolean): Promise<SyncConflict[]> => {
+    if (!activeProjectId) return [];
+    const res = await client.post("/codegen/sync", { projectId: activeProjectId, force });
+    return res.data.conflicts ?? [];
+  },
+  detectSyncConflicts: async (): Promise<SyncConflict[]> => {
+    if (!activeProjectId) return [];
+    const res = await client.get("/codegen/conflicts", {
+      params: { projectId: activeProjectId },
+    });
+    return res.data.conflicts;
+  },
+  syncDiskToProject: async () => {
+    if (!activeProjectId) return false;
+    const res = await client.post("/codegen/sync-from-disk", { projectId: activeProjectId });
+    return res.data.success !== false;
+  },
+  undo: async () => {
+    if (!activeProjectId) throw new Error("No active project");
+    const res = await client.post("/command/undo", { projectId: activeProjectId });
+    return res.data;
+  },
+  redo: async () => {
+    if (!activeProjectId) throw new Error("No active project");
+    const res = await client.post("/command/redo", { projectId: activeProjectId });
+    return res.data;
+  },
+  /** Listen for server-side project changes; returns an unsubscribe function */
+  subscribeToChanges: (onChange: (change: ProjectChange) => void) => {
+    const url = new URL(API_BASE_URL.replace(/^http/, "ws").replace(/\/api$/, "/ws"));
+    if (activeProjectId) url.searchParams.set("projectId", activeProjectId);
+    const socket = new WebSocket(url);
+    socket.onmessage = (event) => {
+      const message = JSON.parse(event.data);
+      if (message.type === "change") onChange(message);
+    };
+    return () => socket.close();
+  },
+  /** Listen for the active project's process output, starting with its recent tail */
+  subscribeToProcessOutput: (onLine: (line: ProcessOutputLine) => void) => {
+    if (!activeProjectId) return () => {};
+    const url = new URL(API_BASE_URL.replace(/^http/, "ws").replace(/\/api$/, "/ws"));
+    url.searchParams.set("projectId", activeProjectId);
+    const socket = new WebSocket(url);
+    socket.onmessage = (event) => {
+      const message = JSON.parse(event.data);
+      if (message.type === "output") onLine(message);
+    };
+    return () => socket.close();
+  },
 
   // ─── Blocks ─────────────────────────────────────
   addBlock: async (
-    _blockType: string,
-    _name: string,
-    _parentId?: string,
-    _pageId?: string,
+    blockType: string,
+    name: string,
+    parentId?: string,
+    pageId?: string,
     _componentId?: string,
   ): Promise<BlockSchema> => {
-    // Need Implementation
-    throw new Error("Not implemented");
+    if (!activeProjectId) throw new Error("No active project");
+    const res = await client.post("/blocks", {
+      projectId: activeProjectId,
+      block_type: blockType,
+      name,
+      parent_id: parentId,
+      page_id: pageId,
+    });
+    return res.data;
   },
   bulkSyncPageBlocks: (
     _projectId: string,
@@ -143,21 +248,67 @@ export const httpApi = {
     return client.post("/blocks/sync", { page_id: pageId, blocks });
   },
   updateBlockProperty: async (
-    _blockId: string,
-    _property: string,
-    _value: unknown,
-  ) => {},
+    blockId: string,
+    property: string,
+    value: unknown,
+  ) => {
+    await client.put(`/blocks/${blockId}`, { property, value });
+  },
   updateBlockStyle: async (
     _blockId: string,
     _style: string,
     _value: string,
   ) => {},
-  archiveBlock: async (_blockId: string) => {},
+  archiveBlock: async (blockId: string) => {
+    await httpApi.archiveBlocks([blockId]);
+  },
+  /** Archive several blocks and their descendants in one step; one result per id */
+  archiveBlocks: async (blockIds: string[]): Promise<BulkBlockResult[]> => {
+    if (!activeProjectId) throw new Error("No active project");
+    const res = await client.post("/blocks/bulk-archive", {
+      projectId: activeProjectId,
+      ids: blockIds,
+    });
+    return res.data.results;
+  },
+  deleteBlocks: async (blockIds: string[]): Promise<BulkBlockResult[]> => {
+    if (!activeProjectId) throw new Error("No active project");
+    const res = await client.post("/blocks/bulk-delete", {
+      projectId: activeProjectId,
+      ids: blockIds,
+    });
+    return res.data.results;
+  },
   moveBlock: async (
-    _blockId: string,
-    _newParentId: string | null,
-    _index: number,
-  ) => {},
+    blockId: string,
+    newParentId: string | null,
+    index: number,
+  ) => {
+    await client.put(`/blocks/${blockId}/move`, {
+      new_parent_id: newParentId,
+      index,
+    });
+  },
+  duplicateBlock: async (blockId: string): Promise<BlockSchema> => {
+    const res = await client.post(`/blocks/${blockId}/duplicate`);
+    return res.data;
+  },
+  extractComponent: async (
+    blockId: string,
+    name: string,
+  ): Promise<{ component_id: string; instance: BlockSchema }> => {
+    const res = await client.post(`/blocks/${blockId}/extract-component`, { name });
+    return res.data;
+  },
+  /** The block's subtree (and the components it uses) as JSON that can be pasted into any project */
+  copyBlock: async (blockId: string): Promise<string> => {
+    const res = await client.get(`/blocks/${blockId}/copy`);
+    return res.data.data;
+  },
+  pasteBlocks: async (data: string, parentId: string): Promise<BlockSchema> => {
+    const res = await client.post("/blocks/paste", { data, parent_id: parentId });
+    return res.data;
+  },
 
   // ─── Pages ──────────────────────────────────────
   addPage: async (name: string, path: string): Promise<PageSchema> => {
@@ -175,6 +326,14 @@ export const httpApi = {
     const res = await client.put(`/pages/${id}`, { name, path });
     return res.data;
   },
+  setPageRequiredRole: async (id: string, role: string | null) => {
+    const res = await client.put(`/pages/${id}`, { required_role: role });
+    return res.data;
+  },
+  setPageLayout: async (id: string, layout: PageLayout) => {
+    const res = await client.put(`/pages/${id}`, { layout });
+    return res.data;
+  },
   archivePage: async (id: string) => {
     await client.delete(`/pages/${id}`);
   },
@@ -233,6 +392,15 @@ export const httpApi = {
     const res = await client.put(`/logic-flows/${id}`, updates);
     return res.data;
   },
+  simulateLogicFlow: async (id: string, input: unknown = {}) => {
+    const res = await client.post(`/logic-flows/${id}/simulate`, { input });
+    return res.data as {
+      output: unknown;
+      state: Record<string, any>;
+      steps: { node_id: string; node_type: string; value?: unknown; skipped?: string }[];
+      error?: string;
+    };
+  },
 
   // ─── Data Models ────────────────────────────────
   getModels: async () => {
@@ -242,11 +410,12 @@ export const httpApi = {
     });
     return res.data;
   },
-  addDataModel: async (name: string) => {
+  addDataModel: async (name: string, fields?: FieldInput[]) => {
     if (!activeProjectId) throw new Error("No active project");
     const res = await client.post("/data-models", {
       projectId: activeProjectId,
       name,
+      fields,
     });
     return res.data;
   },
@@ -254,6 +423,11 @@ export const httpApi = {
     const res = await client.put(`/data-models/${id}`, updates);
     return res.data;
   },
+  /** Rename a model; relations, endpoints, flows and bindings naming it follow along */
+  renameModel: async (id: string, name: string) => {
+    const res = await client.post(`/data-models/${id}/rename`, { name });
+    return res.data;
+  },
   addFieldToModel: async (
     modelId: string,
     name: string,
@@ -337,6 +511,16 @@ export const httpApi = {
     throw new Error("Not implemented");
   },
   archiveApi: async (_id: string) => true,
+  importOpenApi: async (
+    spec: string | Record<string, unknown>,
+  ): Promise<{ imported: ApiSchema[]; skipped: { method: string; path: string }[] }> => {
+    if (!activeProjectId) throw new Error("No active project");
+    const res = await client.post("/import/openapi", {
+      projectId: activeProjectId,
+      spec,
+    });
+    return res.data;
+  },
 
   // ─── Variables ──────────────────────────────────
   getVariables: async () => {
@@ -375,37 +559,199 @@ export const httpApi = {
     });
     return res.data;
   },
-  generateBackend: async () => ({ files: [] }),
-  generateDatabase: async () => ({ files: [] }),
-  downloadZip: async () => new Blob([], { type: "application/zip" }),
+  previewGeneration: async (): Promise<GeneratedFileEntry[]> => {
+    if (!activeProjectId) return [];
+    const res = await client.get("/codegen/preview", {
+      params: { projectId: activeProjectId },
+    });
+    return res.data.files;
+  },
+  generateBackend: async (): Promise<GenerationReport> => {
+    if (!activeProjectId) throw new Error("No active project");
+    const res = await client.post("/codegen/backend", {
+      projectId: activeProjectId,
+    });
+    return res.data;
+  },
+  generateHtml: async (): Promise<GenerationReport> => {
+    if (!activeProjectId) throw new Error("No active project");
+    const res = await client.post("/codegen/html", {
+      projectId: activeProjectId,
+    });
+    return res.data;
+  },
+  generatePostman: async (): Promise<{ files: { path: string; content: string }[] }> => {
+    if (!activeProjectId) throw new Error("No active project");
+    const res = await client.post("/codegen/postman", {
+      projectId: activeProjectId,
+    });
+    return res.data;
+  },
+  /** Root docker-compose.yml expecting the frontend in ./frontend and the backend in ./backend */
+  generateCompose: async (): Promise<{ files: { path: string; content: string }[] }> => {
+    if (!activeProjectId) throw new Error("No active project");
+    const res = await client.post("/codegen/compose", {
+      projectId: activeProjectId,
+    });
+    return res.data;
+  },
+  /** README.md documenting the project's models, pages and endpoints */
+  generateDocs: async (): Promise<{ files: { path: string; content: string }[] }> => {
+    if (!activeProjectId) throw new Error("No active project");
+    const res = await client.post("/codegen/docs", {
+      projectId: activeProjectId,
+    });
+    return res.data;
+  },
+  /** Compiled logic flows for one context: a file per flow plus `src/logic/flow-runner.ts` */
+  generateLogic: async (context: "frontend" | "backend" = "frontend"): Promise<{ files: { path: string; content: string }[] }> => {
+    if (!activeProjectId) throw new Error("No active project");
+    const res = await client.post("/codegen/logic", {
+      projectId: activeProjectId,
+      context,
+    });
+    return res.data;
+  },
+  validateProject: async (): Promise<{ valid: boolean; issues: ProjectIssue[] }> => {
+    if (!activeProjectId) throw new Error("No active project");
+    const res = await client.get(`/project/${activeProjectId}/validate`);
+    return res.data;
+  },
+  searchProject: async (query: string, limit?: number): Promise<SearchHit[]> => {
+    if (!activeProjectId) return [];
+    const res = await client.get("/search", {
+      params: { projectId: activeProjectId, q: query, limit },
+    });
+    return res.data.hits;
+  },
+  generateDatabase: async (): Promise<{ files: { path: string; content: string }[] }> => {
+    if (!activeProjectId) throw new Error("No active project");
+    const res = await client.post("/codegen/database", {
+      projectId: activeProjectId,
+    });
+    return res.data;
+  },
+  downloadZip: async (): Promise<Blob> => {
+    if (!activeProjectId) throw new Error("No active project");
+    const res = await client.post(
+      "/codegen/zip",
+      { projectId: activeProjectId },
+      { responseType: "blob" },
+    );
+    return res.data;
+  },
+  downloadTar: async (): Promise<Blob> => {
+    if (!activeProjectId) throw new Error("No active project");
+    const res = await client.post(
+      "/codegen/tar",
+      { projectId: activeProjectId },
+      { responseType: "blob" },
+    );
+    return res.data;
+  },
+
+  // ─── Trash ──────────────────────────────────────
+  listArchived: async (): Promise<ArchivedSummary> => {
+    if (!activeProjectId) throw new Error("No active project");
+    const res = await client.get("/trash", { params: { projectId: activeProjectId } });
+    return res.data;
+  },
+  restoreBlock: async (id: string): Promise<BlockSchema> => {
+    const res = await client.post(`/blocks/${id}/restore`);
+    return res.data;
+  },
+  restorePage: async (id: string): Promise<PageSchema> => {
+    const res = await client.post(`/pages/${id}/restore`);
+    return res.data;
+  },
+  restoreDataModel: async (id: string) => {
+    await client.post(`/data-models/${id}/restore`);
+  },
+  restoreLogicFlow: async (id: string) => {
+    await client.post(`/logic-flows/${id}/restore`);
+  },
 
   // ─── File System ────────────────────────────────
-  listDirectory: async (path?: string) => ({
-    path: path || "",
-    entries: [] as FileEntry[],
-  }),
-  createFile: async (path: string, _content?: string) => ({
-    name: "",
-    path,
-    is_directory: false,
-  }),
-  createFolder: async (path: string) => ({
-    name: "",
-    path,
-    is_directory: true,
-  }),
-  renameFile: async (_oldPath: string, newPath: string) => ({
-    name: "",
-    path: newPath,
-    is_directory: false,
-  }),
-  deleteFile: async (_path: string) => true,
-  readFileContent: async (path: string) => ({ content: "", path }),
-  writeFileContent: async (path: string, content: string) => ({
-    content,
-    path,
-  }),
-  installDependencies: async () => ({ success: true, steps: [] }),
+  listDirectory: async (path?: string) => {
+    if (!activeProjectId) return { path: path || "", entries: [] as FileEntry[] };
+    const res = await client.get("/files", {
+      params: { projectId: activeProjectId, path },
+    });
+    return res.data as { path: string; entries: FileEntry[] };
+  },
+  createFile: async (path: string, content?: string): Promise<FileEntry> => {
+    const res = await client.post("/files", {
+      projectId: activeProjectId,
+      path,
+      content,
+    });
+    return res.data;
+  },
+  createFolder: async (path: string): Promise<FileEntry> => {
+    const res = await client.post("/files/folder", {
+      projectId: activeProjectId,
+      path,
+    });
+    return res.data;
+  },
+  renameFile: async (oldPath: string, newPath: string): Promise<FileEntry> => {
+    const res = await client.put("/files/rename", {
+      projectId: activeProjectId,
+      oldPath,
+      newPath,
+    });
+    return res.data;
+  },
+  deleteFile: async (path: string) => {
+    await client.delete("/files/delete", {
+      params: { projectId: activeProjectId, path },
+    });
+    return true;
+  },
+  readFileContent: async (path: string) => {
+    const res = await client.get("/files/content", {
+      params: { projectId: activeProjectId, path },
+    });
+    return res.data as { content: string; path: string };
+  },
+  writeFileContent: async (path: string, content: string) => {
+    const res = await client.put("/files/content", {
+      projectId: activeProjectId,
+      path,
+      content,
+    });
+    return res.data as { content: string; path: string };
+  },
+  installDependencies: async (): Promise<InstallResult> => {
+    if (!activeProjectId) return { success: true, steps: [] };
+    const res = await client.post(`/project/${activeProjectId}/install`);
+    return res.data;
+  },
+  startDevServer: async (): Promise<DevServerStatus> => {
+    if (!activeProjectId) throw new Error("No active project");
+    const res = await client.post(`/project/${activeProjectId}/dev-server`);
+    return res.data;
+  },
+  stopDevServer: async () => {
+    if (!activeProjectId) return false;
+    await client.delete(`/project/${activeProjectId}/dev-server`);
+    return true;
+  },
+  getDevServerStatus: async (): Promise<DevServerStatus | null> => {
+    if (!activeProjectId) return null;
+    const res = await client.get(`/project/${activeProjectId}/dev-server`);
+    return res.data;
+  },
+  getProcessOutput: async (): Promise<ProcessOutputLine[]> => {
+    if (!activeProjectId) return [];
+    const res = await client.get(`/project/${activeProjectId}/output`);
+    return res.data.lines;
+  },
+  cancelInstall: async () => {
+    if (!activeProjectId) return false;
+    await client.post(`/project/${activeProjectId}/install/cancel`);
+    return true;
+  },
 
   // ─── Components ─────────────────────────────────
   getComponents: async () => {
@@ -516,26 +862,31 @@ export const httpApi = {
     });
     return res.data.success;
   },
-  analyzeDiagram: async (_name: string) => ({
-    graph: { nodes: [], edges: [] },
-    issues: [],
-    stats: {
-      total_nodes: 0,
-      total_edges: 0,
-      unknown_type_count: 0,
-      issue_count: 0,
-    },
-  }),
-  analyzeDiagramRaw: async (_xml: string) => ({
-    graph: { nodes: [], edges: [] },
-    issues: [],
-    stats: {
-      total_nodes: 0,
-      total_edges: 0,
-      unknown_type_count: 0,
-      issue_count: 0,
-    },
-  }),
+  analyzeDiagram: async (name: string, minSeverity?: Severity) => {
+    if (!activeProjectId) throw new Error("No active project");
+    const res = await client.post(
+      "/diagrams/analyze",
+      { projectId: activeProjectId, name },
+      { params: { min_severity: minSeverity } },
+    );
+    return res.data;
+  },
+  analyzeDiagramRaw: async (xml: string, minSeverity?: Severity) => {
+    const res = await client.post(
+      "/diagrams/analyze",
+      { xml },
+      { params: { min_severity: minSeverity } },
+    );
+    return res.data;
+  },
+  diagramToModels: async (name: string): Promise<DiagramModelsResult> => {
+    if (!activeProjectId) throw new Error("No active project");
+    const res = await client.post("/diagrams/to-models", {
+      projectId: activeProjectId,
+      name,
+    });
+    return res.data;
+  },
 
   // ─── Use Cases ─────────────────────────────────
   listUseCases: async () => {
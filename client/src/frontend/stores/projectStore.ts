@@ -5,7 +5,7 @@
  */
 
 import { api } from "../hooks/useApi";
-import { ProjectSchema, BlockSchema, PageSchema, InstallResult } from "../types/api";
+import { ProjectSchema, BlockSchema, PageSchema, InstallResult, FieldInput, ImportRepairReport, PageLayout, BulkBlockResult, SyncConflict } from "../types/api";
 import type { UiBuilderGenerateResponse, UiBuilderMode } from "../types/uiBuilder";
 import { BLOCK_REGISTRY } from "../components/features/VisualBuilder/hooks/craft/blockRegistry";
 
@@ -40,6 +40,9 @@ interface ProjectState {
     /** Page IDs that are open as tabs (VS Code-style). */
     openPageIds: string[];
 
+    /** Pages the last sync to disk didn't overwrite because their files were edited too */
+    syncConflicts: SyncConflict[];
+
     // Workspace state
     workspacePath: string | null;
     projects: ProjectSchema[];
@@ -70,12 +73,14 @@ const initialState: ProjectState = {
     builderActive: false,
     openPageIds: [],
     diffView: null,
+    syncConflicts: [],
 };
 
 // Internal state
 let state: ProjectState = { ...initialState };
 let listeners: Set<() => void> = new Set();
 let isDirtyValue = false;
+let autoSyncTimer: ReturnType<typeof setTimeout> | null = null;
 
 // ── Session persistence keys (survive page refresh) ──
 const SESSION_PROJECT_ID_KEY = "akasha_active_project_id";
@@ -116,6 +121,45 @@ function formatInstallResult(result: InstallResult): string {
     }).join("\n\n");
 }
 
+/**
+ * Write visual-mode edits to disk as `settings.auto_sync` asks: after every change by default,
+ * once `auto_sync_debounce_ms` passes without further changes when that is set, or not at all
+ * when `auto_sync` is false, leaving it to an explicit save or switching to code mode.
+ * Property edits and bulk removals aren't synced here; the server writes their pages under
+ * the same settings.
+ */
+async function autoSync(): Promise<void> {
+    if (state.editMode !== "visual" || !state.project?.root_path) return;
+    const settings = state.project.settings;
+    if (settings?.auto_sync === false) return;
+
+    const delay = settings?.auto_sync_debounce_ms ?? 0;
+    if (delay > 0) {
+        cancelAutoSync();
+        autoSyncTimer = setTimeout(() => {
+            autoSyncTimer = null;
+            writeToDisk().catch(err => console.error("Auto-sync failed:", err));
+        }, delay);
+        return;
+    }
+    await writeToDisk().catch(err =>
+        console.error("Auto-sync failed:", err)
+    );
+}
+
+/** Sync the project to disk, keeping the pages it refused to overwrite for the conflict banner */
+async function writeToDisk(force?: boolean): Promise<SyncConflict[]> {
+    const syncConflicts = await api.syncToDisk(force);
+    updateState(() => ({ syncConflicts }));
+    return syncConflicts;
+}
+
+/** Drop a pending debounced sync, for when a full sync is about to run anyway */
+function cancelAutoSync() {
+    if (autoSyncTimer) clearTimeout(autoSyncTimer);
+    autoSyncTimer = null;
+}
+
 function getFirstActivePageId(project: ProjectSchema | null | undefined): string | null {
     return project?.pages.find((page) => !page.archived)?.id ?? null;
 }
@@ -161,10 +205,31 @@ export async function installProjectDependencies(): Promise<boolean> {
         installLog: null
     }));
 
+    // Show npm's output as it arrives; the final log below replaces it. Lines from earlier
+    // runs still in the server's tail are skipped by sequence number.
+    const previous = await api.getProcessOutput().catch(() => []);
+    const lastSeq = previous.length ? previous[previous.length - 1].seq : 0;
+    const live: string[] = [];
+    const unsubscribe = api.subscribeToProcessOutput((line) => {
+        if (line.source !== "install" || line.seq <= lastSeq) return;
+        live.push(`[${line.target}${line.stream === "stderr" ? " stderr" : ""}] ${line.line}`);
+        updateState(() => ({ installLog: live.slice(-200).join("\n") }));
+    });
+
     try {
         const result = await api.installDependencies();
+        unsubscribe();
         const log = formatInstallResult(result);
 
+        if (result.cancelled) {
+            updateState(() => ({
+                loadingMessage: "Dependency installation cancelled",
+                installError: "Dependency installation cancelled",
+                installLog: log
+            }));
+            return false;
+        }
+
         if (!result.success) {
             updateState(() => ({
                 loadingMessage: "Dependency installation failed",
@@ -186,6 +251,7 @@ export async function installProjectDependencies(): Promise<boolean> {
         }));
         return true;
     } catch (err) {
+        unsubscribe();
         updateState(() => ({
             loadingMessage: "Dependency installation failed",
             installError: String(err),
@@ -195,6 +261,15 @@ export async function installProjectDependencies(): Promise<boolean> {
     }
 }
 
+/** Stop the running install; installProjectDependencies then reports it as cancelled */
+export async function cancelProjectInstall(): Promise<void> {
+    try {
+        await api.cancelInstall();
+    } catch (err) {
+        console.error("Failed to cancel dependency installation:", err);
+    }
+}
+
 export function clearInstallStatus(): void {
     updateState(() => ({
         loadingMessage: null,
@@ -517,11 +592,7 @@ export async function applyBuilderLayout(
         await loadProject();
         isDirtyValue = true;
 
-        if (state.editMode === "visual" && state.project?.root_path) {
-            await api.syncToDisk().catch((err) =>
-                console.error("Auto-sync failed:", err)
-            );
-        }
+        await autoSync();
     } catch (err) {
         updateState(() => ({ error: String(err) }));
         throw err;
@@ -559,11 +630,11 @@ export async function resetProject(clearDiskFiles?: boolean): Promise<void> {
 /**
  * Create a new project in the workspace
  */
-export async function createProject(name: string, description?: string): Promise<void> {
+export async function createProject(name: string, description?: string, template?: string): Promise<void> {
     updateState(() => ({ loading: true, error: null }));
 
     try {
-        let project = await api.createProject(name, description);
+        let project = await api.createProject(name, description, template);
 
         // If workspace is set, we automatically configure the sync root 
         // as a subfolder within the workspace
@@ -630,6 +701,7 @@ export async function openProject(id: string): Promise<void> {
 
         // ── Persist to sessionStorage so refresh re-opens this project ──
         sessionStorage.setItem(SESSION_PROJECT_ID_KEY, id);
+        api.markProjectOpened(id).catch(err => console.error("Failed to record project open:", err));
 
         // CHECK IF node_modules EXISTS (non-blocking, runs in background)
         if (project.root_path) {
@@ -669,6 +741,14 @@ export async function deleteProject(id: string, deleteFromDisk?: boolean): Promi
     await initWorkspace();
 }
 
+/**
+ * Pin or unpin a project in the workspace list
+ */
+export async function pinProject(id: string, pinned: boolean): Promise<void> {
+    await api.pinProject(id, pinned);
+    await initWorkspace();
+}
+
 
 /**
  * Return to dashboard
@@ -736,18 +816,19 @@ export async function loadProject(): Promise<void> {
 }
 
 /**
- * Import a project from JSON
+ * Import a project from JSON. Resolves with the repairs made to dangling references.
  */
-export async function importProject(json: string): Promise<void> {
+export async function importProject(json: string): Promise<ImportRepairReport> {
     updateState(() => ({ loading: true, error: null }));
 
     try {
-        const project = await api.importProjectJson(json);
+        const { project, report } = await api.importProjectJson(json);
         updateState(() => ({
             project,
             selectedPageId: getFirstActivePageId(project),
             openPageIds: allActivePageIds(project),
         }));
+        return report;
     } catch (err) {
         updateState(() => ({ error: String(err) }));
         throw err;
@@ -759,8 +840,8 @@ export async function importProject(json: string): Promise<void> {
 /**
  * Export the project to JSON
  */
-export async function exportProject(): Promise<string> {
-    return api.exportProjectJson();
+export async function exportProject(minify?: boolean): Promise<string> {
+    return api.exportProjectJson(minify);
 }
 
 
@@ -845,13 +926,6 @@ export async function updateBlockProperty(
     await api.updateBlockProperty(blockId, property, value);
     await loadProject();
     isDirtyValue = true;
-
-    // Auto-sync to disk in visual mode
-    if (state.editMode === "visual" && state.project?.root_path) {
-        await api.syncToDisk().catch(err =>
-            console.error("Auto-sync failed:", err)
-        );
-    }
 }
 
 /**
@@ -865,13 +939,6 @@ export async function updateBlockStyle(
     await api.updateBlockStyle(blockId, style, value);
     await loadProject();
     isDirtyValue = true;
-
-    // Auto-sync to disk in visual mode
-    if (state.editMode === "visual" && state.project?.root_path) {
-        await api.syncToDisk().catch(err =>
-            console.error("Auto-sync failed:", err)
-        );
-    }
 }
 
 /**
@@ -913,6 +980,34 @@ export async function archiveBlock(blockId: string): Promise<void> {
     }
 }
 
+/**
+ * Archive several blocks at once, as a single undo step
+ */
+export async function archiveBlocks(blockIds: string[]): Promise<void> {
+    await api.archiveBlocks(blockIds);
+    await afterBulkRemove(blockIds);
+}
+
+/**
+ * Permanently delete several blocks at once, as a single undo step. A page's root block
+ * is reported as failed rather than deleted.
+ */
+export async function deleteBlocks(blockIds: string[]): Promise<BulkBlockResult[]> {
+    const results = await api.deleteBlocks(blockIds);
+    await afterBulkRemove(results.filter(r => r.success).map(r => r.id));
+    return results;
+}
+
+/** Reload once after a bulk archive or delete; the server has already written the pages to disk */
+async function afterBulkRemove(blockIds: string[]): Promise<void> {
+    await loadProject();
+    isDirtyValue = true;
+
+    if (state.selectedBlockId && blockIds.includes(state.selectedBlockId)) {
+        updateState(() => ({ selectedBlockId: null }));
+    }
+}
+
 /**
  * Move a block to a new parent and/or reorder it
  */
@@ -947,11 +1042,7 @@ export async function addPage(name: string, path: string): Promise<PageSchema> {
     }));
     isDirtyValue = true;
 
-    if (state.editMode === "visual" && state.project?.root_path) {
-        await api.syncToDisk().catch(err =>
-            console.error("Auto-sync failed:", err)
-        );
-    }
+    await autoSync();
 
     return page;
 }
@@ -964,11 +1055,18 @@ export async function updatePage(id: string, name?: string, path?: string): Prom
     await loadProject();
     isDirtyValue = true;
 
-    if (state.editMode === "visual" && state.project?.root_path) {
-        await api.syncToDisk().catch(err =>
-            console.error("Auto-sync failed:", err)
-        );
-    }
+    await autoSync();
+}
+
+/**
+ * Choose the layout a page is generated in
+ */
+export async function setPageLayout(id: string, layout: PageLayout): Promise<void> {
+    await api.setPageLayout(id, layout);
+    await loadProject();
+    isDirtyValue = true;
+
+    await autoSync();
 }
 
 /**
@@ -985,11 +1083,7 @@ export async function archivePage(id: string): Promise<void> {
         updateState(() => ({ selectedPageId: nextId }));
     }
 
-    if (state.editMode === "visual" && state.project?.root_path) {
-        await api.syncToDisk().catch(err =>
-            console.error("Auto-sync failed:", err)
-        );
-    }
+    await autoSync();
 }
 
 /**
@@ -1025,8 +1119,8 @@ export async function createPage(name: string): Promise<PageSchema> {
 /**
  * Add a new data model
  */
-export async function addDataModel(name: string): Promise<void> {
-    await api.addDataModel(name);
+export async function addDataModel(name: string, fields?: FieldInput[]): Promise<void> {
+    await api.addDataModel(name, fields);
     await loadProject();
     isDirtyValue = true;
 }
@@ -1114,6 +1208,15 @@ export async function updateModel(
     isDirtyValue = true;
 }
 
+/**
+ * Rename a data model along with everything that refers to it by name
+ */
+export async function renameModel(id: string, name: string): Promise<void> {
+    await api.renameModel(id, name);
+    await loadProject();
+    isDirtyValue = true;
+}
+
 /**
  * Archive (soft-delete) a data model
  */
@@ -1217,21 +1320,31 @@ export async function generateDatabase(): Promise<{ path: string; content: strin
     return res.files;
 }
 
-/**
- * Download the project as a ZIP file
- */
-export async function downloadProjectZip(): Promise<void> {
-    const blob = await api.downloadZip();
+function saveBlob(blob: Blob, fileName: string): void {
     const url = URL.createObjectURL(blob);
     const a = document.createElement("a");
     a.href = url;
-    a.download = `${state.project?.name || "akasha-project"}.zip`;
+    a.download = fileName;
     document.body.appendChild(a);
     a.click();
     document.body.removeChild(a);
     URL.revokeObjectURL(url);
 }
 
+/**
+ * Download the project as a ZIP file
+ */
+export async function downloadProjectZip(): Promise<void> {
+    saveBlob(await api.downloadZip(), `${state.project?.name || "akasha-project"}.zip`);
+}
+
+/**
+ * Download the project as a .tar.gz, with the same files as the ZIP
+ */
+export async function downloadProjectTar(): Promise<void> {
+    saveBlob(await api.downloadTar(), `${state.project?.name || "akasha-project"}.tar.gz`);
+}
+
 
 /**
  * Select a page
@@ -1386,7 +1499,8 @@ export async function setEditMode(mode: "visual" | "code"): Promise<void> {
             await loadProject();
         } else if (mode === "code") {
             // Entering code mode: Save visual changes to disk
-            await api.syncToDisk();
+            cancelAutoSync();
+            await writeToDisk();
         }
     } catch (err) {
         console.error(`Failed to sync when switching to ${mode} mode:`, err);
@@ -1495,10 +1609,27 @@ export async function saveFileContent(path: string, content: string): Promise<vo
  * Force a manual sync to disk
  */
 export async function syncToDisk(): Promise<void> {
-    await api.syncToDisk();
+    cancelAutoSync();
+    const conflicts = await writeToDisk();
+    isDirtyValue = conflicts.length > 0;
+}
+
+/**
+ * Resolve sync conflicts in the editor's favour, overwriting the edited page files
+ */
+export async function overwriteSyncConflicts(): Promise<void> {
+    cancelAutoSync();
+    await writeToDisk(true);
     isDirtyValue = false;
 }
 
+/**
+ * Hide the sync conflict banner, keeping the files on disk as they are
+ */
+export function dismissSyncConflicts(): void {
+    updateState(() => ({ syncConflicts: [] }));
+}
+
 /**
  * List directory contents
  */
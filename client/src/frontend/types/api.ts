@@ -3,6 +3,8 @@
 
 export interface ProjectSchema {
     version: string;
+    /** Bumped on every mutation and returned as the `ETag`; send it as `If-Match` to reject stale writes with 409 */
+    revision?: number;
     id: string;
     name: string;
     description?: string;
@@ -17,12 +19,16 @@ export interface ProjectSchema {
     settings: ProjectSettings;
     root_path?: string;
     components: BlockSchema[];
+    /** Workspace listing only: pinned projects sort first, then by `last_opened_at` */
+    pinned?: boolean;
+    last_opened_at?: string | null;
 }
 
 export interface InstallStep {
     target: string;
     success: boolean;
     timed_out: boolean;
+    cancelled?: boolean;
     duration_ms: number;
     stdout: string;
     stderr: string;
@@ -31,6 +37,8 @@ export interface InstallStep {
 
 export interface InstallResult {
     success: boolean;
+    /** Stopped through cancelInstall before every target finished */
+    cancelled?: boolean;
     steps: InstallStep[];
 }
 
@@ -53,6 +61,27 @@ export interface BlockSchema {
     classes?: string[];
 }
 
+/** Kept in `properties.position`; offsets are Tailwind spacing steps (1 = 0.25rem) */
+export interface BlockPosition {
+    type: 'static' | 'relative' | 'absolute' | 'fixed';
+    top?: number;
+    right?: number;
+    bottom?: number;
+    left?: number;
+    z_index?: number;
+}
+
+/**
+ * A prop declared on a component block (`properties.props`). Instances pass values through
+ * `properties.prop_values` or a binding keyed by the prop name.
+ */
+export interface ComponentProp {
+    name: string;
+    prop_type: 'string' | 'number' | 'boolean' | 'object' | 'array' | 'any';
+    required: boolean;
+    default?: unknown;
+}
+
 export type StyleValue = string | number | boolean;
 
 export interface DataBinding {
@@ -70,13 +99,19 @@ export interface PageSchema {
     name: string;
     path: string;
     root_block_id?: string;
+    /** Sibling top-level blocks, in order; `root_block_id` is the first */
+    root_block_ids?: string[];
     meta?: PageMeta;
     is_dynamic: boolean;
     dynamic_param?: string;
     layout_id?: string;
+    /** `default` renders inside the app layout with its nav, `auth` centered without it, `blank` bare */
+    layout?: PageLayout;
     archived: boolean;
 }
 
+export type PageLayout = 'default' | 'blank' | 'auth';
+
 export interface PageMeta {
     title?: string;
     description?: string;
@@ -94,6 +129,10 @@ export interface ApiSchema {
     query_params?: ParamSchema[];
     path_params?: ParamSchema[];
     logic_flow_id?: string;
+    /** Status returned on success when it differs from the verb's default (201 for POST, else 200) */
+    success_status?: number;
+    /** Allow at most `requests` calls per `window_seconds` from one client */
+    rate_limit?: { requests: number; window_seconds: number };
     permissions: string[];
     archived: boolean;
 }
@@ -127,6 +166,7 @@ export interface LogicFlowSchema {
     nodes: LogicNode[];
     entry_node_id?: string;
     context: string;
+    transactional?: boolean;
     archived: boolean;
 }
 
@@ -155,6 +195,9 @@ export interface DataModelSchema {
     relations: RelationSchema[];
     timestamps: boolean;
     soft_delete: boolean;
+    owner_field?: string;
+    /** Field-name groups that must be unique together, e.g. [["userId", "slug"]] */
+    unique_constraints?: string[][];
     archived: boolean;
 }
 
@@ -167,6 +210,47 @@ export interface FieldSchema {
     primary_key: boolean;
     default?: string;
     description?: string;
+    /** Allowed values when `field_type` is `enum` */
+    enum_values?: string[];
+    /** Total and fractional digits when `field_type` is `decimal` */
+    precision?: number;
+    scale?: number;
+    /** Add a database index for a frequently queried field */
+    indexed?: boolean;
+}
+
+export interface ImportRepairAction {
+    kind: 'dropped_page' | 'dropped_block' | 'removed_child' | 'fixed_page_root' | 'dropped_unique_constraint';
+    entity_id: string;
+    message: string;
+}
+
+/** What a project import had to fix before storing the file */
+export interface ImportRepairReport {
+    repaired: boolean;
+    actions: ImportRepairAction[];
+}
+
+/** A starter project offered on creation; `models` and `pages` are names */
+export interface ProjectTemplateSummary {
+    id: string;
+    name: string;
+    description: string;
+    models: string[];
+    pages: string[];
+}
+
+/** A field supplied when creating a model; omitted flags default to required, non-unique */
+export interface FieldInput {
+    name: string;
+    field_type?: string;
+    required?: boolean;
+    unique?: boolean;
+    default?: string;
+    description?: string;
+    enum_values?: string[];
+    precision?: number;
+    scale?: number;
 }
 
 export interface RelationSchema {
@@ -174,6 +258,10 @@ export interface RelationSchema {
     name: string;
     target_model_id: string;
     relation_type: string;
+    /** Whether the foreign key may be empty; unset means optional */
+    optional?: boolean;
+    /** What deleting the referenced record does to this one; Prisma's default when unset */
+    on_delete?: "Cascade" | "SetNull" | "Restrict";
 }
 
 export interface VariableSchema {
@@ -181,6 +269,10 @@ export interface VariableSchema {
     name: string;
     variable_type: string;
     scope: string;
+    /** Owning page when `scope` is "page" */
+    page_id?: string;
+    /** Keep the value in localStorage in the generated app */
+    persist?: boolean;
     default_value?: unknown;
     archived: boolean;
 }
@@ -265,6 +357,7 @@ export interface ProjectSettings {
         border_radius?: number;
     };
     build?: {
+        /** Frontend export target: "react" (default) or "vue" */
         frontend_framework?: string;
         backend_framework?: string;
         database_provider?: string;
@@ -276,6 +369,35 @@ export interface ProjectSettings {
         default_og_image?: string;
         favicon?: string;
     };
+    /** Email/password auth in the generated backend */
+    auth?: {
+        enabled?: boolean;
+        /** JWT lifetime, e.g. "15m" or "7d" (default 7d, or 15m with refresh tokens) */
+        access_token_ttl?: string;
+        /** Issue rotating refresh tokens alongside access tokens */
+        refresh_tokens?: boolean;
+        refresh_token_ttl_days?: number;
+    };
+    /** Path the generated API is served under, e.g. "v1" (default "api", "" for none) */
+    api_prefix?: string;
+    /** Layout of generated TypeScript (default single quotes, semicolons, 2-space indent); a matching .prettierrc is emitted */
+    code_style?: {
+        quotes?: "single" | "double";
+        semicolons?: boolean;
+        indent?: number;
+    };
+    /** Public URL of the deployed frontend, used for sitemap.xml and robots.txt */
+    base_url?: string;
+    /** Origins the generated backend accepts cross-origin requests from, e.g. "https://app.example.com" */
+    cors_origins?: string[];
+    /** Directory names the file watcher skips and the generated .gitignore covers */
+    watch_ignore?: string[];
+    /** Quiet period before a burst of file events for one path is reported (default 300ms) */
+    watch_debounce_ms?: number;
+    /** Write visual edits to disk as they happen (default true); when false only explicit saves sync */
+    auto_sync?: boolean;
+    /** Batch auto-sync: write once this long after the last edit instead of after every edit */
+    auto_sync_debounce_ms?: number;
     ideaDetails?: StructuredIdeaDetails;
     uiArchitectPlan?: UiArchitectSavedPlan;
 }
@@ -360,10 +482,99 @@ export interface ProxyResponse {
     error?: boolean;
 }
 
+export interface ProjectIssue {
+    category: 'block' | 'page' | 'api' | 'model' | 'logic_flow' | 'route' | 'component';
+    severity: 'info' | 'warning' | 'error';
+    entity_id: string;
+    message: string;
+}
+
+/** A generate route's output, with problems that left gaps in it */
+export interface GenerationReport {
+    files: { path: string; content: string }[];
+    warnings: ProjectIssue[];
+    stats: { file_count: number; total_bytes: number };
+}
+
+/** Pushed over the `/ws` socket after a successful mutation */
+export interface ProjectChange {
+    project_id: string;
+    /** Singular entity kind from the route, e.g. `block`, `page`, `data_model` */
+    kind: string;
+    entity_id: string | null;
+    action: 'create' | 'update' | 'delete';
+    timestamp: string;
+}
+
+/** A file the generators would produce, listed by the generation preview */
+export interface GeneratedFileEntry {
+    /** Path relative to the output root of the generator that produces it */
+    path: string;
+    size: number;
+    kind: 'frontend' | 'backend' | 'logic' | 'database';
+}
+
+/** The project's `npm run dev` process; `port` is read from its startup output */
+export interface DevServerStatus {
+    running: boolean;
+    pid: number | null;
+    port: number | null;
+    url: string | null;
+    /** Whether the server answered an HTTP request on its port */
+    ready: boolean;
+}
+
+/** A line of install or dev server output, pushed over the `/ws` socket as it is printed */
+export interface ProcessOutputLine {
+    project_id: string;
+    source: string;
+    target: string;
+    stream: 'stdout' | 'stderr';
+    line: string;
+    seq: number;
+    timestamp: string;
+}
+
+/** Outcome for one id of a bulk block archive or delete */
+export interface BulkBlockResult {
+    id: string;
+    success: boolean;
+    error?: string;
+}
+
+/** A page whose generated file and schema were both edited since the last sync */
+export interface SyncConflict {
+    page_id: string;
+    page_name: string;
+    /** File path relative to the project root */
+    file: string;
+    message: string;
+}
+
+export interface SearchHit {
+    kind: 'block' | 'page' | 'data_model' | 'field' | 'api' | 'logic_flow' | 'variable';
+    entity_id: string;
+    /** Owning entity for hits inside another one (a field's model) */
+    parent_id?: string;
+    /** Which attribute matched, e.g. `name`, `path` or `properties.text` */
+    matched: string;
+    snippet: string;
+}
+
+/** Archived entities of a project, as listed by the trash */
+export interface ArchivedSummary {
+    pages: { id: string; name: string; path: string }[];
+    blocks: { id: string; name: string; block_type: string; page_id?: string; parent_id?: string }[];
+    data_models: { id: string; name: string }[];
+    logic_flows: { id: string; name: string }[];
+}
+
 // ===== Akasha Product Intelligence Types =====
 
-export type NodeType = 'actor' | 'feature' | 'screen' | 'api' | 'database' | 'external_service' | 'decision' | 'process' | 'unknown';
+export type NodeType = 'actor' | 'feature' | 'screen' | 'api' | 'database' | 'external_service' | 'decision' | 'process' | 'entity' | 'attribute' | 'unknown';
 export type RelationshipType = 'flow' | 'dependency' | 'association';
+/** Meaning inferred from the edge label ("has many", "triggers", ...) */
+export type RelationKind = 'association' | 'ownership' | 'trigger' | 'data_flow';
 export type Severity = 'info' | 'warning' | 'error';
 
 export interface ProductNode {
@@ -379,6 +590,7 @@ export interface ProductEdge {
     target: string;
     label: string;
     relationship_type: RelationshipType;
+    relation_kind: RelationKind;
 }
 
 export interface ProductGraph {
@@ -398,6 +610,7 @@ export interface GraphStats {
     total_edges: number;
     unknown_type_count: number;
     issue_count: number;
+    relation_counts: Record<RelationKind, number>;
 }
 
 export interface AnalysisResult {
@@ -405,3 +618,9 @@ export interface AnalysisResult {
     issues: ValidationIssue[];
     stats: GraphStats;
 }
+
+/** Models merged from a diagram, plus the entities and attributes skipped for unusable names */
+export interface DiagramModelsResult {
+    models: DataModelSchema[];
+    issues: ValidationIssue[];
+}
@@ -1,5 +1,6 @@
 import type { Request, Response } from 'express';
 import prisma from '../lib/prisma.js';
+import { getProjectStore } from '../services/projectStore.js';
 import { getLLMProvider } from '../lib/llmProvider.js';
 
 interface StructuredChatResponse {
@@ -459,7 +460,7 @@ export async function projectChat(req: Request, res: Response) {
 
     try {
         // Load project idea for context
-        const project = await prisma.project.findUnique({ where: { id: projectId } });
+        const project = await getProjectStore().getProjectById(projectId);
         if (!project) {
             return res.status(404).json({ error: 'Project not found' });
         }
@@ -1312,10 +1313,7 @@ Rules:
 
         if (projectId) {
             try {
-                await prisma.project.update({
-                    where: { id: projectId },
-                    data: { description: refinedMarkdown }
-                });
+                await getProjectStore().saveProject(projectId, { description: refinedMarkdown });
             } catch (err: any) {
                 console.error('Project update after refinement failed:', err.message);
             }
@@ -0,0 +1,184 @@
+import { test, type TestContext } from 'node:test';
+import assert from 'node:assert/strict';
+import type { Request, Response } from 'express';
+import fs from 'fs-extra';
+import os from 'os';
+import path from 'path';
+import { addBlock, bulkArchiveBlocks, restoreBlock, syncBlocks, updateBlockProperty } from './blocksController.js';
+import { getCommandDb, setCommandDb } from '../services/commands.js';
+import { memoryCommandDb } from '../services/memoryDb.js';
+import { getProjectStore, setProjectStore, type ProjectStore } from '../services/projectStore.js';
+import { SyncService } from '../services/sync.js';
+
+class Reply {
+  public statusCode = 200;
+  public body: any;
+
+  status(code: number) {
+    this.statusCode = code;
+    return this;
+  }
+
+  json(body: any) {
+    this.body = body;
+    return this;
+  }
+}
+
+/** A project rooted in a fresh temp dir with one page whose root b0 holds b1, b2 and b3, using `settings` */
+async function useProject(t: TestContext, settings: Record<string, unknown>) {
+  const root = await fs.mkdtemp(path.join(os.tmpdir(), 'akasha-blocks-'));
+  const previousStore = getProjectStore();
+  const previousDb = getCommandDb();
+  const db = memoryCommandDb({
+    pages: [{ id: 'page1', projectId: 'p1', idRoot: 'root1', name: 'Home', path: '/' }],
+    blocks: [
+      { id: 'b0', projectId: 'p1', pageId: 'root1', blockType: 'Container', name: 'Root', children: '["b1","b2","b3"]' },
+      { id: 'b1', projectId: 'p1', parentId: 'b0', blockType: 'Text', name: 'Title' },
+      { id: 'b2', projectId: 'p1', parentId: 'b0', blockType: 'Text', name: 'Subtitle', order: 1 },
+      { id: 'b3', projectId: 'p1', parentId: 'b0', blockType: 'Button', name: 'Buy', order: 2 },
+    ],
+  });
+  setCommandDb(db);
+  setProjectStore({
+    getProjectById: async (id: string) => ({ id, rootPath: root, settings: JSON.stringify(settings) }),
+  } as unknown as ProjectStore);
+  t.after(async () => {
+    setProjectStore(previousStore);
+    setCommandDb(previousDb);
+    await fs.remove(root);
+  });
+  return { root, db };
+}
+
+async function setText(value: string) {
+  const res = new Reply();
+  await updateBlockProperty(
+    { params: { id: 'b1' }, body: { property: 'text', value } } as unknown as Request,
+    res as unknown as Response,
+  );
+  return res;
+}
+
+test('updateBlockProperty leaves the page file alone when auto_sync is off', async t => {
+  const { root, db } = await useProject(t, { auto_sync: false });
+  const sync = t.mock.method(SyncService.prototype, 'syncPageToDisk', async () => null);
+
+  const res = await setText('Hello');
+
+  assert.deepEqual(res.body, { success: true });
+  assert.equal(JSON.parse(db.block.rows.find(b => b.id === 'b1')!.properties).text, 'Hello');
+  assert.equal(sync.mock.callCount(), 0);
+  assert.equal(await fs.pathExists(path.join(root, 'src', 'pages', 'Home.tsx')), false);
+  assert.deepEqual(await fs.readdir(root), []);
+});
+
+test('updateBlockProperty syncs the block\'s page by default', async t => {
+  await useProject(t, {});
+  const sync = t.mock.method(SyncService.prototype, 'syncPageToDisk', async () => null);
+
+  await setText('Hello');
+  assert.deepEqual(sync.mock.calls.map(call => call.arguments), [['page1', 'p1']]);
+});
+
+test('updateBlockProperty syncs once per burst of edits when auto_sync_debounce_ms is set', async t => {
+  await useProject(t, { auto_sync_debounce_ms: 20 });
+  const sync = t.mock.method(SyncService.prototype, 'syncPageToDisk', async () => null);
+
+  await setText('One');
+  await setText('Two');
+  assert.equal(sync.mock.callCount(), 0);
+  await new Promise(resolve => setTimeout(resolve, 60));
+  assert.equal(sync.mock.callCount(), 1);
+});
+
+test('bulkArchiveBlocks archives every block and syncs their page once', async t => {
+  const { db } = await useProject(t, {});
+  const sync = t.mock.method(SyncService.prototype, 'syncPageToDisk', async () => null);
+
+  const res = new Reply();
+  await bulkArchiveBlocks(
+    { body: { projectId: 'p1', ids: ['b1', 'b2', 'b3'] } } as unknown as Request,
+    res as unknown as Response,
+  );
+
+  assert.deepEqual(res.body.results.map((r: { id: string; success: boolean }) => [r.id, r.success]), [['b1', true], ['b2', true], ['b3', true]]);
+  assert.ok(['b1', 'b2', 'b3'].every(id => db.block.rows.find(b => b.id === id)!.archived));
+  assert.equal(db.block.rows.find(b => b.id === 'b0')!.children, '[]');
+  assert.deepEqual(sync.mock.calls.map(call => call.arguments), [['page1', 'p1']]);
+});
+
+test('addBlock stores a custom:<Name> block that the page sync renders as that component', async t => {
+  const { root, db } = await useProject(t, {});
+
+  const res = new Reply();
+  await addBlock(
+    { body: { projectId: 'p1', block_type: 'custom:carousel', page_id: 'page1', parent_id: 'b0' } } as unknown as Request,
+    res as unknown as Response,
+  );
+
+  assert.equal(res.statusCode, 200);
+  const block = db.block.rows.find(b => b.id === res.body.id)!;
+  assert.deepEqual([block.blockType, block.name, block.pageId, block.parentId], ['custom:carousel', 'carousel', 'root1', 'b0']);
+
+  assert.equal(await new SyncService(root).syncPageToDisk('page1', 'p1'), null);
+  const page = await fs.readFile(path.join(root, 'src', 'pages', 'Home.tsx'), 'utf-8');
+  assert.match(page, /import Carousel from '\.\.\/components\/Carousel';/);
+  assert.match(page, /<Carousel\b/);
+});
+
+test('addBlock rejects a custom block type with no usable name', async t => {
+  const { db } = await useProject(t, {});
+
+  const res = new Reply();
+  await addBlock({ body: { projectId: 'p1', block_type: 'custom:!!!', parent_id: 'b0' } } as unknown as Request, res as unknown as Response);
+
+  assert.equal(res.statusCode, 400);
+  assert.equal(db.block.rows.length, 4);
+});
+
+test('restoreBlock brings an archived block back under its parent in the page', async t => {
+  const { root, db } = await useProject(t, {});
+  t.mock.method(SyncService.prototype, 'syncPageToDisk', async () => null);
+  await bulkArchiveBlocks({ body: { projectId: 'p1', ids: ['b2'] } } as unknown as Request, new Reply() as unknown as Response);
+  assert.equal(db.block.rows.find(b => b.id === 'b0')!.children, '["b1","b3"]');
+
+  const res = new Reply();
+  await restoreBlock({ params: { id: 'b2' } } as unknown as Request, res as unknown as Response);
+
+  assert.equal(res.statusCode, 200);
+  assert.equal(res.body.id, 'b2');
+  assert.equal(db.block.rows.find(b => b.id === 'b2')!.archived, false);
+  assert.equal(db.block.rows.find(b => b.id === 'b0')!.children, '["b1","b3","b2"]');
+
+  t.mock.restoreAll();
+  assert.equal(await new SyncService(root).syncPageToDisk('page1', 'p1'), null);
+  const page = await fs.readFile(path.join(root, 'src', 'pages', 'Home.tsx'), 'utf-8');
+  assert.deepEqual([...page.matchAll(/@akasha-block id="(\w+)"/g)].map(m => m[1]), ['b0', 'b1', 'b3', 'b2']);
+});
+
+test('syncBlocks writes only the rows that changed since the last save', async t => {
+  const { db } = await useProject(t, {});
+  const blocks = [
+    { id: 'n0', block_type: 'Container', name: 'Root', children: ['n1', 'n2'] },
+    { id: 'n1', block_type: 'Text', name: 'Title', parent_id: 'n0', properties: { text: 'Hello' } },
+    { id: 'n2', block_type: 'Button', name: 'Buy', parent_id: 'n0', properties: { text: 'Buy' } },
+  ];
+  const save = async (payload: unknown[]) => {
+    const res = new Reply();
+    await syncBlocks({ body: { page_id: 'page1', blocks: payload } } as unknown as Request, res as unknown as Response);
+    return res.body;
+  };
+
+  assert.deepEqual(await save(blocks), { success: true, created: 3, updated: 0, removed: 1 });
+
+  const create = t.mock.method(db.block, 'create');
+  const update = t.mock.method(db.block, 'update');
+  const deleteMany = t.mock.method(db.block, 'deleteMany');
+  const edited = blocks.map(b => b.id === 'n1' ? { ...b, properties: { text: 'Hello again' } } : b);
+
+  assert.deepEqual(await save(edited), { success: true, created: 0, updated: 1, removed: 0 });
+  assert.deepEqual(update.mock.calls.map(call => call.arguments[0].where), [{ id: 'n1' }]);
+  assert.equal(create.mock.callCount() + deleteMany.mock.callCount(), 0);
+  assert.equal(JSON.parse(db.block.rows.find(b => b.id === 'n1')!.properties).text, 'Hello again');
+});
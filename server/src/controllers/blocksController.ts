@@ -1,5 +1,38 @@
 import type { Request, Response } from 'express';
 import prisma from '../lib/prisma.js';
+import { AddBlockCommand, DuplicateBlockCommand, ExtractComponentCommand, MoveBlockCommand, RemoveBlocksCommand, UpdateBlockPropertyCommand, getCommandDb, movesUnderItself, subtreeIds } from '../services/commands.js';
+import { ClipboardError, PasteBlocksCommand, parseClipboard, serializeSubtree } from '../services/clipboard.js';
+import { getCommandStack } from '../services/commandLog.js';
+import { autoSyncBlockPage, autoSyncPages, blockPageIds } from '../services/autoSync.js';
+import { diffBlocks, type BlockRow } from '../services/blockSnapshot.js';
+import { CUSTOM_BLOCK_PREFIX, customBlockName, pageRootIds } from '../services/projectSchema.js';
+import { validatePosition } from '../services/positioning.js';
+import { validateIdentifier } from '../utils/identifiers.js';
+
+function parseJson<T>(value: string, fallback: T): T {
+    try { return JSON.parse(value) as T; } catch { return fallback; }
+}
+
+function toBlockResponse(b: any, pageId: string | null) {
+    const properties = parseJson<Record<string, any>>(b.properties, {});
+    return {
+        id: b.id,
+        block_type: b.blockType,
+        name: b.name,
+        parent_id: b.parentId || undefined,
+        page_id: pageId || undefined,
+        properties,
+        styles: parseJson(b.styles, {}),
+        responsive_styles: parseJson(b.responsiveStyles, {}),
+        classes: parseJson(b.classes, []),
+        event_handlers: parseJson(b.events, []),
+        bindings: parseJson(b.bindings, {}),
+        children: parseJson(b.children, []),
+        order: b.order,
+        archived: b.archived,
+        component_id: properties.component_id,
+    };
+}
 
 export async function syncBlocks(req: Request, res: Response) {
     try {
@@ -10,7 +43,14 @@ export async function syncBlocks(req: Request, res: Response) {
             return;
         }
 
-        const page = await prisma.page.findUnique({
+        const positionError = blocks.map((b: any) => validatePosition(b?.properties?.position)).find(Boolean);
+        if (positionError) {
+            res.status(400).json({ error: positionError });
+            return;
+        }
+
+        const db = getCommandDb();
+        const page = await db.page.findUnique({
             where: { id: page_id },
             select: { projectId: true, idRoot: true }
         });
@@ -22,35 +62,310 @@ export async function syncBlocks(req: Request, res: Response) {
 
         const projectId = page.projectId;
 
-        // removed $transaction to support MongoDB standalone
-        await prisma.block.deleteMany({ where: { pageId: page.idRoot } });
-
-        const operations = blocks.map((b: any, index: number) => {
-            return prisma.block.create({
-                data: {
-                    id: b.id,
-                    projectId: projectId as string,
-                    pageId: page.idRoot,
-                    parentId: b.parent_id || null,
-                    blockType: b.block_type,
-                    name: b.name,
-                    properties: JSON.stringify(b.properties || {}),
-                    styles: JSON.stringify(b.styles || {}),
-                    responsiveStyles: JSON.stringify(b.responsive_styles || {}),
-                    classes: JSON.stringify(b.classes || []),
-                    events: JSON.stringify(b.event_handlers || []),
-                    bindings: JSON.stringify(b.bindings || {}),
-                    children: JSON.stringify(b.children || []),
-                    order: index
-                }
-            });
-        });
+        const rows: BlockRow[] = blocks.map((b: any, index: number) => ({
+            id: b.id,
+            projectId: projectId as string,
+            pageId: page.idRoot,
+            parentId: b.parent_id || null,
+            blockType: b.block_type,
+            name: b.name,
+            properties: JSON.stringify(b.properties || {}),
+            styles: JSON.stringify(b.styles || {}),
+            responsiveStyles: JSON.stringify(b.responsive_styles || {}),
+            classes: JSON.stringify(b.classes || []),
+            events: JSON.stringify(b.event_handlers || []),
+            bindings: JSON.stringify(b.bindings || {}),
+            children: JSON.stringify(b.children || []),
+            order: index,
+            archived: false,
+        }));
 
-        await Promise.all(operations);
+        // Only rows that differ from what's stored are written, so an edit to one block
+        // touches one row. No $transaction, to support MongoDB standalone.
+        const stored = await db.block.findMany({ where: { pageId: page.idRoot } });
+        const diff = diffBlocks(stored, rows);
 
-        res.json({ success: true });
+        if (diff.removed.length) {
+            await db.block.deleteMany({ where: { id: { in: diff.removed } } });
+        }
+        await Promise.all([
+            ...diff.created.map(data => db.block.create({ data })),
+            ...diff.updated.map(({ id, ...data }) => db.block.update({ where: { id }, data })),
+        ]);
+
+        res.json({
+            success: true,
+            created: diff.created.length,
+            updated: diff.updated.length,
+            removed: diff.removed.length,
+        });
     } catch (error) {
         console.error("Sync error:", error);
         res.status(500).json({ error: 'Failed to sync blocks' });
     }
 }
+
+export async function addBlock(req: Request, res: Response) {
+    try {
+        const { projectId, block_type, name, parent_id, page_id, properties } = req.body;
+        if (!projectId || !block_type) {
+            res.status(400).json({ error: 'projectId and block_type are required' });
+            return;
+        }
+
+        let blockType = block_type;
+        if (typeof block_type === 'string' && block_type.toLowerCase().startsWith(CUSTOM_BLOCK_PREFIX)) {
+            const customName = customBlockName(block_type) || '';
+            const nameError = validateIdentifier(customName, 'component');
+            if (nameError) { res.status(400).json({ error: nameError }); return; }
+            blockType = `${CUSTOM_BLOCK_PREFIX}${customName}`;
+        }
+
+        let pageInternalId: string | null = null;
+        if (page_id) {
+            const page = await getCommandDb().page.findUnique({ where: { id: page_id }, select: { idRoot: true } });
+            if (!page) { res.status(404).json({ error: 'Page not found' }); return; }
+            pageInternalId = page.idRoot;
+        }
+
+        const command = new AddBlockCommand({
+            projectId,
+            blockType,
+            name: name || customBlockName(blockType) || blockType,
+            parentId: parent_id || null,
+            pageId: pageInternalId,
+            properties,
+        });
+        await getCommandStack(projectId).execute(command);
+
+        const block = await getCommandDb().block.findUnique({ where: { id: command.blockId as string } });
+        res.json(toBlockResponse(block, page_id || null));
+    } catch (error) {
+        console.error('Error adding block:', error);
+        res.status(500).json({ error: 'Failed to add block' });
+    }
+}
+
+export async function updateBlockProperty(req: Request, res: Response) {
+    try {
+        const { id } = req.params;
+        const { property, value } = req.body;
+        if (!property) { res.status(400).json({ error: 'property is required' }); return; }
+        if (property === 'position') {
+            const positionError = validatePosition(value);
+            if (positionError) { res.status(400).json({ error: positionError }); return; }
+        }
+
+        const block = await getCommandDb().block.findUnique({ where: { id: id as string }, select: { projectId: true } });
+        if (!block) { res.status(404).json({ error: 'Block not found' }); return; }
+
+        await getCommandStack(block.projectId).execute(
+            new UpdateBlockPropertyCommand(block.projectId, id as string, property, value)
+        );
+        await autoSyncBlockPage(block.projectId, id as string);
+        res.json({ success: true });
+    } catch (error) {
+        console.error('Error updating block property:', error);
+        res.status(500).json({ error: 'Failed to update block property' });
+    }
+}
+
+export async function moveBlock(req: Request, res: Response) {
+    try {
+        const { id } = req.params;
+        const { new_parent_id, index } = req.body;
+        if (typeof index !== 'number' || Number.isNaN(index)) {
+            res.status(400).json({ error: 'index must be a number' });
+            return;
+        }
+
+        const block = await prisma.block.findUnique({ where: { id: id as string }, select: { projectId: true } });
+        if (!block) { res.status(404).json({ error: 'Block not found' }); return; }
+        if (await movesUnderItself(block.projectId, id as string, new_parent_id || null)) {
+            res.status(400).json({ error: 'A block cannot be moved into itself or one of its descendants' });
+            return;
+        }
+
+        await getCommandStack(block.projectId).execute(
+            new MoveBlockCommand(block.projectId, id as string, new_parent_id || null, index)
+        );
+        res.json({ success: true });
+    } catch (error) {
+        console.error('Error moving block:', error);
+        res.status(500).json({ error: 'Failed to move block' });
+    }
+}
+
+export async function duplicateBlock(req: Request, res: Response) {
+    try {
+        const { id } = req.params;
+        const block = await prisma.block.findUnique({
+            where: { id: id as string },
+            select: { projectId: true, page: { select: { id: true } } },
+        });
+        if (!block) { res.status(404).json({ error: 'Block not found' }); return; }
+
+        const command = new DuplicateBlockCommand(block.projectId, id as string);
+        await getCommandStack(block.projectId).execute(command);
+
+        const copy = await prisma.block.findUnique({ where: { id: command.rootId as string } });
+        res.json(toBlockResponse(copy, block.page?.id || null));
+    } catch (error) {
+        console.error('Error duplicating block:', error);
+        res.status(500).json({ error: 'Failed to duplicate block' });
+    }
+}
+
+export async function extractComponent(req: Request, res: Response) {
+    try {
+        const { id } = req.params;
+        const { name } = req.body;
+        const nameError = validateIdentifier(name, 'component');
+        if (nameError) { res.status(400).json({ error: nameError }); return; }
+
+        const block = await prisma.block.findUnique({
+            where: { id: id as string },
+            select: { projectId: true, blockType: true, page: { select: { id: true } } },
+        });
+        if (!block) { res.status(404).json({ error: 'Block not found' }); return; }
+        if (block.blockType === 'component') { res.status(400).json({ error: 'Block is already a component' }); return; }
+
+        const command = new ExtractComponentCommand(block.projectId, id as string, name);
+        await getCommandStack(block.projectId).execute(command);
+
+        const instance = await prisma.block.findUnique({ where: { id: command.instanceId } });
+        res.json({ component_id: command.componentId, instance: toBlockResponse(instance, block.page?.id || null) });
+    } catch (error) {
+        console.error('Error extracting component:', error);
+        res.status(500).json({ error: 'Failed to extract component' });
+    }
+}
+
+/** The block's subtree as clipboard JSON, for pasting here or into another project */
+export async function copyBlock(req: Request, res: Response) {
+    try {
+        const { id } = req.params;
+        const block = await prisma.block.findUnique({ where: { id: id as string }, select: { projectId: true } });
+        if (!block) { res.status(404).json({ error: 'Block not found' }); return; }
+
+        const data = await serializeSubtree(block.projectId, id as string);
+        res.json({ data });
+    } catch (error) {
+        if (error instanceof ClipboardError) { res.status(error.status).json({ error: error.message }); return; }
+        console.error('Error copying block:', error);
+        res.status(500).json({ error: 'Failed to copy block' });
+    }
+}
+
+export async function pasteBlocks(req: Request, res: Response) {
+    try {
+        const { data, parent_id } = req.body;
+        if (!data || !parent_id) {
+            res.status(400).json({ error: 'data and parent_id are required' });
+            return;
+        }
+
+        const payload = parseClipboard(data);
+        const parent = await prisma.block.findUnique({
+            where: { id: parent_id },
+            select: { projectId: true, page: { select: { id: true } } },
+        });
+        if (!parent) { res.status(404).json({ error: 'Parent block not found' }); return; }
+
+        const command = new PasteBlocksCommand(parent.projectId, payload, parent_id);
+        await getCommandStack(parent.projectId).execute(command);
+
+        const root = await prisma.block.findUnique({ where: { id: command.rootId as string } });
+        res.json(toBlockResponse(root, parent.page?.id || null));
+    } catch (error) {
+        if (error instanceof ClipboardError) { res.status(error.status).json({ error: error.message }); return; }
+        console.error('Error pasting blocks:', error);
+        res.status(500).json({ error: 'Failed to paste blocks' });
+    }
+}
+
+/**
+ * Archive or delete every block in `ids` with its descendants, as one undo step and one
+ * project change, then write each affected page to disk once. Each id reports its own
+ * result, so a stale id doesn't fail the rest.
+ */
+async function removeBlocks(req: Request, res: Response, mode: 'archive' | 'delete') {
+    const { projectId, ids } = req.body;
+    if (!projectId || typeof projectId !== 'string') {
+        res.status(400).json({ error: 'Project ID required' });
+        return;
+    }
+    if (!Array.isArray(ids) || ids.length === 0 || !ids.every(id => typeof id === 'string')) {
+        res.status(400).json({ error: 'ids must be a non-empty array of block ids' });
+        return;
+    }
+
+    const blockIds = [...new Set<string>(ids)];
+    // Deleted blocks can't be traced to their page afterwards
+    const pageIds = await blockPageIds(blockIds);
+    const command = new RemoveBlocksCommand(projectId, blockIds, mode);
+    await getCommandStack(projectId).execute(command);
+    if (command.results.some(result => result.success)) await autoSyncPages(projectId, pageIds);
+    res.json({ results: command.results });
+}
+
+export async function bulkArchiveBlocks(req: Request, res: Response) {
+    try {
+        await removeBlocks(req, res, 'archive');
+    } catch (error) {
+        console.error('Error archiving blocks:', error);
+        res.status(500).json({ error: 'Failed to archive blocks' });
+    }
+}
+
+export async function bulkDeleteBlocks(req: Request, res: Response) {
+    try {
+        await removeBlocks(req, res, 'delete');
+    } catch (error) {
+        console.error('Error deleting blocks:', error);
+        res.status(500).json({ error: 'Failed to delete blocks' });
+    }
+}
+
+/**
+ * Unarchive a block together with its archived descendants. It goes back under its parent
+ * when that block is still live, and under its page's root block otherwise.
+ */
+export async function restoreBlock(req: Request, res: Response) {
+    try {
+        const { id } = req.params;
+        const db = getCommandDb();
+        const block = await db.block.findUnique({ where: { id: id as string } });
+        if (!block) { res.status(404).json({ error: 'Block not found' }); return; }
+        if (!block.archived) { res.status(400).json({ error: 'Block is not archived' }); return; }
+
+        const rows = await db.block.findMany({ where: { projectId: block.projectId } });
+        const blocks = new Map(rows.map(b => [b.id, b]));
+        const ids = subtreeIds(blocks, block.id).filter(blockId => blocks.get(blockId)?.archived);
+        await db.block.updateMany({ where: { id: { in: ids } }, data: { archived: false } });
+
+        let parent = block.parentId ? blocks.get(block.parentId) : undefined;
+        if (!parent || parent.archived) {
+            const page = block.pageId ? await db.page.findUnique({ where: { idRoot: block.pageId } }) : null;
+            const rootId = pageRootIds(parseJson<Record<string, any>>(page?.meta ?? '{}', {}))[0];
+            parent = rootId && rootId !== block.id ? blocks.get(rootId) : undefined;
+            if (parent?.archived) parent = undefined;
+            if (parent?.id !== block.parentId) {
+                await db.block.update({ where: { id: block.id }, data: { parentId: parent?.id ?? null } });
+            }
+        }
+        if (parent) {
+            const children = parseJson<string[]>(parent.children, []);
+            if (!children.includes(block.id)) {
+                await db.block.update({ where: { id: parent.id }, data: { children: JSON.stringify([...children, block.id]) } });
+            }
+        }
+
+        const restored = await db.block.findUnique({ where: { id: block.id } });
+        const restoredPage = restored?.pageId ? await db.page.findUnique({ where: { idRoot: restored.pageId }, select: { id: true } }) : null;
+        res.json(toBlockResponse(restored, restoredPage?.id || null));
+    } catch (error) {
+        console.error('Error restoring block:', error);
+        res.status(500).json({ error: 'Failed to restore block' });
+    }
+}
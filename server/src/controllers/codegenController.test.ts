@@ -0,0 +1,80 @@
+import { test } from 'node:test';
+import assert from 'node:assert/strict';
+import type { Request, Response } from 'express';
+import { generateLogic, previewGeneration } from './codegenController.js';
+import { useMemoryDb } from '../services/memoryDb.js';
+import { getProjectStore, setProjectStore, type ProjectStore } from '../services/projectStore.js';
+
+class Reply {
+  public statusCode = 200;
+  public body: any;
+
+  status(code: number) {
+    this.statusCode = code;
+    return this;
+  }
+
+  json(body: any) {
+    this.body = body;
+    return this;
+  }
+}
+
+test('previewGeneration lists the files every generator would write without recording a sync', async t => {
+  const page = { id: 'home', projectId: 'p1', idRoot: 'root-home', name: 'Home', path: '/', isDynamic: false, meta: '{}', archived: false };
+  const db = useMemoryDb(t, {
+    pages: [page],
+    blocks: [{ id: 'b1', projectId: 'p1', pageId: 'root-home', blockType: 'Text', name: 'Title', properties: '{"text":"Hi"}' }],
+  });
+  const project = { id: 'p1', name: 'Shop', description: '', settings: '{}', rootPath: null };
+  const product = {
+    id: 'm1', name: 'Product', archived: false,
+    schema: JSON.stringify({ fields: [{ id: 'f1', name: 'title', field_type: 'string', required: true, unique: false, primary_key: false }], relations: [] }),
+  };
+  const previous = getProjectStore();
+  setProjectStore({
+    getProjectById: async () => project,
+    getProjectWithEntities: async () => ({ ...project, pages: [page], blocks: [], dataModels: [product], apis: [], logicFlows: [], variables: [] }),
+  } as unknown as ProjectStore);
+  t.after(() => setProjectStore(previous));
+
+  const res = new Reply();
+  await previewGeneration({ query: { projectId: 'p1' } } as unknown as Request, res as unknown as Response);
+
+  assert.equal(res.statusCode, 200);
+  const files: Array<{ path: string; size: number; kind: string }> = res.body.files;
+  const kindOf = (path: string) => files.find(f => f.path === path)?.kind;
+  assert.equal(kindOf('src/App.tsx'), 'frontend');
+  assert.equal(kindOf('src/pages/Home.tsx'), 'frontend');
+  assert.equal(kindOf('src/main.ts'), 'backend');
+  assert.equal(kindOf('src/product/product.service.ts'), 'backend');
+  assert.ok(files.some(f => f.kind === 'database' && f.path.startsWith('database/migrations/')));
+  assert.ok(files.every(f => f.size > 0));
+  assert.ok(files.every(f => !('content' in f)));
+  assert.equal(db.page.rows[0]!.meta, '{}');
+});
+
+test('generateLogic returns the flow runner and a file per flow of the context, and no pages', async t => {
+  const project = { id: 'p1', name: 'Shop', description: '', settings: '{}', rootPath: null };
+  const flow = (id: string, name: string, context: string) => ({ id, name, context, trigger: '{"type":"manual"}', nodes: '[]', edges: '[]', archived: false });
+  const previous = getProjectStore();
+  setProjectStore({
+    getProjectWithEntities: async () => ({
+      ...project,
+      pages: [{ id: 'home', idRoot: 'root-home', name: 'Home', path: '/', isDynamic: false, meta: '{}', archived: false }],
+      blocks: [], dataModels: [], apis: [], variables: [],
+      logicFlows: [flow('f1', 'Add to cart', 'frontend'), flow('f2', 'Send receipt', 'backend'), flow('f3', 'Open menu', 'frontend')],
+    }),
+  } as unknown as ProjectStore);
+  t.after(() => setProjectStore(previous));
+
+  const res = new Reply();
+  await generateLogic({ body: { projectId: 'p1' }, query: { context: 'frontend' } } as unknown as Request, res as unknown as Response);
+
+  assert.equal(res.statusCode, 200);
+  const paths: string[] = res.body.files.map((f: { path: string }) => f.path);
+  assert.deepEqual(paths.sort(), ['src/logic/flow-runner.ts', 'src/logic/flows/addToCart.ts', 'src/logic/flows/openMenu.ts']);
+  const runner = res.body.files.find((f: { path: string }) => f.path === 'src/logic/flow-runner.ts').content;
+  assert.match(runner, /"f1": addToCart,/);
+  assert.doesNotMatch(runner, /f2/);
+});
@@ -1,47 +1,350 @@
 import type { Request, Response } from 'express';
 import prisma from '../lib/prisma.js';
+import { getProjectStore } from '../services/projectStore.js';
+import fs from 'fs-extra';
+import os from 'os';
+import path from 'path';
 import { GeneratorService } from '../services/generator.js';
-import { SyncService } from '../services/sync.js';
+import { BackendGeneratorService } from '../services/backendGenerator.js';
+import { HtmlGeneratorService } from '../services/htmlGenerator.js';
+import { VueGeneratorService } from '../services/vueGenerator.js';
+import { PostmanGeneratorService } from '../services/postmanGenerator.js';
+import { ComposeGeneratorService } from '../services/composeGenerator.js';
+import { DocsGeneratorService } from '../services/docsGenerator.js';
+import { LogicCompiler } from '../services/logicCompiler.js';
+import { generationReport, generationWarnings } from '../services/generationReport.js';
+import { codeStyle, styleFiles } from '../services/codeStyle.js';
+import { loadProjectSchema, type GeneratedFile, type ProjectSchema } from '../services/projectSchema.js';
+import { buildTarGzBuffer, buildZipBuffer } from '../services/bundle.js';
+import { DatabaseGeneratorService, type SchemaSnapshot } from '../services/databaseGenerator.js';
+import { SyncService, sendSyncError, type SyncConflict } from '../services/sync.js';
+import { requireRoot, sendRootError } from '../utils/projectRoot.js';
 
 const generatorService = new GeneratorService();
 
 export async function syncProject(req: Request, res: Response) {
     try {
-        const { projectId } = req.body;
-        if (!projectId) { res.status(400).json({ error: 'Project ID required' }); return; }
-
-        const project = await prisma.project.findUnique({ where: { id: projectId } });
-        if (!project || !project.rootPath) {
-            res.status(404).json({ error: 'Project not found or no root path' });
-            return;
-        }
-
-        const syncService = new SyncService(project.rootPath);
+        const { projectId, force } = req.body;
+        // The first sync is what fills the folder, so it may not exist yet
+        const syncService = new SyncService(await requireRoot(projectId, { create: true }));
         const pages = await prisma.page.findMany({ where: { projectId: projectId as string } });
+        const conflicts: SyncConflict[] = [];
         for (const page of pages) {
-            await syncService.syncPageToDisk(page.id, projectId);
+            const conflict = await syncService.syncPageToDisk(page.id, projectId, { force: force === true });
+            if (conflict) conflicts.push(conflict);
         }
 
-        res.json({ success: true, message: 'Project synced to disk' });
+        if (conflicts.length > 0) {
+            res.json({ success: false, message: `${conflicts.length} page(s) changed on disk and in the editor; not overwritten`, conflicts });
+            return;
+        }
+        res.json({ success: true, message: 'Project synced to disk', conflicts });
     } catch (error) {
+        if (sendRootError(res, error) || sendSyncError(res, error)) return;
         console.error('Sync error:', error);
         res.status(500).json({ error: 'Failed to sync project' });
     }
 }
 
+/** Pull properties edited in the page files back into the project's blocks */
+export async function syncFromDisk(req: Request, res: Response) {
+    try {
+        const { projectId } = req.body;
+        const syncService = new SyncService(await requireRoot(projectId));
+        const updated = await syncService.syncProjectFromDisk(projectId as string);
+        res.json({ success: true, updated });
+    } catch (error) {
+        if (sendRootError(res, error) || sendSyncError(res, error)) return;
+        console.error('Sync from disk error:', error);
+        res.status(500).json({ error: 'Failed to sync project from disk' });
+    }
+}
+
 export async function exportProject(req: Request, res: Response) {
     try {
         const { projectId, exportPath } = req.body;
-        const project = await prisma.project.findUnique({ where: { id: projectId } });
+        const project = await getProjectStore().getProjectById(projectId);
         if (!project) throw new Error("Project not found");
 
         const targetDir = exportPath || project.rootPath;
         if (!targetDir) throw new Error("No target directory specified");
 
-        const result = await generatorService.generateFrontend(projectId, targetDir);
-        res.json(result);
+        const schema = await loadProjectSchema(projectId);
+        const warnings = generationWarnings(schema!, 'frontend');
+        if (schema!.settings.build?.frontend_framework === 'vue') {
+            for (const file of new VueGeneratorService(schema!).generate()) {
+                await fs.outputFile(path.join(targetDir, file.path), file.content);
+            }
+            res.json({ success: true, path: targetDir, warnings });
+            return;
+        }
+
+        // Sync state describes the project's own root; exporting elsewhere mustn't overwrite it
+        const result = await generatorService.generateFrontend(projectId, targetDir, { recordState: targetDir === project.rootPath });
+        res.json({ ...result, warnings });
     } catch (error) {
+        if (sendSyncError(res, error)) return;
         console.error('Export error:', error);
         res.status(500).json({ error: 'Failed to export project' });
     }
 }
+
+export async function generateBackend(req: Request, res: Response) {
+    try {
+        const { projectId } = req.body;
+        if (!projectId) { res.status(400).json({ error: 'Project ID required' }); return; }
+
+        const project = await loadProjectSchema(projectId);
+        if (!project) { res.status(404).json({ error: 'Project not found' }); return; }
+
+        const files = new BackendGeneratorService(project).generate();
+        res.json(generationReport(project, 'backend', files));
+    } catch (error) {
+        console.error('Backend generation error:', error);
+        res.status(500).json({ error: 'Failed to generate backend' });
+    }
+}
+
+interface PreviewEntry {
+    path: string;
+    size: number;
+    kind: 'frontend' | 'backend' | 'logic' | 'database';
+}
+
+/** Relative paths of every file under `dir` */
+async function listFiles(dir: string, prefix = ''): Promise<string[]> {
+    const files: string[] = [];
+    for (const entry of await fs.readdir(path.join(dir, prefix), { withFileTypes: true })) {
+        const relative = prefix ? `${prefix}/${entry.name}` : entry.name;
+        if (entry.isDirectory()) files.push(...await listFiles(dir, relative));
+        else files.push(relative);
+    }
+    return files;
+}
+
+/**
+ * The React frontend is only written to disk, so it is generated into a scratch directory
+ * that is listed and removed again
+ */
+async function previewFrontend(projectId: string): Promise<GeneratedFile[]> {
+    const dir = await fs.mkdtemp(path.join(os.tmpdir(), 'akasha-preview-'));
+    try {
+        await generatorService.generateFrontend(projectId, dir, { recordState: false });
+        return Promise.all((await listFiles(dir)).map(async file => ({
+            path: file,
+            content: await fs.readFile(path.join(dir, file), 'utf-8'),
+        })));
+    } finally {
+        await fs.remove(dir);
+    }
+}
+
+/** The frontend a project generates, in its configured framework, without writing it anywhere */
+async function frontendFiles(project: ProjectSchema, projectId: string): Promise<GeneratedFile[]> {
+    return project.settings.build?.frontend_framework === 'vue'
+        ? new VueGeneratorService(project).generate()
+        : previewFrontend(projectId);
+}
+
+/**
+ * Every file the frontend, backend, logic and database generators would produce, without
+ * writing anything: paths are relative to each generator's output root.
+ */
+export async function previewGeneration(req: Request, res: Response) {
+    try {
+        const { projectId } = req.query;
+        if (!projectId || typeof projectId !== 'string') { res.status(400).json({ error: 'Project ID required' }); return; }
+
+        const project = await loadProjectSchema(projectId);
+        if (!project) { res.status(404).json({ error: 'Project not found' }); return; }
+
+        const entry = (file: GeneratedFile, kind: PreviewEntry['kind']): PreviewEntry => ({
+            path: file.path,
+            size: Buffer.byteLength(file.content, 'utf-8'),
+            kind,
+        });
+
+        const frontend = await frontendFiles(project, projectId);
+        const backend = new BackendGeneratorService(project).generate();
+        // A full migration from an empty database; the stored snapshot is left alone
+        const database = new DatabaseGeneratorService(project).generateMigrations(null).files;
+
+        const files = [
+            ...frontend.map(f => entry(f, 'frontend')),
+            ...backend.map(f => entry(f, f.path.startsWith('src/logic/') ? 'logic' : 'backend')),
+            ...database.map(f => entry(f, 'database')),
+        ];
+        res.json({ files });
+    } catch (error) {
+        if (sendSyncError(res, error)) return;
+        console.error('Generation preview error:', error);
+        res.status(500).json({ error: 'Failed to preview generated files' });
+    }
+}
+
+export async function generateHtml(req: Request, res: Response) {
+    try {
+        const { projectId } = req.body;
+        if (!projectId) { res.status(400).json({ error: 'Project ID required' }); return; }
+
+        const project = await loadProjectSchema(projectId);
+        if (!project) { res.status(404).json({ error: 'Project not found' }); return; }
+
+        const files = new HtmlGeneratorService(project).generate();
+        res.json(generationReport(project, 'frontend', files));
+    } catch (error) {
+        console.error('HTML generation error:', error);
+        res.status(500).json({ error: 'Failed to generate HTML' });
+    }
+}
+
+export async function generatePostman(req: Request, res: Response) {
+    try {
+        const { projectId } = req.body;
+        if (!projectId) { res.status(400).json({ error: 'Project ID required' }); return; }
+
+        const project = await loadProjectSchema(projectId);
+        if (!project) { res.status(404).json({ error: 'Project not found' }); return; }
+
+        const file = new PostmanGeneratorService(project).generate();
+        res.json({ files: [file] });
+    } catch (error) {
+        console.error('Postman generation error:', error);
+        res.status(500).json({ error: 'Failed to generate Postman collection' });
+    }
+}
+
+export async function generateCompose(req: Request, res: Response) {
+    try {
+        const { projectId } = req.body;
+        if (!projectId) { res.status(400).json({ error: 'Project ID required' }); return; }
+
+        const project = await loadProjectSchema(projectId);
+        if (!project) { res.status(404).json({ error: 'Project not found' }); return; }
+
+        const file = new ComposeGeneratorService(project).generate();
+        res.json({ files: [file] });
+    } catch (error) {
+        console.error('Compose generation error:', error);
+        res.status(500).json({ error: 'Failed to generate docker-compose file' });
+    }
+}
+
+export async function generateDocs(req: Request, res: Response) {
+    try {
+        const { projectId } = req.body;
+        if (!projectId) { res.status(400).json({ error: 'Project ID required' }); return; }
+
+        const project = await loadProjectSchema(projectId);
+        if (!project) { res.status(404).json({ error: 'Project not found' }); return; }
+
+        const file = new DocsGeneratorService(project).generate();
+        res.json({ files: [file] });
+    } catch (error) {
+        console.error('Docs generation error:', error);
+        res.status(500).json({ error: 'Failed to generate project README' });
+    }
+}
+
+/** Only the compiled logic flows for one context, for refreshing flows without a full generate */
+export async function generateLogic(req: Request, res: Response) {
+    try {
+        const { projectId } = req.body;
+        const context = req.body.context ?? req.query.context ?? 'frontend';
+        if (!projectId) { res.status(400).json({ error: 'Project ID required' }); return; }
+        if (context !== 'frontend' && context !== 'backend') {
+            res.status(400).json({ error: 'context must be "frontend" or "backend"' });
+            return;
+        }
+
+        const project = await loadProjectSchema(projectId);
+        if (!project) { res.status(404).json({ error: 'Project not found' }); return; }
+
+        const files = new LogicCompiler().compileBundle(project.logic_flows, context, 'src/logic');
+        res.json({ files: styleFiles(files, codeStyle(project)) });
+    } catch (error) {
+        console.error('Logic generation error:', error);
+        res.status(500).json({ error: 'Failed to generate logic flows' });
+    }
+}
+
+export async function downloadZip(req: Request, res: Response) {
+    try {
+        const { projectId } = req.body;
+        if (!projectId) { res.status(400).json({ error: 'Project ID required' }); return; }
+
+        const project = await loadProjectSchema(projectId);
+        if (!project) { res.status(404).json({ error: 'Project not found' }); return; }
+
+        const fileName = `${project.name.toLowerCase().replace(/[^a-z0-9]+/g, '-') || 'akasha-project'}.zip`;
+        const bundle = buildZipBuffer(project, await frontendFiles(project, projectId));
+        res.setHeader('Content-Type', 'application/zip');
+        res.setHeader('Content-Disposition', `attachment; filename="${fileName}"`);
+        res.send(bundle);
+    } catch (error) {
+        if (sendSyncError(res, error)) return;
+        console.error('Zip export error:', error);
+        res.status(500).json({ error: 'Failed to build project bundle' });
+    }
+}
+
+export async function downloadTar(req: Request, res: Response) {
+    try {
+        const { projectId } = req.body;
+        if (!projectId) { res.status(400).json({ error: 'Project ID required' }); return; }
+
+        const project = await loadProjectSchema(projectId);
+        if (!project) { res.status(404).json({ error: 'Project not found' }); return; }
+
+        const fileName = `${project.name.toLowerCase().replace(/[^a-z0-9]+/g, '-') || 'akasha-project'}.tar.gz`;
+        const bundle = buildTarGzBuffer(project, await frontendFiles(project, projectId));
+        res.setHeader('Content-Type', 'application/gzip');
+        res.setHeader('Content-Disposition', `attachment; filename="${fileName}"`);
+        res.send(bundle);
+    } catch (error) {
+        if (sendSyncError(res, error)) return;
+        console.error('Tar export error:', error);
+        res.status(500).json({ error: 'Failed to build project bundle' });
+    }
+}
+
+/**
+ * Emit the SQL migration for whatever changed since the last run and remember the new shape
+ */
+export async function generateDatabase(req: Request, res: Response) {
+    try {
+        const { projectId } = req.body;
+        if (!projectId) { res.status(400).json({ error: 'Project ID required' }); return; }
+
+        const project = await loadProjectSchema(projectId);
+        if (!project) { res.status(404).json({ error: 'Project not found' }); return; }
+
+        const latest = await prisma.migrationSnapshot.findFirst({ where: { projectId }, orderBy: { createdAt: 'desc' } });
+        const previous = latest ? JSON.parse(latest.snapshot) as SchemaSnapshot : null;
+
+        const result = new DatabaseGeneratorService(project).generateMigrations(previous);
+        if (result.files.length > 0) {
+            await prisma.migrationSnapshot.create({
+                data: { projectId, name: result.name, snapshot: JSON.stringify(result.snapshot) }
+            });
+        }
+
+        res.json({ files: result.files });
+    } catch (error) {
+        console.error('Database generation error:', error);
+        res.status(500).json({ error: 'Failed to generate database migrations' });
+    }
+}
+
+export async function detectConflicts(req: Request, res: Response) {
+    try {
+        const { projectId } = req.query;
+        const rootPath = await requireRoot(projectId);
+        const conflicts = await new SyncService(rootPath).detectConflicts(projectId as string);
+        res.json({ conflicts });
+    } catch (error) {
+        if (sendRootError(res, error) || sendSyncError(res, error)) return;
+        console.error('Conflict detection error:', error);
+        res.status(500).json({ error: 'Failed to detect sync conflicts' });
+    }
+}
@@ -0,0 +1,48 @@
+import type { Request, Response } from 'express';
+import { getCommandStack, listCommandLog } from '../services/commandLog.js';
+
+async function step(req: Request, res: Response, direction: 'undo' | 'redo') {
+    try {
+        const { projectId } = req.body;
+        if (!projectId) { res.status(400).json({ error: 'Project ID required' }); return; }
+
+        const stack = getCommandStack(projectId);
+        const command = direction === 'undo' ? await stack.undo() : await stack.redo();
+        if (!command) { res.status(409).json({ error: `Nothing to ${direction}` }); return; }
+
+        res.json({
+            success: true,
+            description: command.description,
+            can_undo: stack.canUndo,
+            can_redo: stack.canRedo,
+        });
+    } catch (error) {
+        console.error(`Error during ${direction}:`, error);
+        res.status(500).json({ error: `Failed to ${direction}` });
+    }
+}
+
+export async function undo(req: Request, res: Response) {
+    await step(req, res, 'undo');
+}
+
+export async function redo(req: Request, res: Response) {
+    await step(req, res, 'redo');
+}
+
+export async function getLog(req: Request, res: Response) {
+    try {
+        const { projectId } = req.query;
+        if (!projectId || typeof projectId !== 'string') {
+            res.status(400).json({ error: 'Project ID required' });
+            return;
+        }
+
+        const page = Math.max(1, Number(req.query.page) || 1);
+        const limit = Math.min(200, Math.max(1, Number(req.query.limit) || 50));
+        res.json(await listCommandLog(projectId, page, limit));
+    } catch (error) {
+        console.error('Error reading command log:', error);
+        res.status(500).json({ error: 'Failed to read command log' });
+    }
+}
@@ -1,6 +1,7 @@
 import type { Request, Response } from 'express';
 import prisma from '../lib/prisma.js';
 import { randomUUID } from 'crypto';
+import { validateIdentifier } from '../utils/identifiers.js';
 
 export async function listComponents(req: Request, res: Response) {
     try {
@@ -35,6 +36,8 @@ export async function listComponents(req: Request, res: Response) {
 export async function createComponent(req: Request, res: Response) {
     try {
         const { name, description } = req.body;
+        const nameError = validateIdentifier(name, 'component');
+        if (nameError) { res.status(400).json({ error: nameError }); return; }
         res.json({ id: randomUUID(), name });
     } catch (error) {
         res.status(500).json({ error: 'Failed' });
@@ -0,0 +1,66 @@
+import { test } from 'node:test';
+import assert from 'node:assert/strict';
+import type { Request, Response } from 'express';
+import { createDataModel } from './dataModelsController.js';
+import { useMemoryDb } from '../services/memoryDb.js';
+
+class Reply {
+  public statusCode = 200;
+  public body: any;
+
+  status(code: number) {
+    this.statusCode = code;
+    return this;
+  }
+
+  json(body: any) {
+    this.body = body;
+    return this;
+  }
+}
+
+async function create(body: Record<string, unknown>) {
+  const res = new Reply();
+  await createDataModel({ body: { projectId: 'p1', ...body } } as unknown as Request, res as unknown as Response);
+  return res;
+}
+
+test('createDataModel stores a model with all of its fields from one request', async t => {
+  const db = useMemoryDb(t);
+
+  const res = await create({
+    name: 'Product',
+    fields: [
+      { name: 'title', field_type: 'string' },
+      { name: 'price', field_type: 'decimal', precision: 8 },
+      { name: 'inStock', field_type: 'boolean', required: false },
+    ],
+  });
+
+  assert.equal(res.statusCode, 200);
+  const fields = (schema: { fields: any[] }) => schema.fields.map(f => [f.name, f.field_type, f.required]);
+  const expected = [['id', 'uuid', true], ['title', 'string', true], ['price', 'decimal', true], ['inStock', 'boolean', false]];
+  assert.deepEqual(fields(res.body), expected);
+  assert.equal(db.dataModel.rows.length, 1);
+  assert.deepEqual(fields(JSON.parse(db.dataModel.rows[0]!.schema)), expected);
+  assert.deepEqual([res.body.fields[2].precision, res.body.fields[2].scale], [8, 2]);
+});
+
+test('createDataModel rejects duplicate and reserved field names without storing anything', async t => {
+  const db = useMemoryDb(t);
+
+  const duplicate = await create({ name: 'Product', fields: [{ name: 'title' }, { name: 'Title' }] });
+  assert.deepEqual([duplicate.statusCode, duplicate.body.error], [400, 'Duplicate field name "Title"']);
+
+  const reserved = await create({ name: 'Product', fields: [{ name: 'createdAt', field_type: 'datetime' }] });
+  assert.deepEqual([reserved.statusCode, reserved.body.error], [400, '"createdAt" is a reserved field name']);
+  assert.equal(db.dataModel.rows.length, 0);
+});
+
+test('createDataModel rejects a field whose name is a TypeScript reserved word', async t => {
+  const db = useMemoryDb(t);
+
+  const res = await create({ name: 'Course', fields: [{ name: 'class', field_type: 'string' }] });
+  assert.deepEqual([res.statusCode, res.body.error], [400, 'Field name "class" is a reserved word in TypeScript']);
+  assert.equal(db.dataModel.rows.length, 0);
+});
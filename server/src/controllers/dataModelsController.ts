@@ -1,5 +1,113 @@
 import type { Request, Response } from 'express';
+import crypto from 'crypto';
 import prisma from '../lib/prisma.js';
+import { validateIdentifier } from '../utils/identifiers.js';
+import { getCommandDb } from '../services/commands.js';
+import { renameModelReferences } from '../services/modelRename.js';
+import { foreignKeyColumns } from '../services/backendGenerator.js';
+import type { DataModelSchema } from '../services/projectSchema.js';
+
+/** Names the generators add to every model themselves */
+const RESERVED_FIELD_NAMES = new Set(['id', 'createdat', 'updatedat']);
+
+interface FieldInput {
+    name: string;
+    field_type?: string;
+    required?: boolean;
+    unique?: boolean;
+    default?: string;
+    description?: string;
+    enum_values?: string[];
+    precision?: number;
+    scale?: number;
+    indexed?: boolean;
+}
+
+/** Decimal digits must fit PostgreSQL's NUMERIC limits, with the scale inside the precision */
+function validateDecimalDigits(field: any): string | null {
+    if (field?.field_type !== 'decimal') return null;
+
+    const precision = field.precision ?? 10;
+    const scale = field.scale ?? 2;
+    if (!Number.isInteger(precision) || precision < 1 || precision > 65) {
+        return `Precision for decimal field "${field.name}" must be an integer from 1 to 65`;
+    }
+    if (!Number.isInteger(scale) || scale < 0 || scale > precision) {
+        return `Scale for decimal field "${field.name}" must be an integer from 0 to its precision`;
+    }
+    return null;
+}
+
+/** Enum fields need at least one value, each usable as a Prisma enum member */
+function validateEnumValues(field: any): string | null {
+    if (field?.field_type !== 'enum') return null;
+
+    const values = field.enum_values;
+    if (!Array.isArray(values) || values.length === 0) return `Enum field "${field.name}" needs at least one value`;
+    const invalid = values.find((v: unknown) => typeof v !== 'string' || !/^[A-Za-z_][A-Za-z0-9_]*$/.test(v));
+    if (invalid !== undefined) return `Invalid value "${invalid}" for enum field "${field.name}"`;
+    if (new Set(values).size !== values.length) return `Enum field "${field.name}" has duplicate values`;
+    return null;
+}
+
+/** Returns an error message for the first invalid field, or null when all are usable */
+function validateFieldInputs(fields: unknown): string | null {
+    if (!Array.isArray(fields)) return 'fields must be an array';
+
+    const seen = new Set<string>();
+    for (const field of fields) {
+        const identifierError = validateIdentifier(field?.name, 'field');
+        if (identifierError) return identifierError;
+
+        const name = field.name.trim();
+        if (!/^[A-Za-z_][A-Za-z0-9_]*$/.test(name)) return `Invalid field name "${name}"`;
+
+        const key = name.toLowerCase();
+        if (RESERVED_FIELD_NAMES.has(key)) return `"${name}" is a reserved field name`;
+        if (seen.has(key)) return `Duplicate field name "${name}"`;
+        seen.add(key);
+
+        const typeError = validateEnumValues(field) || validateDecimalDigits(field);
+        if (typeError) return typeError;
+    }
+    return null;
+}
+
+/** The owner field holds a user id, so it must be a usable field name other than the primary key */
+function validateOwnerField(ownerField: unknown): string | null {
+    if (ownerField === undefined || ownerField === null || ownerField === '') return null;
+    const identifierError = validateIdentifier(ownerField, 'field');
+    if (identifierError) return identifierError;
+    if (!/^[A-Za-z_][A-Za-z0-9_]*$/.test(ownerField as string)) return `Invalid owner field "${ownerField}"`;
+    if (RESERVED_FIELD_NAMES.has((ownerField as string).toLowerCase())) return `"${ownerField}" can't be the owner field`;
+    return null;
+}
+
+/**
+ * Each unique constraint is a group of two or more distinct fields of the model (the owner
+ * field counts, since the generator adds it when missing)
+ */
+function validateUniqueConstraints(constraints: unknown, fields: any[], ownerField?: string, foreignKeys: string[] = []): string | null {
+    if (constraints === undefined || constraints === null) return null;
+    if (!Array.isArray(constraints)) return 'unique_constraints must be an array';
+
+    const names = new Set([...fields.map(f => typeof f?.name === 'string' ? f.name.trim() : f?.name), ...foreignKeys]);
+    if (ownerField) names.add(ownerField);
+    for (const columns of constraints) {
+        if (!Array.isArray(columns) || columns.length < 2) return 'Each unique constraint needs at least two fields';
+        const missing = columns.find(c => typeof c !== 'string' || !names.has(c));
+        if (missing !== undefined) return `Unique constraint references unknown field "${missing}"`;
+        if (new Set(columns).size !== columns.length) return `Unique constraint (${columns.join(', ')}) repeats a field`;
+    }
+    return null;
+}
+
+/** Foreign key columns the model gets from its own relations and from other models' one-to-many ones */
+async function projectForeignKeys(projectId: string, model: DataModelSchema): Promise<string[]> {
+    const rows = await prisma.dataModel.findMany({ where: { projectId, archived: false } });
+    const models = rows.map(m => ({ id: m.id, name: m.name, relations: [], ...JSON.parse(m.schema) }) as DataModelSchema);
+    return foreignKeyColumns(model, models);
+}
 
 export async function listDataModels(req: Request, res: Response) {
     try {
@@ -19,6 +127,8 @@ export async function listDataModels(req: Request, res: Response) {
             return {
                 id: m.id, name: m.name,
                 fields: schema.fields || [], relations: schema.relations || [],
+                owner_field: schema.owner_field || undefined,
+                unique_constraints: schema.unique_constraints || undefined,
                 timestamps: true, soft_delete: false, archived: m.archived
             };
         });
@@ -32,19 +142,61 @@ export async function listDataModels(req: Request, res: Response) {
 
 export async function createDataModel(req: Request, res: Response) {
     try {
-        const { projectId, name } = req.body;
+        const { projectId, name, fields = [], owner_field, unique_constraints } = req.body;
+        if (!projectId || !name) {
+            res.status(400).json({ error: 'projectId and name are required' });
+            return;
+        }
+
+        const nameError = validateIdentifier(name, 'model');
+        if (nameError) {
+            res.status(400).json({ error: nameError });
+            return;
+        }
+
+        const fieldError = validateFieldInputs(fields) || validateOwnerField(owner_field);
+        if (fieldError) {
+            res.status(400).json({ error: fieldError });
+            return;
+        }
+
+        const constraintError = validateUniqueConstraints(unique_constraints, fields, owner_field);
+        if (constraintError) {
+            res.status(400).json({ error: constraintError });
+            return;
+        }
+
         const schema = {
-            fields: [{ id: 'id', name: 'id', field_type: 'uuid', required: true, unique: true, primary_key: true }],
-            relations: []
+            fields: [
+                { id: 'id', name: 'id', field_type: 'uuid', required: true, unique: true, primary_key: true },
+                ...(fields as FieldInput[]).map(f => ({
+                    id: crypto.randomUUID(),
+                    name: f.name.trim(),
+                    field_type: f.field_type || 'string',
+                    required: f.required ?? true,
+                    unique: f.unique ?? false,
+                    primary_key: false,
+                    ...(f.default !== undefined && { default: f.default }),
+                    ...(f.description && { description: f.description }),
+                    ...(f.field_type === 'enum' && { enum_values: f.enum_values }),
+                    ...(f.field_type === 'decimal' && { precision: f.precision ?? 10, scale: f.scale ?? 2 }),
+                    ...(f.indexed && { indexed: true }),
+                })),
+            ],
+            relations: [],
+            ...(owner_field && { owner_field }),
+            ...(unique_constraints?.length && { unique_constraints })
         };
 
-        const model = await prisma.dataModel.create({
+        const model = await getCommandDb().dataModel.create({
             data: { projectId, name, schema: JSON.stringify(schema) }
         });
 
         res.json({
             id: model.id, name: model.name,
             fields: schema.fields, relations: schema.relations,
+            owner_field: schema.owner_field,
+            unique_constraints: schema.unique_constraints,
             timestamps: true, soft_delete: false, archived: false
         });
     } catch (error) {
@@ -56,25 +208,66 @@ export async function createDataModel(req: Request, res: Response) {
 export async function updateDataModel(req: Request, res: Response) {
     try {
         const { id } = req.params;
-        const { name, fields, relations } = req.body;
+        const { name, fields, relations, owner_field, unique_constraints } = req.body;
 
         const model = await prisma.dataModel.findUnique({ where: { id: id as string } });
         if (!model) { res.status(404).json({ error: 'Model not found' }); return; }
 
+        if (name) {
+            const nameError = validateIdentifier(name, 'model');
+            if (nameError) { res.status(400).json({ error: nameError }); return; }
+
+            const clash = await prisma.dataModel.findFirst({ where: { projectId: model.projectId, name, archived: false, NOT: { id: model.id } } });
+            if (clash) { res.status(409).json({ error: `A model named "${name}" already exists` }); return; }
+        }
+
         const currentSchema = JSON.parse(model.schema);
+        if (fields) {
+            // Only fields added by this update are checked; existing ones (the id key included) stay as they are
+            const existingIds = new Set((currentSchema.fields || []).map((f: any) => f.id));
+            const added = (fields as any[]).filter(f => !existingIds.has(f?.id));
+            const fieldError = added.map(f => validateIdentifier(f?.name, 'field')).find(Boolean)
+                || (fields as any[]).map(f => validateEnumValues(f) || validateDecimalDigits(f)).find(Boolean);
+            if (fieldError) { res.status(400).json({ error: fieldError }); return; }
+        }
+
+        const ownerError = validateOwnerField(owner_field);
+        if (ownerError) { res.status(400).json({ error: ownerError }); return; }
+
         const newSchema = {
+            ...currentSchema,
             fields: fields || currentSchema.fields,
             relations: relations || currentSchema.relations
         };
+        // null or '' removes the owner scoping
+        if (owner_field !== undefined) {
+            if (owner_field) newSchema.owner_field = owner_field;
+            else delete newSchema.owner_field;
+        }
+        // Checked against the updated fields too, so a field can't be removed while a constraint uses it
+        if (unique_constraints !== undefined) {
+            if (unique_constraints?.length) newSchema.unique_constraints = unique_constraints;
+            else delete newSchema.unique_constraints;
+        }
+        const foreignKeys = newSchema.unique_constraints?.length
+            ? await projectForeignKeys(model.projectId, { ...newSchema, id: model.id, name: name || model.name, relations: newSchema.relations || [] })
+            : [];
+        const constraintError = validateUniqueConstraints(newSchema.unique_constraints, newSchema.fields || [], newSchema.owner_field, foreignKeys);
+        if (constraintError) { res.status(400).json({ error: constraintError }); return; }
 
         const updated = await prisma.dataModel.update({
             where: { id: id as string },
             data: { name: name || model.name, schema: JSON.stringify(newSchema) }
         });
+        if (name && name !== model.name) {
+            await renameModelReferences(model.projectId, model.id, model.name, name);
+        }
 
         res.json({
             id: updated.id, name: updated.name,
             fields: newSchema.fields, relations: newSchema.relations,
+            owner_field: newSchema.owner_field,
+            unique_constraints: newSchema.unique_constraints,
             timestamps: true, soft_delete: false, archived: updated.archived
         });
     } catch (error) {
@@ -83,6 +276,30 @@ export async function updateDataModel(req: Request, res: Response) {
     }
 }
 
+/** Rename a model and rewrite the relations, endpoints, flows and bindings that name it */
+export async function renameDataModel(req: Request, res: Response) {
+    try {
+        const { id } = req.params;
+        const { name } = req.body;
+
+        const nameError = validateIdentifier(name, 'model');
+        if (nameError) { res.status(400).json({ error: nameError }); return; }
+
+        const model = await prisma.dataModel.findUnique({ where: { id: id as string } });
+        if (!model) { res.status(404).json({ error: 'Model not found' }); return; }
+
+        const clash = await prisma.dataModel.findFirst({ where: { projectId: model.projectId, name, archived: false, NOT: { id: model.id } } });
+        if (clash) { res.status(409).json({ error: `A model named "${name}" already exists` }); return; }
+
+        const updated = await prisma.dataModel.update({ where: { id: model.id }, data: { name } });
+        const references = await renameModelReferences(model.projectId, model.id, model.name, name);
+        res.json({ id: updated.id, name: updated.name, previous_name: model.name, references });
+    } catch (error) {
+        console.error('Error renaming data model:', error);
+        res.status(500).json({ error: 'Failed to rename data model' });
+    }
+}
+
 export async function deleteDataModel(req: Request, res: Response) {
     try {
         const { id } = req.params;
@@ -92,3 +309,20 @@ export async function deleteDataModel(req: Request, res: Response) {
         res.status(500).json({ error: 'Failed' });
     }
 }
+
+export async function restoreDataModel(req: Request, res: Response) {
+    try {
+        const { id } = req.params;
+        const model = await prisma.dataModel.findUnique({ where: { id: id as string } });
+        if (!model) { res.status(404).json({ error: 'Model not found' }); return; }
+
+        const clash = await prisma.dataModel.findFirst({ where: { projectId: model.projectId, name: model.name, archived: false, NOT: { id: model.id } } });
+        if (clash) { res.status(409).json({ error: `A model named "${model.name}" already exists; rename it before restoring this one` }); return; }
+
+        await prisma.dataModel.update({ where: { id: id as string }, data: { archived: false } });
+        res.json({ success: true });
+    } catch (error) {
+        console.error('Error restoring data model:', error);
+        res.status(500).json({ error: 'Failed to restore data model' });
+    }
+}
@@ -1,13 +1,18 @@
 import type { Request, Response } from 'express';
+import crypto from 'crypto';
 import prisma from '../lib/prisma.js';
+import { getProjectStore } from '../services/projectStore.js';
 import fs from 'fs-extra';
 import path from 'path';
+import { resolveWithinRoot, PathOutsideRootError } from '../utils/path.js';
+import { WORKSPACE_DIR } from '../utils/projectRoot.js';
+import { analyzeDiagram as runAnalysis, filterIssues, isSeverity, toDataModels } from '../services/akasha.js';
 
 async function getProjectRoot(projectId: string) {
-    const project = await prisma.project.findUnique({ where: { id: projectId } });
+    const project = await getProjectStore().getProjectById(projectId);
     if (!project) throw new Error('Project not found');
     if (project.rootPath) return project.rootPath;
-    const fallbackRoot = path.join(process.cwd(), 'projects', projectId);
+    const fallbackRoot = path.join(WORKSPACE_DIR, projectId);
     await fs.ensureDir(fallbackRoot);
     return fallbackRoot;
 }
@@ -50,15 +55,29 @@ export async function createDiagram(req: Request, res: Response) {
         await fs.ensureDir(diagramsDir);
 
         const fileName = (name as string).endsWith('.drawio') ? name : `${name}.drawio`;
-        const filePath = path.join(diagramsDir, fileName);
+        const filePath = await resolveWithinRoot(diagramsDir, fileName);
 
         await fs.writeFile(filePath, content || '<mxfile host="Electron" />');
         res.json({ success: true, path: filePath });
     } catch (error) {
+        if (error instanceof PathOutsideRootError) { res.status(403).json({ error: error.message }); return; }
         res.status(500).json({ error: 'Failed' });
     }
 }
 
+async function readDiagramXml(projectId: string, name: string) {
+    const root = await getProjectRoot(projectId);
+    const diagramsDir = path.join(root, 'diagrams');
+    let filePath = await resolveWithinRoot(diagramsDir, name);
+
+    if (!fs.existsSync(filePath) && !name.endsWith('.drawio')) {
+        filePath = await resolveWithinRoot(diagramsDir, `${name}.drawio`);
+    }
+
+    if (!fs.existsSync(filePath)) return null;
+    return fs.readFile(filePath, 'utf-8');
+}
+
 export async function getDiagram(req: Request, res: Response) {
     try {
         const { projectId } = req.query;
@@ -66,10 +85,11 @@ export async function getDiagram(req: Request, res: Response) {
         if (!projectId || typeof projectId !== 'string') throw new Error('Project ID required');
 
         const root = await getProjectRoot(projectId);
-        let filePath = path.join(root, 'diagrams', name as string);
+        const diagramsDir = path.join(root, 'diagrams');
+        let filePath = await resolveWithinRoot(diagramsDir, name as string);
 
         if (!fs.existsSync(filePath) && !(name as string).endsWith('.drawio')) {
-            filePath = path.join(root, 'diagrams', `${name}.drawio`);
+            filePath = await resolveWithinRoot(diagramsDir, `${name}.drawio`);
         }
 
         if (!fs.existsSync(filePath)) { res.status(404).json({ error: 'File not found' }); return; }
@@ -77,6 +97,7 @@ export async function getDiagram(req: Request, res: Response) {
         const content = await fs.readFile(filePath, 'utf-8');
         res.send(content);
     } catch (error) {
+        if (error instanceof PathOutsideRootError) { res.status(403).json({ error: error.message }); return; }
         console.error('Error reading diagram:', error);
         res.status(500).json({ error: 'Failed to read diagram' });
     }
@@ -92,10 +113,11 @@ export async function deleteDiagram(req: Request, res: Response) {
         }
 
         const root = await getProjectRoot(projectId);
-        let filePath = path.join(root, 'diagrams', name as string);
+        const diagramsDir = path.join(root, 'diagrams');
+        let filePath = await resolveWithinRoot(diagramsDir, name as string);
 
         if (!fs.existsSync(filePath) && !(name as string).endsWith('.drawio')) {
-            filePath = path.join(root, 'diagrams', `${name}.drawio`);
+            filePath = await resolveWithinRoot(diagramsDir, `${name}.drawio`);
         }
 
         if (!fs.existsSync(filePath)) { res.status(404).json({ error: 'File not found' }); return; }
@@ -103,7 +125,112 @@ export async function deleteDiagram(req: Request, res: Response) {
         await fs.remove(filePath);
         res.json({ success: true });
     } catch (error) {
+        if (error instanceof PathOutsideRootError) { res.status(403).json({ error: error.message }); return; }
         console.error('Error deleting diagram:', error);
         res.status(500).json({ error: 'Failed to delete diagram' });
     }
 }
+
+/**
+ * Analyze a saved diagram (`name`) or raw draw.io XML (`xml`) into a product graph.
+ * `?min_severity=warning|error` drops lower-severity issues.
+ */
+export async function analyzeDiagram(req: Request, res: Response) {
+    try {
+        const { projectId, name, xml } = req.body;
+        let source: string | null = typeof xml === 'string' ? xml : null;
+
+        if (source === null) {
+            if (!projectId || !name) {
+                res.status(400).json({ error: 'Either xml or projectId and name are required' });
+                return;
+            }
+            source = await readDiagramXml(projectId, name);
+            if (source === null) { res.status(404).json({ error: 'File not found' }); return; }
+        }
+
+        const { min_severity } = req.query;
+        if (min_severity !== undefined && !isSeverity(min_severity)) {
+            res.status(400).json({ error: 'min_severity must be one of info, warning, error' });
+            return;
+        }
+
+        const result = runAnalysis(source);
+        res.json(min_severity ? filterIssues(result, min_severity) : result);
+    } catch (error) {
+        if (error instanceof PathOutsideRootError) { res.status(403).json({ error: error.message }); return; }
+        console.error('Error analyzing diagram:', error);
+        res.status(500).json({ error: 'Failed to analyze diagram' });
+    }
+}
+
+/**
+ * Turn the entities of a diagram into data models and merge them into the project.
+ * Models are matched by name; existing models only gain the fields and relations they lack.
+ * New fields and relations get fresh ids, as when they are added by hand; entities and
+ * attributes whose labels can't be used as names come back as `issues`.
+ */
+export async function diagramToModels(req: Request, res: Response) {
+    try {
+        const { projectId, name, xml } = req.body;
+        if (!projectId) {
+            res.status(400).json({ error: 'Project ID required' });
+            return;
+        }
+
+        const source = typeof xml === 'string' ? xml : name ? await readDiagramXml(projectId, name) : null;
+        if (source === null) { res.status(404).json({ error: 'File not found' }); return; }
+
+        const { models: drafts, issues } = toDataModels(runAnalysis(source).graph);
+        const existing = await prisma.dataModel.findMany({ where: { projectId, archived: false } });
+
+        // First pass: find or create every model so relations can point at real ids
+        const idByDiagramId = new Map<string, string>();
+        const records = new Map<string, { id: string; name: string; schema: any }>();
+        for (const draft of drafts) {
+            const match = existing.find(m => m.name.toLowerCase() === draft.name.toLowerCase());
+            if (match) {
+                idByDiagramId.set(draft.id, match.id);
+                records.set(draft.id, { id: match.id, name: match.name, schema: JSON.parse(match.schema) });
+                continue;
+            }
+            const created = await prisma.dataModel.create({
+                data: { projectId, name: draft.name, schema: JSON.stringify({ fields: [], relations: [] }) }
+            });
+            idByDiagramId.set(draft.id, created.id);
+            records.set(draft.id, { id: created.id, name: created.name, schema: { fields: [], relations: [] } });
+        }
+
+        const models = [];
+        for (const draft of drafts) {
+            const record = records.get(draft.id)!;
+            const fields = [...(record.schema.fields || [])];
+            const relations = [...(record.schema.relations || [])];
+
+            for (const field of draft.fields) {
+                if (fields.some((f: any) => f.name === field.name)) continue;
+                fields.push({ ...field, id: field.primary_key && field.name === 'id' ? 'id' : crypto.randomUUID() });
+            }
+            for (const relation of draft.relations) {
+                const target = idByDiagramId.get(relation.target_model_id);
+                if (!target || relations.some((r: any) => r.name === relation.name)) continue;
+                relations.push({ ...relation, id: crypto.randomUUID(), target_model_id: target });
+            }
+
+            await prisma.dataModel.update({
+                where: { id: record.id },
+                data: { schema: JSON.stringify({ ...record.schema, fields, relations }) }
+            });
+            models.push({
+                id: record.id, name: record.name, fields, relations,
+                timestamps: record.schema.timestamps ?? true, soft_delete: record.schema.soft_delete ?? false, archived: false
+            });
+        }
+
+        res.json({ models, issues });
+    } catch (error) {
+        if (error instanceof PathOutsideRootError) { res.status(403).json({ error: error.message }); return; }
+        console.error('Error converting diagram to models:', error);
+        res.status(500).json({ error: 'Failed to convert diagram to models' });
+    }
+}
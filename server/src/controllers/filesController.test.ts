@@ -0,0 +1,92 @@
+import { test } from 'node:test';
+import assert from 'node:assert/strict';
+import type { Request, Response } from 'express';
+import fs from 'fs-extra';
+import os from 'os';
+import path from 'path';
+import { createFile, readFile } from './filesController.js';
+import { getProjectStore, setProjectStore, type ProjectStore } from '../services/projectStore.js';
+
+class Reply {
+  public statusCode = 200;
+  public body: any;
+  public headers: Record<string, string> = {};
+
+  status(code: number) {
+    this.statusCode = code;
+    return this;
+  }
+
+  json(body: any) {
+    this.body = body;
+    return this;
+  }
+
+  setHeader(name: string, value: string) {
+    this.headers[name.toLowerCase()] = value;
+    return this;
+  }
+
+  end() {
+    return this;
+  }
+}
+
+test('createFile answers 400 for a missing or empty path and creates nested files', async t => {
+  const root = await fs.mkdtemp(path.join(os.tmpdir(), 'akasha-files-'));
+  const previous = getProjectStore();
+  setProjectStore({ getProjectById: async (id: string) => ({ id, rootPath: root }) } as unknown as ProjectStore);
+  t.after(async () => {
+    setProjectStore(previous);
+    await fs.remove(root);
+  });
+
+  for (const body of [{}, { path: '' }, { path: '   ' }, { path: 42 }]) {
+    const res = new Reply();
+    await createFile({ query: {}, body: { projectId: 'p1', ...body } } as unknown as Request, res as unknown as Response);
+    assert.equal(res.statusCode, 400, JSON.stringify(body));
+  }
+
+  const escaped = new Reply();
+  await createFile({ query: {}, body: { projectId: 'p1', path: '../outside.txt' } } as unknown as Request, escaped as unknown as Response);
+  assert.equal(escaped.statusCode, 403);
+
+  const created = new Reply();
+  await createFile({ query: {}, body: { projectId: 'p1', path: 'src/notes.md', content: 'hi' } } as unknown as Request, created as unknown as Response);
+  assert.equal(created.body.path, 'src/notes.md');
+  assert.equal(await fs.readFile(path.join(root, 'src/notes.md'), 'utf-8'), 'hi');
+});
+
+test('readFile answers 304 when If-None-Match carries the ETag of the current content', async t => {
+  const root = await fs.mkdtemp(path.join(os.tmpdir(), 'akasha-files-'));
+  const previous = getProjectStore();
+  setProjectStore({ getProjectById: async (id: string) => ({ id, rootPath: root }) } as unknown as ProjectStore);
+  t.after(async () => {
+    setProjectStore(previous);
+    await fs.remove(root);
+  });
+  await fs.outputFile(path.join(root, 'src/notes.md'), 'first');
+
+  const read = async (ifNoneMatch?: string) => {
+    const res = new Reply();
+    const headers: Record<string, string | undefined> = { 'if-none-match': ifNoneMatch };
+    await readFile(
+      { query: { projectId: 'p1', path: 'src/notes.md' }, body: {}, get: (name: string) => headers[name.toLowerCase()] } as unknown as Request,
+      res as unknown as Response,
+    );
+    return res;
+  };
+
+  const first = await read();
+  const etag = first.headers.etag!;
+  assert.deepEqual([first.statusCode, first.body.content], [200, 'first']);
+  assert.match(etag, /^"[0-9a-f]{32}"$/);
+
+  const cached = await read(etag);
+  assert.deepEqual([cached.statusCode, cached.body], [304, undefined]);
+
+  await fs.outputFile(path.join(root, 'src/notes.md'), 'second');
+  const changed = await read(etag);
+  assert.deepEqual([changed.statusCode, changed.body.content], [200, 'second']);
+  assert.notEqual(changed.headers.etag, etag);
+});
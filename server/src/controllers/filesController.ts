@@ -0,0 +1,130 @@
+import type { Request, Response } from 'express';
+import fs from 'fs-extra';
+import path from 'path';
+import { resolveWithinRoot, PathOutsideRootError } from '../utils/path.js';
+import { contentEtag, sendNotModified } from '../utils/etag.js';
+import { requireRoot, sendRootError } from '../utils/projectRoot.js';
+
+function toEntry(root: string, absPath: string, stat: fs.Stats) {
+    return {
+        name: path.basename(absPath),
+        path: path.relative(root, absPath).split(path.sep).join('/'),
+        is_directory: stat.isDirectory(),
+        size: stat.isDirectory() ? undefined : stat.size,
+        extension: stat.isDirectory() ? undefined : path.extname(absPath).replace(/^\./, '') || undefined,
+    };
+}
+
+/** A non-empty path from the request body; otherwise answers 400 and returns null */
+function bodyPath(res: Response, value: unknown, field: string): string | null {
+    if (typeof value === 'string' && value.trim()) return value;
+    res.status(400).json({ error: `${field} must be a non-empty path` });
+    return null;
+}
+
+/**
+ * Shared wrapper: resolves the project root, maps traversal attempts to 403.
+ */
+async function withRoot(req: Request, res: Response, label: string, fn: (root: string) => Promise<void>) {
+    try {
+        const rootPath = await requireRoot(req.query.projectId ?? req.body?.projectId);
+        await fn(await resolveWithinRoot(rootPath));
+    } catch (error: any) {
+        if (sendRootError(res, error)) return;
+        if (error instanceof PathOutsideRootError) {
+            res.status(403).json({ error: error.message });
+            return;
+        }
+        console.error(`Error ${label}:`, error);
+        res.status(500).json({ error: `Failed ${label}` });
+    }
+}
+
+export async function listDirectory(req: Request, res: Response) {
+    await withRoot(req, res, 'listing directory', async (root) => {
+        const dir = await resolveWithinRoot(root, String(req.query.path || ''));
+        if (!(await fs.pathExists(dir))) { res.status(404).json({ error: 'Directory not found' }); return; }
+
+        const names = await fs.readdir(dir);
+        const entries = await Promise.all(names.map(async (name) => {
+            const abs = path.join(dir, name);
+            return toEntry(root, abs, await fs.stat(abs));
+        }));
+        entries.sort((a, b) => Number(b.is_directory) - Number(a.is_directory) || a.name.localeCompare(b.name));
+
+        res.json({ path: path.relative(root, dir).split(path.sep).join('/'), entries });
+    });
+}
+
+export async function createFile(req: Request, res: Response) {
+    await withRoot(req, res, 'creating file', async (root) => {
+        const filePath = bodyPath(res, req.body.path, 'path');
+        if (filePath === null) return;
+        const target = await resolveWithinRoot(root, filePath);
+        if (await fs.pathExists(target)) { res.status(409).json({ error: 'File already exists' }); return; }
+
+        await fs.outputFile(target, req.body.content || '');
+        res.json(toEntry(root, target, await fs.stat(target)));
+    });
+}
+
+export async function createFolder(req: Request, res: Response) {
+    await withRoot(req, res, 'creating folder', async (root) => {
+        const folderPath = bodyPath(res, req.body.path, 'path');
+        if (folderPath === null) return;
+        const target = await resolveWithinRoot(root, folderPath);
+        await fs.ensureDir(target);
+        res.json(toEntry(root, target, await fs.stat(target)));
+    });
+}
+
+export async function renameFile(req: Request, res: Response) {
+    await withRoot(req, res, 'renaming file', async (root) => {
+        const oldPath = bodyPath(res, req.body.oldPath, 'oldPath');
+        if (oldPath === null) return;
+        const newPath = bodyPath(res, req.body.newPath, 'newPath');
+        if (newPath === null) return;
+        const from = await resolveWithinRoot(root, oldPath);
+        const to = await resolveWithinRoot(root, newPath);
+        if (from === root) { res.status(403).json({ error: 'Cannot rename the project root' }); return; }
+        if (!(await fs.pathExists(from))) { res.status(404).json({ error: 'File not found' }); return; }
+
+        await fs.move(from, to);
+        res.json(toEntry(root, to, await fs.stat(to)));
+    });
+}
+
+export async function deleteFile(req: Request, res: Response) {
+    await withRoot(req, res, 'deleting file', async (root) => {
+        const target = await resolveWithinRoot(root, String(req.query.path ?? req.body?.path ?? ''));
+        if (target === root) { res.status(403).json({ error: 'Cannot delete the project root' }); return; }
+        if (!(await fs.pathExists(target))) { res.status(404).json({ error: 'File not found' }); return; }
+
+        await fs.remove(target);
+        res.json({ success: true });
+    });
+}
+
+export async function readFile(req: Request, res: Response) {
+    await withRoot(req, res, 'reading file', async (root) => {
+        const target = await resolveWithinRoot(root, String(req.query.path || ''));
+        if (!(await fs.pathExists(target))) { res.status(404).json({ error: 'File not found' }); return; }
+
+        const content = await fs.readFile(target, 'utf-8');
+        if (sendNotModified(req, res, contentEtag(content))) return;
+        res.json({ path: req.query.path, content });
+    });
+}
+
+export async function writeFile(req: Request, res: Response) {
+    await withRoot(req, res, 'writing file', async (root) => {
+        const filePath = bodyPath(res, req.body.path, 'path');
+        if (filePath === null) return;
+        const { content } = req.body;
+        const target = await resolveWithinRoot(root, filePath);
+        if (target === root) { res.status(403).json({ error: 'Cannot write to the project root' }); return; }
+
+        await fs.outputFile(target, content ?? '');
+        res.json({ path: filePath, content });
+    });
+}
@@ -0,0 +1,38 @@
+import { test } from 'node:test';
+import assert from 'node:assert/strict';
+import type { Request, Response } from 'express';
+import { getStatus } from './gitController.js';
+import { getProjectStore, setProjectStore, type ProjectStore } from '../services/projectStore.js';
+
+class Reply {
+  public statusCode = 200;
+  public body: any;
+
+  status(code: number) {
+    this.statusCode = code;
+    return this;
+  }
+
+  json(body: any) {
+    this.body = body;
+    return this;
+  }
+}
+
+test('getStatus reports a project without a root path as NoProjectRootError', async t => {
+  const previous = getProjectStore();
+  setProjectStore({ getProjectById: async (id: string) => (id === 'p1' ? { id, rootPath: null } : null) } as unknown as ProjectStore);
+  t.after(() => setProjectStore(previous));
+
+  const status = async (projectId: string) => {
+    const res = new Reply();
+    await getStatus({ params: { projectId } } as unknown as Request, res as unknown as Response);
+    return res;
+  };
+
+  const noRoot = await status('p1');
+  assert.deepEqual([noRoot.statusCode, noRoot.body], [409, { error: 'No project root path set', code: 'NoProjectRootError' }]);
+
+  const unknown = await status('p2');
+  assert.deepEqual([unknown.statusCode, unknown.body], [404, { error: 'Project p2 not found', code: 'ProjectNotFoundError' }]);
+});
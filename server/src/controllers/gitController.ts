@@ -1,17 +1,9 @@
 import type { Request, Response } from 'express';
-import prisma from '../lib/prisma.js';
 import { simpleGit } from 'simple-git';
-import fs from 'fs-extra';
+import { requireRoot, sendRootError } from '../utils/projectRoot.js';
 
 async function getGit(projectId: string) {
-    const project = await prisma.project.findUnique({ where: { id: projectId } });
-    if (!project || !project.rootPath) {
-        throw new Error('Project not tracked in git');
-    }
-    if (!fs.existsSync(project.rootPath)) {
-        throw new Error('Project path does not exist');
-    }
-    return simpleGit(project.rootPath);
+    return simpleGit(await requireRoot(projectId));
 }
 
 export async function getStatus(req: Request, res: Response) {
@@ -21,6 +13,7 @@ export async function getStatus(req: Request, res: Response) {
         const status = await git.status();
         res.json(status);
     } catch (error: any) {
+        if (sendRootError(res, error)) return;
         res.status(500).json({ error: error.message });
     }
 }
@@ -33,6 +26,7 @@ export async function getHistory(req: Request, res: Response) {
         const log = await git.log({ maxCount: limit });
         res.json(log.all);
     } catch (error: any) {
+        if (sendRootError(res, error)) return;
         res.status(500).json({ error: error.message });
     }
 }
@@ -47,6 +41,7 @@ export async function commitChanges(req: Request, res: Response) {
         const commit = await git.commit(message);
         res.json(commit);
     } catch (error: any) {
+        if (sendRootError(res, error)) return;
         res.status(500).json({ error: error.message });
     }
 }
@@ -60,6 +55,7 @@ export async function getDiff(req: Request, res: Response) {
         const diff = await git.diff(commitId ? [String(commitId)] : []);
         res.send(diff);
     } catch (error: any) {
+        if (sendRootError(res, error)) return;
         res.status(500).json({ error: error.message });
     }
 }
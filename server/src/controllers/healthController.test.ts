@@ -0,0 +1,48 @@
+import { test } from 'node:test';
+import assert from 'node:assert/strict';
+import type { Request, Response } from 'express';
+import prisma from '../lib/prisma.js';
+import { getReadiness } from './healthController.js';
+
+class Reply {
+  public statusCode = 200;
+  public body: any;
+
+  status(code: number) {
+    this.statusCode = code;
+    return this;
+  }
+
+  json(body: any) {
+    this.body = body;
+    return this;
+  }
+}
+
+async function readiness() {
+  const res = new Reply();
+  await getReadiness({} as Request, res as unknown as Response);
+  return res;
+}
+
+test('getReadiness reports a reachable database and no loaded project on a fresh server', async t => {
+  t.mock.method(prisma, '$runCommandRaw', async () => ({ ok: 1 }));
+
+  const res = await readiness();
+
+  assert.equal(res.statusCode, 200);
+  assert.equal(res.body.status, 'ready');
+  assert.equal(res.body.db_ok, true);
+  assert.equal(res.body.project_loaded, false);
+  assert.equal(typeof res.body.workspace_writable, 'boolean');
+});
+
+test('getReadiness answers 503 when the database ping fails', async t => {
+  t.mock.method(prisma, '$runCommandRaw', async () => { throw new Error('connection refused'); });
+  t.mock.method(console, 'error', () => {});
+
+  const res = await readiness();
+
+  assert.equal(res.statusCode, 503);
+  assert.deepEqual([res.body.status, res.body.db_ok], ['unavailable', false]);
+});
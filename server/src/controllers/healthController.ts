@@ -0,0 +1,39 @@
+import type { Request, Response } from 'express';
+import fs from 'fs-extra';
+import path from 'path';
+import prisma from '../lib/prisma.js';
+import { projectWatcher } from '../services/watcher.js';
+import { WORKSPACE_DIR } from '../utils/projectRoot.js';
+
+/** The workspace folder is created on first use, so a missing one only needs a writable parent */
+async function isWritable(dir: string): Promise<boolean> {
+    const target = (await fs.pathExists(dir)) ? dir : path.dirname(dir);
+    try {
+        await fs.access(target, fs.constants.W_OK);
+        return true;
+    } catch {
+        return false;
+    }
+}
+
+/**
+ * Readiness for operators and load balancers: database reachability, the workspace folder
+ * and whether any project root is loaded (watched). Answers 503 when the database is down.
+ */
+export async function getReadiness(req: Request, res: Response) {
+    let dbOk = true;
+    try {
+        await prisma.$runCommandRaw({ ping: 1 });
+    } catch (error) {
+        console.error('[Health] Database ping failed:', error);
+        dbOk = false;
+    }
+
+    res.status(dbOk ? 200 : 503).json({
+        status: dbOk ? 'ready' : 'unavailable',
+        db_ok: dbOk,
+        workspace_path: WORKSPACE_DIR,
+        workspace_writable: await isWritable(WORKSPACE_DIR),
+        project_loaded: projectWatcher.size > 0,
+    });
+}
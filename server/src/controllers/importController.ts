@@ -0,0 +1,80 @@
+import type { Request, Response } from 'express';
+import prisma from '../lib/prisma.js';
+import { getProjectStore } from '../services/projectStore.js';
+import { endpointKey, importOpenApi as parseOpenApi, OpenApiImportError } from '../services/openapiImport.js';
+import { toApiSchema } from '../services/projectSchema.js';
+import { createProjectFromSchema, parseProjectDocument, ProjectImportError, repairProject } from '../services/projectImport.js';
+
+/**
+ * Create endpoints from an OpenAPI document. Operations whose method and path already
+ * exist in the project are reported as skipped instead of duplicated.
+ */
+export async function importOpenApi(req: Request, res: Response) {
+    try {
+        const { projectId, spec } = req.body;
+        if (!projectId) { res.status(400).json({ error: 'Project ID required' }); return; }
+        if (!spec) { res.status(400).json({ error: 'OpenAPI spec required' }); return; }
+
+        const project = await getProjectStore().getProjectById(projectId);
+        if (!project) { res.status(404).json({ error: 'Project not found' }); return; }
+
+        let apis;
+        try {
+            apis = parseOpenApi(spec);
+        } catch (error) {
+            if (error instanceof OpenApiImportError) { res.status(400).json({ error: error.message }); return; }
+            throw error;
+        }
+
+        const existing = await prisma.apiEndpoint.findMany({ where: { projectId, archived: false } });
+        const taken = new Set(existing.map(e => endpointKey(e.method, e.path)));
+
+        const created = [];
+        const skipped: { method: string; path: string }[] = [];
+        for (const { method, path, name, ...config } of apis) {
+            const key = endpointKey(method, path);
+            if (taken.has(key)) {
+                skipped.push({ method, path });
+                continue;
+            }
+            taken.add(key);
+
+            const endpoint = await prisma.apiEndpoint.create({
+                data: { projectId, method, path, name, config: JSON.stringify(config) }
+            });
+            created.push(toApiSchema(endpoint));
+        }
+
+        res.json({ imported: created, skipped });
+    } catch (error) {
+        console.error('OpenAPI import error:', error);
+        res.status(500).json({ error: 'Failed to import OpenAPI spec' });
+    }
+}
+
+/**
+ * Create a new project from an exported project JSON. Dangling references are repaired
+ * before anything is stored, and the repairs are returned alongside the new project's id.
+ */
+export async function importProject(req: Request, res: Response) {
+    try {
+        const { project } = req.body;
+        if (!project) { res.status(400).json({ error: 'Project JSON required' }); return; }
+
+        let schema;
+        try {
+            schema = parseProjectDocument(project);
+        } catch (error) {
+            if (error instanceof ProjectImportError) { res.status(400).json({ error: error.message }); return; }
+            throw error;
+        }
+
+        const report = repairProject(schema);
+        const projectId = await createProjectFromSchema(schema);
+
+        res.json({ project_id: projectId, report });
+    } catch (error) {
+        console.error('Project import error:', error);
+        res.status(500).json({ error: 'Failed to import project' });
+    }
+}
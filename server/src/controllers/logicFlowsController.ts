@@ -1,5 +1,7 @@
 import type { Request, Response } from 'express';
 import prisma from '../lib/prisma.js';
+import { FlowSimulator } from '../services/flowSimulator.js';
+import { LogicCompiler, toLogicFlow } from '../services/logicCompiler.js';
 
 export async function listLogicFlows(req: Request, res: Response) {
     try {
@@ -30,7 +32,7 @@ export async function listLogicFlows(req: Request, res: Response) {
 
 export async function createLogicFlow(req: Request, res: Response) {
     try {
-        const { projectId, name, context } = req.body;
+        const { projectId, name, context, transactional } = req.body;
         const trigger = { type: 'manual' };
         const nodes: any[] = [];
         const edges: any[] = [];
@@ -39,9 +41,11 @@ export async function createLogicFlow(req: Request, res: Response) {
             data: {
                 projectId,
                 name,
+                context: context === 'backend' ? 'backend' : 'frontend',
                 trigger: JSON.stringify(trigger),
                 nodes: JSON.stringify(nodes),
-                edges: JSON.stringify(edges)
+                edges: JSON.stringify(edges),
+                transactional: transactional === true
             }
         });
 
@@ -55,13 +59,14 @@ export async function createLogicFlow(req: Request, res: Response) {
 export async function updateLogicFlow(req: Request, res: Response) {
     try {
         const { id } = req.params;
-        const { name, trigger, nodes, edges, description } = req.body;
+        const { name, trigger, nodes, edges, description, transactional } = req.body;
 
         const updates: any = {};
         if (name) updates.name = name;
         if (trigger) updates.trigger = JSON.stringify(trigger);
         if (nodes) updates.nodes = JSON.stringify(nodes);
         if (edges) updates.edges = JSON.stringify(edges);
+        if (typeof transactional === 'boolean') updates.transactional = transactional;
 
         const flow = await prisma.logicFlow.update({
             where: { id: id as string },
@@ -93,3 +98,68 @@ export async function deleteLogicFlow(req: Request, res: Response) {
         res.status(500).json({ error: 'Failed to delete logic flow' });
     }
 }
+
+export async function restoreLogicFlow(req: Request, res: Response) {
+    try {
+        const { id } = req.params;
+        const flow = await prisma.logicFlow.findUnique({ where: { id: id as string } });
+        if (!flow) { res.status(404).json({ error: 'Logic flow not found' }); return; }
+
+        await prisma.logicFlow.update({
+            where: { id: id as string },
+            data: { archived: false }
+        });
+        res.json({ success: true });
+    } catch (error) {
+        console.error('Error restoring logic flow:', error);
+        res.status(500).json({ error: 'Failed to restore logic flow' });
+    }
+}
+
+export async function compileLogicFlow(req: Request, res: Response) {
+    try {
+        const { id } = req.params;
+        const flow = await prisma.logicFlow.findUnique({ where: { id: id as string } });
+        if (!flow) { res.status(404).json({ error: 'Logic flow not found' }); return; }
+
+        const code = new LogicCompiler().compileFlow(toLogicFlow(flow));
+        res.json({ code });
+    } catch (error) {
+        console.error('Error compiling logic flow:', error);
+        res.status(500).json({ error: 'Failed to compile logic flow' });
+    }
+}
+
+export async function validateLogicFlow(req: Request, res: Response) {
+    try {
+        const { id } = req.params;
+        const flow = await prisma.logicFlow.findUnique({ where: { id: id as string } });
+        if (!flow) { res.status(404).json({ error: 'Logic flow not found' }); return; }
+
+        const issues = new LogicCompiler().validateFlow(toLogicFlow(flow));
+        res.json({ valid: issues.length === 0, issues });
+    } catch (error) {
+        console.error('Error validating logic flow:', error);
+        res.status(500).json({ error: 'Failed to validate logic flow' });
+    }
+}
+
+export async function simulateLogicFlow(req: Request, res: Response) {
+    try {
+        const { id } = req.params;
+        const { input, state } = req.body ?? {};
+        if (state !== undefined && (typeof state !== 'object' || state === null || Array.isArray(state))) {
+            res.status(400).json({ error: 'state must be an object' });
+            return;
+        }
+
+        const flow = await prisma.logicFlow.findUnique({ where: { id: id as string } });
+        if (!flow) { res.status(404).json({ error: 'Logic flow not found' }); return; }
+
+        const result = new FlowSimulator().simulate(toLogicFlow(flow), input ?? {}, state ?? {});
+        res.json(result);
+    } catch (error) {
+        console.error('Error simulating logic flow:', error);
+        res.status(500).json({ error: 'Failed to simulate logic flow' });
+    }
+}
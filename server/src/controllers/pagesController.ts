@@ -1,6 +1,9 @@
 import type { Request, Response } from "express";
 import { randomUUID } from "node:crypto";
 import prisma from "../lib/prisma.js";
+import { contentEtag, sendNotModified } from "../utils/etag.js";
+import { validateIdentifier } from "../utils/identifiers.js";
+import { PAGE_LAYOUTS, pageLayout, pageRootIds } from "../services/projectSchema.js";
 
 function parseJsonValue<T>(value: unknown, fallback: T): T {
   if (typeof value !== "string") {
@@ -21,8 +24,10 @@ function toPageSchema(page: any) {
     id: page.id,
     name: page.name,
     path: page.path,
-    root_block_id: typeof meta.root_block_id === "string" ? meta.root_block_id : undefined,
+    root_block_id: pageRootIds(meta)[0],
+    root_block_ids: pageRootIds(meta),
     is_dynamic: page.isDynamic || false,
+    layout: pageLayout(meta),
     meta,
     archived: page.archived || false,
   };
@@ -62,7 +67,11 @@ export async function getPageContent(req: Request, res: Response) {
       archived: b.archived,
     }));
 
-    res.json({ content: JSON.stringify(serializedBlocks) });
+    // Editors poll this; an unchanged page answers 304 instead of resending every block
+    const content = JSON.stringify(serializedBlocks);
+    if (sendNotModified(req, res, contentEtag(content))) return;
+
+    res.json({ content });
   } catch (error) {
     console.error("Error getting page content:", error);
     res.status(500).json({ error: "Failed to get page content" });
@@ -78,6 +87,10 @@ export async function createPage(req: Request, res: Response) {
     const { name, path, projectId } = req.body;
     if (!projectId)
       return res.status(400).json({ error: "projectId required" });
+    if (name !== undefined) {
+      const nameError = validateIdentifier(name, "page");
+      if (nameError) return res.status(400).json({ error: nameError });
+    }
 
     const page = await prisma.page.create({
       data: {
@@ -136,13 +149,65 @@ export async function archivePage(req: Request, res: Response) {
   }
 }
 
+export async function restorePage(req: Request, res: Response) {
+  try {
+    const { id } = req.params;
+    const existing = await prisma.page.findUnique({ where: { id: id as string } });
+    if (!existing) {
+      return res.status(404).json({ error: "Page not found" });
+    }
+
+    const clash = await prisma.page.findFirst({
+      where: { projectId: existing.projectId, path: existing.path, archived: false, NOT: { idRoot: existing.idRoot } },
+    });
+    if (clash) {
+      return res.status(409).json({ error: `Page "${clash.name}" already uses the path ${existing.path}` });
+    }
+
+    const page = await prisma.page.update({
+      where: { id: id as string },
+      data: { archived: false },
+    });
+    res.json(toPageSchema(page));
+  } catch (error) {
+    console.error("Error restoring page:", error);
+    res.status(500).json({ error: "Failed to restore page" });
+  }
+}
+
 export async function updatePage(req: Request, res: Response) {
   try {
     const { id } = req.params;
-    const { name, path } = req.body;
+    const { name, path, required_role, layout } = req.body;
+    if (name !== undefined) {
+      const nameError = validateIdentifier(name, "page");
+      if (nameError) return res.status(400).json({ error: nameError });
+    }
+    if (layout && !(PAGE_LAYOUTS as readonly string[]).includes(layout)) {
+      return res.status(400).json({ error: `layout must be one of ${PAGE_LAYOUTS.join(", ")}` });
+    }
+
+    // `required_role` and `layout` live in meta; null or an empty string resets either one,
+    // making the page public again or putting it back in the default layout
+    let meta: string | undefined;
+    if (required_role !== undefined || layout !== undefined) {
+      const current = await prisma.page.findUnique({ where: { id: id as string }, select: { meta: true } });
+      if (!current) return res.status(404).json({ error: "Page not found" });
+      const next = parseJsonValue<Record<string, unknown>>(current.meta, {});
+      if (required_role !== undefined) {
+        delete next.required_role;
+        if (required_role) next.required_role = String(required_role);
+      }
+      if (layout !== undefined) {
+        delete next.layout;
+        if (layout && layout !== "default") next.layout = layout;
+      }
+      meta = JSON.stringify(next);
+    }
+
     const page = await prisma.page.update({
       where: { id: id as string },
-      data: { name, path },
+      data: { name, path, meta },
     });
     res.json(toPageSchema(page));
   } catch (error) {
@@ -0,0 +1,74 @@
+import { test } from 'node:test';
+import assert from 'node:assert/strict';
+import type { Request, Response } from 'express';
+import { exportProjectJson } from './projectController.js';
+import { parseProjectDocument } from '../services/projectImport.js';
+import { getProjectStore, setProjectStore, type ProjectStore } from '../services/projectStore.js';
+
+class Reply {
+  public statusCode = 200;
+  public contentType = '';
+  public body: any;
+
+  status(code: number) {
+    this.statusCode = code;
+    return this;
+  }
+
+  type(contentType: string) {
+    this.contentType = contentType;
+    return this;
+  }
+
+  send(body: any) {
+    this.body = body;
+    return this;
+  }
+
+  json(body: any) {
+    this.body = body;
+    return this;
+  }
+}
+
+test('exportProjectJson minifies on request and the export reads back the same project', async t => {
+  const project = { id: 'p1', name: 'Shop', description: 'Summer store', settings: '{"api_prefix":"v1"}', rootPath: '/home/me/shop' };
+  const previous = getProjectStore();
+  setProjectStore({
+    getProjectWithEntities: async () => ({
+      ...project,
+      pages: [{ id: 'home', idRoot: 'root-home', name: 'Home', path: '/', isDynamic: false, meta: '{"root_block_ids":["b1"]}', archived: false }],
+      blocks: [{
+        id: 'b1', projectId: 'p1', pageId: 'root-home', parentId: null, blockType: 'Text', name: 'Title', properties: '{"text":"Line one\\nLine two"}',
+        styles: '{}', responsiveStyles: '{}', classes: '[]', events: '[]', bindings: '{}', children: '[]', order: 0, archived: false,
+      }],
+      dataModels: [{ id: 'm1', name: 'Product', schema: '{"fields":[{"id":"f1","name":"title","field_type":"string","required":true}]}', archived: false }],
+      apis: [],
+      logicFlows: [],
+      variables: [],
+    }),
+  } as unknown as ProjectStore);
+  t.after(() => setProjectStore(previous));
+
+  const exportJson = async (query: Record<string, string>) => {
+    const res = new Reply();
+    await exportProjectJson({ params: { id: 'p1' }, query } as unknown as Request, res as unknown as Response);
+    assert.equal(res.contentType, 'application/json');
+    return res.body as string;
+  };
+  const pretty = await exportJson({});
+  const minified = await exportJson({ minify: 'true' });
+
+  assert.match(pretty, /^\{\n {2}"id": "p1",/);
+  assert.doesNotMatch(minified, /\n/);
+  assert.ok(minified.length < pretty.length);
+  assert.deepEqual(JSON.parse(minified), JSON.parse(pretty));
+  assert.ok(!('root_path' in JSON.parse(minified)));
+
+  const restored = parseProjectDocument(minified);
+  assert.equal(restored.name, 'Shop');
+  assert.deepEqual(restored.settings, { api_prefix: 'v1' });
+  assert.deepEqual(restored.pages.map(p => [p.id, p.path, p.root_block_ids]), [['home', '/', ['b1']]]);
+  assert.deepEqual(restored.blocks.map(b => [b.id, b.page_id, b.properties.text]), [['b1', 'home', 'Line one\nLine two']]);
+  assert.deepEqual(restored.data_models.map(m => [m.name, m.fields.map(f => f.name)]), [['Product', ['title']]]);
+});
@@ -1,6 +1,18 @@
 import type { Request, Response } from 'express';
-import prisma from '../lib/prisma.js';
-import { randomUUID } from 'crypto';
+import type { Project } from '@prisma/client';
+import fs from 'fs-extra';
+import path from 'path';
+import { SyncService } from '../services/sync.js';
+import { validateAutoSyncSettings } from '../services/autoSync.js';
+import { projectWatcher, DEFAULT_WATCH_IGNORE } from '../services/watcher.js';
+import { loadProjectSchema, pageLayout, pageRootIds } from '../services/projectSchema.js';
+import { validateProject as runValidation } from '../services/projectValidation.js';
+import { DEFAULT_TEMPLATE_ID, PROJECT_TEMPLATES, applyProjectTemplate, findTemplate } from '../services/projectTemplates.js';
+import { InstallInProgressError, cancelInstall, installDependencies } from '../services/installer.js';
+import { processOutput } from '../services/processOutput.js';
+import { getDevServerStatus, startDevServer, stopDevServer } from '../services/devServer.js';
+import { getProjectStore } from '../services/projectStore.js';
+import { requireRoot, sendRootError } from '../utils/projectRoot.js';
 
 function parseJsonValue<T>(value: unknown, fallback: T): T {
     if (typeof value !== 'string') {
@@ -16,8 +28,9 @@ function parseJsonValue<T>(value: unknown, fallback: T): T {
 
 function toPageSchema(page: any, blocks: any[] = []) {
     const meta = parseJsonValue<Record<string, unknown>>(page.meta, {});
-    const inferredRootBlockId = typeof meta.root_block_id === 'string' && meta.root_block_id
-        ? meta.root_block_id
+    const rootBlockIds = pageRootIds(meta);
+    const inferredRootBlockId = rootBlockIds.length
+        ? rootBlockIds[0]
         : blocks.find((block) => String(block.pageId) === String(page.idRoot) && !block.parentId && !block.archived)?.id;
 
     return {
@@ -25,7 +38,9 @@ function toPageSchema(page: any, blocks: any[] = []) {
         name: page.name,
         path: page.path,
         root_block_id: inferredRootBlockId,
+        root_block_ids: rootBlockIds.length ? rootBlockIds : undefined,
         is_dynamic: page.isDynamic || false,
+        layout: pageLayout(meta),
         meta,
         archived: page.archived || false,
     };
@@ -50,6 +65,7 @@ function toProjectSchema(p: any, pages: any[] = [], blocks: any[] = []) {
         updated_at: (p.updatedAt instanceof Date ? p.updatedAt : new Date(p.updatedAt)).toISOString(),
         root_path: p.rootPath || '',
         version: '1.0.0',
+        revision: p.revision ?? 0,
         settings: parseJsonValue<Record<string, unknown>>(p.settings, {}),
         blocks: blocks.map((b: any) => ({
             id: b.id,
@@ -66,6 +82,7 @@ function toProjectSchema(p: any, pages: any[] = [], blocks: any[] = []) {
             page_id: b.pageId ? pageIdByInternalId.get(String(b.pageId)) || null : null,
             order: b.order || 0,
             archived: b.archived || false,
+            component_id: parseJsonValue<Record<string, any>>(b.properties, {}).component_id,
         })),
         pages: serializedPages,
         apis: [],
@@ -76,11 +93,15 @@ function toProjectSchema(p: any, pages: any[] = [], blocks: any[] = []) {
     };
 }
 
+/** The project with its pages and blocks, in the shape the client expects */
+async function withContent(project: Project) {
+    const { pages, blocks } = await getProjectStore().getProjectContent(project.id);
+    return toProjectSchema(project, pages, blocks);
+}
+
 export async function listProjects(req: Request, res: Response) {
     try {
-        const projects = await prisma.project.findMany({
-            orderBy: { updatedAt: 'desc' }
-        });
+        const projects = await getProjectStore().getAllProjects();
         res.json(projects);
     } catch (error) {
         console.error('Error listing projects:', error);
@@ -91,15 +112,12 @@ export async function listProjects(req: Request, res: Response) {
 export async function getProject(req: Request, res: Response) {
     try {
         const { id } = req.params;
-        const project = await prisma.project.findUnique({
-            where: { id: id as string },
-            include: { pages: true, blocks: true }
-        });
+        const project = await getProjectStore().getProjectById(id as string);
 
         if (!project) {
             return res.status(404).json({ error: 'Project not found' });
         }
-        res.json(toProjectSchema(project, project.pages, project.blocks));
+        res.json(await withContent(project));
     } catch (error) {
         console.error('Error getting project:', error);
         res.status(500).json({ error: 'Failed to get project' });
@@ -108,89 +126,161 @@ export async function getProject(req: Request, res: Response) {
 
 export async function createProject(req: Request, res: Response) {
     try {
-        const { name, description } = req.body;
-        const project = await prisma.project.create({
-            data: {
-                name,
-                description,
-                settings: JSON.stringify({
-                    theme: { primary_color: '#3b82f6' }
-                })
-            }
-        });
-
-        // Prisma requires explicit ObjectID referencing for relations on MongoDB
-        // but simple scalar assignment usually doesn't trigger a transaction 
-        // if we don't use nested 'page: { create: {} }' syntax.
-        const homePage = await prisma.page.create({
-            data: {
-                id: randomUUID(),
-                projectId: project.id, // Explicit scalar foreign key
-                name: 'Home',
-                path: '/',
-                isDynamic: false
-            }
-        });
+        const { name, description, template: templateId = DEFAULT_TEMPLATE_ID } = req.body;
+        const template = typeof templateId === 'string' ? findTemplate(templateId) : undefined;
+        if (!template) {
+            return res.status(400).json({ error: `Unknown project template "${templateId}"` });
+        }
 
-        const rootBlock = await prisma.block.create({
-            data: {
-                projectId: project.id,
-                pageId: homePage.idRoot,
-                parentId: null,
-                blockType: 'canvas',
-                name: 'Page Root',
-                properties: JSON.stringify({}),
-                styles: JSON.stringify({}),
-                responsiveStyles: JSON.stringify({}),
-                classes: JSON.stringify([]),
-                events: JSON.stringify([]),
-                bindings: JSON.stringify({}),
-                children: JSON.stringify([]),
-                order: 0,
-            }
+        const project = await getProjectStore().createProject({
+            name,
+            description,
+            settings: JSON.stringify({
+                theme: { primary_color: '#3b82f6' }
+            })
         });
 
-        const updatedHomePage = await prisma.page.update({
-            where: { id: homePage.id },
-            data: {
-                meta: JSON.stringify({
-                    root_block_id: rootBlock.id,
-                }),
-            }
-        });
+        // Pages and blocks are created with explicit scalar foreign keys; Prisma on MongoDB
+        // would otherwise wrap nested creates in a transaction
+        await applyProjectTemplate(project.id, template);
 
-        res.json(toProjectSchema(project, [updatedHomePage], [rootBlock]));
+        res.json(await withContent(project));
     } catch (error) {
         console.error('Error creating project:', error);
         res.status(500).json({ error: 'Failed to create project' });
     }
 }
 
+export async function listTemplates(req: Request, res: Response) {
+    res.json(PROJECT_TEMPLATES.map(t => ({
+        id: t.id,
+        name: t.name,
+        description: t.description,
+        models: t.data_models.map(m => m.name),
+        pages: t.pages.map(p => p.name),
+    })));
+}
+
 export async function updateProject(req: Request, res: Response) {
     try {
         const { id } = req.params;
         const { name, description, settings } = req.body;
+        const settingsError = settings && validateAutoSyncSettings(settings);
+        if (settingsError) {
+            res.status(400).json({ error: settingsError });
+            return;
+        }
 
-        const project = await prisma.project.update({
-            where: { id: id as string },
-            data: {
-                name,
-                description,
-                ...(settings && { settings: JSON.stringify(settings) })
-            },
-            include: { pages: true, blocks: true }
+        const project = await getProjectStore().saveProject(id as string, {
+            name,
+            description,
+            ...(settings && { settings: JSON.stringify(settings) })
         });
-        res.json(toProjectSchema(project, project.pages, project.blocks));
+        res.json(await withContent(project));
     } catch (error) {
         console.error('Error updating project:', error);
         res.status(500).json({ error: 'Failed to update project' });
     }
 }
 
+export async function setProjectRoot(req: Request, res: Response) {
+    try {
+        const { id } = req.params;
+        const { rootPath, force } = req.body;
+        if (!rootPath || typeof rootPath !== 'string') {
+            return res.status(400).json({ error: 'rootPath required' });
+        }
+
+        const absoluteRoot = path.resolve(rootPath);
+        await fs.ensureDir(absoluteRoot);
+
+        const project = await getProjectStore().saveProject(id as string, { rootPath: absoluteRoot });
+
+        const settings = parseJsonValue<Record<string, any>>(project.settings, {});
+        const ignore: string[] = Array.isArray(settings.watch_ignore) ? settings.watch_ignore : DEFAULT_WATCH_IGNORE;
+
+        await new SyncService(absoluteRoot).initProjectStructure(ignore, {
+            force: force === true,
+            project: { id: project.id, name: project.name },
+        });
+        const debounceMs = typeof settings.watch_debounce_ms === 'number' ? settings.watch_debounce_ms : undefined;
+        projectWatcher.watch(project.id, absoluteRoot, ignore, debounceMs);
+
+        res.json(await withContent(project));
+    } catch (error) {
+        console.error('Error setting project root:', error);
+        res.status(500).json({ error: 'Failed to set project root' });
+    }
+}
+
+export async function installProjectDependencies(req: Request, res: Response) {
+    try {
+        const { id } = req.params;
+        const rootPath = await requireRoot(id);
+        res.json(await installDependencies(id as string, rootPath));
+    } catch (error) {
+        if (sendRootError(res, error)) return;
+        if (error instanceof InstallInProgressError) {
+            res.status(409).json({ error: error.message });
+            return;
+        }
+        console.error('Error installing dependencies:', error);
+        res.status(500).json({ error: 'Failed to install dependencies' });
+    }
+}
+
+/** The install request itself answers with the cancelled result once npm has stopped */
+export async function cancelProjectInstall(req: Request, res: Response) {
+    const { id } = req.params;
+    if (!cancelInstall(id as string)) {
+        res.status(404).json({ error: 'No dependency install is running for this project' });
+        return;
+    }
+    res.json({ success: true, status: 'cancelled' });
+}
+
+/** Recent install output for clients that aren't on the socket */
+export async function getProcessOutput(req: Request, res: Response) {
+    const { id } = req.params;
+    res.json({ lines: processOutput.tail(id as string) });
+}
+
+export async function getDevServer(req: Request, res: Response) {
+    try {
+        const { id } = req.params;
+        res.json(await getDevServerStatus(id as string));
+    } catch (error) {
+        console.error('Error getting dev server status:', error);
+        res.status(500).json({ error: 'Failed to get dev server status' });
+    }
+}
+
+/** Starts `npm run dev` in the project root; poll getDevServer for the detected port */
+export async function startProjectDevServer(req: Request, res: Response) {
+    try {
+        const { id } = req.params;
+        startDevServer(id as string, await requireRoot(id));
+        res.json(await getDevServerStatus(id as string));
+    } catch (error) {
+        if (sendRootError(res, error)) return;
+        console.error('Error starting dev server:', error);
+        res.status(500).json({ error: 'Failed to start dev server' });
+    }
+}
+
+export async function stopProjectDevServer(req: Request, res: Response) {
+    const { id } = req.params;
+    if (!stopDevServer(id as string)) {
+        res.status(404).json({ error: 'No dev server is running for this project' });
+        return;
+    }
+    res.json({ success: true });
+}
+
 export async function deleteProject(req: Request, res: Response) {
     try {
         const { id } = req.params;
-        await prisma.project.delete({ where: { id: id as string } });
+        await getProjectStore().deleteProject(id as string);
         res.json({ success: true });
     } catch (error) {
         console.error('Error deleting project:', error);
@@ -203,12 +293,8 @@ export async function updateProjectIdea(req: Request, res: Response) {
         const { id } = req.params;
         const { idea } = req.body;
 
-        const project = await prisma.project.update({
-            where: { id: id as string },
-            data: { description: idea || '' },
-            include: { pages: true, blocks: true }
-        });
-        res.json(toProjectSchema(project, project.pages, project.blocks));
+        const project = await getProjectStore().saveProject(id as string, { description: idea || '' });
+        res.json(await withContent(project));
     } catch (error) {
         console.error('Error updating project idea:', error);
         res.status(500).json({ error: 'Failed to update project idea' });
@@ -221,9 +307,7 @@ export async function generateStructuredIdea(req: Request, res: Response) {
     try {
         const { id } = req.params;
         
-        const project = await prisma.project.findUnique({
-            where: { id: id as string }
-        });
+        const project = await getProjectStore().getProjectById(id as string);
         
         if (!project) {
             return res.status(404).json({ error: 'Project not found' });
@@ -338,13 +422,9 @@ Fill ALL string values with real content inferred from the project idea. Return
             : (project.settings || {});
         settings.ideaDetails = structuredIdea;
 
-        const updatedProject = await prisma.project.update({
-            where: { id: id as string },
-            data: { settings: JSON.stringify(settings) },
-            include: { pages: true, blocks: true }
-        });
+        const updatedProject = await getProjectStore().saveProject(id as string, { settings: JSON.stringify(settings) });
 
-        res.json(toProjectSchema(updatedProject, updatedProject.pages, updatedProject.blocks));
+        res.json(await withContent(updatedProject));
 
     } catch (error: any) {
         console.error('Error generating structured idea details:', error);
@@ -352,4 +432,37 @@ Fill ALL string values with real content inferred from the project idea. Return
     }
 }
 
+export async function validateProject(req: Request, res: Response) {
+    try {
+        const { id } = req.params;
+        const project = await loadProjectSchema(id as string);
+        if (!project) { res.status(404).json({ error: 'Project not found' }); return; }
+
+        const issues = runValidation(project);
+        res.json({ valid: !issues.some(i => i.severity === 'error'), issues });
+    } catch (error) {
+        console.error('Error validating project:', error);
+        res.status(500).json({ error: 'Failed to validate project' });
+    }
+}
+
+/**
+ * The project as one JSON document, in the shape `POST /api/import/project` accepts
+ */
+export async function exportProjectJson(req: Request, res: Response) {
+    try {
+        const schema = await loadProjectSchema(req.params.id as string);
+        if (!schema) {
+            return res.status(404).json({ error: 'Project not found' });
+        }
 
+        const { root_path: _rootPath, ...project } = schema;
+        const exported = { ...project, pages: project.pages.map(({ idRoot: _idRoot, ...page }) => page) };
+        // Pretty-printed for reading by default; `?minify=true` keeps large projects small
+        const minify = req.query.minify === 'true' || req.query.minify === '1';
+        res.type('application/json').send(minify ? JSON.stringify(exported) : `${JSON.stringify(exported, null, 2)}\n`);
+    } catch (error) {
+        console.error('Error exporting project:', error);
+        res.status(500).json({ error: 'Failed to export project' });
+    }
+}
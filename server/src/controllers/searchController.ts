@@ -0,0 +1,26 @@
+import type { Request, Response } from 'express';
+import { loadProjectSchema } from '../services/projectSchema.js';
+import { searchProject } from '../services/projectSearch.js';
+
+export async function search(req: Request, res: Response) {
+    try {
+        const { projectId, q, limit } = req.query;
+        if (!projectId || typeof projectId !== 'string') {
+            res.status(400).json({ error: 'Project ID required' });
+            return;
+        }
+        if (typeof q !== 'string' || !q.trim()) {
+            res.status(400).json({ error: 'Query "q" required' });
+            return;
+        }
+
+        const project = await loadProjectSchema(projectId);
+        if (!project) { res.status(404).json({ error: 'Project not found' }); return; }
+
+        const hits = searchProject(project, q, typeof limit === 'string' ? Number(limit) : undefined);
+        res.json({ query: q, hits });
+    } catch (error) {
+        console.error('Search error:', error);
+        res.status(500).json({ error: 'Failed to search project' });
+    }
+}
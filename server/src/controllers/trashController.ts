@@ -0,0 +1,46 @@
+import type { Request, Response } from 'express';
+import prisma from '../lib/prisma.js';
+
+/**
+ * Everything archived in a project, grouped by type, with enough of each entry to show it
+ * in a trash list. `POST /api/<type>/:id/restore` brings an entry back.
+ */
+export async function listArchived(req: Request, res: Response) {
+    try {
+        const { projectId } = req.query;
+        if (!projectId || typeof projectId !== 'string') {
+            res.status(400).json({ error: 'Project ID required' });
+            return;
+        }
+
+        const where = { projectId, archived: true };
+        const [pages, blocks, models, flows] = await Promise.all([
+            prisma.page.findMany({ where, orderBy: { name: 'asc' } }),
+            prisma.block.findMany({ where, orderBy: { name: 'asc' } }),
+            prisma.dataModel.findMany({ where, orderBy: { name: 'asc' } }),
+            prisma.logicFlow.findMany({ where, orderBy: { name: 'asc' } }),
+        ]);
+
+        // Blocks point at the page's internal id; the API speaks public page ids
+        const pageIds = new Map(
+            (await prisma.page.findMany({ where: { projectId }, select: { id: true, idRoot: true } }))
+                .map(p => [p.idRoot, p.id])
+        );
+
+        res.json({
+            pages: pages.map(p => ({ id: p.id, name: p.name, path: p.path })),
+            blocks: blocks.map(b => ({
+                id: b.id,
+                name: b.name,
+                block_type: b.blockType,
+                page_id: (b.pageId && pageIds.get(b.pageId)) || undefined,
+                parent_id: b.parentId || undefined,
+            })),
+            data_models: models.map(m => ({ id: m.id, name: m.name })),
+            logic_flows: flows.map(f => ({ id: f.id, name: f.name })),
+        });
+    } catch (error) {
+        console.error('Error listing archived items:', error);
+        res.status(500).json({ error: 'Failed to list archived items' });
+    }
+}
@@ -17,7 +17,9 @@ export async function listVariables(req: Request, res: Response) {
         const response = vars.map(v => ({
             id: v.id, name: v.name,
             variable_type: v.type,
-            scope: 'global',
+            scope: v.scope,
+            page_id: v.pageId || undefined,
+            persist: v.persist,
             default_value: v.value ? JSON.parse(v.value) : null,
             archived: false
         }));
@@ -30,21 +32,26 @@ export async function listVariables(req: Request, res: Response) {
 
 export async function createVariable(req: Request, res: Response) {
     try {
-        const { projectId, name, variable_type, default_value, isSecret } = req.body;
+        const { projectId, name, variable_type, default_value, isSecret, scope, page_id, persist } = req.body;
 
         const v = await prisma.variable.create({
             data: {
                 projectId, name,
                 type: variable_type,
                 value: JSON.stringify(default_value),
-                isSecret: !!isSecret
+                isSecret: !!isSecret,
+                scope: scope === 'page' ? 'page' : 'global',
+                pageId: scope === 'page' ? page_id || null : null,
+                persist: !!persist
             }
         });
 
         res.json({
             id: v.id, name: v.name,
             variable_type: v.type,
-            scope: 'global',
+            scope: v.scope,
+            page_id: v.pageId || undefined,
+            persist: v.persist,
             default_value: default_value
         });
     } catch (error) {
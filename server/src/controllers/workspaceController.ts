@@ -1,21 +1,21 @@
 import type { Request, Response } from 'express';
-import prisma from '../lib/prisma.js';
+import { getProjectStore } from '../services/projectStore.js';
 
 export async function getWorkspace(req: Request, res: Response) {
     try {
-        const projects = await prisma.project.findMany({
-            orderBy: { updatedAt: 'desc' },
-            include: { pages: { take: 1 } }
-        });
+        const store = getProjectStore();
+        const [projects, settings] = await Promise.all([store.getAllProjects(), store.getWorkspaceSettings()]);
 
         res.json({
-            workspace_path: 'Cloud Workspace',
+            workspace_path: settings.workspace_path || 'Cloud Workspace',
             projects: projects.map(p => ({
                 id: p.id,
                 name: p.name,
                 description: p.description || '',
                 created_at: p.createdAt.toISOString(),
                 updated_at: p.updatedAt.toISOString(),
+                pinned: p.pinned,
+                last_opened_at: p.lastOpenedAt?.toISOString() ?? null,
                 root_path: p.rootPath || '',
                 version: '1.0.0',
                 settings: JSON.parse(p.settings || '{}'),
@@ -33,3 +33,59 @@ export async function getWorkspace(req: Request, res: Response) {
         res.status(500).json({ error: 'Failed to get workspace' });
     }
 }
+
+export async function updateWorkspaceSettings(req: Request, res: Response) {
+    try {
+        const { workspace_path } = req.body;
+        if (workspace_path !== undefined && typeof workspace_path !== 'string') {
+            res.status(400).json({ error: 'workspace_path must be a string' });
+            return;
+        }
+
+        const store = getProjectStore();
+        const settings = await store.saveWorkspaceSettings({ ...await store.getWorkspaceSettings(), workspace_path });
+        res.json(settings);
+    } catch (error) {
+        console.error('Error saving workspace settings:', error);
+        res.status(500).json({ error: 'Failed to save workspace settings' });
+    }
+}
+
+export async function pinProject(req: Request, res: Response) {
+    try {
+        const { id } = req.params;
+        const { pinned } = req.body;
+        if (typeof pinned !== 'boolean') {
+            res.status(400).json({ error: 'pinned must be true or false' });
+            return;
+        }
+
+        const store = getProjectStore();
+        if (!await store.getProjectById(id as string)) {
+            res.status(404).json({ error: 'Project not found' });
+            return;
+        }
+        const project = await store.setPinned(id as string, pinned);
+        res.json({ id: project.id, pinned: project.pinned });
+    } catch (error) {
+        console.error('Error pinning project:', error);
+        res.status(500).json({ error: 'Failed to pin project' });
+    }
+}
+
+/** Record that the editor opened a project, for the recent list */
+export async function markProjectOpened(req: Request, res: Response) {
+    try {
+        const { id } = req.params;
+        const store = getProjectStore();
+        if (!await store.getProjectById(id as string)) {
+            res.status(404).json({ error: 'Project not found' });
+            return;
+        }
+        const project = await store.markOpened(id as string);
+        res.json({ id: project.id, last_opened_at: project.lastOpenedAt?.toISOString() ?? null });
+    } catch (error) {
+        console.error('Error recording project open:', error);
+        res.status(500).json({ error: 'Failed to record project open' });
+    }
+}
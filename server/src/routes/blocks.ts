@@ -4,5 +4,15 @@ import * as ctrl from '../controllers/blocksController.js';
 const router = Router();
 
 router.post('/sync', ctrl.syncBlocks);
+router.post('/paste', ctrl.pasteBlocks);
+router.post('/bulk-archive', ctrl.bulkArchiveBlocks);
+router.post('/bulk-delete', ctrl.bulkDeleteBlocks);
+router.post('/', ctrl.addBlock);
+router.put('/:id', ctrl.updateBlockProperty);
+router.put('/:id/move', ctrl.moveBlock);
+router.post('/:id/duplicate', ctrl.duplicateBlock);
+router.get('/:id/copy', ctrl.copyBlock);
+router.post('/:id/restore', ctrl.restoreBlock);
+router.post('/:id/extract-component', ctrl.extractComponent);
 
 export default router;
@@ -4,6 +4,18 @@ import * as ctrl from '../controllers/codegenController.js';
 const router = Router();
 
 router.post('/sync', ctrl.syncProject);
+router.post('/sync-from-disk', ctrl.syncFromDisk);
+router.get('/conflicts', ctrl.detectConflicts);
+router.get('/preview', ctrl.previewGeneration);
 router.post('/export', ctrl.exportProject);
+router.post('/backend', ctrl.generateBackend);
+router.post('/html', ctrl.generateHtml);
+router.post('/postman', ctrl.generatePostman);
+router.post('/compose', ctrl.generateCompose);
+router.post('/logic', ctrl.generateLogic);
+router.post('/docs', ctrl.generateDocs);
+router.post('/zip', ctrl.downloadZip);
+router.post('/tar', ctrl.downloadTar);
+router.post('/database', ctrl.generateDatabase);
 
 export default router;
@@ -0,0 +1,10 @@
+import { Router } from 'express';
+import * as ctrl from '../controllers/commandController.js';
+
+const router = Router();
+
+router.post('/undo', ctrl.undo);
+router.post('/redo', ctrl.redo);
+router.get('/log', ctrl.getLog);
+
+export default router;
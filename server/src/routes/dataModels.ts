@@ -6,6 +6,8 @@ const router = Router();
 router.get('/', ctrl.listDataModels);
 router.post('/', ctrl.createDataModel);
 router.put('/:id', ctrl.updateDataModel);
+router.post('/:id/rename', ctrl.renameDataModel);
 router.delete('/:id', ctrl.deleteDataModel);
+router.post('/:id/restore', ctrl.restoreDataModel);
 
 export default router;
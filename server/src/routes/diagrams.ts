@@ -5,6 +5,8 @@ const router = Router();
 
 router.get('/', ctrl.listDiagrams);
 router.post('/', ctrl.createDiagram);
+router.post('/analyze', ctrl.analyzeDiagram);
+router.post('/to-models', ctrl.diagramToModels);
 router.get('/:name', ctrl.getDiagram);
 router.delete('/:name', ctrl.deleteDiagram);
 
@@ -0,0 +1,14 @@
+import { Router } from 'express';
+import * as ctrl from '../controllers/filesController.js';
+
+const router = Router();
+
+router.get('/', ctrl.listDirectory);
+router.post('/', ctrl.createFile);
+router.post('/folder', ctrl.createFolder);
+router.put('/rename', ctrl.renameFile);
+router.delete('/delete', ctrl.deleteFile);
+router.get('/content', ctrl.readFile);
+router.put('/content', ctrl.writeFile);
+
+export default router;
@@ -0,0 +1,9 @@
+import { Router } from 'express';
+import * as ctrl from '../controllers/importController.js';
+
+const router = Router();
+
+router.post('/openapi', ctrl.importOpenApi);
+router.post('/project', ctrl.importProject);
+
+export default router;
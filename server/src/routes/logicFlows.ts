@@ -7,5 +7,9 @@ router.get('/', ctrl.listLogicFlows);
 router.post('/', ctrl.createLogicFlow);
 router.put('/:id', ctrl.updateLogicFlow);
 router.delete('/:id', ctrl.deleteLogicFlow);
+router.post('/:id/restore', ctrl.restoreLogicFlow);
+router.get('/:id/compile', ctrl.compileLogicFlow);
+router.get('/:id/validate', ctrl.validateLogicFlow);
+router.post('/:id/simulate', ctrl.simulateLogicFlow);
 
 export default router;
@@ -8,5 +8,6 @@ router.get("/", ctrl.listPages);
 router.post("/", ctrl.createPage);
 router.put("/:id", ctrl.updatePage);
 router.delete("/:id", ctrl.archivePage);
+router.post("/:id/restore", ctrl.restorePage);
 
 export default router;
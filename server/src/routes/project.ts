@@ -8,6 +8,15 @@ router.get('/:id', ctrl.getProject);
 router.post('/', ctrl.createProject);
 router.put('/:id', ctrl.updateProject);
 router.put('/:id/idea', ctrl.updateProjectIdea);
+router.put('/:id/root', ctrl.setProjectRoot);
+router.post('/:id/install', ctrl.installProjectDependencies);
+router.post('/:id/install/cancel', ctrl.cancelProjectInstall);
+router.get('/:id/output', ctrl.getProcessOutput);
+router.get('/:id/dev-server', ctrl.getDevServer);
+router.post('/:id/dev-server', ctrl.startProjectDevServer);
+router.delete('/:id/dev-server', ctrl.stopProjectDevServer);
+router.get('/:id/validate', ctrl.validateProject);
+router.get('/:id/export', ctrl.exportProjectJson);
 router.post('/:id/generate-idea-details', ctrl.generateStructuredIdea);
 router.delete('/:id', ctrl.deleteProject);
 
@@ -0,0 +1,8 @@
+import { Router } from 'express';
+import * as ctrl from '../controllers/searchController.js';
+
+const router = Router();
+
+router.get('/', ctrl.search);
+
+export default router;
@@ -0,0 +1,8 @@
+import { Router } from 'express';
+import * as ctrl from '../controllers/projectController.js';
+
+const router = Router();
+
+router.get('/', ctrl.listTemplates);
+
+export default router;
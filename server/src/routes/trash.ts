@@ -0,0 +1,8 @@
+import { Router } from 'express';
+import * as ctrl from '../controllers/trashController.js';
+
+const router = Router();
+
+router.get('/', ctrl.listArchived);
+
+export default router;
@@ -4,5 +4,8 @@ import * as ctrl from '../controllers/workspaceController.js';
 const router = Router();
 
 router.get('/', ctrl.getWorkspace);
+router.put('/', ctrl.updateWorkspaceSettings);
+router.put('/projects/:id/pin', ctrl.pinProject);
+router.post('/projects/:id/open', ctrl.markProjectOpened);
 
 export default router;
@@ -6,6 +6,8 @@ import path from 'path';
 import { fileURLToPath } from 'url';
 import { initializeLLMProvider } from './lib/llmProvider.js';
 import { startQwenServer, stopQwenServer } from './lib/qwenManager.js';
+import { attachChangeSocket, trackMutations } from './services/changeFeed.js';
+import { REQUEST_ID_HEADER, assignRequestId } from './utils/requestId.js';
 
 const __filename = fileURLToPath(import.meta.url);
 const __dirname = path.dirname(__filename);
@@ -14,8 +16,20 @@ const __dirname = path.dirname(__filename);
 const app = express();
 const PORT = process.env.PORT || 3001;
 
-app.use(cors());
+// CORS_ORIGINS (comma-separated) limits which sites may call the API; unset, any origin is allowed outside production
+const corsOrigins = process.env.CORS_ORIGINS?.split(',').map(o => o.trim()).filter(Boolean) || [];
+app.use(cors({
+    origin: corsOrigins.length > 0 ? corsOrigins : process.env.NODE_ENV !== 'production',
+    // ETag carries the project revision after a mutation
+    exposedHeaders: [REQUEST_ID_HEADER, 'ETag'],
+}));
+// First, so every log line and error body for a request carries the same id
+app.use(assignRequestId);
+// Exported projects are far bigger than any other request body; this parser claims the body first
+app.use('/api/import/project', express.json({ limit: '50mb' }));
 app.use(express.json());
+// Push successful mutations to /ws subscribers so editors don't have to poll
+app.use(trackMutations);
 
 // Determine operational mode (Web vs Tauri-replacement)
 
@@ -27,6 +41,8 @@ app.use(express.static(path.join(__dirname, '../../public')));
 app.get('/health', (req, res) => {
     res.json({ status: 'healthy', version: '1.0.0' });
 });
+import { getReadiness } from './controllers/healthController.js';
+app.get('/health/ready', getReadiness);
 
 // Routes
 import projectRouter from './routes/project.js';
@@ -60,6 +76,18 @@ import apiHistoryRouter from './routes/apiHistory.js';
 app.use('/api/api-history', apiHistoryRouter);
 import aiRouter from './routes/ai.js';
 app.use('/api/ai', aiRouter);
+import filesRouter from './routes/files.js';
+app.use('/api/files', filesRouter);
+import commandRouter from './routes/command.js';
+app.use('/api/command', commandRouter);
+import importRouter from './routes/import.js';
+app.use('/api/import', importRouter);
+import searchRouter from './routes/search.js';
+app.use('/api/search', searchRouter);
+import templatesRouter from './routes/templates.js';
+app.use('/api/templates', templatesRouter);
+import trashRouter from './routes/trash.js';
+app.use('/api/trash', trashRouter);
 
 // Initialize servers
 async function startServer() {
@@ -75,9 +103,10 @@ async function startServer() {
         console.log('[LLM Provider] Initialized successfully');
 
         // Start Express server
-        app.listen(PORT, () => {
+        const server = app.listen(PORT, () => {
             console.log(`✓ Server running on http://localhost:${PORT}`);
         });
+        attachChangeSocket(server);
 
         // Handle graceful shutdown
         process.on('SIGINT', async () => {
@@ -0,0 +1,36 @@
+import type { BlockSchema } from './projectSchema.js';
+
+/**
+ * Accessibility props kept in a block's `properties`. `aria_label` and `role` work on any
+ * block; images describe themselves with `alt`, where `''` marks one as decorative.
+ */
+export interface BlockA11y {
+  aria_label?: string;
+  role?: string;
+  alt?: string;
+}
+
+const hasText = (value: unknown) => typeof value === 'string' && value.trim() !== '';
+
+/** `aria-label` and `role` attributes for a block's properties, leaving out unset ones */
+export function ariaAttributes(properties: Record<string, any> | undefined): Array<[string, string]> {
+  const attributes: Array<[string, string]> = [];
+  if (hasText(properties?.aria_label)) attributes.push(['aria-label', properties!.aria_label.trim()]);
+  // ARIA roles are single lowercase words
+  if (typeof properties?.role === 'string' && /^[a-z]+$/.test(properties.role.trim())) {
+    attributes.push(['role', properties.role.trim()]);
+  }
+  return attributes;
+}
+
+/** What keeps a block from being usable with a screen reader, or null when nothing does */
+export function accessibilityIssue(block: BlockSchema): string | null {
+  const { properties, bindings } = block;
+  if (block.block_type === 'Image' && typeof properties.alt !== 'string' && !bindings.alt) {
+    return `Image "${block.name}" has no alt text; set an empty alt if it is decorative`;
+  }
+  if (block.block_type === 'Button' && !hasText(properties.text) && !bindings.text && !hasText(properties.aria_label)) {
+    return `Button "${block.name}" has no text or aria label`;
+  }
+  return null;
+}
@@ -0,0 +1,85 @@
+import { test } from 'node:test';
+import assert from 'node:assert/strict';
+import { analyzeDiagram, classifyRelation, filterIssues, issuesBySeverity, issuesForNode, toDataModels, type AnalysisResult, type ValidationIssue } from './akasha.js';
+
+test('classifyRelation reads cardinality labels that end in a symbol', () => {
+  assert.equal(classifyRelation('1:*'), 'ownership');
+  assert.equal(classifyRelation('1..n'), 'ownership');
+  assert.equal(classifyRelation('sends data'), 'data_flow');
+  assert.equal(classifyRelation('related'), 'association');
+});
+
+const result = (issues: ValidationIssue[]): AnalysisResult => ({
+  graph: { nodes: [], edges: [] },
+  issues,
+  stats: {
+    total_nodes: 0, total_edges: 0, unknown_type_count: 0, issue_count: issues.length,
+    relation_counts: { association: 0, ownership: 0, trigger: 0, data_flow: 0 },
+  },
+});
+
+const issues: ValidationIssue[] = [
+  { severity: 'error', rule: 'dangling_edge', element_id: 'e1', message: 'Edge is not connected at both ends' },
+  { severity: 'warning', rule: 'unlabeled_node', element_id: 'n1', message: 'Node has no label' },
+  { severity: 'info', rule: 'unknown_type', element_id: 'n1', message: 'Could not infer a specific type for "x"' },
+];
+
+test('filterIssues keeps only errors when filtering to error', () => {
+  const filtered = filterIssues(result(issues), 'error');
+  assert.deepEqual(filtered.issues.map(i => i.rule), ['dangling_edge']);
+  assert.equal(filtered.stats.issue_count, 1);
+  assert.deepEqual(filterIssues(result(issues), 'warning').issues.map(i => i.severity), ['error', 'warning']);
+});
+
+test('issuesBySeverity and issuesForNode bucket each issue once', () => {
+  const groups = issuesBySeverity(result(issues));
+  assert.deepEqual(groups.error.map(i => i.rule), ['dangling_edge']);
+  assert.deepEqual(groups.warning.map(i => i.rule), ['unlabeled_node']);
+  assert.deepEqual(groups.info.map(i => i.rule), ['unknown_type']);
+  assert.deepEqual(issuesForNode(result(issues), 'n1').map(i => i.rule), ['unlabeled_node', 'unknown_type']);
+  assert.deepEqual(issuesForNode(result(issues), 'missing'), []);
+});
+
+const erDiagram = (cells: string) => `<mxfile><diagram><mxGraphModel><root>
+<mxCell id="0"/><mxCell id="1" parent="0"/>
+${cells}
+</root></mxGraphModel></diagram></mxfile>`;
+
+test('toDataModels turns two entities into models with their fields and relation', () => {
+  const { models, issues } = toDataModels(analyzeDiagram(erDiagram(`
+<mxCell id="user" value="User" style="swimlane;" vertex="1" parent="1"/>
+<mxCell id="u1" value="email" vertex="1" parent="user"/>
+<mxCell id="u2" value="+ age: int" vertex="1" parent="user"/>
+<mxCell id="post" value="Post" style="swimlane;" vertex="1" parent="1"/>
+<mxCell id="p1" value="title" vertex="1" parent="post"/>
+<mxCell id="p2" value="publishedAt" vertex="1" parent="post"/>
+<mxCell id="e1" value="has many" edge="1" source="user" target="post" parent="1"/>
+`)).graph);
+
+  assert.deepEqual(issues, []);
+  assert.deepEqual(models.map(m => [m.name, m.fields.map(f => `${f.name}:${f.field_type}`)]), [
+    ['User', ['id:uuid', 'email:string', 'age:int']],
+    ['Post', ['id:uuid', 'title:string', 'publishedAt:datetime']],
+  ]);
+  assert.deepEqual(models[0]!.relations.map(r => [r.name, r.target_model_id, r.relation_type]), [['posts', 'post', 'one_to_many']]);
+});
+
+test('toDataModels skips entities and attributes whose labels cannot be names', () => {
+  const { models, issues } = toDataModels(analyzeDiagram(erDiagram(`
+<mxCell id="user" value="User" style="swimlane;" vertex="1" parent="1"/>
+<mxCell id="u1" value="class" vertex="1" parent="user"/>
+<mxCell id="u2" value="!!!" vertex="1" parent="user"/>
+<mxCell id="again" value="user" style="swimlane;" vertex="1" parent="1"/>
+<mxCell id="bad" value="!!!" style="swimlane;" vertex="1" parent="1"/>
+<mxCell id="blank" value="" style="swimlane;" vertex="1" parent="1"/>
+`)).graph);
+
+  assert.deepEqual(models.map(m => [m.name, m.fields.map(f => f.name)]), [['User', ['id']]]);
+  assert.deepEqual(issues.map(i => [i.element_id, i.rule]), [
+    ['again', 'duplicate_model_name'],
+    ['bad', 'invalid_model_name'],
+    ['blank', 'invalid_model_name'],
+    ['u1', 'invalid_field_name'],
+    ['u2', 'invalid_field_name'],
+  ]);
+});
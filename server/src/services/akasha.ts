@@ -0,0 +1,453 @@
+import zlib from 'zlib';
+import { camelCase, pascalCase } from '../utils/string.js';
+import { validateIdentifier } from '../utils/identifiers.js';
+import type { DataModelSchema, FieldSchema, RelationSchema } from './projectSchema.js';
+
+export type NodeType = 'actor' | 'feature' | 'screen' | 'api' | 'database' | 'external_service' | 'decision' | 'process' | 'entity' | 'attribute' | 'unknown';
+export type RelationshipType = 'flow' | 'dependency' | 'association';
+export type RelationKind = 'association' | 'ownership' | 'trigger' | 'data_flow';
+export type Severity = 'info' | 'warning' | 'error';
+
+export interface ProductNode {
+  id: string;
+  label: string;
+  node_type: NodeType;
+  properties: Record<string, string>;
+}
+
+export interface ProductEdge {
+  id: string;
+  source: string;
+  target: string;
+  label: string;
+  relationship_type: RelationshipType;
+  relation_kind: RelationKind;
+}
+
+export interface ProductGraph {
+  nodes: ProductNode[];
+  edges: ProductEdge[];
+}
+
+export interface ValidationIssue {
+  severity: Severity;
+  message: string;
+  element_id?: string;
+  rule: string;
+}
+
+export interface GraphStats {
+  total_nodes: number;
+  total_edges: number;
+  unknown_type_count: number;
+  issue_count: number;
+  relation_counts: Record<RelationKind, number>;
+}
+
+export interface AnalysisResult {
+  graph: ProductGraph;
+  issues: ValidationIssue[];
+  stats: GraphStats;
+}
+
+interface RawCell {
+  id: string;
+  value: string;
+  style: string;
+  parent?: string;
+  vertex: boolean;
+  edge: boolean;
+  source?: string;
+  target?: string;
+}
+
+function parseAttributes(source: string): Record<string, string> {
+  const attrs: Record<string, string> = {};
+  for (const match of source.matchAll(/([\w:-]+)\s*=\s*"([^"]*)"/g)) {
+    attrs[match[1]] = decodeEntities(match[2]);
+  }
+  return attrs;
+}
+
+function decodeEntities(text: string): string {
+  return text
+    .replace(/&lt;/g, '<')
+    .replace(/&gt;/g, '>')
+    .replace(/&quot;/g, '"')
+    .replace(/&#39;|&apos;/g, "'")
+    .replace(/&#xa;|&#10;/gi, '\n')
+    .replace(/&nbsp;/g, ' ')
+    .replace(/&amp;/g, '&');
+}
+
+/** draw.io labels are often HTML; keep only the visible text */
+function plainLabel(value: string): string {
+  return decodeEntities(value.replace(/<br\s*\/?>/gi, ' ').replace(/<[^>]+>/g, ''))
+    .replace(/\s+/g, ' ')
+    .trim();
+}
+
+/**
+ * Compressed diagrams store deflated, base64 encoded, URI-escaped XML inside <diagram>
+ */
+function expandDiagrams(xml: string): string {
+  return xml.replace(/<diagram\b([^>]*)>([\s\S]*?)<\/diagram>/g, (whole, _attrs, body: string) => {
+    const trimmed = body.trim();
+    if (!trimmed || trimmed.startsWith('<')) return whole;
+    try {
+      const inflated = zlib.inflateRawSync(Buffer.from(trimmed, 'base64')).toString('utf-8');
+      return decodeURIComponent(inflated);
+    } catch {
+      return whole;
+    }
+  });
+}
+
+function parseCells(xml: string): RawCell[] {
+  const cells: RawCell[] = [];
+  const source = expandDiagrams(xml);
+
+  // <object>/<UserObject> wrappers carry the id and label for the mxCell nested inside
+  const pattern = /<(?:(UserObject|object)\b([^>]*)>\s*<mxCell\b([^>]*?)\/?>|mxCell\b([^>]*?)\/?>)/g;
+  for (const match of source.matchAll(pattern)) {
+    const wrapper = match[1] ? parseAttributes(match[2]) : {};
+    const attrs = parseAttributes(match[3] ?? match[4] ?? '');
+    const id = wrapper.id || attrs.id;
+    if (!id) continue;
+
+    cells.push({
+      id,
+      value: wrapper.label ?? attrs.value ?? '',
+      style: attrs.style || '',
+      parent: attrs.parent,
+      vertex: attrs.vertex === '1',
+      edge: attrs.edge === '1',
+      source: attrs.source,
+      target: attrs.target,
+    });
+  }
+  return cells;
+}
+
+function parseStyle(style: string): Record<string, string> {
+  const props: Record<string, string> = {};
+  for (const part of style.split(';').filter(Boolean)) {
+    const [key, value] = part.split('=');
+    props[key] = value ?? '';
+  }
+  return props;
+}
+
+/**
+ * Parse draw.io XML into an untyped graph; node types and edge kinds are filled in by later passes
+ */
+export function parseDrawio(xml: string): ProductGraph {
+  const cells = parseCells(xml);
+  const nodes: ProductNode[] = [];
+  const edges: ProductEdge[] = [];
+
+  for (const cell of cells) {
+    if (cell.vertex) {
+      nodes.push({
+        id: cell.id,
+        label: plainLabel(cell.value),
+        node_type: 'unknown',
+        properties: { style: cell.style, ...(cell.parent && { parent: cell.parent }) },
+      });
+    } else if (cell.edge) {
+      const style = parseStyle(cell.style);
+      edges.push({
+        id: cell.id,
+        source: cell.source || '',
+        target: cell.target || '',
+        label: plainLabel(cell.value),
+        relationship_type: style.dashed === '1' ? 'dependency' : style.endArrow === 'none' ? 'association' : 'flow',
+        relation_kind: 'association',
+      });
+    }
+  }
+
+  // Edge labels are usually child vertices of the edge (style "edgeLabel")
+  const edgeById = new Map(edges.map(e => [e.id, e]));
+  const labelNodes = new Set<string>();
+  for (const node of nodes) {
+    const parentEdge = node.properties.parent && edgeById.get(node.properties.parent);
+    if (parentEdge && node.properties.style.includes('edgeLabel')) {
+      if (!parentEdge.label) parentEdge.label = node.label;
+      labelNodes.add(node.id);
+    }
+  }
+
+  // Table rows split their text across child cells ("PK" | "id"); fold them into the row
+  for (const row of nodes.filter(n => n.properties.style.includes('shape=tableRow'))) {
+    const cells = nodes.filter(n => n.properties.parent === row.id);
+    row.label = [row.label, ...cells.map(c => c.label)].filter(Boolean).join(' ');
+    cells.forEach(c => labelNodes.add(c.id));
+  }
+
+  return { nodes: nodes.filter(n => !labelNodes.has(n.id)), edges };
+}
+
+const TYPE_RULES: Array<{ type: NodeType; style?: RegExp; label?: RegExp }> = [
+  { type: 'actor', style: /shape=umlActor/, label: /\b(user|admin|customer|visitor|actor|member|guest)s?\b/i },
+  { type: 'database', style: /shape=(cylinder\d?|datastore)/, label: /\b(database|db|table|collection|storage|store)\b/i },
+  { type: 'decision', style: /\brhombus\b/, label: /\?$/ },
+  { type: 'external_service', style: /shape=(cloud|mxgraph\.aws)/, label: /\b(stripe|paypal|twilio|sendgrid|smtp|third[- ]party|external|oauth|google|github)\b/i },
+  { type: 'api', label: /\b(api|endpoint|rest|graphql|webhook)\b|^(GET|POST|PUT|PATCH|DELETE)\s/i },
+  { type: 'screen', label: /\b(screen|page|view|dashboard|form|modal|ui)\b/i },
+  { type: 'feature', label: /\b(feature|module|manage|management|checkout|login|signup|search|notifications?)\b/i },
+];
+
+/**
+ * Infer a node type from its draw.io style first, then from label keywords.
+ * ER shapes (tables/swimlanes, or any shape holding other shapes) are entities and their
+ * children are attributes.
+ */
+export function inferTypes(graph: ProductGraph): ProductGraph {
+  const ids = new Set(graph.nodes.map(n => n.id));
+  const containers = new Set(graph.nodes.map(n => n.properties.parent).filter((p): p is string => !!p && ids.has(p)));
+  const entities = new Set(
+    graph.nodes
+      .filter(n => containers.has(n.id) || /\b(swimlane|shape=table)\b/.test(n.properties.style || ''))
+      .map(n => n.id)
+  );
+
+  for (const node of graph.nodes) {
+    if (entities.has(node.id)) {
+      node.node_type = 'entity';
+      continue;
+    }
+    if (node.properties.parent && entities.has(node.properties.parent)) {
+      node.node_type = 'attribute';
+      continue;
+    }
+
+    const style = node.properties.style || '';
+    const byStyle = TYPE_RULES.find(rule => rule.style?.test(style));
+    const byLabel = TYPE_RULES.find(rule => rule.label?.test(node.label));
+    node.node_type = byStyle?.type || byLabel?.type || (node.label ? 'process' : 'unknown');
+  }
+  return graph;
+}
+
+// `(?!\w)` rather than `\b` so labels ending in a symbol, like `1:*`, still match
+const RELATION_RULES: Array<{ kind: RelationKind; pattern: RegExp }> = [
+  { kind: 'ownership', pattern: /\b(has (many|one|a|an)|owns?|contains?|belongs to|consists of|includes?|1\s*[:.]+\s*(n|\*|many))(?!\w)/i },
+  { kind: 'trigger', pattern: /\b(triggers?|fires?|invokes?|calls?|emits?|notif(y|ies)|starts?|on\s+\w+|when)(?!\w)/i },
+  { kind: 'data_flow', pattern: /\b(reads?|writes?|sends?|stores?|saves?|fetch(es)?|loads?|returns?|queries|query|syncs?|data|uploads?|downloads?)(?!\w)/i },
+];
+
+export function classifyRelation(label: string): RelationKind {
+  return RELATION_RULES.find(rule => rule.pattern.test(label))?.kind || 'association';
+}
+
+/**
+ * Derive each edge's relation kind from its label text ("has many", "triggers", ...)
+ */
+export function classifyEdges(graph: ProductGraph): ProductGraph {
+  for (const edge of graph.edges) {
+    edge.relation_kind = classifyRelation(edge.label);
+  }
+  return graph;
+}
+
+export function validateGraph(graph: ProductGraph): ValidationIssue[] {
+  const issues: ValidationIssue[] = [];
+  const ids = new Set(graph.nodes.map(n => n.id));
+  const connected = new Set<string>();
+
+  for (const edge of graph.edges) {
+    if (!ids.has(edge.source) || !ids.has(edge.target)) {
+      issues.push({ severity: 'error', rule: 'dangling_edge', element_id: edge.id, message: 'Edge is not connected at both ends' });
+    }
+    connected.add(edge.source);
+    connected.add(edge.target);
+  }
+
+  for (const node of graph.nodes) {
+    if (!node.label) {
+      issues.push({ severity: 'warning', rule: 'unlabeled_node', element_id: node.id, message: 'Node has no label' });
+    } else if (node.node_type === 'unknown' || node.node_type === 'process') {
+      issues.push({ severity: 'info', rule: 'unknown_type', element_id: node.id, message: `Could not infer a specific type for "${node.label}"` });
+    }
+    // Attributes hang off their entity, not off edges
+    if (!connected.has(node.id) && node.node_type !== 'attribute') {
+      issues.push({ severity: 'warning', rule: 'isolated_node', element_id: node.id, message: `"${node.label || node.id}" is not connected to anything` });
+    }
+    if (node.node_type === 'decision') {
+      const outgoing = graph.edges.filter(e => e.source === node.id).length;
+      if (outgoing < 2) {
+        issues.push({ severity: 'warning', rule: 'decision_branches', element_id: node.id, message: `Decision "${node.label}" should have at least two outgoing edges` });
+      }
+    }
+  }
+
+  return issues;
+}
+
+export function computeStats(graph: ProductGraph, issues: ValidationIssue[]): GraphStats {
+  const relation_counts: Record<RelationKind, number> = { association: 0, ownership: 0, trigger: 0, data_flow: 0 };
+  for (const edge of graph.edges) relation_counts[edge.relation_kind]++;
+
+  return {
+    total_nodes: graph.nodes.length,
+    total_edges: graph.edges.length,
+    unknown_type_count: graph.nodes.filter(n => n.node_type === 'unknown').length,
+    issue_count: issues.length,
+    relation_counts,
+  };
+}
+
+const SEVERITY_RANK: Record<Severity, number> = { info: 0, warning: 1, error: 2 };
+
+export function isSeverity(value: unknown): value is Severity {
+  return typeof value === 'string' && Object.hasOwn(SEVERITY_RANK, value);
+}
+
+/**
+ * Drop issues below `minSeverity`, keeping the stats in step with what is returned
+ */
+export function filterIssues(result: AnalysisResult, minSeverity: Severity): AnalysisResult {
+  const issues = result.issues.filter(issue => SEVERITY_RANK[issue.severity] >= SEVERITY_RANK[minSeverity]);
+  return { ...result, issues, stats: { ...result.stats, issue_count: issues.length } };
+}
+
+/** The issues bucketed by severity, every bucket present even when empty */
+export function issuesBySeverity(result: AnalysisResult): Record<Severity, ValidationIssue[]> {
+  const groups: Record<Severity, ValidationIssue[]> = { info: [], warning: [], error: [] };
+  for (const issue of result.issues) groups[issue.severity].push(issue);
+  return groups;
+}
+
+/** The issues raised against one node or edge */
+export function issuesForNode(result: AnalysisResult, nodeId: string): ValidationIssue[] {
+  return result.issues.filter(issue => issue.element_id === nodeId);
+}
+
+/**
+ * Full pipeline: parse → infer node types → classify edges → validate
+ */
+export function analyzeDiagram(xml: string): AnalysisResult {
+  const graph = classifyEdges(inferTypes(parseDrawio(xml)));
+  const issues = validateGraph(graph);
+  return { graph, issues, stats: computeStats(graph, issues) };
+}
+
+const FIELD_TYPE_ALIASES: Record<string, string> = {
+  string: 'string', varchar: 'string', char: 'string', email: 'string',
+  text: 'text',
+  int: 'int', integer: 'int', number: 'int', bigint: 'int',
+  float: 'float', double: 'float', decimal: 'decimal', money: 'decimal',
+  bool: 'boolean', boolean: 'boolean',
+  date: 'datetime', datetime: 'datetime', timestamp: 'datetime',
+  uuid: 'uuid', id: 'uuid', objectid: 'uuid',
+};
+
+const FIELD_NAME_HINTS: Array<{ type: string; pattern: RegExp }> = [
+  { type: 'uuid', pattern: /^id$|Id$|_id$/ },
+  { type: 'datetime', pattern: /date|time|At$|_at$|birthday/i },
+  { type: 'int', pattern: /count|quantity|qty|age|number|num|total|stock|position/i },
+  { type: 'float', pattern: /price|amount|cost|rate|balance|score|lat|lng|longitude|latitude/i },
+  { type: 'boolean', pattern: /^(is|has|can|should)[A-Z_]|enabled|active|verified|published/ },
+  { type: 'text', pattern: /description|body|content|bio|notes?|summary/i },
+];
+
+/**
+ * Parse an ER attribute label such as "email", "+ age: int" or "id (PK)" into a field
+ */
+function toField(node: ProductNode): FieldSchema | null {
+  const [rawName, rawType] = node.label.split(':').map(part => part.trim());
+  const primaryKey = /\b(pk|primary key)\b/i.test(node.label);
+  const name = camelCase(rawName.replace(/\((pk|fk|primary key)\)|\b(pk|fk)\b/gi, '').replace(/^[^a-zA-Z0-9]+|[^a-zA-Z0-9]+$/g, ''));
+  if (!name) return null;
+
+  const explicit = rawType && FIELD_TYPE_ALIASES[rawType.toLowerCase().replace(/[^a-z]/g, '')];
+  const hinted = FIELD_NAME_HINTS.find(hint => hint.pattern.test(name))?.type;
+
+  return {
+    id: node.id,
+    name,
+    field_type: explicit || hinted || 'string',
+    required: primaryKey || !/\?|optional|nullable/i.test(node.label),
+    unique: primaryKey || /\b(unique|uq)\b/i.test(node.label) || name === 'email',
+    primary_key: primaryKey || name === 'id',
+  };
+}
+
+export interface DataModelDrafts {
+  models: DataModelSchema[];
+  /** Entities and attributes left out because their label can't name a model or field */
+  issues: ValidationIssue[];
+}
+
+/**
+ * Turn entity nodes into data models, their attribute children into fields and
+ * ownership edges between entities into relations. Model, field and relation ids are the
+ * diagram cell ids; callers persisting the result must remap them. Labels that don't make a
+ * valid identifier, and entities repeating an earlier entity's name, are skipped with an issue.
+ */
+export function toDataModels(graph: ProductGraph): DataModelDrafts {
+  const issues: ValidationIssue[] = [];
+  const skip = (node: ProductNode, rule: string, message: string) => {
+    issues.push({ severity: 'warning', rule, element_id: node.id, message });
+    return false;
+  };
+
+  const names = new Set<string>();
+  const entities = graph.nodes.filter(node => {
+    if (node.node_type !== 'entity') return false;
+    const error = validateIdentifier(node.label, 'model');
+    if (error) return skip(node, 'invalid_model_name', error);
+    const name = modelName(node.label);
+    if (names.has(name.toLowerCase())) return skip(node, 'duplicate_model_name', `Another entity already makes a model named "${name}"`);
+    names.add(name.toLowerCase());
+    return true;
+  });
+  const entityIds = new Set(entities.map(n => n.id));
+
+  const models = entities.map(entity => {
+    const fields: FieldSchema[] = [];
+    for (const node of graph.nodes) {
+      if (node.node_type !== 'attribute' || node.properties.parent !== entity.id) continue;
+      const field = toField(node);
+      const error = validateIdentifier(field?.name ?? node.label, 'field');
+      if (!field || error) {
+        skip(node, 'invalid_field_name', error || `Field name "${node.label}" has no letters or digits to build an identifier from`);
+        continue;
+      }
+      if (!fields.some(f => f.name === field.name)) fields.push(field);
+    }
+    if (!fields.some(f => f.primary_key)) {
+      fields.unshift({ id: 'id', name: 'id', field_type: 'uuid', required: true, unique: true, primary_key: true });
+    }
+
+    const relations: RelationSchema[] = graph.edges
+      .filter(e => e.relation_kind === 'ownership' && e.source === entity.id && entityIds.has(e.target))
+      .map(e => {
+        const target = entities.find(n => n.id === e.target)!;
+        const many = /many|\*|\b1\s*[:.]+\s*n\b/i.test(e.label);
+        return {
+          id: e.id,
+          name: camelCase(target.label) + (many ? 's' : ''),
+          target_model_id: target.id,
+          relation_type: many ? 'one_to_many' : 'one_to_one',
+        };
+      });
+
+    return {
+      id: entity.id,
+      name: modelName(entity.label),
+      fields,
+      relations,
+      timestamps: true,
+      soft_delete: false,
+      archived: false,
+    };
+  });
+
+  return { models, issues };
+}
+
+function modelName(label: string): string {
+  return pascalCase(label.replace(/[^a-zA-Z0-9 _-]/g, ''));
+}
@@ -0,0 +1,100 @@
+import { getCommandDb } from './commands.js';
+import { getProjectStore } from './projectStore.js';
+import { parseJsonValue } from './projectSchema.js';
+import { SyncService } from './sync.js';
+
+/** How a project wants block edits written to disk, read from `settings.auto_sync` */
+export interface AutoSyncSettings {
+  /** Write after each edit (default true); when false only an explicit sync writes */
+  enabled: boolean;
+  /** When above zero, write once this long after the last edit to a page instead of after each one */
+  debounceMs: number;
+}
+
+export function autoSyncSettings(settings: Record<string, any>): AutoSyncSettings {
+  const debounceMs = settings.auto_sync_debounce_ms;
+  return {
+    enabled: settings.auto_sync !== false,
+    debounceMs: typeof debounceMs === 'number' && debounceMs > 0 ? debounceMs : 0,
+  };
+}
+
+/** Why `auto_sync`/`auto_sync_debounce_ms` in a settings update can't be saved, or null */
+export function validateAutoSyncSettings(settings: Record<string, unknown>): string | null {
+  if (settings.auto_sync !== undefined && typeof settings.auto_sync !== 'boolean') {
+    return 'auto_sync must be true or false';
+  }
+  const debounceMs = settings.auto_sync_debounce_ms;
+  if (debounceMs !== undefined && (typeof debounceMs !== 'number' || !Number.isFinite(debounceMs) || debounceMs < 0)) {
+    return 'auto_sync_debounce_ms must be a non-negative number';
+  }
+  return null;
+}
+
+/** Debounced page syncs waiting to run, by page id */
+const pending = new Map<string, NodeJS.Timeout>();
+
+/** The page a block is on, following its parents when the block itself has no page */
+async function blockPageId(blockId: string): Promise<string | null> {
+  const db = getCommandDb();
+  let block = await db.block.findUnique({ where: { id: blockId }, select: { pageId: true, parentId: true } });
+  const seen = new Set<string>();
+  while (block && !block.pageId && block.parentId && !seen.has(block.parentId)) {
+    seen.add(block.parentId);
+    block = await db.block.findUnique({ where: { id: block.parentId }, select: { pageId: true, parentId: true } });
+  }
+  if (!block?.pageId) return null;
+  const page = await db.page.findFirst({ where: { idRoot: block.pageId }, select: { id: true } });
+  return page?.id ?? null;
+}
+
+/** The pages holding `blockIds`, each once; look them up before the blocks are deleted */
+export async function blockPageIds(blockIds: string[]): Promise<string[]> {
+  const pageIds = new Set<string>();
+  for (const blockId of blockIds) {
+    const pageId = await blockPageId(blockId);
+    if (pageId) pageIds.add(pageId);
+  }
+  return [...pageIds];
+}
+
+async function syncPage(rootPath: string, pageId: string, projectId: string) {
+  try {
+    const conflict = await new SyncService(rootPath).syncPageToDisk(pageId, projectId);
+    if (conflict) console.warn(`[AutoSync] ${conflict.message}`);
+  } catch (error) {
+    console.error('[AutoSync] Failed to sync page:', error);
+  }
+}
+
+/**
+ * Write pages to disk after an edit, as the project's `auto_sync` settings ask: right away
+ * by default, once `auto_sync_debounce_ms` passes without another edit to the page when that
+ * is set, or not at all when `auto_sync` is false. Projects without a root path are skipped.
+ * A failed write is logged; the edit itself has succeeded.
+ */
+export async function autoSyncPages(projectId: string, pageIds: string[]): Promise<void> {
+  if (pageIds.length === 0) return;
+  const project = await getProjectStore().getProjectById(projectId);
+  if (!project?.rootPath) return;
+  const settings = autoSyncSettings(parseJsonValue<Record<string, any>>(project.settings, {}));
+  if (!settings.enabled) return;
+
+  const rootPath = project.rootPath;
+  for (const pageId of pageIds) {
+    if (settings.debounceMs > 0) {
+      clearTimeout(pending.get(pageId));
+      pending.set(pageId, setTimeout(() => {
+        pending.delete(pageId);
+        void syncPage(rootPath, pageId, projectId);
+      }, settings.debounceMs));
+    } else {
+      await syncPage(rootPath, pageId, projectId);
+    }
+  }
+}
+
+/** `autoSyncPages` for the page holding one edited block */
+export async function autoSyncBlockPage(projectId: string, blockId: string): Promise<void> {
+  await autoSyncPages(projectId, await blockPageIds([blockId]));
+}
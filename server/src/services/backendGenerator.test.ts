@@ -0,0 +1,342 @@
+import { test } from 'node:test';
+import assert from 'node:assert/strict';
+import { BackendGeneratorService } from './backendGenerator.js';
+import type { ProjectSchema } from './projectSchema.js';
+
+const project = (settings: Record<string, unknown>) => ({
+  id: 'p1', name: 'Shop', settings, data_models: [], apis: [], logic_flows: [], pages: [], blocks: [], variables: [],
+} as unknown as ProjectSchema);
+
+const file = (settings: Record<string, unknown>, path: string) =>
+  new BackendGeneratorService(project(settings)).generate().find(f => f.path === path)?.content ?? '';
+
+test('generated auth signs tokens only with a configured secret', () => {
+  const settings = { auth: { enabled: true } };
+  assert.doesNotMatch(file(settings, 'src/auth/auth.module.ts'), /change-me|JWT_SECRET \|\|/);
+  assert.match(file(settings, 'src/env.ts'), /const REQUIRED = \['DATABASE_URL', 'JWT_SECRET'\];/);
+  assert.match(file({}, 'src/env.ts'), /const REQUIRED = \['DATABASE_URL'\];/);
+});
+
+test('generated refresh revokes the presented token only while it is still live', () => {
+  const service = file({ auth: { enabled: true, refresh_tokens: true } }, 'src/auth/auth.service.ts');
+  assert.match(service, /updateMany\(\{\s*where: \{ id: stored\.id, revokedAt: null \}/);
+  assert.match(service, /if \(claimed\.count === 0\)/);
+  assert.doesNotMatch(service, /refreshToken\.update\(/);
+});
+
+test('generated e2e harness has a starter suite per model', () => {
+  const schema = {
+    ...project({}),
+    data_models: [
+      { id: 'm1', name: 'Product', fields: [], relations: [] },
+      { id: 'm2', name: 'Order', fields: [], relations: [], owner_field: 'userId' },
+    ],
+  } as unknown as ProjectSchema;
+  const files = new BackendGeneratorService(schema).generate();
+  const spec = (path: string) => files.find(f => f.path === path)?.content ?? '';
+
+  assert.match(spec('test/product.e2e-spec.ts'), /get\('\/products'\)\.expect\(200\)/);
+  assert.match(spec('test/order.e2e-spec.ts'), /get\('\/orders'\)\.expect\(401\)/);
+  assert.match(spec('package.json'), /--passWithNoTests/);
+});
+
+test('generated endpoints treat prototype keys as unknown types', () => {
+  const schema = {
+    ...project({}),
+    apis: [{
+      id: 'a1', method: 'GET', path: '/search', name: 'search', permissions: [], archived: false,
+      query_params: [{ name: 'q', param_type: 'constructor' }],
+      response_body: { shape_type: 'toString' },
+    }],
+  } as unknown as ProjectSchema;
+  const files = new BackendGeneratorService(schema).generate();
+  const controller = files.find(f => f.path === 'src/endpoints/endpoints.controller.ts')?.content ?? '';
+
+  assert.match(controller, /@ApiQuery\(\{ name: 'q', type: String/);
+  assert.doesNotMatch(files.map(f => f.content).join('\n'), /native code|constructor\(\) \{ \[/);
+});
+
+test('generated upload routes cap the file size', () => {
+  const schema = {
+    ...project({}),
+    data_models: [{
+      id: 'm1', name: 'Profile', relations: [],
+      fields: [{ id: 'f1', name: 'avatar', field_type: 'file', required: false, unique: false, primary_key: false }],
+    }],
+  } as unknown as ProjectSchema;
+  const files = new BackendGeneratorService(schema).generate();
+  const file = (path: string) => files.find(f => f.path === path)?.content ?? '';
+
+  assert.match(file('src/profile/profile.controller.ts'), /FileInterceptor\('file', \{ limits: \{ fileSize: MAX_UPLOAD_BYTES \} \}\)/);
+  assert.match(file('src/storage/storage.service.ts'), /export const MAX_UPLOAD_BYTES = Number\(process\.env\.MAX_UPLOAD_BYTES\)/);
+});
+
+test('generated seed creates a relation\'s target before the model that references it', () => {
+  const text = (name: string) => ({ id: name, name, field_type: 'string', required: true, unique: false, primary_key: false });
+  const schema = {
+    ...project({}),
+    data_models: [
+      { id: 'post', name: 'Post', fields: [text('title')], relations: [{ id: 'r1', name: 'author', target_model_id: 'user', relation_type: 'many_to_one' }] },
+      { id: 'user', name: 'User', fields: [text('name')], relations: [] },
+    ],
+  } as unknown as ProjectSchema;
+  const seed = new BackendGeneratorService(schema).generate().find(f => f.path === 'prisma/seed.ts')?.content ?? '';
+
+  const user = seed.indexOf('const user = await prisma.user.create(');
+  const post = seed.indexOf('const post = await prisma.post.create(');
+  assert.ok(user !== -1 && post !== -1 && user < post);
+  assert.match(seed.slice(post), /author: \{ connect: \{ id: user\.id \} \},/);
+});
+
+test('generated list route for a soft-delete model honours ?includeDeleted=true', () => {
+  const schema = {
+    ...project({}),
+    data_models: [{ id: 'm1', name: 'Order', fields: [], relations: [], soft_delete: true }],
+  } as unknown as ProjectSchema;
+  const files = new BackendGeneratorService(schema).generate();
+  const file = (path: string) => files.find(f => f.path === path)?.content ?? '';
+
+  assert.match(file('src/order/order.controller.ts'), /findAll\(@Query\('includeDeleted'\) includeDeleted\?: string\) \{\s*return this\.service\.findAll\(includeDeleted === 'true'\);/);
+  assert.match(file('src/order/order.service.ts'), /includeDeleted \? \{\} : \{ deletedAt: null \}/);
+});
+
+test('generated unique email field is @unique in Prisma and validated with @IsEmail()', () => {
+  const schema = {
+    ...project({}),
+    data_models: [{
+      id: 'm1', name: 'User', relations: [],
+      fields: [{ id: 'f1', name: 'email', field_type: 'email', required: true, unique: true, primary_key: false }],
+    }],
+  } as unknown as ProjectSchema;
+  const files = new BackendGeneratorService(schema).generate();
+  const file = (path: string) => files.find(f => f.path === path)?.content ?? '';
+
+  assert.match(file('prisma/schema.prisma'), /^ {2}email String @db\.VarChar\(255\) @unique$/m);
+  assert.match(file('src/user/dto/create-user.dto.ts'), /@IsNotEmpty\(\)\n {2}@IsEmail\(\)\n {2}email!: string;/);
+  assert.match(file('src/user/dto/update-user.dto.ts'), /@IsOptional\(\)\n {2}@IsEmail\(\)\n {2}email\?: string;/);
+});
+
+test('generated endpoint with a rate limit is throttled and the app imports ThrottlerModule', () => {
+  const schema = {
+    ...project({}),
+    apis: [
+      { id: 'a1', method: 'POST', path: '/api/login', name: 'login', permissions: [], archived: false, rate_limit: { requests: 5, window_seconds: 30 } },
+      { id: 'a2', method: 'GET', path: '/api/status', name: 'status', permissions: [], archived: false },
+    ],
+  } as unknown as ProjectSchema;
+  const files = new BackendGeneratorService(schema).generate();
+  const file = (path: string) => files.find(f => f.path === path)?.content ?? '';
+
+  const controller = file('src/endpoints/endpoints.controller.ts');
+  assert.match(controller, /import \{ Throttle, ThrottlerGuard \} from '@nestjs\/throttler';/);
+  assert.match(controller, /@UseGuards\(ThrottlerGuard\)\n {2}@Throttle\(\{ default: \{ limit: 5, ttl: 30000 \} \}\)\n(?: {2}@.*\n)* {2}async login\(/);
+  assert.doesNotMatch(controller.slice(controller.indexOf("@Get('status')")), /@Throttle\(/);
+  assert.match(file('src/app.module.ts'), /import \{ ThrottlerModule \} from '@nestjs\/throttler';[\s\S]*ThrottlerModule\.forRoot\(/);
+  assert.equal(JSON.parse(file('package.json')).dependencies['@nestjs/throttler'], '^5.1.0');
+});
+
+test('generated enum field gets a Prisma enum block and @IsEnum validation', () => {
+  const schema = {
+    ...project({}),
+    data_models: [{
+      id: 'm1', name: 'Order', relations: [],
+      fields: [{ id: 'f1', name: 'status', field_type: 'enum', enum_values: ['PENDING', 'SHIPPED'], required: true, unique: false, primary_key: false }],
+    }],
+  } as unknown as ProjectSchema;
+  const files = new BackendGeneratorService(schema).generate();
+  const file = (path: string) => files.find(f => f.path === path)?.content ?? '';
+
+  const prisma = file('prisma/schema.prisma');
+  assert.match(prisma, /^enum OrderStatus \{\n {2}PENDING\n {2}SHIPPED\n\}$/m);
+  assert.match(prisma, /^ {2}status OrderStatus$/m);
+  const dto = file('src/order/dto/create-order.dto.ts');
+  assert.match(dto, /import \{ OrderStatus \} from '@prisma\/client';/);
+  assert.match(dto, /@IsEnum\(OrderStatus\)\n {2}status!: OrderStatus;/);
+});
+
+test('generated decimal field keeps its precision in Prisma and is validated with @IsDecimal()', () => {
+  const schema = {
+    ...project({}),
+    data_models: [{
+      id: 'm1', name: 'Product', relations: [],
+      fields: [{ id: 'f1', name: 'price', field_type: 'decimal', precision: 10, scale: 2, required: true, unique: false, primary_key: false }],
+    }],
+  } as unknown as ProjectSchema;
+  const files = new BackendGeneratorService(schema).generate();
+  const file = (path: string) => files.find(f => f.path === path)?.content ?? '';
+
+  assert.match(file('prisma/schema.prisma'), /^ {2}price Decimal @db\.Decimal\(10,2\)$/m);
+  assert.match(file('src/product/dto/create-product.dto.ts'), /@IsDecimal\(\)\n {2}price!: string;/);
+  assert.match(file('prisma/seed.ts'), /price: '[\d.]+'/);
+});
+
+test('generated endpoint bound to a backend flow runs it with the api trigger and request context', () => {
+  const schema = {
+    ...project({}),
+    logic_flows: [{ id: 'flow1', name: 'Checkout', context: 'backend', nodes: [], trigger: { type: 'api' } }],
+    apis: [
+      { id: 'a1', method: 'POST', path: '/api/checkout', name: 'checkout', permissions: [], archived: false, logic_flow_id: 'flow1' },
+      { id: 'a2', method: 'GET', path: '/api/status', name: 'status', permissions: [], archived: false },
+    ],
+  } as unknown as ProjectSchema;
+  const controller = new BackendGeneratorService(schema).generate().find(f => f.path === 'src/endpoints/endpoints.controller.ts')?.content ?? '';
+
+  assert.match(controller, /import \{ runFlow \} from '\.\.\/logic\/flow-runner';/);
+  assert.match(controller, /async checkout\(.*@Req\(\) req: Request, @Res\(\{ passthrough: true \}\) res: Response\) \{\n\s*return runFlow\('flow1', \{ trigger: 'api', [^\n]*context: \{ req, res \} \}\);/);
+  assert.doesNotMatch(controller.slice(controller.indexOf('async status(')), /runFlow/);
+});
+
+test('generated service for a model with an owner field scopes reads and stamps creates with the user', () => {
+  const schema = {
+    ...project({ auth: { enabled: true } }),
+    data_models: [{
+      id: 'm1', name: 'Note', relations: [], owner_field: 'userId',
+      fields: [{ id: 'f1', name: 'body', field_type: 'string', required: true, unique: false, primary_key: false }],
+    }],
+  } as unknown as ProjectSchema;
+  const files = new BackendGeneratorService(schema).generate();
+  const file = (path: string) => files.find(f => f.path === path)?.content ?? '';
+
+  const service = file('src/note/note.service.ts');
+  assert.match(service, /findAll\(ownerId: string\) \{\n\s*return prisma\.note\.findMany\(\{ where: \{ userId: ownerId \} \}\);/);
+  assert.match(service, /findOne\(id: string, ownerId: string\) \{\n\s*return prisma\.note\.findFirst\(\{ where: \{ id, userId: ownerId \} \}\);/);
+  assert.match(service, /create\(dto: CreateNoteDto, ownerId: string\) \{\n\s*return prisma\.note\.create\(\{ data: \{ \.\.\.dto, userId: ownerId \} as Prisma\.NoteUncheckedCreateInput \}\);/);
+
+  const controller = file('src/note/note.controller.ts');
+  assert.match(controller, /return this\.service\.findAll\(req\.user\.sub\);/);
+  assert.match(controller, /return this\.service\.create\(dto, req\.user\.sub\);/);
+  assert.doesNotMatch(file('src/note/dto/create-note.dto.ts'), /userId/);
+});
+
+test('generating the same project twice gives the same files in the same order', () => {
+  const schema = {
+    ...project({ auth: { enabled: true } }),
+    data_models: ['Customer', 'Order', 'Product'].map((name, i) => ({
+      id: `m${i}`, name, relations: [],
+      fields: [{ id: `f${i}`, name: 'title', field_type: 'string', required: true, unique: false, primary_key: false }],
+    })),
+    apis: [
+      { id: 'a1', method: 'GET', path: '/reports', name: 'reports', permissions: [], archived: false },
+      { id: 'a2', method: 'POST', path: '/checkout', name: 'checkout', permissions: [], archived: false },
+    ],
+  } as unknown as ProjectSchema;
+
+  const first = new BackendGeneratorService(schema).generate();
+  const second = new BackendGeneratorService(schema).generate();
+  assert.deepEqual(second.map(f => [f.path, f.content]), first.map(f => [f.path, f.content]));
+
+  const appModule = first.find(f => f.path === 'src/app.module.ts')?.content ?? '';
+  const modules = [...appModule.matchAll(/import \{ (\w+)Module \} from '\.\/\w+\/\w+\.module';/g)].map(m => m[1]);
+  assert.deepEqual(modules.filter(name => ['Customer', 'Order', 'Product'].includes(name!)), ['Customer', 'Order', 'Product']);
+});
+
+test('generated model gets an @@index per indexed field', () => {
+  const schema = {
+    ...project({}),
+    data_models: [{
+      id: 'm1', name: 'Order', relations: [],
+      fields: [
+        { id: 'f1', name: 'status', field_type: 'string', required: true, unique: false, primary_key: false, indexed: true },
+        { id: 'f2', name: 'placedAt', field_type: 'datetime', required: true, unique: false, primary_key: false, indexed: true },
+        { id: 'f3', name: 'note', field_type: 'string', required: false, unique: false, primary_key: false },
+      ],
+    }],
+  } as unknown as ProjectSchema;
+  const prisma = new BackendGeneratorService(schema).generate().find(f => f.path === 'prisma/schema.prisma')?.content ?? '';
+  const model = prisma.slice(prisma.indexOf('model Order {'));
+
+  assert.deepEqual(model.match(/^ {2}@@index\(.*\)$/gm), ['  @@index([status])', '  @@index([placedAt])']);
+});
+
+test('generated main.ts allows the configured CORS origins instead of any origin', () => {
+  const main = file({ cors_origins: ['https://shop.example.com/', 'https://admin.example.com', 'javascript:alert(1)'] }, 'src/main.ts');
+  assert.match(main, /\?\? \['https:\/\/shop\.example\.com', 'https:\/\/admin\.example\.com'\];/);
+  assert.match(main, /app\.enableCors\(corsOrigins\.length > 0 \? \{ origin: corsOrigins \} : undefined\);/);
+  assert.doesNotMatch(main, /'\*'|javascript:/);
+
+  assert.match(file({}, 'src/main.ts'), /\?\? \[\];/);
+});
+
+test('generated POST endpoint documents an example request body with @ApiBody', () => {
+  const schema = {
+    ...project({}),
+    apis: [{
+      id: 'a1', method: 'POST', path: '/newsletter', name: 'subscribe', permissions: [], archived: false,
+      request_body: { shape_type: 'object', fields: [{ name: 'email', field_type: 'string' }, { name: 'weekly', field_type: 'boolean' }] },
+    }],
+  } as unknown as ProjectSchema;
+  const controller = new BackendGeneratorService(schema).generate().find(f => f.path === 'src/endpoints/endpoints.controller.ts')?.content ?? '';
+
+  assert.match(controller, /import \{ [^}]*ApiBody[^}]* \} from '@nestjs\/swagger';/);
+  assert.match(controller, /@ApiBody\(\{ examples: \{ default: \{ value: \{ email: 'string', weekly: true \} \} \} \}\)\n(?: {2}@.*\n)*\s*async subscribe\(/);
+});
+
+test('generated endpoint with a status query param takes it as a typed @Query argument', () => {
+  const schema = {
+    ...project({}),
+    apis: [{
+      id: 'a1', method: 'GET', path: '/orders/:customerId', name: 'customerOrders', permissions: [], archived: false,
+      path_params: [{ name: 'customerId', param_type: 'number' }],
+      query_params: [{ name: 'status', param_type: 'string', required: true }, { name: 'limit', param_type: 'number', default: '20' }],
+    }],
+  } as unknown as ProjectSchema;
+  const controller = new BackendGeneratorService(schema).generate().find(f => f.path === 'src/endpoints/endpoints.controller.ts')?.content ?? '';
+
+  assert.match(controller, /async customerOrders\(@Param\('customerId', ParseFloatPipe\) customerId: number, @Query\('status'\) status: string, @Query\('limit', new DefaultValuePipe\(20\), ParseFloatPipe\) limit: number/);
+  assert.match(controller, /@ApiQuery\(\{ name: 'status', type: String \}\)/);
+  assert.match(controller, /@ApiQuery\(\{ name: 'limit', type: Number, required: false \}\)/);
+});
+
+test('generated relation with on_delete Cascade passes it to Prisma, and a required one has a required key', () => {
+  const schema = {
+    ...project({}),
+    data_models: [
+      { id: 'order', name: 'Order', fields: [], relations: [] },
+      {
+        id: 'item', name: 'OrderItem', fields: [],
+        relations: [
+          { id: 'r1', name: 'order', target_model_id: 'order', relation_type: 'many_to_one', optional: false, on_delete: 'Cascade' },
+          { id: 'r2', name: 'coupon', target_model_id: 'order', relation_type: 'many_to_one' },
+        ],
+      },
+    ],
+  } as unknown as ProjectSchema;
+  const prisma = new BackendGeneratorService(schema).generate().find(f => f.path === 'prisma/schema.prisma')?.content ?? '';
+
+  assert.match(prisma, /^ {2}order Order @relation\("\w+", fields: \[orderId\], references: \[id\], onDelete: Cascade\)$/m);
+  assert.match(prisma, /^ {2}orderId String @db\.Uuid/m);
+  assert.match(prisma, /^ {2}coupon Order\? @relation\("\w+", fields: \[couponId\], references: \[id\]\)$/m);
+  assert.match(prisma, /^ {2}couponId String\? @db\.Uuid/m);
+});
+
+test('generated main.ts validates the environment before creating the app', () => {
+  const main = file({}, 'src/main.ts');
+  assert.match(main, /^import \{ validateEnv \} from '\.\/env';$/m);
+  assert.match(main, /async function bootstrap\(\) \{\n {2}validateEnv\(\);\n {2}const app = await NestFactory\.create/);
+
+  // Run the generated check against an environment missing DATABASE_URL
+  const source = file({}, 'src/env.ts').replace(/^export /m, '').replace('(): void', '()');
+  const errors: string[] = [];
+  const fakeProcess = { env: { PORT: 'eighty' }, exit: (code: number) => errors.push(`exit ${code}`) };
+  const fakeConsole = { error: (message: string) => errors.push(message) };
+  new Function('process', 'console', `${source}\nvalidateEnv();`)(fakeProcess, fakeConsole);
+
+  assert.match(errors[0]!, /DATABASE_URL is not set/);
+  assert.match(errors[0]!, /PORT must be a number, got "eighty"/);
+  assert.equal(errors[1], 'exit 1');
+});
+
+test('generated field with a description documents it in the create DTO and the Prisma schema', () => {
+  const schema = {
+    ...project({}),
+    data_models: [{
+      id: 'm1', name: 'Product', relations: [],
+      fields: [{ id: 'f1', name: 'sku', field_type: 'string', required: true, unique: false, primary_key: false, description: 'Stock keeping unit, as printed on the label' }],
+    }],
+  } as unknown as ProjectSchema;
+  const files = new BackendGeneratorService(schema).generate();
+  const file = (path: string) => files.find(f => f.path === path)?.content ?? '';
+
+  assert.match(file('src/product/dto/create-product.dto.ts'), /@ApiProperty\(\{ description: 'Stock keeping unit, as printed on the label' \}\)\n {2}@IsNotEmpty\(\)\n {2}@IsString\(\)\n {2}sku!: string;/);
+  assert.match(file('prisma/schema.prisma'), /^ {2}\/\/\/ Stock keeping unit, as printed on the label\n {2}sku String/m);
+});
@@ -0,0 +1,1484 @@
+import { LogicCompiler } from './logicCompiler.js';
+import { codeStyle, styleFiles } from './codeStyle.js';
+import { camelCase, kebabCase, pascalCase, snakeCase } from '../utils/string.js';
+import { apiPrefix, corsOrigins, decimalDigits, indexedFields, type ApiSchema, type DataModelSchema, type FieldSchema, type GeneratedFile, type ParamSchema, type ProjectSchema } from './projectSchema.js';
+
+const PRISMA_TYPES: Record<string, string> = {
+  string: 'String',
+  text: 'String',
+  email: 'String',
+  url: 'String',
+  uuid: 'String',
+  file: 'String',
+  int: 'Int',
+  float: 'Float',
+  decimal: 'Decimal',
+  boolean: 'Boolean',
+  datetime: 'DateTime',
+  date: 'DateTime',
+  json: 'Json',
+};
+
+/**
+ * Native column types matching the SQL migrations (`databaseGenerator`), so a database built
+ * by `prisma db push` and one built from the migrations are the same
+ */
+const PRISMA_NATIVE_TYPES: Record<string, string> = {
+  string: '@db.VarChar(255)',
+  email: '@db.VarChar(255)',
+  url: '@db.VarChar(2048)',
+  file: '@db.VarChar(2048)',
+  uuid: '@db.Uuid',
+};
+
+/** `@map` to the column name the SQL migrations use, when it differs from the field name */
+function columnMap(field: string, column = snakeCase(field)): string {
+  return column === field ? '' : ` @map("${column}")`;
+}
+
+const TS_TYPES: Record<string, string> = {
+  int: 'number',
+  float: 'number',
+  boolean: 'boolean',
+  json: 'any',
+};
+
+const VALIDATORS: Record<string, string> = {
+  string: 'IsString',
+  text: 'IsString',
+  email: 'IsEmail',
+  url: 'IsUrl',
+  uuid: 'IsUUID',
+  int: 'IsInt',
+  float: 'IsNumber',
+  decimal: 'IsDecimal',
+  boolean: 'IsBoolean',
+  datetime: 'IsDateString',
+  date: 'IsDateString',
+  json: 'IsObject',
+};
+
+const STRING_TYPES = new Set(['string', 'text', 'email', 'url', 'uuid']);
+
+/** File fields hold the stored upload's path, set through the upload route rather than the DTOs */
+function isFile(field: FieldSchema): boolean {
+  return field.field_type === 'file';
+}
+
+/** Enum fields without any values are generated as plain strings */
+function isEnum(field: FieldSchema): boolean {
+  return field.field_type === 'enum' && !!field.enum_values?.length;
+}
+
+/** String-literal union for an enum field, e.g. `'admin' | 'member'` */
+function enumUnion(field: FieldSchema): string {
+  return isEnum(field) ? field.enum_values!.map(v => `'${v}'`).join(' | ') : 'string';
+}
+
+const HTTP_DECORATORS: Record<string, string> = {
+  GET: 'Get',
+  POST: 'Post',
+  PUT: 'Put',
+  PATCH: 'Patch',
+  DELETE: 'Delete',
+};
+
+const SWAGGER_PRIMITIVES: Record<string, string> = {
+  string: 'String',
+  number: 'Number',
+  boolean: 'Boolean',
+};
+
+/** Pipe converting a path or query string to the param's declared type */
+const PARAM_PIPES: Record<string, string> = {
+  number: 'ParseFloatPipe',
+  boolean: 'ParseBoolPipe',
+};
+
+/** Method arguments an endpoint already uses, which a param can't be named */
+const RESERVED_ARGS = new Set(['params', 'query', 'body', 'req', 'res']);
+
+/** Sample values for Swagger examples, by field type */
+const FIELD_EXAMPLES: Record<string, unknown> = {
+  string: 'string',
+  text: 'Some text',
+  email: 'user@example.com',
+  url: 'https://example.com',
+  uuid: '3fa85f64-5717-4562-b3fc-2c963f66afa6',
+  int: 1,
+  float: 1.5,
+  decimal: '9.99',
+  boolean: true,
+  datetime: '2024-01-01T00:00:00.000Z',
+  date: '2024-01-01T00:00:00.000Z',
+  json: {},
+  file: 'uploads/example.png',
+};
+
+function fieldExample(field: FieldSchema): unknown {
+  if (isEnum(field)) return field.enum_values![0];
+  return FIELD_EXAMPLES[field.field_type] ?? 'string';
+}
+
+/** A field's description on one line, for decorator options; undefined when it has none */
+function fieldDescription(field: FieldSchema): string | undefined {
+  return field.description?.trim().replace(/\s+/g, ' ') || undefined;
+}
+
+/** A value as a TypeScript literal in the generated code's style: bare keys, single quotes */
+function tsLiteral(value: unknown): string {
+  if (Array.isArray(value)) return `[${value.map(tsLiteral).join(', ')}]`;
+  if (value && typeof value === 'object') {
+    const entries = Object.entries(value).map(([k, v]) => `${/^[A-Za-z_$][\w$]*$/.test(k) ? k : `'${k}'`}: ${tsLiteral(v)}`);
+    return entries.length ? `{ ${entries.join(', ')} }` : '{}';
+  }
+  if (typeof value === 'string') return `'${value.replace(/\\/g, '\\\\').replace(/'/g, "\\'")}'`;
+  return String(value);
+}
+
+/** One side of a relation that stores the foreign key */
+export interface ForeignKey {
+  holder: DataModelSchema;
+  target: DataModelSchema;
+  field: string;
+  backField: string;
+  relationName: string;
+  many: boolean;
+  /** Whether the key column is nullable; SetNull needs it to be */
+  optional: boolean;
+  /** Explicit `onDelete`; otherwise Prisma sets null on optional keys and restricts required ones */
+  onDelete?: 'Cascade' | 'SetNull' | 'Restrict';
+}
+
+const ON_DELETE_ACTIONS = new Set(['Cascade', 'SetNull', 'Restrict']);
+
+/** The delete action a key ends up with, spelled out where Prisma would default it */
+export function onDeleteAction(key: ForeignKey): 'Cascade' | 'SetNull' | 'Restrict' {
+  return key.onDelete || (key.optional ? 'SetNull' : 'Restrict');
+}
+
+/** Field holding the id of the user who owns a record (the JWT's `sub`), or null when the model isn't scoped */
+export function ownerField(model: DataModelSchema): string | null {
+  return model.owner_field?.trim() || null;
+}
+
+/**
+ * Resolve relations to the model that holds the foreign key. `one_to_many` is declared
+ * on the "one" side, so the key lives on the target; every other kind keeps it on the owner.
+ * Many-to-many relations have no foreign key and are left out. A relation's `optional` and
+ * `on_delete` carry over to its key.
+ */
+export function resolveForeignKeys(models: DataModelSchema[]): ForeignKey[] {
+  const byId = new Map(models.map(m => [m.id, m]));
+  const keys: ForeignKey[] = [];
+
+  for (const model of models) {
+    const clientName = camelCase(pascalCase(model.name));
+    for (const relation of model.relations) {
+      const target = byId.get(relation.target_model_id);
+      if (!target || relation.relation_type === 'many_to_many') continue;
+
+      const relationName = `${pascalCase(model.name)}${pascalCase(relation.name)}`;
+      const onDelete = ON_DELETE_ACTIONS.has(relation.on_delete as string) ? relation.on_delete : undefined;
+      const behavior = { optional: relation.optional !== false || onDelete === 'SetNull', onDelete };
+      if (relation.relation_type === 'one_to_many') {
+        keys.push({ holder: target, target: model, field: clientName, backField: relation.name, relationName, many: true, ...behavior });
+      } else {
+        const many = relation.relation_type !== 'one_to_one';
+        keys.push({ holder: model, target, field: relation.name, backField: many ? `${clientName}s` : clientName, relationName, many, ...behavior });
+      }
+    }
+  }
+  return keys;
+}
+
+/** The foreign key columns (`userId`) the generated model holds, with `model` standing in for its stored version */
+export function foreignKeyColumns(model: DataModelSchema, models: DataModelSchema[]): string[] {
+  const all = [...models.filter(m => m.id !== model.id), model];
+  return resolveForeignKeys(all).filter(k => k.holder.id === model.id).map(k => `${k.field}Id`);
+}
+
+/**
+ * Generates a NestJS + Prisma backend for a project.
+ * Output is an in-memory file list; callers decide whether to write it to disk or zip it.
+ */
+export class BackendGeneratorService {
+  private project: ProjectSchema;
+  private logicCompiler = new LogicCompiler();
+
+  constructor(project: ProjectSchema) {
+    this.project = project;
+  }
+
+  public generate(): GeneratedFile[] {
+    const files: GeneratedFile[] = [];
+    const scheduleFiles = this.logicCompiler.genScheduleRunner(this.project.logic_flows);
+
+    files.push(this.genPackageJson(scheduleFiles.length > 0));
+    files.push(this.genTsConfig());
+    files.push({ path: 'nest-cli.json', content: JSON.stringify({ collection: '@nestjs/schematics', sourceRoot: 'src' }, null, 2) + '\n' });
+    files.push(this.genEnv());
+    files.push(this.genMain());
+    files.push(this.genAppModule(scheduleFiles.length > 0));
+    files.push(this.genPrismaClient());
+    for (const model of this.project.data_models) {
+      files.push(...this.genModelModule(model));
+    }
+    if (this.hasFileFields) files.push(this.genStorageService());
+    if (this.authSettings) files.push(...this.genAuthModule());
+    if (this.project.apis.length > 0) {
+      files.push(...this.genEndpointsModule());
+    }
+    files.push(this.genPrismaSchema());
+    files.push(this.genSeed());
+    files.push(...this.genE2eSetup());
+    files.push(...this.genDockerFiles());
+    files.push(...this.logicCompiler.compileBundle(this.project.logic_flows, 'backend', 'src/logic'));
+    files.push(...scheduleFiles);
+
+    return styleFiles(files, codeStyle(this.project));
+  }
+
+  private genPackageJson(hasSchedule: boolean): GeneratedFile {
+    const dependencies: Record<string, string> = {
+      '@nestjs/common': '^10.3.0',
+      '@nestjs/core': '^10.3.0',
+      '@nestjs/platform-express': '^10.3.0',
+      '@nestjs/swagger': '^7.2.0',
+      '@prisma/client': '^5.8.0',
+      'class-transformer': '^0.5.1',
+      'class-validator': '^0.14.0',
+      'reflect-metadata': '^0.2.1',
+      'rxjs': '^7.8.1',
+    };
+    if (hasSchedule) dependencies['@nestjs/schedule'] = '^4.0.0';
+    if (this.hasRateLimits) dependencies['@nestjs/throttler'] = '^5.1.0';
+    if (this.authSettings) {
+      dependencies['@nestjs/jwt'] = '^10.2.0';
+      dependencies['bcryptjs'] = '^2.4.3';
+    }
+
+    const packageJson = {
+      name: `${this.project.name.toLowerCase().replace(/\s+/g, '-')}-api`,
+      version: '0.1.0',
+      private: true,
+      scripts: {
+        build: 'nest build',
+        start: 'nest start',
+        'start:dev': 'nest start --watch',
+        'prisma:generate': 'prisma generate',
+        'prisma:migrate': 'prisma migrate dev',
+        'prisma:seed': 'prisma db seed',
+        'test:e2e': 'jest --config ./test/jest-e2e.json --runInBand --passWithNoTests',
+      },
+      prisma: { seed: 'ts-node prisma/seed.ts' },
+      dependencies,
+      devDependencies: {
+        '@nestjs/cli': '^10.3.0',
+        '@nestjs/schematics': '^10.1.0',
+        '@nestjs/testing': '^10.3.0',
+        ...(this.authSettings && { '@types/bcryptjs': '^2.4.6' }),
+        '@types/express': '^4.17.21',
+        '@types/jest': '^29.5.11',
+        ...(this.hasFileFields && { '@types/multer': '^1.4.11' }),
+        '@types/node': '^20.11.0',
+        '@types/supertest': '^6.0.2',
+        'jest': '^29.7.0',
+        'prisma': '^5.8.0',
+        'supertest': '^6.3.3',
+        'ts-jest': '^29.1.1',
+        'ts-node': '^10.9.2',
+        'typescript': '^5.3.3',
+      },
+    };
+    return { path: 'package.json', content: JSON.stringify(packageJson, null, 2) + '\n' };
+  }
+
+  private genTsConfig(): GeneratedFile {
+    const tsconfig = {
+      compilerOptions: {
+        module: 'commonjs',
+        target: 'ES2021',
+        declaration: true,
+        emitDecoratorMetadata: true,
+        experimentalDecorators: true,
+        outDir: './dist',
+        baseUrl: './',
+        strict: true,
+        skipLibCheck: true,
+      },
+    };
+    return { path: 'tsconfig.json', content: JSON.stringify(tsconfig, null, 2) + '\n' };
+  }
+
+  /**
+   * `src/env.ts`, checked first thing in `bootstrap()`: a missing `DATABASE_URL` (or
+   * `JWT_SECRET` when auth is on) stops the process with a message naming it instead of
+   * failing later on the first query or sign-in.
+   */
+  private genEnv(): GeneratedFile {
+    const required = ["'DATABASE_URL'", ...(this.authSettings ? ["'JWT_SECRET'"] : [])];
+    return {
+      path: 'src/env.ts',
+      content: `// Variables the API cannot start without. Prisma has loaded .env by the time this runs.
+const REQUIRED = [${required.join(', ')}];
+
+export function validateEnv(): void {
+  const problems = REQUIRED.filter((name) => !process.env[name]?.trim()).map((name) => \`\${name} is not set\`);
+  if (process.env.PORT && !/^\\d+$/.test(process.env.PORT)) {
+    problems.push(\`PORT must be a number, got "\${process.env.PORT}"\`);
+  }
+
+  if (problems.length > 0) {
+    console.error(\`Invalid environment:\\n\${problems.map((p) => \`  - \${p}\`).join('\\n')}\\nSet them in .env or the process environment and restart.\`);
+    process.exit(1);
+  }
+}
+`,
+    };
+  }
+
+  private genMain(): GeneratedFile {
+    const prefix = apiPrefix(this.project);
+    const origins = corsOrigins(this.project).map(o => `'${o}'`).join(', ');
+    return {
+      path: 'src/main.ts',
+      content: `import { NestFactory } from '@nestjs/core';
+import { ValidationPipe } from '@nestjs/common';
+import { AppModule } from './app.module';
+import { validateEnv } from './env';
+
+async function bootstrap() {
+  validateEnv();
+  const app = await NestFactory.create(AppModule);
+${prefix ? `  app.setGlobalPrefix('${prefix}');\n` : ''}  // CORS_ORIGINS (comma-separated) overrides the project's origins; with none, any origin is allowed
+  const corsOrigins = process.env.CORS_ORIGINS?.split(',').map((o) => o.trim()).filter(Boolean) ?? [${origins}];
+  app.enableCors(corsOrigins.length > 0 ? { origin: corsOrigins } : undefined);
+  app.useGlobalPipes(new ValidationPipe({ whitelist: true, transform: true }));
+  await app.listen(process.env.PORT || 3000);
+}
+bootstrap();
+`,
+    };
+  }
+
+  private genAppModule(hasSchedule: boolean): GeneratedFile {
+    const imports: string[] = [`import { Module } from '@nestjs/common';`];
+    const moduleNames: string[] = [];
+
+    for (const model of this.project.data_models) {
+      imports.push(`import { ${this.modelName(model)}Module } from './${kebabCase(model.name)}/${kebabCase(model.name)}.module';`);
+      moduleNames.push(`${this.modelName(model)}Module`);
+    }
+
+    // Per-endpoint limits come from @Throttle; this default only applies where no override is set
+    if (this.hasRateLimits) {
+      imports.push(`import { ThrottlerModule } from '@nestjs/throttler';`);
+      moduleNames.push('ThrottlerModule.forRoot([{ ttl: 60000, limit: 100 }])');
+    }
+
+    if (this.authSettings) {
+      imports.push(`import { AuthModule } from './auth/auth.module';`);
+      moduleNames.push('AuthModule');
+    }
+
+    if (this.project.apis.length > 0) {
+      imports.push(`import { EndpointsModule } from './endpoints/endpoints.module';`);
+      moduleNames.push('EndpointsModule');
+    }
+
+    if (hasSchedule) {
+      imports.push(`import { FlowScheduleModule } from './schedule/schedule.module';`);
+      moduleNames.push('FlowScheduleModule');
+    }
+
+    return {
+      path: 'src/app.module.ts',
+      content: `${imports.join('\n')}
+
+@Module({
+  imports: [${moduleNames.join(', ')}],
+})
+export class AppModule {}
+`,
+    };
+  }
+
+  private genPrismaClient(): GeneratedFile {
+    return {
+      path: 'src/prisma/client.ts',
+      content: `import { PrismaClient } from '@prisma/client';
+
+export const prisma = new PrismaClient();
+`,
+    };
+  }
+
+  /** Auth is generated when `settings.auth.enabled` is set, or when a model is owner-scoped */
+  private get authSettings(): { access_token_ttl: string; refresh_tokens: boolean; refresh_token_ttl_days: number } | null {
+    const auth = this.project.settings.auth;
+    if (!auth?.enabled && !this.project.data_models.some(m => ownerField(m))) return null;
+    const refresh = !!auth?.refresh_tokens;
+    return {
+      // Short-lived access tokens only make sense when they can be refreshed
+      access_token_ttl: typeof auth?.access_token_ttl === 'string' && auth.access_token_ttl ? auth.access_token_ttl : refresh ? '15m' : '7d',
+      refresh_tokens: refresh,
+      refresh_token_ttl_days: Number(auth?.refresh_token_ttl_days) > 0 ? Number(auth.refresh_token_ttl_days) : 30,
+    };
+  }
+
+  /**
+   * Email/password auth backed by an `AuthAccount` model. Login and register return a JWT;
+   * with `refresh_tokens` on they also return a refresh token, stored hashed and rotated
+   * on every use. Presenting an already-rotated token revokes all of the account's tokens.
+   */
+  private genAuthModule(): GeneratedFile[] {
+    const auth = this.authSettings!;
+    const refresh = auth.refresh_tokens;
+
+    const dto = `import { IsEmail, IsNotEmpty, IsString, MinLength } from 'class-validator';
+
+export class CredentialsDto {
+  @IsEmail()
+  email!: string;
+
+  @IsString()
+  @MinLength(8)
+  password!: string;
+}
+${refresh ? `
+export class RefreshDto {
+  @IsNotEmpty()
+  @IsString()
+  refreshToken!: string;
+}
+` : ''}`;
+
+    const service = `import { ConflictException, Injectable, UnauthorizedException } from '@nestjs/common';
+import { JwtService } from '@nestjs/jwt';
+import * as bcrypt from 'bcryptjs';
+${refresh ? `import { createHash, randomBytes } from 'crypto';\n` : ''}import { prisma } from '../prisma/client';
+import { CredentialsDto } from './dto/auth.dto';
+${refresh ? `
+const REFRESH_TOKEN_TTL_MS = ${auth.refresh_token_ttl_days} * 24 * 60 * 60 * 1000;
+
+function hashToken(token: string): string {
+  return createHash('sha256').update(token).digest('hex');
+}
+` : ''}
+interface AccountInfo {
+  id: string;
+  email: string;
+  role: string;
+}
+
+@Injectable()
+export class AuthService {
+  constructor(private readonly jwt: JwtService) {}
+
+  async register(dto: CredentialsDto) {
+    const existing = await prisma.authAccount.findUnique({ where: { email: dto.email } });
+    if (existing) throw new ConflictException('Email is already registered');
+
+    const account = await prisma.authAccount.create({
+      data: { email: dto.email, passwordHash: await bcrypt.hash(dto.password, 10) },
+    });
+    return this.issueTokens(account);
+  }
+
+  async login(dto: CredentialsDto) {
+    const account = await prisma.authAccount.findUnique({ where: { email: dto.email } });
+    if (!account || !(await bcrypt.compare(dto.password, account.passwordHash))) {
+      throw new UnauthorizedException('Invalid email or password');
+    }
+    return this.issueTokens(account);
+  }
+${refresh ? `
+  /**
+   * Swap a refresh token for a new pair. The presented token is revoked so each one works
+   * once; revoking only where it's still live makes two concurrent refreshes race for it.
+   */
+  async refresh(refreshToken: string) {
+    const stored = await prisma.refreshToken.findUnique({
+      where: { tokenHash: hashToken(refreshToken) },
+      include: { account: true },
+    });
+    if (!stored || stored.expiresAt < new Date()) throw new UnauthorizedException('Invalid refresh token');
+
+    const claimed = await prisma.refreshToken.updateMany({
+      where: { id: stored.id, revokedAt: null },
+      data: { revokedAt: new Date() },
+    });
+    if (claimed.count === 0) {
+      // A rotated token came back: assume it leaked and end every session for the account
+      await prisma.refreshToken.updateMany({
+        where: { accountId: stored.accountId, revokedAt: null },
+        data: { revokedAt: new Date() },
+      });
+      throw new UnauthorizedException('Invalid refresh token');
+    }
+    return this.issueTokens(stored.account);
+  }
+` : ''}
+  private async issueTokens(account: AccountInfo) {
+    const user = { id: account.id, email: account.email, role: account.role };
+    const accessToken = await this.jwt.signAsync({ sub: account.id, email: account.email, role: account.role });${refresh ? `
+
+    const refreshToken = randomBytes(48).toString('hex');
+    await prisma.refreshToken.create({
+      data: {
+        tokenHash: hashToken(refreshToken),
+        accountId: account.id,
+        expiresAt: new Date(Date.now() + REFRESH_TOKEN_TTL_MS),
+      },
+    });
+    return { accessToken, refreshToken, user };` : `
+    return { accessToken, user };`}
+  }
+}
+`;
+
+    const controller = `import { Body, Controller, HttpCode, Post } from '@nestjs/common';
+import { AuthService } from './auth.service';
+import { CredentialsDto${refresh ? ', RefreshDto' : ''} } from './dto/auth.dto';
+
+@Controller('auth')
+export class AuthController {
+  constructor(private readonly auth: AuthService) {}
+
+  @Post('register')
+  register(@Body() dto: CredentialsDto) {
+    return this.auth.register(dto);
+  }
+
+  @Post('login')
+  @HttpCode(200)
+  login(@Body() dto: CredentialsDto) {
+    return this.auth.login(dto);
+  }${refresh ? `
+
+  @Post('refresh')
+  @HttpCode(200)
+  refresh(@Body() dto: RefreshDto) {
+    return this.auth.refresh(dto.refreshToken);
+  }` : ''}
+}
+`;
+
+    const guard = `import { CanActivate, ExecutionContext, Injectable, UnauthorizedException } from '@nestjs/common';
+import { JwtService } from '@nestjs/jwt';
+
+/** Requires \`Authorization: Bearer <access token>\` and puts the token's claims on \`request.user\` */
+@Injectable()
+export class JwtAuthGuard implements CanActivate {
+  constructor(private readonly jwt: JwtService) {}
+
+  async canActivate(context: ExecutionContext): Promise<boolean> {
+    const request = context.switchToHttp().getRequest();
+    const [type, token] = String(request.headers.authorization || '').split(' ');
+    if (type !== 'Bearer' || !token) throw new UnauthorizedException();
+
+    try {
+      request.user = await this.jwt.verifyAsync(token);
+    } catch {
+      throw new UnauthorizedException();
+    }
+    return true;
+  }
+}
+`;
+
+    const module = `import { Global, Module } from '@nestjs/common';
+import { JwtModule } from '@nestjs/jwt';
+import { AuthController } from './auth.controller';
+import { AuthService } from './auth.service';
+import { JwtAuthGuard } from './jwt-auth.guard';
+
+@Global()
+@Module({
+  imports: [
+    JwtModule.register({
+      secret: process.env.JWT_SECRET,
+      signOptions: { expiresIn: process.env.JWT_EXPIRES_IN || '${auth.access_token_ttl}' },
+    }),
+  ],
+  controllers: [AuthController],
+  providers: [AuthService, JwtAuthGuard],
+  exports: [JwtAuthGuard, JwtModule],
+})
+export class AuthModule {}
+`;
+
+    return [
+      { path: 'src/auth/dto/auth.dto.ts', content: dto },
+      { path: 'src/auth/auth.service.ts', content: service },
+      { path: 'src/auth/auth.controller.ts', content: controller },
+      { path: 'src/auth/jwt-auth.guard.ts', content: guard },
+      { path: 'src/auth/auth.module.ts', content: module },
+    ];
+  }
+
+  private genAuthPrismaModels(): string[] {
+    const refresh = this.authSettings!.refresh_tokens;
+    const account = `model AuthAccount {
+  id           String   @id @default(uuid())
+  email        String   @unique
+  passwordHash String
+  role         String   @default("user")
+  createdAt    DateTime @default(now())${refresh ? '\n  refreshTokens RefreshToken[]' : ''}
+}`;
+    if (!refresh) return [account];
+
+    return [account, `model RefreshToken {
+  id        String      @id @default(uuid())
+  tokenHash String      @unique
+  accountId String
+  account   AuthAccount @relation(fields: [accountId], references: [id], onDelete: Cascade)
+  expiresAt DateTime
+  revokedAt DateTime?
+  createdAt DateTime    @default(now())
+
+  @@index([accountId])
+}`];
+  }
+
+  private get hasRateLimits(): boolean {
+    return this.project.apis.some(api => api.rate_limit && api.rate_limit.requests > 0);
+  }
+
+  private get hasFileFields(): boolean {
+    return this.project.data_models.some(m => m.fields.some(isFile));
+  }
+
+  /** Writes uploads under `uploads/` on local disk; swap the body for S3 or similar in production */
+  private genStorageService(): GeneratedFile {
+    return {
+      path: 'src/storage/storage.service.ts',
+      content: `import { Injectable } from '@nestjs/common';
+import { randomUUID } from 'crypto';
+import { mkdir, writeFile } from 'fs/promises';
+import { extname, join } from 'path';
+
+/** Largest upload accepted; bigger files are refused with 413 before they reach a handler */
+export const MAX_UPLOAD_BYTES = Number(process.env.MAX_UPLOAD_BYTES) || 10 * 1024 * 1024;
+
+@Injectable()
+export class StorageService {
+  private readonly root = process.env.UPLOAD_DIR || 'uploads';
+
+  /** Store an uploaded file and return the path saved on the record */
+  async save(file: Express.Multer.File, folder: string): Promise<string> {
+    const dir = join(this.root, folder);
+    await mkdir(dir, { recursive: true });
+    const path = join(dir, \`\${randomUUID()}\${extname(file.originalname)}\`);
+    await writeFile(path, file.buffer);
+    return path;
+  }
+}
+`,
+    };
+  }
+
+  private modelName(model: DataModelSchema): string {
+    return pascalCase(model.name);
+  }
+
+  private clientName(model: DataModelSchema): string {
+    return camelCase(this.modelName(model));
+  }
+
+  /**
+   * Service, controller and module for one model's CRUD routes at `/api/<kebab-name>s`.
+   * Soft-delete models hide rows with `deletedAt` set unless `?includeDeleted=true` is passed.
+   * Owner-scoped models require a JWT; records are created for and only visible to its user.
+   */
+  private genModelModule(model: DataModelSchema): GeneratedFile[] {
+    const name = this.modelName(model);
+    const client = this.clientName(model);
+    const dir = `src/${kebabCase(model.name)}`;
+    const file = kebabCase(model.name);
+    const soft = model.soft_delete;
+    const owner = ownerField(model);
+    const fileFields = model.fields.filter(isFile);
+    const uploads = fileFields.length > 0;
+
+    const findAllParams = [owner && 'ownerId: string', soft && 'includeDeleted = false'].filter(Boolean).join(', ');
+    const findAllWhere = [owner && `${owner}: ownerId`, soft && `...(includeDeleted ? {} : { deletedAt: null })`].filter(Boolean);
+    const findAllArgs = owner
+      ? `{ where: { ${findAllWhere.join(', ')} } }`
+      : soft ? `{ where: includeDeleted ? {} : { deletedAt: null } }` : '';
+
+    const service = `import { Injectable } from '@nestjs/common';
+import { Prisma } from '@prisma/client';
+import { prisma } from '../prisma/client';
+import { Create${name}Dto } from './dto/create-${file}.dto';
+import { Update${name}Dto } from './dto/update-${file}.dto';
+
+@Injectable()
+export class ${name}Service {
+  findAll(${findAllParams}) {
+    return prisma.${client}.findMany(${findAllArgs});
+  }
+
+  findOne(id: string${owner ? ', ownerId: string' : ''}) {
+    return prisma.${client}.findFirst({ where: { id${owner ? `, ${owner}: ownerId` : ''}${soft ? ', deletedAt: null' : ''} } });
+  }
+
+  create(dto: Create${name}Dto${owner ? ', ownerId: string' : ''}) {
+    return prisma.${client}.create({ data: ${owner ? `{ ...dto, ${owner}: ownerId }` : 'dto'} as Prisma.${name}UncheckedCreateInput });
+  }
+
+  update(id: string, dto: Update${name}Dto) {
+    return prisma.${client}.update({ where: { id }, data: dto as Prisma.${name}UncheckedUpdateInput });
+  }
+
+  remove(id: string) {
+    return ${soft ? `prisma.${client}.update({ where: { id }, data: { deletedAt: new Date() } })` : `prisma.${client}.delete({ where: { id } })`};
+  }${uploads ? `
+
+  attachFile(id: string, data: Prisma.${name}UncheckedUpdateInput) {
+    return prisma.${client}.update({ where: { id }, data });
+  }` : ''}
+}
+`;
+
+    const commonImports = ['Body', 'Controller', 'Delete', 'Get', 'NotFoundException', 'Param', 'Patch', 'Post'];
+    if (soft) commonImports.push('Query');
+    if (uploads) commonImports.push('BadRequestException', 'UploadedFile', 'UseInterceptors');
+    if (owner) commonImports.push('Request', 'UseGuards');
+
+    // Owner-scoped routes take the request for the JWT's user id
+    const reqParam = owner ? ', @Request() req: AuthedRequest' : '';
+    const ownerArg = owner ? ', req.user.sub' : '';
+
+    const uploadMethods = fileFields.map(f => `
+
+  @Post(':id/${kebabCase(f.name)}')
+  @UseInterceptors(FileInterceptor('file', { limits: { fileSize: MAX_UPLOAD_BYTES } }))
+  async upload${pascalCase(f.name)}(@Param('id') id: string, @UploadedFile() file?: Express.Multer.File${reqParam}) {
+    if (!file) throw new BadRequestException('file is required');
+    if (!(await this.service.findOne(id${ownerArg}))) throw new NotFoundException('${name} not found');
+    const path = await this.storage.save(file, '${file}/${kebabCase(f.name)}');
+    return this.service.attachFile(id, { ${f.name}: path });
+  }`).join('');
+
+    // Swagger examples: a DTO's body for writes, a stored record for reads
+    const input = tsLiteral(this.modelExample(model, true));
+    const record = tsLiteral(this.modelExample(model, false));
+
+    const controller = `import { ${commonImports.sort().join(', ')} } from '@nestjs/common';
+import { ApiBody, ApiResponse } from '@nestjs/swagger';
+${uploads ? `import { FileInterceptor } from '@nestjs/platform-express';
+import { MAX_UPLOAD_BYTES, StorageService } from '../storage/storage.service';
+` : ''}${owner ? `import { JwtAuthGuard } from '../auth/jwt-auth.guard';
+` : ''}import { ${name}Service } from './${file}.service';
+import { Create${name}Dto } from './dto/create-${file}.dto';
+import { Update${name}Dto } from './dto/update-${file}.dto';
+${owner ? `
+type AuthedRequest = { user: { sub: string } };
+` : ''}
+@Controller('${file}s')${owner ? `
+@UseGuards(JwtAuthGuard)` : ''}
+export class ${name}Controller {
+  constructor(${uploads ? `
+    private readonly service: ${name}Service,
+    private readonly storage: StorageService,
+  ` : `private readonly service: ${name}Service`}) {}
+
+  @Get()
+  @ApiResponse({ status: 200, schema: { example: [${record}] } })
+  findAll(${[owner && '@Request() req: AuthedRequest', soft && `@Query('includeDeleted') includeDeleted?: string`].filter(Boolean).join(', ')}) {
+    return this.service.findAll(${[owner && 'req.user.sub', soft && `includeDeleted === 'true'`].filter(Boolean).join(', ')});
+  }
+
+  @Get(':id')
+  @ApiResponse({ status: 200, schema: { example: ${record} } })
+  async findOne(@Param('id') id: string${reqParam}) {
+    const record = await this.service.findOne(id${ownerArg});
+    if (!record) throw new NotFoundException('${name} not found');
+    return record;
+  }
+
+  @Post()
+  @ApiBody({ type: Create${name}Dto, examples: { default: { value: ${input} } } })
+  @ApiResponse({ status: 201, schema: { example: ${record} } })
+  create(@Body() dto: Create${name}Dto${reqParam}) {
+    return this.service.create(dto${ownerArg});
+  }
+${owner ? `
+  @Patch(':id')
+  @ApiBody({ type: Update${name}Dto, examples: { default: { value: ${input} } } })
+  @ApiResponse({ status: 200, schema: { example: ${record} } })
+  async update(@Param('id') id: string, @Body() dto: Update${name}Dto, @Request() req: AuthedRequest) {
+    await this.findOne(id, req);
+    return this.service.update(id, dto);
+  }
+
+  @Delete(':id')
+  async remove(@Param('id') id: string, @Request() req: AuthedRequest) {
+    await this.findOne(id, req);
+    return this.service.remove(id);
+  }` : `
+  @Patch(':id')
+  @ApiBody({ type: Update${name}Dto, examples: { default: { value: ${input} } } })
+  @ApiResponse({ status: 200, schema: { example: ${record} } })
+  update(@Param('id') id: string, @Body() dto: Update${name}Dto) {
+    return this.service.update(id, dto);
+  }
+
+  @Delete(':id')
+  remove(@Param('id') id: string) {
+    return this.service.remove(id);
+  }`}${uploadMethods}
+}
+`;
+
+    const module = `import { Module } from '@nestjs/common';
+import { ${name}Controller } from './${file}.controller';
+import { ${name}Service } from './${file}.service';
+${uploads ? `import { StorageService } from '../storage/storage.service';\n` : ''}
+@Module({
+  controllers: [${name}Controller],
+  providers: [${name}Service${uploads ? ', StorageService' : ''}],
+})
+export class ${name}Module {}
+`;
+
+    return [
+      { path: `${dir}/${file}.service.ts`, content: service },
+      { path: `${dir}/${file}.controller.ts`, content: controller },
+      { path: `${dir}/${file}.module.ts`, content: module },
+      ...this.genDtos(model),
+    ];
+  }
+
+  /** Prisma enum name for an enum field: the model name followed by the field name */
+  private enumName(model: DataModelSchema, field: FieldSchema): string {
+    return `${this.modelName(model)}${pascalCase(field.name)}`;
+  }
+
+  private genDtoField(field: FieldSchema, optional: boolean, enumName?: string): { validators: string[]; documented: boolean; code: string } {
+    const validators: string[] = [];
+    const lines: string[] = [];
+    const description = fieldDescription(field);
+
+    if (field.unique) lines.push(`  // Must be unique: enforced by @unique on the Prisma model`);
+    if (description) lines.push(`  @ApiProperty({ description: ${tsLiteral(description)}${optional ? ', required: false' : ''} })`);
+    if (optional) {
+      validators.push('IsOptional');
+      lines.push('  @IsOptional()');
+    } else if (STRING_TYPES.has(field.field_type)) {
+      validators.push('IsNotEmpty');
+      lines.push('  @IsNotEmpty()');
+    }
+
+    if (enumName) {
+      validators.push('IsEnum');
+      lines.push(`  @IsEnum(${enumName})`);
+      lines.push(`  ${field.name}${optional ? '?' : '!'}: ${enumName};`);
+      return { validators, documented: !!description, code: lines.join('\n') };
+    }
+
+    const validator = VALIDATORS[field.field_type] || 'IsString';
+    validators.push(validator);
+    lines.push(`  @${validator}()`);
+    lines.push(`  ${field.name}${optional ? '?' : '!'}: ${TS_TYPES[field.field_type] || 'string'};`);
+
+    return { validators, documented: !!description, code: lines.join('\n') };
+  }
+
+  /**
+   * Create and update DTOs. The update DTO lists every field as optional but keeps its
+   * format validators, rather than extending PartialType and losing them.
+   */
+  private genDtos(model: DataModelSchema): GeneratedFile[] {
+    const name = this.modelName(model);
+    const file = kebabCase(model.name);
+    const owner = ownerField(model);
+    const fields = model.fields.filter(f => !f.primary_key && !isFile(f) && f.name !== owner);
+    const keyFields: FieldSchema[] = this.foreignKeys()
+      .filter(k => k.holder.id === model.id)
+      .map(k => ({ id: `${k.field}Id`, name: `${k.field}Id`, field_type: 'uuid', required: !k.optional, unique: false, primary_key: false }));
+
+    const enumNames = new Map(fields.filter(isEnum).map(f => [f.id, this.enumName(model, f)]));
+
+    const build = (className: string, partial: boolean) => {
+      const entries = [...fields, ...keyFields].map(f => this.genDtoField(f, partial || !f.required, enumNames.get(f.id)));
+      const validators = [...new Set(entries.flatMap(e => e.validators))].sort();
+      let imports = entries.some(e => e.documented) ? `import { ApiProperty } from '@nestjs/swagger';\n` : '';
+      if (validators.length) imports += `import { ${validators.join(', ')} } from 'class-validator';\n`;
+      if (enumNames.size) imports += `import { ${[...enumNames.values()].join(', ')} } from '@prisma/client';\n`;
+      if (imports) imports += '\n';
+      return `${imports}export class ${className} {
+${entries.map(e => e.code).join('\n\n')}
+}
+`;
+    };
+
+    return [
+      { path: `src/${file}/dto/create-${file}.dto.ts`, content: build(`Create${name}Dto`, false) },
+      { path: `src/${file}/dto/update-${file}.dto.ts`, content: build(`Update${name}Dto`, true) },
+    ];
+  }
+
+  /**
+   * Example record for a model: what a client sends (`input`: no primary key, file or owner
+   * fields) or what the API returns (every field, plus timestamps)
+   */
+  private modelExample(model: DataModelSchema, input: boolean): Record<string, unknown> {
+    const owner = ownerField(model);
+    const fields = input ? model.fields.filter(f => !f.primary_key && !isFile(f) && f.name !== owner) : model.fields;
+    const example: Record<string, unknown> = Object.fromEntries(fields.map(f => [f.name, fieldExample(f)]));
+    if (!input && model.timestamps) {
+      example.createdAt = FIELD_EXAMPLES.datetime;
+      example.updatedAt = FIELD_EXAMPLES.datetime;
+    }
+    return example;
+  }
+
+  /** Example value for a DataShape, following model refs to their record example */
+  private shapeExample(shape: any): unknown {
+    if (!shape) return null;
+    switch (shape.shape_type) {
+      case 'array':
+        return [this.shapeExample(shape.item_shape)];
+      case 'model': {
+        const model = this.project.data_models.find(m => m.id === shape.model_ref || m.name === shape.model_ref);
+        return model ? this.modelExample(model, false) : {};
+      }
+      case 'object':
+        return Object.fromEntries((shape.fields || []).map((f: any) => [
+          f.name,
+          f.nested ? this.shapeExample(f.nested) : ({ number: 0, boolean: true, array: [], object: {} } as Record<string, unknown>)[f.field_type] ?? 'string',
+        ]));
+      case 'number':
+        return 0;
+      case 'boolean':
+        return true;
+      default:
+        return 'string';
+    }
+  }
+
+  /**
+   * Emit response classes for a DataShape and return the TypeScript type it maps to.
+   * Object shapes become classes named after `className`; model refs reuse one class per model.
+   */
+  private genShapeType(shape: any, className: string, classes: Map<string, string>): string {
+    if (!shape) return 'any';
+
+    switch (shape.shape_type) {
+      case 'array':
+        return `${this.genShapeType(shape.item_shape, `${className}Item`, classes)}[]`;
+
+      case 'model': {
+        const model = this.project.data_models.find(m => m.id === shape.model_ref || m.name === shape.model_ref);
+        if (!model) return 'any';
+        const name = `${this.modelName(model)}Response`;
+        if (!classes.has(name)) {
+          classes.set(name, '');
+          const fields = model.fields.map(f => `  @ApiProperty({ ${fieldDescription(f) ? `description: ${tsLiteral(fieldDescription(f))}, ` : ''}example: ${tsLiteral(fieldExample(f))}${f.required ? '' : ', required: false'} })
+  ${f.name}${f.required ? '!' : '?'}: ${f.field_type === 'enum' ? enumUnion(f) : TS_TYPES[f.field_type] || 'string'};`);
+          classes.set(name, `export class ${name} {\n${fields.join('\n\n')}\n}`);
+        }
+        return name;
+      }
+
+      case 'object': {
+        if (!classes.has(className)) {
+          classes.set(className, '');
+          const fields = (shape.fields || []).map((f: any) => {
+            const nested = f.nested ? this.genShapeType(f.nested, `${className}${pascalCase(f.name)}`, classes) : null;
+            const type = nested || ({ number: 'number', boolean: 'boolean' } as Record<string, string>)[f.field_type] || (f.field_type === 'array' ? 'any[]' : f.field_type === 'object' ? 'Record<string, any>' : 'string');
+            const options = [this.swaggerType(type) && `type: ${this.swaggerType(type)}`, !f.required && 'required: false'].filter(Boolean);
+            return `  @ApiProperty(${options.length ? `{ ${options.join(', ')} }` : ''})
+  ${f.name}${f.required ? '!' : '?'}: ${type};`;
+          });
+          // Re-insert so nested classes are declared before the class that references them
+          classes.delete(className);
+          classes.set(className, `export class ${className} {\n${fields.join('\n\n')}\n}`);
+        }
+        return className;
+      }
+
+      default:
+        return Object.hasOwn(SWAGGER_PRIMITIVES, shape.shape_type) ? shape.shape_type : 'any';
+    }
+  }
+
+  /** Swagger `type:` value for a generated TypeScript type, or null when it can't be expressed */
+  private swaggerType(type: string): string | null {
+    const isArray = type.endsWith('[]');
+    const base = isArray ? type.slice(0, -2) : type;
+    const ref = (Object.hasOwn(SWAGGER_PRIMITIVES, base) && SWAGGER_PRIMITIVES[base]) || (/^[A-Z]/.test(base) && !base.startsWith('Record') ? base : null);
+    if (!ref) return null;
+    return isArray ? `[${ref}]` : ref;
+  }
+
+  /**
+   * One controller method per custom API endpoint. Endpoints wired to a backend logic flow run
+   * it; the rest return a placeholder. A custom `success_status` adds @HttpCode, a
+   * `response_body` shape types the method and its @ApiResponse, and `rate_limit` adds @Throttle.
+   * `request_body` and `response_body` also give the Swagger docs example payloads, and
+   * route and query params become typed arguments.
+   */
+  private genEndpointsModule(): GeneratedFile[] {
+    const backendFlows = new Set(this.project.logic_flows.filter(f => f.context === 'backend').map(f => f.id));
+    const classes = new Map<string, string>();
+    const usedNames = new Set<string>();
+    const nestImports = new Set(['Controller']);
+    const swaggerImports = new Set(['ApiResponse']);
+    let usesRunner = false;
+    let usesThrottle = false;
+
+    const methods = this.project.apis.map((api: ApiSchema) => {
+      const verb = api.method.toUpperCase();
+      const decorator = HTTP_DECORATORS[verb] || 'Get';
+      nestImports.add(decorator);
+
+      let name = camelCase(api.name || `${verb.toLowerCase()} ${api.path}`) || 'endpoint';
+      while (usedNames.has(name)) name += '_';
+      usedNames.add(name);
+
+      const route = api.path.replace(/^\/+/, '').replace(/^api\//, '');
+      const defaultStatus = verb === 'POST' ? 201 : 200;
+      const status = Number(api.success_status) || defaultStatus;
+      const responseType = api.response_body ? this.genShapeType(api.response_body, `${pascalCase(name)}Response`, classes) : null;
+
+      const decorators = [`  @${decorator}('${route}')`];
+      if (status !== defaultStatus) {
+        nestImports.add('HttpCode');
+        decorators.push(`  @HttpCode(${status})`);
+      }
+      if (api.rate_limit && api.rate_limit.requests > 0) {
+        const ttl = Math.max(1, Number(api.rate_limit.window_seconds) || 60) * 1000;
+        usesThrottle = true;
+        nestImports.add('UseGuards');
+        decorators.push(`  @UseGuards(ThrottlerGuard)`);
+        decorators.push(`  @Throttle({ default: { limit: ${Math.floor(api.rate_limit.requests)}, ttl: ${ttl} } })`);
+      }
+      const hasBody = verb !== 'GET' && verb !== 'DELETE';
+      if (hasBody && api.request_body) {
+        swaggerImports.add('ApiBody');
+        decorators.push(`  @ApiBody({ examples: { default: { value: ${tsLiteral(this.shapeExample(api.request_body))} } } })`);
+      }
+      const swagger = responseType && this.swaggerType(responseType);
+      if (responseType || status !== defaultStatus) {
+        // A typed response is documented by its class; anything else carries an example
+        const example = responseType && !swagger ? `, schema: { example: ${tsLiteral(this.shapeExample(api.response_body))} }` : '';
+        decorators.push(`  @ApiResponse({ status: ${status}${swagger ? `, type: ${swagger}` : ''}${example} })`);
+      }
+
+      const typed = this.endpointParams(api, route, nestImports, swaggerImports);
+      decorators.push(...typed.decorators);
+      if (hasBody) nestImports.add('Body');
+      const params = [...typed.args];
+      if (hasBody) params.push('@Body() body: any');
+
+      let body: string;
+      if (api.logic_flow_id && backendFlows.has(api.logic_flow_id)) {
+        // The flow gets the raw request and response too; `passthrough` keeps Nest sending its return value
+        usesRunner = true;
+        nestImports.add('Req').add('Res');
+        params.push('@Req() req: Request', '@Res({ passthrough: true }) res: Response');
+        body = `return runFlow('${api.logic_flow_id}', { trigger: 'api', ${typed.params}, ${typed.query}${hasBody ? ', body' : ''}, context: { req, res } });`;
+      } else if (responseType) {
+        nestImports.add('NotImplementedException');
+        body = `throw new NotImplementedException('${(api.name || route).replace(/'/g, '')} has no backend logic flow');`;
+      } else {
+        body = `return { message: '${(api.name || route).replace(/'/g, '')} is not implemented yet' };`;
+      }
+
+      return `${decorators.join('\n')}
+  async ${name}(${params.join(', ')})${responseType ? `: Promise<${responseType}>` : ''} {
+    ${body}
+  }`;
+    });
+
+    const responseClasses = [...classes.values()].filter(Boolean);
+    const responseImports = [...classes.keys()].filter(k => classes.get(k));
+    const controller = `import { ${[...nestImports].sort().join(', ')} } from '@nestjs/common';
+import { ${[...swaggerImports].sort().join(', ')} } from '@nestjs/swagger';
+${usesThrottle ? `import { Throttle, ThrottlerGuard } from '@nestjs/throttler';\n` : ''}${usesRunner ? `import type { Request, Response } from 'express';\nimport { runFlow } from '../logic/flow-runner';\n` : ''}${responseImports.length ? `import { ${responseImports.join(', ')} } from './endpoints.responses';\n` : ''}
+@Controller()
+export class EndpointsController {
+${methods.join('\n\n')}
+}
+`;
+
+    const files: GeneratedFile[] = [
+      { path: 'src/endpoints/endpoints.controller.ts', content: controller },
+      {
+        path: 'src/endpoints/endpoints.module.ts',
+        content: `import { Module } from '@nestjs/common';
+import { EndpointsController } from './endpoints.controller';
+
+@Module({
+  controllers: [EndpointsController],
+})
+export class EndpointsModule {}
+`,
+      },
+    ];
+    if (responseClasses.length) {
+      files.push({
+        path: 'src/endpoints/endpoints.responses.ts',
+        content: `import { ApiProperty } from '@nestjs/swagger';\n\n${responseClasses.join('\n\n')}\n`,
+      });
+    }
+    return files;
+  }
+
+  /**
+   * `@Param`/`@Query` arguments for an endpoint. Every `:name` in the route becomes a path
+   * argument, typed by its `path_params` entry; each `query_params` entry becomes a query
+   * argument. Numbers and booleans are converted by Nest's parse pipes, and each param gets
+   * an @ApiParam/@ApiQuery entry. An endpoint declaring no query params keeps the raw
+   * `query` object. `params` and `query` are the objects handed to a logic flow.
+   */
+  private endpointParams(api: ApiSchema, route: string, nestImports: Set<string>, swaggerImports: Set<string>) {
+    const declared = new Map((api.path_params || []).filter(p => p?.name).map(p => [p.name, p]));
+    const pathParams: ParamSchema[] = route.split('/').filter(s => s.startsWith(':')).map(s => s.slice(1))
+      .map(name => ({ ...declared.get(name), name, param_type: declared.get(name)?.param_type || 'string', required: true }));
+    const queryParams = (api.query_params || []).filter(p => p?.name);
+
+    const args: string[] = [];
+    const decorators: string[] = [];
+    const usedArgs = new Set(RESERVED_ARGS);
+    const entries = (params: ParamSchema[], decorator: 'Param' | 'Query') => params.map(p => {
+      let arg = camelCase(p.name) || 'param';
+      while (usedArgs.has(arg)) arg += '_';
+      usedArgs.add(arg);
+
+      const type = Object.hasOwn(PARAM_PIPES, p.param_type) ? p.param_type : 'string';
+      const hasDefault = p.default !== undefined && p.default !== '';
+      const optional = decorator === 'Query' && !p.required && !hasDefault;
+      const pipes: string[] = [];
+      if (decorator === 'Query' && hasDefault) {
+        nestImports.add('DefaultValuePipe');
+        pipes.push(`new DefaultValuePipe(${tsLiteral(type === 'number' ? Number(p.default) : type === 'boolean' ? p.default === 'true' : p.default)})`);
+      }
+      if (PARAM_PIPES[type]) {
+        nestImports.add(PARAM_PIPES[type]);
+        pipes.push(optional ? `new ${PARAM_PIPES[type]}({ optional: true })` : PARAM_PIPES[type]);
+      }
+      nestImports.add(decorator);
+      args.push(`@${decorator}(${[`'${p.name}'`, ...pipes].join(', ')}) ${arg}${optional ? '?' : ''}: ${type}`);
+
+      const docs = decorator === 'Param' ? 'ApiParam' : 'ApiQuery';
+      swaggerImports.add(docs);
+      decorators.push(`  @${docs}({ name: '${p.name}', type: ${SWAGGER_PRIMITIVES[type]}${optional || hasDefault ? ', required: false' : ''} })`);
+      return arg === p.name ? arg : `${/^[A-Za-z_$][\w$]*$/.test(p.name) ? p.name : `'${p.name}'`}: ${arg}`;
+    });
+
+    const pathEntries = entries(pathParams, 'Param');
+    const queryEntries = entries(queryParams, 'Query');
+    if (!queryParams.length) {
+      nestImports.add('Query');
+      args.push(`@Query() query: Record<string, string>`);
+    }
+    return {
+      args,
+      decorators,
+      params: pathEntries.length ? `params: { ${pathEntries.join(', ')} }` : 'params: {}',
+      query: queryParams.length ? `query: { ${queryEntries.join(', ')} }` : 'query',
+    };
+  }
+
+  private foreignKeys(): ForeignKey[] {
+    return resolveForeignKeys(this.project.data_models);
+  }
+
+  private genPrismaField(model: DataModelSchema, field: FieldSchema): string {
+    // Prisma carries `///` comments into the generated client's types
+    const doc = field.description?.trim() ? field.description.trim().split(/\r?\n/).map(line => `  /// ${line.trim()}\n`).join('') : '';
+    if (field.primary_key) return `${doc}  ${field.name} String @id @default(uuid()) @db.Uuid${columnMap(field.name)}`;
+
+    const type = isEnum(field) ? this.enumName(model, field) : PRISMA_TYPES[field.field_type] || 'String';
+    const attrs: string[] = [];
+    if (PRISMA_NATIVE_TYPES[field.field_type] && !isEnum(field)) attrs.push(PRISMA_NATIVE_TYPES[field.field_type]!);
+    if (type === 'Decimal') {
+      // Stored exactly; the generated DTOs and client types carry it as a string
+      const { precision, scale } = decimalDigits(field);
+      attrs.push(`@db.Decimal(${precision},${scale})`);
+    }
+    if (field.unique) attrs.push('@unique');
+    if (field.default !== undefined && field.default !== '') {
+      attrs.push(type === 'String' ? `@default(${JSON.stringify(field.default)})` : `@default(${field.default})`);
+    }
+    const map = columnMap(field.name);
+    if (map) attrs.push(map.trim());
+    // Records are created before their upload, so file columns are always nullable
+    return `${doc}  ${field.name} ${type}${field.required && !isFile(field) ? '' : '?'}${attrs.length ? ' ' + attrs.join(' ') : ''}`;
+  }
+
+  private genPrismaSchema(): GeneratedFile {
+    const keys = this.foreignKeys();
+
+    const enums = this.project.data_models.flatMap(model => model.fields
+      .filter(isEnum)
+      .map(f => `enum ${this.enumName(model, f)} {\n${f.enum_values!.map(v => `  ${v}`).join('\n')}\n}`));
+
+    const models = this.project.data_models.map(model => {
+      const lines = model.fields.map(f => this.genPrismaField(model, f));
+
+      for (const key of keys.filter(k => k.holder.id === model.id)) {
+        const target = this.modelName(key.target);
+        const optional = key.optional ? '?' : '';
+        lines.push(`  ${key.field} ${target}${optional} @relation("${key.relationName}", fields: [${key.field}Id], references: [id]${key.onDelete ? `, onDelete: ${key.onDelete}` : ''})`);
+        lines.push(`  ${key.field}Id String${optional}${key.many ? '' : ' @unique'} @db.Uuid${columnMap(`${key.field}Id`, `${snakeCase(key.field)}_id`)}`);
+      }
+      for (const key of keys.filter(k => k.target.id === model.id)) {
+        const holder = this.modelName(key.holder);
+        lines.push(`  ${key.backField} ${holder}${key.many ? '[]' : '?'} @relation("${key.relationName}")`);
+      }
+      for (const relation of model.relations.filter(r => r.relation_type === 'many_to_many')) {
+        const target = this.project.data_models.find(m => m.id === relation.target_model_id);
+        if (target) lines.push(`  ${relation.name} ${this.modelName(target)}[]`);
+      }
+
+      if (model.timestamps) {
+        lines.push('  createdAt DateTime @default(now()) @map("created_at")');
+        lines.push('  updatedAt DateTime @default(now()) @updatedAt @map("updated_at")');
+      }
+      if (model.soft_delete) lines.push('  deletedAt DateTime? @map("deleted_at")');
+
+      const attributes: string[] = [];
+      const owner = ownerField(model);
+      if (owner && !model.fields.some(f => f.name === owner)) lines.push(`  ${owner} String @db.VarChar(255)${columnMap(owner)}`);
+      // One single-column index per field; the owner field is indexed whether or not it's flagged
+      const indexed = new Set(indexedFields(model).map(f => f.name));
+      if (owner) indexed.add(owner);
+      for (const column of indexed) attributes.push(`  @@index([${column}])`);
+      for (const columns of model.unique_constraints || []) {
+        attributes.push(`  @@unique([${columns.join(', ')}])`);
+      }
+      // Tables and columns are named the way the SQL migrations create them
+      const table = snakeCase(model.name);
+      if (table !== this.modelName(model)) attributes.push(`  @@map("${table}")`);
+      if (attributes.length) lines.push('', ...attributes);
+
+      return `model ${this.modelName(model)} {\n${lines.join('\n')}\n}`;
+    });
+
+    return {
+      path: 'prisma/schema.prisma',
+      content: `generator client {
+  provider = "prisma-client-js"
+}
+
+datasource db {
+  provider = "postgresql"
+  url      = env("DATABASE_URL")
+}
+${[...enums, ...models, ...(this.authSettings ? this.genAuthPrismaModels() : [])].map(m => `\n${m}\n`).join('')}`,
+    };
+  }
+
+  /**
+   * Order models so every foreign-key target is seeded before the model referencing it.
+   * When only cyclic models remain, the next one is taken in declaration order and its
+   * unseeded targets are reported as deferred.
+   */
+  private seedOrder(): Array<{ model: DataModelSchema; deferred: Set<string> }> {
+    const models = this.project.data_models;
+    const deps = new Map(models.map(m => [m.id, new Set<string>()]));
+    for (const key of this.foreignKeys()) {
+      if (key.holder.id !== key.target.id) deps.get(key.holder.id)!.add(key.target.id);
+    }
+
+    const seeded = new Set<string>();
+    const order: Array<{ model: DataModelSchema; deferred: Set<string> }> = [];
+    while (order.length < models.length) {
+      const pending = models.filter(m => !seeded.has(m.id));
+      const next = pending.find(m => [...deps.get(m.id)!].every(d => seeded.has(d))) || pending[0];
+      order.push({ model: next, deferred: new Set([...deps.get(next.id)!].filter(d => !seeded.has(d))) });
+      seeded.add(next.id);
+    }
+    return order;
+  }
+
+  private sampleValue(field: FieldSchema, modelName: string): string {
+    if (isEnum(field)) return `'${field.enum_values![0]}'`;
+    switch (field.field_type) {
+      case 'decimal': return `'${(1.5).toFixed(decimalDigits(field).scale)}'`;
+      case 'int': return '1';
+      case 'float': return '1.5';
+      case 'boolean': return 'true';
+      case 'datetime':
+      case 'date': return 'new Date()';
+      case 'json': return '{}';
+      case 'email': return `'${camelCase(modelName).toLowerCase()}@example.com'`;
+      case 'url': return `'https://example.com'`;
+      default: return `'Sample ${modelName} ${field.name}'`;
+    }
+  }
+
+  private genSeed(): GeneratedFile {
+    const keys = this.foreignKeys();
+    const blocks = this.seedOrder().map(({ model, deferred }) => {
+      const name = this.modelName(model);
+      const data = model.fields
+        .filter(f => !f.primary_key && !isFile(f))
+        .map(f => `      ${f.name}: ${this.sampleValue(f, name)},`);
+      const owner = ownerField(model);
+      if (owner && !model.fields.some(f => f.name === owner)) data.push(`      ${owner}: 'seed-user',`);
+
+      const comments: string[] = [];
+      for (const key of keys.filter(k => k.holder.id === model.id)) {
+        if (key.target.id === model.id) continue;
+        if (deferred.has(key.target.id)) {
+          comments.push(`  // Cycle with ${this.modelName(key.target)}: "${key.field}" is left unset`);
+          continue;
+        }
+        data.push(`      ${key.field}: { connect: { id: ${this.clientName(key.target)}.id } },`);
+      }
+
+      return `${comments.map(c => c + '\n').join('')}  const ${this.clientName(model)} = await prisma.${this.clientName(model)}.create({
+    data: {
+${data.join('\n')}
+    },
+  });`;
+    });
+
+    return {
+      path: 'prisma/seed.ts',
+      content: `import { PrismaClient } from '@prisma/client';
+
+const prisma = new PrismaClient();
+
+async function main() {
+${blocks.join('\n\n')}
+}
+
+main()
+  .catch((error) => {
+    console.error(error);
+    process.exit(1);
+  })
+  .finally(() => prisma.$disconnect());
+`,
+    };
+  }
+
+  /**
+   * Container image for the API. The schema is pushed on start, so a fresh database is
+   * usable without running migrations by hand.
+   */
+  private genDockerFiles(): GeneratedFile[] {
+    return [
+      {
+        path: 'Dockerfile',
+        content: `FROM node:20-alpine
+WORKDIR /app
+COPY package*.json ./
+RUN npm install
+COPY . .
+RUN npx prisma generate && npm run build
+EXPOSE 3000
+CMD ["sh", "-c", "npx prisma db push --skip-generate && node dist/main"]
+`,
+      },
+      { path: '.dockerignore', content: `node_modules\ndist\n.env\n*.log\n` },
+    ];
+  }
+
+  /**
+   * Jest e2e harness isolated from the dev database. Suites run against `TEST_DATABASE_URL`:
+   * `setup-env.ts` points Prisma at it (and gives auth a signing secret) in every worker, and `setup-db.ts`, the global setup
+   * and teardown, pushes the schema and truncates every table so each run starts empty.
+   * Each model gets a starter suite that boots the app and lists its records.
+   */
+  private genE2eSetup(): GeneratedFile[] {
+    const jestConfig = {
+      moduleFileExtensions: ['js', 'json', 'ts'],
+      rootDir: '.',
+      testEnvironment: 'node',
+      testRegex: '.e2e-spec.ts$',
+      transform: { '^.+\\.(t|j)s$': 'ts-jest' },
+      setupFiles: ['<rootDir>/setup-env.ts'],
+      globalSetup: '<rootDir>/setup-db.ts',
+      globalTeardown: '<rootDir>/setup-db.ts',
+    };
+
+    const specs = this.project.data_models.map(model => {
+      const file = kebabCase(model.name);
+      // The testing app skips main.ts, so routes have no global prefix here
+      const check = ownerField(model)
+        ? `it('GET /${file}s needs a signed-in user', () => request(app.getHttpServer()).get('/${file}s').expect(401));`
+        : `it('GET /${file}s lists records', () => request(app.getHttpServer()).get('/${file}s').expect(200));`;
+      return {
+        path: `test/${file}.e2e-spec.ts`,
+        content: `import { INestApplication } from '@nestjs/common';
+import { Test } from '@nestjs/testing';
+import * as request from 'supertest';
+import { AppModule } from '../src/app.module';
+
+describe('${pascalCase(model.name)} (e2e)', () => {
+  let app: INestApplication;
+
+  beforeAll(async () => {
+    const moduleRef = await Test.createTestingModule({ imports: [AppModule] }).compile();
+    app = moduleRef.createNestApplication();
+    await app.init();
+  });
+
+  afterAll(async () => {
+    await app.close();
+  });
+
+  ${check}
+});
+`,
+      };
+    });
+
+    return [
+      ...specs,
+      { path: 'test/jest-e2e.json', content: JSON.stringify(jestConfig, null, 2) + '\n' },
+      {
+        path: 'test/setup-env.ts',
+        content: `// Runs in each test worker before its suites, so the Prisma client connects to the test database
+if (process.env.TEST_DATABASE_URL) {
+  process.env.DATABASE_URL = process.env.TEST_DATABASE_URL;
+}${this.authSettings ? `
+// Tokens only need to be signed consistently within a run
+process.env.JWT_SECRET ||= 'e2e-test-secret';` : ''}
+`,
+      },
+      {
+        path: 'test/setup-db.ts',
+        content: `import { execSync } from 'child_process';
+import { PrismaClient } from '@prisma/client';
+
+/**
+ * Jest global setup and teardown: bring the test database up to the current schema and
+ * empty every table. Refuses to run without TEST_DATABASE_URL so dev data is never wiped.
+ */
+export default async function resetDatabase() {
+  const url = process.env.TEST_DATABASE_URL;
+  if (!url) throw new Error('TEST_DATABASE_URL must be set to run e2e tests');
+
+  execSync('npx prisma db push --skip-generate', {
+    env: { ...process.env, DATABASE_URL: url },
+    stdio: 'inherit',
+  });
+
+  const prisma = new PrismaClient({ datasources: { db: { url } } });
+  try {
+    const tables = await prisma.$queryRaw<{ tablename: string }[]>\`
+      SELECT tablename FROM pg_tables
+      WHERE schemaname = current_schema() AND tablename <> '_prisma_migrations'\`;
+    if (tables.length > 0) {
+      const names = tables.map(t => \`"\${t.tablename}"\`).join(', ');
+      await prisma.$executeRawUnsafe(\`TRUNCATE TABLE \${names} RESTART IDENTITY CASCADE\`);
+    }
+  } finally {
+    await prisma.$disconnect();
+  }
+}
+`,
+      },
+    ];
+  }
+}
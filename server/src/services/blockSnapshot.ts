@@ -0,0 +1,37 @@
+import type { Block } from '@prisma/client';
+import { sha256 } from './bundle.js';
+
+/** Columns a page save writes; a row whose values all match is left alone */
+const ROW_FIELDS = [
+  'projectId', 'pageId', 'parentId', 'blockType', 'name', 'properties', 'styles',
+  'responsiveStyles', 'classes', 'events', 'bindings', 'children', 'order', 'archived',
+] as const;
+
+export type BlockRow = Pick<Block, 'id' | typeof ROW_FIELDS[number]>;
+
+/** What a page save has to write to turn the stored rows into the saved ones */
+export interface BlockDiff {
+  created: BlockRow[];
+  updated: BlockRow[];
+  removed: string[];
+}
+
+/** Hash of a row's saved columns, comparable between stored and incoming rows */
+export function rowHash(row: BlockRow): string {
+  return sha256(JSON.stringify(ROW_FIELDS.map(field => row[field] ?? null)));
+}
+
+/**
+ * Compare a page's stored rows with the rows being saved. Stored rows missing from the save
+ * are removed, except archived ones, which stay restorable from the trash.
+ */
+export function diffBlocks(stored: BlockRow[], next: BlockRow[]): BlockDiff {
+  const hashes = new Map(stored.map(row => [row.id, rowHash(row)]));
+  const nextIds = new Set(next.map(row => row.id));
+
+  return {
+    created: next.filter(row => !hashes.has(row.id)),
+    updated: next.filter(row => hashes.has(row.id) && hashes.get(row.id) !== rowHash(row)),
+    removed: stored.filter(row => !nextIds.has(row.id) && !row.archived).map(row => row.id),
+  };
+}
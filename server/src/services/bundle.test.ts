@@ -0,0 +1,79 @@
+import { test } from 'node:test';
+import assert from 'node:assert/strict';
+import zlib from 'zlib';
+import { buildManifest, buildTarGzBuffer, buildZipBuffer, collectBundleFiles, sha256 } from './bundle.js';
+import type { ProjectSchema } from './projectSchema.js';
+
+const project = {
+  id: 'p1', name: 'Shop', settings: {}, data_models: [], apis: [], logic_flows: [], pages: [], blocks: [], variables: [],
+} as unknown as ProjectSchema;
+
+test('collectBundleFiles lays out the frontend, backend and compose file the way the compose file builds them', () => {
+  const files = collectBundleFiles(project, [{ path: 'Dockerfile', content: 'FROM nginx' }, { path: 'src/App.tsx', content: '' }]);
+  const paths = files.map(f => f.path);
+
+  assert.ok(paths.includes('docker-compose.yml'));
+  assert.ok(paths.includes('frontend/Dockerfile'));
+  assert.ok(paths.includes('frontend/src/App.tsx'));
+  assert.ok(paths.includes('backend/Dockerfile'));
+  assert.ok(paths.every(p => p === 'docker-compose.yml' || p.startsWith('frontend/') || p.startsWith('backend/')));
+  assert.deepEqual(paths, [...paths].sort());
+
+  const compose = files.find(f => f.path === 'docker-compose.yml')!.content;
+  assert.match(compose, /build: \.\/backend/);
+  assert.match(compose, /context: \.\/frontend/);
+});
+
+test('buildManifest hashes every bundled file', () => {
+  const files = [{ path: 'a.txt', content: 'a' }];
+  assert.deepEqual(buildManifest(project, files), {
+    project_id: 'p1', project_name: 'Shop', version: '0.1.0', files: { 'a.txt': sha256('a') },
+  });
+});
+
+/** Path → content of each entry in a ZIP, read from the local file headers */
+function readZip(zip: Buffer): Map<string, string> {
+  const files = new Map<string, string>();
+  let offset = 0;
+  while (zip.readUInt32LE(offset) === 0x04034b50) {
+    const size = zip.readUInt32LE(offset + 18);
+    const nameLength = zip.readUInt16LE(offset + 26);
+    const start = offset + 30 + nameLength;
+    const name = zip.subarray(offset + 30, start).toString('utf-8');
+    files.set(name, zlib.inflateRawSync(zip.subarray(start, start + size)).toString('utf-8'));
+    offset = start + size;
+  }
+  return files;
+}
+
+/** Path → content of each file in a .tar.gz, honouring PAX path records */
+function readTarGz(archive: Buffer): Map<string, string> {
+  const tar = zlib.gunzipSync(archive);
+  const files = new Map<string, string>();
+  let paxPath: string | null = null;
+  for (let offset = 0; tar[offset] !== 0; ) {
+    const name = tar.subarray(offset, offset + 100).toString('utf-8').replace(/\0.*$/s, '');
+    const size = parseInt(tar.subarray(offset + 124, offset + 136).toString('ascii'), 8);
+    const data = tar.subarray(offset + 512, offset + 512 + size).toString('utf-8');
+    if (tar.toString('ascii', offset + 156, offset + 157) === 'x') {
+      paxPath = data.match(/ path=(.*)\n/)![1]!;
+    } else {
+      files.set(paxPath ?? name, data);
+      paxPath = null;
+    }
+    offset += 512 + Math.ceil(size / 512) * 512;
+  }
+  return files;
+}
+
+test('the .tar.gz export holds the same files as the ZIP', () => {
+  const longPath = `src/components/${'Nested'.repeat(20)}.tsx`;
+  const frontend = [{ path: 'src/App.tsx', content: 'export default function App() {}' }, { path: longPath, content: 'export {};' }];
+
+  const zip = readZip(buildZipBuffer(project, frontend));
+  const tar = readTarGz(buildTarGzBuffer(project, frontend));
+
+  assert.ok(['docker-compose.yml', 'frontend/src/App.tsx', `frontend/${longPath}`, 'backend/package.json'].every(p => zip.has(p)));
+  assert.deepEqual([...tar.keys()], [...zip.keys()]);
+  assert.deepEqual(tar, zip);
+});
@@ -0,0 +1,68 @@
+import crypto from 'crypto';
+import { buildTarGz } from '../utils/tar.js';
+import { buildZip, type ZipEntry } from '../utils/zip.js';
+import { BackendGeneratorService } from './backendGenerator.js';
+import { ComposeGeneratorService } from './composeGenerator.js';
+import type { GeneratedFile, ProjectSchema } from './projectSchema.js';
+
+export const MANIFEST_PATH = 'akasha-manifest.json';
+
+export interface BundleManifest {
+  project_id: string;
+  project_name: string;
+  version: string;
+  files: Record<string, string>;
+}
+
+export function sha256(content: string): string {
+  return crypto.createHash('sha256').update(content, 'utf-8').digest('hex');
+}
+
+/**
+ * Collect every generated file for a project under one tree, sorted by path: the frontend
+ * under `frontend/`, the backend under `backend/` and, at the root, the `docker-compose.yml`
+ * that builds both from there. The frontend is passed in since the React generator only
+ * writes to disk.
+ */
+export function collectBundleFiles(project: ProjectSchema, frontend: GeneratedFile[]): GeneratedFile[] {
+  const files = [
+    ...frontend.map(f => ({ path: `frontend/${f.path}`, content: f.content })),
+    ...new BackendGeneratorService(project).generate().map(f => ({ path: `backend/${f.path}`, content: f.content })),
+    new ComposeGeneratorService(project).generate(),
+  ];
+
+  return files.sort((a, b) => (a.path < b.path ? -1 : a.path > b.path ? 1 : 0));
+}
+
+export function buildManifest(project: ProjectSchema, files: GeneratedFile[]): BundleManifest {
+  return {
+    project_id: project.id,
+    project_name: project.name,
+    version: String(project.settings.version || '0.1.0'),
+    files: Object.fromEntries(files.map(f => [f.path, sha256(f.content)])),
+  };
+}
+
+/** The manifest followed by the generated files; both archive formats hold exactly these */
+function bundleEntries(project: ProjectSchema, frontend: GeneratedFile[]): ZipEntry[] {
+  const files = collectBundleFiles(project, frontend);
+  const manifest = buildManifest(project, files);
+
+  return [
+    { path: MANIFEST_PATH, content: JSON.stringify(manifest, null, 2) + '\n' },
+    ...files,
+  ];
+}
+
+/**
+ * Build a reproducible ZIP of the generated project: entries are sorted, timestamps fixed,
+ * and `akasha-manifest.json` lists the SHA-256 of every other file so CI can verify it.
+ */
+export function buildZipBuffer(project: ProjectSchema, frontend: GeneratedFile[]): Buffer {
+  return buildZip(bundleEntries(project, frontend));
+}
+
+/** The same bundle as `buildZipBuffer`, as a `.tar.gz` */
+export function buildTarGzBuffer(project: ProjectSchema, frontend: GeneratedFile[]): Buffer {
+  return buildTarGz(bundleEntries(project, frontend));
+}
@@ -0,0 +1,120 @@
+import { test } from 'node:test';
+import assert from 'node:assert/strict';
+import { EventEmitter } from 'events';
+import type { Request, Response } from 'express';
+import { trackMutations } from './changeFeed.js';
+import { getProjectStore, setProjectStore, type ProjectStore } from './projectStore.js';
+import { getCommandStack } from './commandLog.js';
+import { getCommandDb, setCommandDb } from './commands.js';
+import { memoryCommandDb } from './memoryDb.js';
+
+/** Just the revision half of a store, for one project */
+function revisionStore(id: string, revision: number) {
+  const store = {
+    revision,
+    async getRevision(projectId: string) { return projectId === id ? store.revision : null; },
+    async claimRevision(projectId: string, expected: number) {
+      if (projectId !== id || store.revision !== expected) return false;
+      store.revision++;
+      return true;
+    },
+    async releaseRevision(projectId: string, claimed: number) {
+      if (projectId !== id || store.revision !== claimed) return false;
+      store.revision--;
+      return true;
+    },
+    async bumpRevision(projectId: string) { return projectId === id ? ++store.revision : null; },
+  };
+  return store;
+}
+
+/** The parts of an Express response the middleware wraps */
+class FakeResponse extends EventEmitter {
+  public statusCode = 200;
+  public headers: Record<string, string> = {};
+  public body: any;
+
+  status(code: number) {
+    this.statusCode = code;
+    return this;
+  }
+
+  setHeader(name: string, value: string) {
+    this.headers[name.toLowerCase()] = value;
+  }
+
+  json(body: any) {
+    this.body = body;
+    return this.send(JSON.stringify(body));
+  }
+
+  send(_body?: any) {
+    this.emit('finish');
+    return this;
+  }
+}
+
+/** Run a request through trackMutations and `handler`, resolving once the response is sent */
+function mutate(method: string, url: string, ifMatch: string | undefined, handler: (res: FakeResponse) => void) {
+  const req = {
+    method, originalUrl: url, body: { projectId: 'p1' }, query: {},
+    get: (name: string) => (name === 'If-Match' ? ifMatch : undefined),
+  };
+  const res = new FakeResponse();
+  const finished = new Promise<FakeResponse>(resolve => res.once('finish', () => resolve(res)));
+  void trackMutations(req as unknown as Request, res as unknown as Response, () => handler(res));
+  return finished;
+}
+
+const ok = (res: FakeResponse) => res.json({ id: 'u1' });
+
+test('trackMutations rejects a stale If-Match revision and accepts a matching one', async t => {
+  const previous = getProjectStore();
+  const store = revisionStore('p1', 3);
+  setProjectStore(store as unknown as ProjectStore);
+  t.after(() => setProjectStore(previous));
+
+  const matching = await mutate('PUT', '/api/usecases/u1', '"3"', ok);
+  assert.equal(matching.statusCode, 200);
+  assert.equal(matching.headers.etag, '"4"');
+
+  const stale = await mutate('PUT', '/api/usecases/u1', '"3"', () => assert.fail('handler ran on a stale revision'));
+  assert.equal(stale.statusCode, 409);
+  assert.equal(stale.body.revision, 4);
+  assert.equal(store.revision, 4);
+});
+
+test('trackMutations hands the revision back when the handler fails', async t => {
+  const previous = getProjectStore();
+  const store = revisionStore('p1', 3);
+  setProjectStore(store as unknown as ProjectStore);
+  t.after(() => setProjectStore(previous));
+
+  const failed = await mutate('DELETE', '/api/diagrams/flow.drawio', '3', res => res.status(500).json({ error: 'boom' }));
+  assert.equal(failed.statusCode, 500);
+  assert.equal(store.revision, 3);
+
+  const retried = await mutate('DELETE', '/api/diagrams/flow.drawio', '3', ok);
+  assert.equal(retried.statusCode, 200);
+  assert.equal(store.revision, 4);
+});
+
+test('trackMutations clears the undo history after a write that bypasses the commands', async t => {
+  const previousStore = getProjectStore();
+  const previousDb = getCommandDb();
+  setProjectStore(revisionStore('p1', 0) as unknown as ProjectStore);
+  setCommandDb(memoryCommandDb());
+  t.after(() => {
+    setProjectStore(previousStore);
+    setCommandDb(previousDb);
+  });
+
+  const stack = getCommandStack('p1');
+  await stack.execute({ description: 'Rename', opKind: 'rename', projectId: 'p1', execute: async () => {}, undo: async () => {} });
+
+  await mutate('PUT', '/api/blocks/0123456789abcdef01234567', undefined, ok);
+  assert.ok(stack.canUndo);
+
+  await mutate('POST', '/api/blocks/sync', undefined, ok);
+  assert.ok(!stack.canUndo);
+});
@@ -0,0 +1,225 @@
+import { EventEmitter } from 'events';
+import type { Server } from 'http';
+import type { NextFunction, Request, Response } from 'express';
+import { acceptWebSocket, type WebSocketConnection } from '../utils/websocket.js';
+import { StaleRevisionError, claimRevision, parseIfMatch, projectIdForEntity, releaseRevision, touchProject } from './revision.js';
+import { processOutput, type ProcessOutputLine } from './processOutput.js';
+import { clearCommandStack } from './commandLog.js';
+
+export const CHANGE_SOCKET_PATH = '/ws';
+
+/**
+ * Routes that change a project's content. Anything else (code generation, the outbound proxy,
+ * dev servers, flow simulation, AI chat) leaves the revision alone, so new routes have to
+ * opt in here rather than bumping it by accident.
+ */
+const MUTATING_ROUTES = [
+  'POST /api/project', 'PUT /api/project/:id', 'PUT /api/project/:id/idea', 'PUT /api/project/:id/root',
+  'POST /api/project/:id/generate-idea-details', 'DELETE /api/project/:id',
+  'POST /api/blocks', 'POST /api/blocks/sync', 'POST /api/blocks/paste', 'POST /api/blocks/bulk-archive',
+  'POST /api/blocks/bulk-delete', 'PUT /api/blocks/:id', 'PUT /api/blocks/:id/move', 'POST /api/blocks/:id/duplicate',
+  'POST /api/blocks/:id/restore', 'POST /api/blocks/:id/extract-component',
+  'POST /api/pages', 'PUT /api/pages/:id', 'DELETE /api/pages/:id', 'POST /api/pages/:id/restore',
+  'POST /api/logic-flows', 'PUT /api/logic-flows/:id', 'DELETE /api/logic-flows/:id', 'POST /api/logic-flows/:id/restore',
+  'POST /api/data-models', 'PUT /api/data-models/:id', 'POST /api/data-models/:id/rename', 'DELETE /api/data-models/:id',
+  'POST /api/data-models/:id/restore',
+  'POST /api/variables', 'DELETE /api/variables/:id',
+  'POST /api/components',
+  'POST /api/usecases', 'PUT /api/usecases/:id', 'DELETE /api/usecases/:id',
+  'POST /api/diagrams', 'POST /api/diagrams/to-models', 'DELETE /api/diagrams/:id',
+  'POST /api/command/undo', 'POST /api/command/redo',
+  'POST /api/import/openapi', 'POST /api/import/project',
+  'POST /api/codegen/sync-from-disk',
+  'POST /api/ai/ui-builder/apply',
+].map(toRoutePattern);
+
+/**
+ * The mutating routes whose handlers write through the project's CommandStack. A change made
+ * by any other route clears the undo history, since undoing past it would overwrite it.
+ */
+const COMMAND_ROUTES = [
+  'POST /api/blocks', 'PUT /api/blocks/:id', 'PUT /api/blocks/:id/move', 'POST /api/blocks/:id/duplicate',
+  'POST /api/blocks/:id/extract-component', 'POST /api/blocks/paste', 'POST /api/blocks/bulk-archive',
+  'POST /api/blocks/bulk-delete', 'POST /api/command/undo', 'POST /api/command/redo',
+].map(toRoutePattern);
+
+function toRoutePattern(route: string) {
+  const [method, path] = route.split(' ');
+  return { method, pattern: new RegExp(`^${path!.replace(/:\w+/g, '[^/]+')}/?$`) };
+}
+
+function matchesRoute(routes: Array<{ method?: string; pattern: RegExp }>, method: string, url: string): boolean {
+  return routes.some(route => route.method === method && route.pattern.test(url));
+}
+
+const ENTITY_ID = /^([0-9a-f]{24}|[0-9a-f]{8}-[0-9a-f]{4}-[0-9a-f]{4}-[0-9a-f]{4}-[0-9a-f]{12})$/i;
+
+export interface ProjectChange {
+  project_id: string;
+  /** Singular entity kind from the route, e.g. `block`, `page`, `data_model` */
+  kind: string;
+  entity_id: string | null;
+  action: 'create' | 'update' | 'delete';
+  /** Project revision after the change; null once the project itself is deleted */
+  revision: number | null;
+  timestamp: string;
+}
+
+class ChangeFeed extends EventEmitter {
+  public publish(change: Omit<ProjectChange, 'timestamp'>) {
+    this.emit('change', { ...change, timestamp: new Date().toISOString() } satisfies ProjectChange);
+  }
+}
+
+export const changeFeed = new ChangeFeed();
+
+/** `/api/data-models/…` → `data_model` */
+export function entityKind(resource: string): string {
+  const kind = resource.replace(/-/g, '_');
+  return kind.endsWith('s') ? kind.slice(0, -1) : kind;
+}
+
+/**
+ * Express middleware for the routes in MUTATING_ROUTES. When the request carries
+ * `If-Match: <revision>`, the revision is claimed atomically before the handler runs and a
+ * project that has moved on answers 409; if the handler then fails, the claim is handed back
+ * so the client's revision stays current. Without the header a successful response bumps it
+ * before being sent. Either way the new revision goes out as the response's `ETag` and the
+ * change is published; the entity id comes from the URL, or from the response body for creates.
+ */
+export async function trackMutations(req: Request, res: Response, next: NextFunction) {
+  const url = req.originalUrl.split('?')[0];
+  if (!matchesRoute(MUTATING_ROUTES, req.method, url)) {
+    next();
+    return;
+  }
+
+  const [, , resource, segment] = url.split('/');
+  const kind = entityKind(resource);
+  const urlEntityId = segment && ENTITY_ID.test(segment) ? segment : null;
+  const requestProjectId = [req.body?.projectId, req.query.projectId].find(v => typeof v === 'string') as string | undefined;
+
+  // Resolved up front so deletes still know their project once the entity is gone
+  let knownProjectId: string | null = requestProjectId || null;
+  // Set when If-Match claimed the revision, so the response doesn't bump it a second time
+  let claimedRevision: number | null = null;
+  try {
+    if (!knownProjectId && urlEntityId) knownProjectId = await projectIdForEntity(kind, urlEntityId);
+    const expected = parseIfMatch(req.get('If-Match'));
+    if (expected !== null && !knownProjectId) {
+      res.status(412).json({ error: 'If-Match was sent but the request names no project to check it against' });
+      return;
+    }
+    if (knownProjectId && expected !== null) claimedRevision = await claimRevision(knownProjectId, expected);
+  } catch (error) {
+    if (error instanceof StaleRevisionError) {
+      res.status(409).json({ error: error.message, revision: error.current });
+      return;
+    }
+    next(error);
+    return;
+  }
+
+  let responseBody: any;
+  let recorded = false;
+
+  /**
+   * Bump the revision (unless already claimed) and publish the change; returns the new revision.
+   * A change whose project can't be worked out isn't published, since no socket can be told
+   * apart as interested in it.
+   */
+  const recordChange = async (): Promise<number | null> => {
+    recorded = true;
+    const entityId = urlEntityId || (typeof responseBody?.id === 'string' ? responseBody.id : null);
+    const projectId = knownProjectId || responseBody?.project_id || responseBody?.projectId
+      || (entityId ? await projectIdForEntity(kind, entityId) : null);
+    if (typeof projectId !== 'string') return claimedRevision;
+    if (!matchesRoute(COMMAND_ROUTES, req.method, url)) clearCommandStack(projectId);
+    const revision = claimedRevision ?? await touchProject(projectId);
+
+    changeFeed.publish({
+      project_id: projectId,
+      kind,
+      entity_id: entityId,
+      action: req.method === 'DELETE' ? 'delete' : req.method === 'POST' && !segment ? 'create' : 'update',
+      revision,
+    });
+    return revision;
+  };
+
+  /** A failed request changes nothing, so any revision it claimed goes back */
+  const releaseClaim = async () => {
+    recorded = true;
+    if (knownProjectId && claimedRevision !== null) await releaseRevision(knownProjectId, claimedRevision);
+  };
+
+  const json = res.json.bind(res);
+  const send = res.send.bind(res);
+  res.json = (body: any) => {
+    responseBody = body;
+    return json(body);
+  };
+  // `res.json` and `res.sendStatus` both end in `send`, so holding it back until the revision
+  // is recorded lets the client read the new revision off this very response
+  res.send = (body?: any) => {
+    if (recorded) return send(body);
+    const settled = res.statusCode >= 400
+      ? releaseClaim()
+      : recordChange().then(revision => {
+        if (revision !== null) res.setHeader('ETag', `"${revision}"`);
+      });
+    settled
+      .catch(error => console.error('[ChangeFeed] Failed to record change:', error))
+      .finally(() => send(body));
+    return res;
+  };
+
+  // Responses that bypass `send` (streams, `res.end`) are still recorded, just without the header
+  res.on('finish', () => {
+    if (recorded) return;
+    (res.statusCode >= 400 ? releaseClaim() : recordChange())
+      .catch(error => console.error('[ChangeFeed] Failed to record change:', error));
+  });
+
+  next();
+}
+
+/**
+ * Serve `ws://…/ws?projectId=…` on the HTTP server. Each socket receives the feed's changes
+ * as JSON, filtered to its project when one is given. Sockets opened for a project also get its process output (`type: 'output'`),
+ * starting with the recent tail.
+ */
+export function attachChangeSocket(server: Server) {
+  const clients = new Map<WebSocketConnection, string | null>();
+
+  server.on('upgrade', (req, socket) => {
+    const url = new URL(req.url || '/', 'http://localhost');
+    if (url.pathname !== CHANGE_SOCKET_PATH) {
+      socket.destroy();
+      return;
+    }
+
+    const connection = acceptWebSocket(req, socket, () => clients.delete(connection as WebSocketConnection));
+    if (!connection) return;
+
+    const projectId = url.searchParams.get('projectId');
+    clients.set(connection, projectId);
+    if (projectId) {
+      for (const line of processOutput.tail(projectId)) connection.send(JSON.stringify({ type: 'output', ...line }));
+    }
+  });
+
+  changeFeed.on('change', (change: ProjectChange) => {
+    const message = JSON.stringify({ type: 'change', ...change });
+    for (const [connection, projectId] of clients) {
+      if (!projectId || projectId === change.project_id) connection.send(message);
+    }
+  });
+
+  processOutput.on('line', (line: ProcessOutputLine) => {
+    const message = JSON.stringify({ type: 'output', ...line });
+    for (const [connection, projectId] of clients) {
+      if (projectId === line.project_id) connection.send(message);
+    }
+  });
+}
@@ -0,0 +1,72 @@
+import { test } from 'node:test';
+import assert from 'node:assert/strict';
+import type { Block } from '@prisma/client';
+import { CLIPBOARD_FORMAT, CLIPBOARD_VERSION, PasteBlocksCommand, parseClipboard, serializeSubtree } from './clipboard.js';
+import { cloneBlockTree } from './commands.js';
+import { useMemoryDb } from './memoryDb.js';
+
+const block = (id: string, children: string[] = []) => ({ id, block_type: 'Container', children });
+
+const payload = (blocks: unknown[], root_id = 'a', component_ids: string[] = []) =>
+  ({ format: CLIPBOARD_FORMAT, version: CLIPBOARD_VERSION, root_id, component_ids, blocks });
+
+test('parseClipboard accepts a copied subtree and its component trees', () => {
+  const data = payload([block('a', ['b']), block('b'), block('c', ['d']), block('d')], 'a', ['c']);
+  assert.equal(parseClipboard(JSON.stringify(data)).root_id, 'a');
+});
+
+test('parseClipboard rejects blocks that don\'t form trees under the roots', () => {
+  for (const [blocks, message] of [
+    [[block('a', ['b']), block('b'), block('b')], /duplicate block ids/],
+    [[block('a', ['b', 'c']), block('b', ['c']), block('c')], /more than one parent/],
+    [[block('a', ['b']), block('b', ['a'])], /no single root/],
+    [[block('a'), block('b', ['c']), block('c', ['b'])], /do not form a tree/],
+    [[block('a', ['x'])], /missing blocks/],
+  ] as const) {
+    assert.throws(() => parseClipboard(payload([...blocks])), message);
+  }
+});
+
+test('cloneBlockTree copies each block once even when children lists loop', () => {
+  const row = (id: string, children: string[]) => ({ id, parentId: null, children: JSON.stringify(children) }) as Block;
+  const blocks = new Map([row('a', ['b', 'b']), row('b', ['a', 'c']), row('c', ['b'])].map(b => [b.id, b]));
+
+  const clones = cloneBlockTree(blocks, 'a', new Map());
+  assert.equal(clones.length, 3);
+  assert.equal(new Set(clones.map(c => c.id)).size, 3);
+  assert.deepEqual(clones.map(c => JSON.parse(c.children).length), [1, 1, 0]);
+});
+
+test('a subtree copied from one project pastes into another with new ids and its components', async t => {
+  const db = useMemoryDb(t, {
+    blocks: [
+      { id: 'root1', projectId: 'p1', pageId: 'page1', blockType: 'Container', name: 'Root', children: '["card"]' },
+      { id: 'card', projectId: 'p1', pageId: 'page1', parentId: 'root1', blockType: 'Card', name: 'Card', children: '["title","badge"]' },
+      { id: 'title', projectId: 'p1', pageId: 'page1', parentId: 'card', blockType: 'Text', name: 'Title', properties: '{"text":"Hi"}' },
+      { id: 'badge', projectId: 'p1', pageId: 'page1', parentId: 'card', blockType: 'instance', name: 'Badge', properties: '{"component_id":"comp"}', order: 1 },
+      { id: 'comp', projectId: 'p1', blockType: 'component', name: 'Badge', children: '["label"]' },
+      { id: 'label', projectId: 'p1', parentId: 'comp', blockType: 'Text', name: 'Label' },
+      { id: 'root2', projectId: 'p2', pageId: 'page2', blockType: 'Container', name: 'Root', children: '["existing"]' },
+      { id: 'existing', projectId: 'p2', pageId: 'page2', parentId: 'root2', blockType: 'Text', name: 'Existing' },
+    ],
+  });
+  const original = new Set(db.block.rows.map(b => b.id));
+
+  const copied = parseClipboard(await serializeSubtree('p1', 'card'));
+  assert.deepEqual(copied.component_ids, ['comp']);
+  const paste = new PasteBlocksCommand('p2', copied, 'root2');
+  await paste.execute();
+
+  const pasted = db.block.rows.filter(b => b.projectId === 'p2' && !original.has(b.id));
+  const byName = (name: string, type: string) => pasted.find(b => b.name === name && b.blockType === type)!;
+  assert.equal(pasted.length, 5);
+  assert.equal(byName('Card', 'Card').id, paste.rootId);
+  assert.equal(db.block.rows.find(b => b.id === 'root2')!.children, JSON.stringify(['existing', paste.rootId]));
+  assert.deepEqual(JSON.parse(byName('Card', 'Card').children), [byName('Title', 'Text').id, byName('Badge', 'instance').id]);
+  assert.equal(byName('Title', 'Text').pageId, 'page2');
+
+  const component = byName('Badge', 'component');
+  assert.equal(JSON.parse(byName('Badge', 'instance').properties).component_id, component.id);
+  assert.deepEqual([component.pageId, component.parentId, JSON.parse(component.children)], [null, null, [byName('Label', 'Text').id]]);
+  assert.equal(db.block.rows.filter(b => b.projectId === 'p1').length, 6);
+});
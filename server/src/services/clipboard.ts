@@ -0,0 +1,222 @@
+import type { Block } from '@prisma/client';
+import { cloneBlockTree, getCommandDb, siblingIds, subtreeIds, writeSiblings, type Command, type SiblingList } from './commands.js';
+import { parseJsonValue } from './projectSchema.js';
+
+export const CLIPBOARD_FORMAT = 'akasha/blocks';
+export const CLIPBOARD_VERSION = 1;
+
+/** One copied block; ids only need to be unique inside the payload */
+export interface ClipboardBlock {
+  id: string;
+  block_type: string;
+  name: string;
+  parent_id: string | null;
+  properties: Record<string, any>;
+  styles: Record<string, any>;
+  responsive_styles: Record<string, any>;
+  classes: string[];
+  events: any;
+  bindings: Record<string, any>;
+  children: string[];
+  order: number;
+}
+
+/**
+ * A copied subtree that can be pasted into any project: the copied block and its descendants,
+ * plus the subtrees of every component their instances use (`component_ids` are those roots).
+ */
+export interface ClipboardPayload {
+  format: typeof CLIPBOARD_FORMAT;
+  version: number;
+  root_id: string;
+  component_ids: string[];
+  blocks: ClipboardBlock[];
+}
+
+export class ClipboardError extends Error {
+  public readonly status: number;
+
+  constructor(message: string, status = 400) {
+    super(message);
+    this.name = 'ClipboardError';
+    this.status = status;
+  }
+}
+
+function toClipboardBlock(block: Block): ClipboardBlock {
+  return {
+    id: block.id,
+    block_type: block.blockType,
+    name: block.name,
+    parent_id: block.parentId || null,
+    properties: parseJsonValue(block.properties, {}),
+    styles: parseJsonValue(block.styles, {}),
+    responsive_styles: parseJsonValue(block.responsiveStyles, {}),
+    classes: parseJsonValue(block.classes, []),
+    events: parseJsonValue(block.events, []),
+    bindings: parseJsonValue(block.bindings, {}),
+    children: parseJsonValue(block.children, []),
+    order: block.order,
+  };
+}
+
+/** Serialize `blockId`'s subtree, with the components it instantiates, as clipboard JSON */
+export async function serializeSubtree(projectId: string, blockId: string): Promise<string> {
+  const rows = await getCommandDb().block.findMany({ where: { projectId, archived: false } });
+  const blocks = new Map(rows.map(b => [b.id, b]));
+  if (!blocks.has(blockId)) throw new ClipboardError(`Block ${blockId} not found`, 404);
+
+  const ids = subtreeIds(blocks, blockId);
+  const componentIds: string[] = [];
+  // Components can hold instances of other components, so keep going until none are new
+  for (let i = 0; i < ids.length; i++) {
+    const componentId = parseJsonValue<Record<string, any>>(blocks.get(ids[i])?.properties, {}).component_id;
+    if (typeof componentId !== 'string' || componentIds.includes(componentId) || !blocks.has(componentId)) continue;
+    componentIds.push(componentId);
+    ids.push(...subtreeIds(blocks, componentId).filter(id => !ids.includes(id)));
+  }
+
+  const payload: ClipboardPayload = {
+    format: CLIPBOARD_FORMAT,
+    version: CLIPBOARD_VERSION,
+    root_id: blockId,
+    component_ids: componentIds,
+    blocks: ids.map(id => toClipboardBlock(blocks.get(id) as Block)),
+  };
+  return JSON.stringify(payload);
+}
+
+/**
+ * Parse clipboard JSON (text or already parsed), checking it holds the blocks it names and
+ * that they form trees: ids are unique, no block has two parents, and every block hangs
+ * under the copied root or a component root, which themselves have no parent.
+ */
+export function parseClipboard(data: unknown): ClipboardPayload {
+  let payload: any = data;
+  if (typeof data === 'string') {
+    try {
+      payload = JSON.parse(data);
+    } catch {
+      throw new ClipboardError('Clipboard data is not valid JSON');
+    }
+  }
+  if (!payload || payload.format !== CLIPBOARD_FORMAT) throw new ClipboardError('Clipboard data is not a copied block');
+  if (payload.version !== CLIPBOARD_VERSION) throw new ClipboardError(`Unsupported clipboard version ${payload.version}`);
+  if (!Array.isArray(payload.blocks) || !Array.isArray(payload.component_ids)) throw new ClipboardError('Clipboard data has no blocks');
+
+  const blocks = payload.blocks as ClipboardBlock[];
+  if (blocks.some(b => typeof b?.id !== 'string' || typeof b.block_type !== 'string' || (b.children !== undefined && !Array.isArray(b.children)))) {
+    throw new ClipboardError('Clipboard data has a malformed block');
+  }
+  const ids = new Set(blocks.map(b => b.id));
+  if (ids.size !== blocks.length) throw new ClipboardError('Clipboard data has duplicate block ids');
+  const roots: unknown[] = [payload.root_id, ...payload.component_ids];
+  if (roots.some(id => !ids.has(id as string))) {
+    throw new ClipboardError('Clipboard data is missing blocks it references');
+  }
+
+  const parents = new Map<string, string>();
+  for (const block of blocks) {
+    for (const child of block.children || []) {
+      if (!ids.has(child)) throw new ClipboardError('Clipboard data is missing blocks it references');
+      if (parents.has(child)) throw new ClipboardError(`Block ${child} appears under more than one parent`);
+      parents.set(child, block.id);
+    }
+  }
+  if (new Set(roots).size !== roots.length || roots.some(id => parents.has(id as string))) {
+    throw new ClipboardError('Clipboard data has no single root block');
+  }
+
+  // With one parent each, a block the roots can't reach is part of a cycle or detached
+  const reached = new Set<string>();
+  const children = new Map(blocks.map(b => [b.id, b.children || []]));
+  const pending = roots as string[];
+  while (pending.length) {
+    const id = pending.pop() as string;
+    reached.add(id);
+    pending.push(...(children.get(id) || []));
+  }
+  if (reached.size !== ids.size) throw new ClipboardError('Clipboard blocks do not form a tree under the copied block');
+  return payload as ClipboardPayload;
+}
+
+/**
+ * Insert a clipboard payload as the last child of `parentId`. Every block, components
+ * included, gets a fresh id and instances are pointed at the pasted components, so the
+ * same payload can be pasted any number of times and into any project.
+ */
+export class PasteBlocksCommand implements Command {
+  public readonly opKind = 'paste_blocks';
+  public readonly description: string;
+  public readonly projectId: string;
+  public rootId: string | null = null;
+  private payload: ClipboardPayload;
+  private parentId: string;
+  private idMap = new Map<string, string>();
+  private before: SiblingList | null = null;
+
+  constructor(projectId: string, payload: ClipboardPayload, parentId: string) {
+    this.projectId = projectId;
+    this.payload = payload;
+    this.parentId = parentId;
+    this.description = `Paste ${payload.blocks.length} block(s) into ${parentId}`;
+  }
+
+  async execute() {
+    const parent = await getCommandDb().block.findUnique({ where: { id: this.parentId } });
+    if (!parent || parent.projectId !== this.projectId) throw new ClipboardError(`Block ${this.parentId} not found`, 404);
+
+    const siblings = await siblingIds(this.projectId, this.parentId, parent.pageId);
+    this.before = { parentId: this.parentId, ids: siblings };
+
+    const rows = new Map<string, Block>(this.payload.blocks.map(b => [b.id, {
+      id: b.id,
+      projectId: this.projectId,
+      pageId: null,
+      parentId: b.parent_id,
+      blockType: b.block_type,
+      name: b.name || b.block_type,
+      properties: JSON.stringify(b.properties || {}),
+      styles: JSON.stringify(b.styles || {}),
+      responsiveStyles: JSON.stringify(b.responsive_styles || {}),
+      classes: JSON.stringify(b.classes || []),
+      events: JSON.stringify(b.events || []),
+      bindings: JSON.stringify(b.bindings || {}),
+      children: JSON.stringify(b.children || []),
+      order: b.order || 0,
+      archived: false,
+    }]));
+
+    // The pasted subtree joins the parent's page; components stay outside pages
+    const pasted = cloneBlockTree(rows, this.payload.root_id, this.idMap)
+      .map(block => ({ ...block, pageId: parent.pageId }));
+    pasted[0] = { ...pasted[0], parentId: this.parentId, order: siblings.length };
+    const components = this.payload.component_ids.flatMap(id => {
+      const tree = cloneBlockTree(rows, id, this.idMap);
+      return tree.length ? [{ ...tree[0], parentId: null }, ...tree.slice(1)] : [];
+    });
+
+    // Pasting is many writes with no transaction, so a failure part way removes what was created
+    const created: string[] = [];
+    try {
+      for (const block of [...pasted, ...components]) {
+        const properties = parseJsonValue<Record<string, any>>(block.properties, {});
+        const componentId = this.idMap.get(properties.component_id);
+        const data = componentId ? { ...block, properties: JSON.stringify({ ...properties, component_id: componentId }) } : block;
+        await getCommandDb().block.create({ data });
+        created.push(block.id);
+      }
+      await writeSiblings({ parentId: this.parentId, ids: [...siblings, pasted[0].id] });
+    } catch (error) {
+      await getCommandDb().block.deleteMany({ where: { id: { in: created } } });
+      await writeSiblings({ parentId: this.parentId, ids: siblings });
+      throw error;
+    }
+    this.rootId = pasted[0].id;
+  }
+
+  async undo() {
+    await getCommandDb().block.deleteMany({ where: { id: { in: [...this.idMap.values()] } } });
+    if (this.before) await writeSiblings(this.before);
+  }
+}
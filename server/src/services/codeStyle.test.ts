@@ -0,0 +1,44 @@
+import { test } from 'node:test';
+import assert from 'node:assert/strict';
+import { DEFAULT_CODE_STYLE, formatCode, type CodeStyle } from './codeStyle.js';
+
+const style = (overrides: Partial<CodeStyle>): CodeStyle => ({ ...DEFAULT_CODE_STYLE, ...overrides });
+
+test('formatCode leaves code alone in the default style', () => {
+  const source = "const a = 'x';\n";
+  assert.equal(formatCode(source, DEFAULT_CODE_STYLE), source);
+});
+
+test('formatCode re-quotes string literals unless the other quote is inside', () => {
+  const double = style({ quotes: 'double' });
+  assert.equal(formatCode("import x from 'y';", double), 'import x from "y";');
+  assert.equal(formatCode("const a = 'say \"hi\"';", double), "const a = 'say \"hi\"';");
+  assert.equal(formatCode("const a = 'it\\'s';", double), 'const a = "it\'s";');
+  assert.equal(formatCode('const a = `keep \'this\'`;', double), 'const a = `keep \'this\'`;');
+});
+
+test('formatCode keeps JSX attributes, JSON attribute expressions and JSX text', () => {
+  // Semicolons off, so single quotes alone don't make it the default style
+  const single = style({ quotes: 'single', semicolons: false });
+  const double = style({ quotes: 'double' });
+  assert.equal(formatCode('<Text text={"Say \\"hi\\""} />', single), '<Text text={"Say \\"hi\\""} />');
+  assert.equal(formatCode('<Text text={["a", "b"]} level={2} />', single), '<Text text={["a", "b"]} level={2} />');
+  assert.equal(formatCode('<input type="text" />', single), '<input type="text" />');
+  assert.equal(formatCode('<p>Say "hi" to \'them\'</p>', double), '<p>Say "hi" to \'them\'</p>');
+  assert.equal(formatCode('  and "more" text</p>', double), '  and "more" text</p>');
+  assert.equal(formatCode("<p>Don't</p>", double), "<p>Don't</p>");
+  assert.equal(formatCode("<Button onClick={() => go('/home')} />", double), '<Button onClick={() => go("/home")} />');
+});
+
+test('formatCode drops semicolons only where no ASI hazard follows', () => {
+  const bare = style({ semicolons: false });
+  assert.equal(formatCode('const a = 1;\nconst b = 2;', bare), 'const a = 1\nconst b = 2');
+  assert.equal(formatCode('const a = b;\n(c || d).run();', bare), 'const a = b;\n(c || d).run()');
+  assert.equal(formatCode('for (let i = 0; i < 3; i++) {}', bare), 'for (let i = 0; i < 3; i++) {}');
+});
+
+test('formatCode re-indents outside template literals', () => {
+  const four = style({ indent: 4 });
+  assert.equal(formatCode('if (a) {\n  b();\n}', four), 'if (a) {\n    b();\n}');
+  assert.equal(formatCode('const t = `\n  kept\n`;', four), 'const t = `\n  kept\n`;');
+});
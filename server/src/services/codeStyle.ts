@@ -0,0 +1,231 @@
+import type { GeneratedFile, ProjectSchema } from './projectSchema.js';
+
+/**
+ * How generated TypeScript is laid out, from `settings.code_style`. The generators build
+ * code in one fixed style (single quotes, semicolons, 2-space indent); `formatCode` rewrites
+ * it to match a team's Prettier config.
+ */
+export interface CodeStyle {
+  quotes: 'single' | 'double';
+  semicolons: boolean;
+  indent: number;
+}
+
+export const DEFAULT_CODE_STYLE: CodeStyle = { quotes: 'single', semicolons: true, indent: 2 };
+
+const SOURCE_EXTENSIONS = /\.(ts|tsx|js|jsx|mjs|cjs)$/;
+/** A line starting with one of these would join the previous statement without a semicolon */
+const ASI_HAZARD = /^[([`+\-/]/;
+/** A `/` after one of these starts a regex literal rather than a division */
+const REGEX_PREFIX = /[(,=:[!&|?{};]$/;
+
+/** The project's code style, with unset or invalid values left at the defaults */
+export function codeStyle(project: Pick<ProjectSchema, 'settings'>): CodeStyle {
+  const raw = project.settings?.code_style || {};
+  return {
+    quotes: raw.quotes === 'double' ? 'double' : 'single',
+    semicolons: raw.semicolons !== false,
+    indent: Number.isInteger(raw.indent) && raw.indent >= 1 && raw.indent <= 8 ? raw.indent : DEFAULT_CODE_STYLE.indent,
+  };
+}
+
+const isDefault = (style: CodeStyle) =>
+  style.quotes === DEFAULT_CODE_STYLE.quotes && style.semicolons === DEFAULT_CODE_STYLE.semicolons && style.indent === DEFAULT_CODE_STYLE.indent;
+
+/** `.prettierrc` matching the style, so formatting on save doesn't undo it */
+export function prettierConfig(style: CodeStyle): GeneratedFile {
+  const config = { singleQuote: style.quotes === 'single', semi: style.semicolons, tabWidth: style.indent };
+  return { path: '.prettierrc', content: `${JSON.stringify(config, null, 2)}\n` };
+}
+
+/** A string literal re-quoted, or unchanged when the other quote appears inside it */
+function requote(literal: string, quote: string): string {
+  const body = literal.slice(1, -1);
+  if (literal[0] === quote || body.includes(quote)) return literal;
+  return `${quote}${body.replace(/\\(['"])/g, '$1')}${quote}`;
+}
+
+/** Index just past the closing quote of the literal opening at `start`, or -1 */
+function stringEnd(line: string, start: number): number {
+  for (let i = start + 1; i < line.length; i++) {
+    if (line[i] === '\\') i++;
+    else if (line[i] === line[start]) return i + 1;
+  }
+  return -1;
+}
+
+/**
+ * Index just past a `{…}` attribute expression opening at `start` when it holds a JSON
+ * literal (`text={"Say \"hi\""}`), or -1. Sync reads those back with `JSON.parse`, so their
+ * double quotes have to survive.
+ */
+function jsonExpressionEnd(line: string, start: number): number {
+  let depth = 0;
+  for (let i = start; i < line.length; i++) {
+    if (line[i] === '"') {
+      const end = stringEnd(line, i);
+      if (end === -1) return -1;
+      i = end - 1;
+    } else if (line[i] === '{') {
+      depth++;
+    } else if (line[i] === '}' && --depth === 0) {
+      try {
+        JSON.parse(line.slice(start + 1, i));
+        return i + 1;
+      } catch {
+        return -1;
+      }
+    }
+  }
+  return -1;
+}
+
+/**
+ * Whether a quote sits in JSX text, where it is a literal character rather than a string:
+ * just after an opening tag on the same line (`<p>Say "hi"`), or before a closing one
+ * (`Say "hi"</p>`)
+ */
+function inJsxText(before: string, after: string): boolean {
+  return /(<[A-Za-z][^<>]*[^/<>=]|<)>[^<>{}()]*$/.test(before) || /^[^<>{}]*<\/[A-Za-z.]*>/.test(after);
+}
+
+/** Index just past the regex literal opening at `start`, or -1 */
+function regexEnd(line: string, start: number): number {
+  let inClass = false;
+  for (let i = start + 1; i < line.length; i++) {
+    if (line[i] === '\\') i++;
+    else if (line[i] === '[') inClass = true;
+    else if (line[i] === ']') inClass = false;
+    else if (line[i] === '/' && !inClass) {
+      let end = i + 1;
+      while (end < line.length && /[a-z]/.test(line[end])) end++;
+      return end;
+    }
+  }
+  return -1;
+}
+
+/**
+ * Rewrite generated source to a code style: re-quote string literals, drop statement-ending
+ * semicolons where that is safe, and re-indent from 2 spaces. Template literal contents,
+ * comments, JSX text, JSX attributes and JSON-literal attribute expressions are left as they are.
+ */
+export function formatCode(content: string, style: CodeStyle): string {
+  if (isDefault(style)) return content;
+  const quote = style.quotes === 'double' ? '"' : "'";
+
+  // Open template literals and `${` expressions, innermost last
+  const stack: Array<{ template: boolean; braces: number }> = [{ template: false, braces: 0 }];
+  let inComment = false;
+  const lines: string[] = [];
+  const semicolonAt: number[] = [];
+
+  for (const line of content.split('\n')) {
+    // Comment lines are re-indented with the code around them; template contents are not
+    const reindent = !stack[stack.length - 1].template;
+    let out = '';
+    // Position in `out` of the last code character, and whether it was a semicolon
+    let last = -1;
+    let lastIsSemicolon = false;
+    const code = (text: string, semicolon = false) => {
+      out += text;
+      if (text.trim()) {
+        last = out.length - 1;
+        lastIsSemicolon = semicolon;
+      }
+    };
+
+    for (let i = 0; i < line.length; i++) {
+      const ch = line[i];
+      const top = stack[stack.length - 1];
+
+      if (inComment) {
+        const end = line.indexOf('*/', i);
+        if (end === -1) { out += line.slice(i); break; }
+        out += line.slice(i, end + 2);
+        i = end + 1;
+        inComment = false;
+      } else if (top.template) {
+        if (ch === '\\') { code(line.slice(i, i + 2)); i++; }
+        else if (ch === '`') { code(ch); stack.pop(); }
+        else if (ch === '$' && line[i + 1] === '{') { code('${'); i++; stack.push({ template: false, braces: 0 }); }
+        else code(ch);
+      } else if (ch === '/' && line[i + 1] === '/') {
+        out += line.slice(i);
+        break;
+      } else if (ch === '/' && line[i + 1] === '*') {
+        out += '/*';
+        i++;
+        inComment = true;
+      } else if (ch === '`') {
+        code(ch);
+        stack.push({ template: true, braces: 0 });
+      } else if (ch === '{' && /[\w-]=$/.test(out) && jsonExpressionEnd(line, i) !== -1) {
+        const end = jsonExpressionEnd(line, i);
+        code(line.slice(i, end));
+        i = end - 1;
+      } else if (ch === '{') {
+        top.braces++;
+        code(ch);
+      } else if (ch === '}') {
+        if (top.braces === 0 && stack.length > 1) stack.pop();
+        else top.braces--;
+        code(ch);
+      } else if ((ch === "'" || ch === '"') && inJsxText(out, line.slice(i))) {
+        // Copy the text up to the next tag or expression
+        const end = line.slice(i).search(/[<{]/);
+        if (end === -1) { code(line.slice(i)); break; }
+        code(line.slice(i, i + end));
+        i += end - 1;
+      } else if (ch === "'" || ch === '"') {
+        const end = stringEnd(line, i);
+        // An unclosed quote is JSX text such as an apostrophe; leave the rest of the line
+        if (end === -1) { code(line.slice(i)); break; }
+        const literal = line.slice(i, end);
+        const jsxAttribute = ch === '"' && /[\w-]=$/.test(out);
+        code(jsxAttribute ? literal : requote(literal, quote));
+        i = end - 1;
+      } else if (ch === '/' && line[i + 1] !== '>' && REGEX_PREFIX.test(out.trimEnd() || ';')) {
+        const end = regexEnd(line, i);
+        if (end === -1) { code(ch); continue; }
+        code(line.slice(i, end));
+        i = end - 1;
+      } else {
+        code(ch, ch === ';');
+      }
+    }
+
+    if (reindent && style.indent !== DEFAULT_CODE_STYLE.indent) {
+      const leading = out.length - out.trimStart().length;
+      const width = Math.floor(leading / 2) * style.indent + (leading % 2);
+      out = ' '.repeat(width) + out.slice(leading);
+      if (last !== -1) last += width - leading;
+    }
+    // Only statements that end the line outside a template literal lose their semicolon
+    const dropSemicolon = !style.semicolons && lastIsSemicolon && !stack[stack.length - 1].template;
+    lines.push(out);
+    semicolonAt.push(dropSemicolon ? last : -1);
+  }
+
+  return lines.map((line, index) => {
+    const at = semicolonAt[index];
+    if (at === -1) return line;
+    const next = lines.slice(index + 1).find(l => l.trim() && !/^(\/\/|\/\*|\*)/.test(l.trim()));
+    if (next && ASI_HAZARD.test(next.trim())) return line;
+    return line.slice(0, at) + line.slice(at + 1);
+  }).join('\n');
+}
+
+/** Apply a code style to every source file in a generated set, adding a matching `.prettierrc` */
+export function styleFiles(files: GeneratedFile[], style: CodeStyle): GeneratedFile[] {
+  if (isDefault(style)) return files;
+  return [
+    ...files.map(file => SOURCE_EXTENSIONS.test(file.path) ? { ...file, content: formatCode(file.content, style) } : file),
+    prettierConfig(style),
+  ];
+}
+
+/** `formatCode` for a file path, leaving anything that isn't JS or TS untouched */
+export function formatFile(filePath: string, content: string, style: CodeStyle): string {
+  return SOURCE_EXTENSIONS.test(filePath) ? formatCode(content, style) : content;
+}
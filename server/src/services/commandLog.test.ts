@@ -0,0 +1,53 @@
+import { test } from 'node:test';
+import assert from 'node:assert/strict';
+import { CommandStack, clearCommandStack, getCommandStack, listCommandLog } from './commandLog.js';
+import { AddBlockCommand, UpdateBlockPropertyCommand } from './commands.js';
+import { useMemoryDb } from './memoryDb.js';
+
+test('CommandStack undoes and redoes an added block', async t => {
+  const db = useMemoryDb(t, { blocks: [{ id: 'root', projectId: 'p1', blockType: 'Container', name: 'Root' }] });
+  const stack = new CommandStack('p1');
+  const add = new AddBlockCommand({ projectId: 'p1', blockType: 'Text', name: 'Title', parentId: 'root' });
+
+  await stack.execute(add);
+  const blockId = add.blockId!;
+  assert.ok(db.block.rows.some(b => b.id === blockId));
+  assert.equal(db.block.rows.find(b => b.id === 'root')!.children, JSON.stringify([blockId]));
+
+  await stack.undo();
+  assert.ok(!db.block.rows.some(b => b.id === blockId));
+  assert.equal(db.block.rows.find(b => b.id === 'root')!.children, '[]');
+  assert.ok(!stack.canUndo && stack.canRedo);
+
+  await stack.redo();
+  assert.ok(db.block.rows.some(b => b.id === blockId && b.name === 'Title'));
+  assert.equal(db.block.rows.find(b => b.id === 'root')!.children, JSON.stringify([blockId]));
+});
+
+test('CommandStack records each mutation in order with its description', async t => {
+  const db = useMemoryDb(t, { blocks: [{ id: 'b1', projectId: 'p1', blockType: 'Text', name: 'Title' }] });
+  const stack = new CommandStack('p1');
+
+  await stack.execute(new UpdateBlockPropertyCommand('p1', 'b1', 'text', 'Hello'));
+  await stack.execute(new AddBlockCommand({ projectId: 'p1', blockType: 'Button', name: 'Buy' }));
+
+  assert.deepEqual(
+    stack.recent().map(e => [e.op_kind, e.description]),
+    [['add_block', 'Add Button block "Buy"'], ['update_block_property', 'Update "text" on block b1']],
+  );
+  assert.equal(db.commandLog.rows.length, 2);
+  const { entries, total } = await listCommandLog('p1');
+  assert.equal(total, 2);
+  assert.deepEqual(entries.map(e => e.description), ['Add Button block "Buy"', 'Update "text" on block b1']);
+});
+
+test('clearCommandStack forgets the history but keeps the log', async t => {
+  useMemoryDb(t, { blocks: [{ id: 'b1', projectId: 'p2', blockType: 'Text', name: 'Title' }] });
+  const stack = getCommandStack('p2');
+  await stack.execute(new UpdateBlockPropertyCommand('p2', 'b1', 'text', 'Hello'));
+
+  clearCommandStack('p2');
+  assert.ok(!stack.canUndo);
+  assert.equal(await stack.undo(), null);
+  assert.equal(stack.recent().length, 1);
+});
@@ -0,0 +1,123 @@
+import { randomUUID } from 'crypto';
+import { getCommandDb, type Command } from './commands.js';
+
+const MAX_HISTORY = 100;
+const MAX_LOG_ENTRIES = 200;
+
+export interface CommandLogEntry {
+  id: string;
+  description: string;
+  timestamp: string;
+  op_kind: string;
+}
+
+/**
+ * Undo/redo history for one project. Executing a new command clears the redo side.
+ */
+export class CommandStack {
+  private done: Command[] = [];
+  private undone: Command[] = [];
+  private entries: CommandLogEntry[] = [];
+  private projectId: string;
+
+  constructor(projectId: string) {
+    this.projectId = projectId;
+  }
+
+  public async execute(command: Command) {
+    await command.execute();
+    this.done.push(command);
+    if (this.done.length > MAX_HISTORY) this.done.shift();
+    this.undone = [];
+    await this.record(command.description, command.opKind);
+  }
+
+  public async undo(): Promise<Command | null> {
+    const command = this.done.pop();
+    if (!command) return null;
+    await command.undo();
+    this.undone.push(command);
+    await this.record(`Undo: ${command.description}`, 'undo');
+    return command;
+  }
+
+  public async redo(): Promise<Command | null> {
+    const command = this.undone.pop();
+    if (!command) return null;
+    await command.execute();
+    this.done.push(command);
+    await this.record(`Redo: ${command.description}`, 'redo');
+    return command;
+  }
+
+  /** Most recent entries first, from the capped in-memory log */
+  public recent(limit = 50): CommandLogEntry[] {
+    return this.entries.slice(-limit).reverse();
+  }
+
+  private async record(description: string, opKind: string) {
+    const entry: CommandLogEntry = { id: randomUUID(), description, timestamp: new Date().toISOString(), op_kind: opKind };
+    this.entries.push(entry);
+    if (this.entries.length > MAX_LOG_ENTRIES) this.entries.shift();
+
+    // The audit trail must not fail the mutation itself
+    try {
+      await getCommandDb().commandLog.create({ data: { projectId: this.projectId, description, opKind } });
+    } catch (error) {
+      console.error('[CommandLog] Failed to persist entry:', error);
+    }
+  }
+
+  /** Forget the history, e.g. after a write the commands don't know about */
+  public clear() {
+    this.done = [];
+    this.undone = [];
+  }
+
+  public get canUndo() {
+    return this.done.length > 0;
+  }
+
+  public get canRedo() {
+    return this.undone.length > 0;
+  }
+}
+
+const stacks = new Map<string, CommandStack>();
+
+export function getCommandStack(projectId: string): CommandStack {
+  let stack = stacks.get(projectId);
+  if (!stack) {
+    stack = new CommandStack(projectId);
+    stacks.set(projectId, stack);
+  }
+  return stack;
+}
+
+export function clearCommandStack(projectId: string) {
+  stacks.get(projectId)?.clear();
+}
+
+/**
+ * Page through the persisted audit trail, newest first
+ */
+export async function listCommandLog(projectId: string, page = 1, limit = 50) {
+  const [rows, total] = await Promise.all([
+    getCommandDb().commandLog.findMany({
+      where: { projectId },
+      // Entries written in the same millisecond fall back to id order
+      orderBy: [{ createdAt: 'desc' }, { id: 'desc' }],
+      skip: (page - 1) * limit,
+      take: limit,
+    }),
+    getCommandDb().commandLog.count({ where: { projectId } }),
+  ]);
+
+  const entries: CommandLogEntry[] = rows.map(r => ({
+    id: r.id,
+    description: r.description,
+    timestamp: r.createdAt.toISOString(),
+    op_kind: r.opKind,
+  }));
+  return { entries, page, limit, total };
+}
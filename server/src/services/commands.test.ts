@@ -0,0 +1,90 @@
+import { test } from 'node:test';
+import assert from 'node:assert/strict';
+import { DuplicateBlockCommand, ExtractComponentCommand, MoveBlockCommand, movesUnderItself, reorderChildren } from './commands.js';
+import { useMemoryDb } from './memoryDb.js';
+
+/** A page root `root` holding `a` (with child `a1`), `b` and `c` */
+const tree = () => ({
+  blocks: [
+    { id: 'root', projectId: 'p1', pageId: 'page', blockType: 'Container', name: 'Root', children: '["a","b","c"]' },
+    { id: 'a', projectId: 'p1', pageId: 'page', parentId: 'root', blockType: 'Card', name: 'A', children: '["a1"]', order: 0 },
+    { id: 'a1', projectId: 'p1', pageId: 'page', parentId: 'a', blockType: 'Text', name: 'A1' },
+    { id: 'b', projectId: 'p1', pageId: 'page', parentId: 'root', blockType: 'Text', name: 'B', order: 1 },
+    { id: 'c', projectId: 'p1', pageId: 'page', parentId: 'root', blockType: 'Text', name: 'C', order: 2 },
+  ],
+});
+
+test('reorderChildren clamps the index and accounts for the block\'s old slot', () => {
+  assert.deepEqual(reorderChildren(['a', 'b', 'c'], 'd', 99), ['a', 'b', 'c', 'd']);
+  assert.deepEqual(reorderChildren(['a', 'b', 'c'], 'd', -5), ['d', 'a', 'b', 'c']);
+  assert.deepEqual(reorderChildren(['a', 'b', 'c'], 'a', 99), ['b', 'c', 'a']);
+  assert.deepEqual(reorderChildren(['a', 'b', 'c'], 'a', 2), ['b', 'a', 'c']);
+  assert.deepEqual(reorderChildren(['a', 'b', 'c'], 'c', 0), ['c', 'a', 'b']);
+});
+
+test('MoveBlockCommand reorders within the same parent and undoes it', async t => {
+  const db = useMemoryDb(t, tree());
+  const children = () => db.block.rows.find(b => b.id === 'root')!.children;
+  const move = new MoveBlockCommand('p1', 'a', 'root', 10);
+
+  await move.execute();
+  assert.equal(children(), '["b","c","a"]');
+  assert.deepEqual(['b', 'c', 'a'].map(id => db.block.rows.find(b => b.id === id)!.order), [0, 1, 2]);
+
+  await move.undo();
+  assert.equal(children(), '["a","b","c"]');
+});
+
+test('MoveBlockCommand rejects moving a block under its own descendant', async t => {
+  const db = useMemoryDb(t, tree());
+
+  assert.equal(await movesUnderItself('p1', 'a', 'a1'), true);
+  assert.equal(await movesUnderItself('p1', 'a', 'a'), true);
+  assert.equal(await movesUnderItself('p1', 'a', 'b'), false);
+  await assert.rejects(new MoveBlockCommand('p1', 'a', 'a1', 0).execute(), /cannot be moved into itself/);
+  assert.equal(db.block.rows.find(b => b.id === 'a')!.parentId, 'root');
+  assert.equal(db.block.rows.find(b => b.id === 'a1')!.children, '[]');
+});
+
+test('DuplicateBlockCommand copies the subtree with new ids right after the original', async t => {
+  const db = useMemoryDb(t, tree());
+  const row = (id: string) => db.block.rows.find(b => b.id === id)!;
+  const duplicate = new DuplicateBlockCommand('p1', 'a');
+
+  await duplicate.execute();
+  const copyId = duplicate.rootId!;
+  assert.equal(row('root').children, JSON.stringify(['a', copyId, 'b', 'c']));
+
+  const copy = row(copyId);
+  const [childId] = JSON.parse(copy.children) as string[];
+  assert.ok(childId && !['a', 'a1'].includes(copyId) && !['a', 'a1', copyId].includes(childId));
+  assert.deepEqual([copy.blockType, copy.name, copy.parentId], ['Card', 'A', 'root']);
+  assert.deepEqual([row(childId!).blockType, row(childId!).name, row(childId!).parentId], ['Text', 'A1', copyId]);
+  assert.equal(row('a').children, '["a1"]');
+  assert.equal(db.block.rows.length, 7);
+
+  await duplicate.undo();
+  assert.equal(db.block.rows.length, 5);
+  assert.equal(row('root').children, '["a","b","c"]');
+});
+
+test('ExtractComponentCommand moves the subtree into a component and leaves an instance in its place', async t => {
+  const db = useMemoryDb(t, tree());
+  const row = (id: string) => db.block.rows.find(b => b.id === id)!;
+  const extract = new ExtractComponentCommand('p1', 'a', 'ProfileCard');
+
+  await extract.execute();
+  const component = row(extract.componentId);
+  assert.deepEqual([component.blockType, component.name, component.pageId, component.children], ['component', 'ProfileCard', null, '["a"]']);
+  assert.deepEqual([row('a').parentId, row('a').pageId, row('a1').pageId], [extract.componentId, null, null]);
+
+  const instance = row(extract.instanceId);
+  assert.deepEqual([instance.blockType, instance.parentId, instance.pageId], ['instance', 'root', 'page']);
+  assert.equal(JSON.parse(instance.properties).component_id, extract.componentId);
+  assert.equal(row('root').children, JSON.stringify([extract.instanceId, 'b', 'c']));
+
+  await extract.undo();
+  assert.equal(row('root').children, '["a","b","c"]');
+  assert.deepEqual([row('a').parentId, row('a').pageId], ['root', 'page']);
+  assert.equal(db.block.rows.length, 5);
+});
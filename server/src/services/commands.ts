@@ -0,0 +1,498 @@
+import { ObjectId } from 'mongodb';
+import type { Block } from '@prisma/client';
+import prisma from '../lib/prisma.js';
+import { pageRootIds, parseJsonValue } from './projectSchema.js';
+
+/** The collections commands, page syncs and model creation use; the Prisma client unless a test installs its own */
+export type CommandDb = Pick<typeof prisma, 'block' | 'page' | 'variable' | 'dataModel' | 'commandLog'>;
+
+let db: CommandDb = prisma;
+
+export function getCommandDb(): CommandDb {
+  return db;
+}
+
+export function setCommandDb(next: CommandDb) {
+  db = next;
+}
+
+/**
+ * A reversible project mutation. `execute` is also used for redo, so it must be
+ * safe to call again after `undo`.
+ */
+export interface Command {
+  readonly description: string;
+  readonly opKind: string;
+  readonly projectId: string;
+  execute(): Promise<void>;
+  undo(): Promise<void>;
+}
+
+async function setParentChildren(parentId: string, update: (children: string[]) => string[]) {
+  const parent = await db.block.findUnique({ where: { id: parentId } });
+  if (!parent) return;
+  const children = update(parseJsonValue<string[]>(parent.children, []));
+  await db.block.update({ where: { id: parentId }, data: { children: JSON.stringify(children) } });
+}
+
+export interface AddBlockInput {
+  projectId: string;
+  blockType: string;
+  name: string;
+  parentId?: string | null;
+  pageId?: string | null; // internal page id (Page.idRoot)
+  properties?: Record<string, unknown>;
+}
+
+export class AddBlockCommand implements Command {
+  public readonly opKind = 'add_block';
+  public readonly description: string;
+  public readonly projectId: string;
+  public blockId: string | null = null;
+  private input: AddBlockInput;
+
+  constructor(input: AddBlockInput) {
+    this.input = input;
+    this.projectId = input.projectId;
+    this.description = `Add ${input.blockType} block "${input.name}"`;
+  }
+
+  async execute() {
+    const { projectId, blockType, name, parentId, pageId, properties } = this.input;
+    const order = parentId
+      ? parseJsonValue<string[]>((await db.block.findUnique({ where: { id: parentId } }))?.children, []).length
+      : 0;
+
+    const block = await db.block.create({
+      data: {
+        // Reuse the original id on redo so later commands still point at this block
+        ...(this.blockId && { id: this.blockId }),
+        projectId,
+        pageId: pageId || null,
+        parentId: parentId || null,
+        blockType,
+        name,
+        properties: JSON.stringify(properties || {}),
+        order,
+      }
+    });
+    this.blockId = block.id;
+
+    if (parentId) {
+      await setParentChildren(parentId, children => [...children.filter(id => id !== block.id), block.id]);
+    }
+  }
+
+  async undo() {
+    if (!this.blockId) return;
+    const blockId = this.blockId;
+    await db.block.delete({ where: { id: blockId } });
+    if (this.input.parentId) {
+      await setParentChildren(this.input.parentId, children => children.filter(id => id !== blockId));
+    }
+  }
+}
+
+/**
+ * Set `properties[key]`; `events.<name>` and `bindings.<name>` target those maps instead.
+ */
+export class UpdateBlockPropertyCommand implements Command {
+  public readonly opKind = 'update_block_property';
+  public readonly description: string;
+  public readonly projectId: string;
+  private blockId: string;
+  private property: string;
+  private value: unknown;
+  private previous: unknown = undefined;
+
+  constructor(projectId: string, blockId: string, property: string, value: unknown) {
+    this.projectId = projectId;
+    this.blockId = blockId;
+    this.property = property;
+    this.value = value;
+    this.description = `Update "${property}" on block ${blockId}`;
+  }
+
+  private target(): { column: 'properties' | 'events' | 'bindings'; key: string } {
+    const [head, ...rest] = this.property.split('.');
+    if ((head === 'events' || head === 'bindings') && rest.length) {
+      return { column: head, key: rest.join('.') };
+    }
+    return { column: 'properties', key: this.property };
+  }
+
+  private async write(value: unknown, capturePrevious: boolean) {
+    const block = await db.block.findUnique({ where: { id: this.blockId } });
+    if (!block) throw new Error(`Block ${this.blockId} not found`);
+
+    const { column, key } = this.target();
+
+    if (column === 'events') {
+      // Events are stored as an array of { event, logic_flow_id }
+      const handlers = parseJsonValue<Array<{ event: string; logic_flow_id: string }>>(block.events, []);
+      const list = Array.isArray(handlers) ? handlers : [];
+      if (capturePrevious) this.previous = list.find(h => h.event === key)?.logic_flow_id;
+
+      const next = list.filter(h => h.event !== key);
+      if (value !== undefined && value !== null) next.push({ event: key, logic_flow_id: String(value) });
+      await db.block.update({ where: { id: this.blockId }, data: { events: JSON.stringify(next) } });
+      return;
+    }
+
+    const map = parseJsonValue<Record<string, unknown>>(block[column], {});
+    if (capturePrevious) this.previous = map[key];
+
+    if (value === undefined) delete map[key];
+    else map[key] = value;
+
+    await db.block.update({ where: { id: this.blockId }, data: { [column]: JSON.stringify(map) } });
+  }
+
+  async execute() {
+    await this.write(this.value, true);
+  }
+
+  async undo() {
+    await this.write(this.previous, false);
+  }
+}
+
+/**
+ * Place `blockId` in `children` at `index`, clamped to `[0, children.length]`. A block already
+ * in the list is removed first; if it sat before the target the index shifts down by one so it
+ * lands where the caller pointed.
+ */
+export function reorderChildren(children: string[], blockId: string, index: number): string[] {
+  const current = children.indexOf(blockId);
+  const next = children.filter(id => id !== blockId);
+  let target = Math.max(0, Math.min(Math.trunc(index) || 0, children.length));
+  if (current !== -1 && current < target) target -= 1;
+  next.splice(target, 0, blockId);
+  return next;
+}
+
+export interface SiblingList {
+  parentId: string | null;
+  ids: string[];
+}
+
+/** Children of a parent block, or the page's root blocks in `order` when there is no parent */
+export async function siblingIds(projectId: string, parentId: string | null, pageId: string | null): Promise<string[]> {
+  if (parentId) {
+    const parent = await db.block.findUnique({ where: { id: parentId } });
+    if (!parent) throw new Error(`Block ${parentId} not found`);
+    return parseJsonValue<string[]>(parent.children, []);
+  }
+  const roots = await db.block.findMany({
+    where: { projectId, pageId, parentId: null, archived: false },
+    orderBy: { order: 'asc' },
+    select: { id: true },
+  });
+  return roots.map(b => b.id);
+}
+
+export async function writeSiblings({ parentId, ids }: SiblingList) {
+  if (parentId) {
+    await db.block.update({ where: { id: parentId }, data: { children: JSON.stringify(ids) } });
+  }
+  for (const [order, id] of ids.entries()) {
+    await db.block.update({ where: { id }, data: { parentId, order } });
+  }
+}
+
+/** Whether `parentId` is the block itself or one of its descendants, which would detach the subtree into a cycle */
+export async function movesUnderItself(projectId: string, blockId: string, parentId: string | null): Promise<boolean> {
+  if (!parentId) return false;
+  if (parentId === blockId) return true;
+  const blocks = new Map((await db.block.findMany({ where: { projectId } })).map(b => [b.id, b]));
+  return subtreeIds(blocks, blockId).includes(parentId);
+}
+
+export class MoveBlockCommand implements Command {
+  public readonly opKind = 'move_block';
+  public readonly description: string;
+  public readonly projectId: string;
+  private blockId: string;
+  private newParentId: string | null;
+  private index: number;
+  private before: SiblingList[] = [];
+
+  constructor(projectId: string, blockId: string, newParentId: string | null, index: number) {
+    this.projectId = projectId;
+    this.blockId = blockId;
+    this.newParentId = newParentId;
+    this.index = index;
+    this.description = `Move block ${blockId}`;
+  }
+
+  async execute() {
+    const block = await db.block.findUnique({ where: { id: this.blockId } });
+    if (!block) throw new Error(`Block ${this.blockId} not found`);
+    if (await movesUnderItself(this.projectId, this.blockId, this.newParentId)) {
+      throw new Error('A block cannot be moved into itself or one of its descendants');
+    }
+
+    const oldParentId = block.parentId || null;
+    const oldSiblings = await siblingIds(this.projectId, oldParentId, block.pageId);
+    this.before = [{ parentId: oldParentId, ids: oldSiblings }];
+
+    if (oldParentId === this.newParentId) {
+      await writeSiblings({ parentId: oldParentId, ids: reorderChildren(oldSiblings, this.blockId, this.index) });
+      return;
+    }
+
+    const newSiblings = await siblingIds(this.projectId, this.newParentId, block.pageId);
+    this.before.push({ parentId: this.newParentId, ids: newSiblings });
+
+    await writeSiblings({ parentId: oldParentId, ids: oldSiblings.filter(id => id !== this.blockId) });
+    await writeSiblings({ parentId: this.newParentId, ids: reorderChildren(newSiblings, this.blockId, this.index) });
+  }
+
+  async undo() {
+    // Restore the destination first so the source list wins for the moved block's parentId
+    for (const list of [...this.before].reverse()) {
+      await writeSiblings(list);
+    }
+  }
+}
+
+/**
+ * Copy the subtree under `rootId` with fresh ids, root first. `children` and `parentId` are
+ * rewritten to point at the copies and the copied root keeps the original's parent. Ids
+ * already in `idMap` are reused, so running it again (redo) recreates the same blocks.
+ */
+export function cloneBlockTree(blocks: Map<string, Block>, rootId: string, idMap: Map<string, string>): Block[] {
+  const clones: Block[] = [];
+  const newId = (id: string) => {
+    if (!idMap.has(id)) idMap.set(id, new ObjectId().toHexString());
+    return idMap.get(id) as string;
+  };
+
+  // A block listed under two parents, or under its own descendant, is only copied once
+  const claimed = new Set([rootId]);
+  const visit = (id: string, parentId: string | null) => {
+    const block = blocks.get(id);
+    if (!block) return;
+    const children = parseJsonValue<string[]>(block.children, []).filter(child => {
+      if (!blocks.has(child) || claimed.has(child)) return false;
+      claimed.add(child);
+      return true;
+    });
+    const clone: Block = {
+      ...block,
+      id: newId(id),
+      parentId,
+      children: JSON.stringify(children.map(newId)),
+    };
+    clones.push(clone);
+    for (const child of children) visit(child, clone.id);
+  };
+
+  visit(rootId, blocks.get(rootId)?.parentId || null);
+  return clones;
+}
+
+/**
+ * Deep-copy a block and its descendants and insert the copy right after the original.
+ */
+export class DuplicateBlockCommand implements Command {
+  public readonly opKind = 'duplicate_block';
+  public readonly description: string;
+  public readonly projectId: string;
+  public rootId: string | null = null;
+  private blockId: string;
+  private idMap = new Map<string, string>();
+  private before: SiblingList | null = null;
+
+  constructor(projectId: string, blockId: string) {
+    this.projectId = projectId;
+    this.blockId = blockId;
+    this.description = `Duplicate block ${blockId}`;
+  }
+
+  async execute() {
+    const source = await db.block.findUnique({ where: { id: this.blockId } });
+    if (!source) throw new Error(`Block ${this.blockId} not found`);
+
+    const parentId = source.parentId || null;
+    // Read the siblings before the copy exists so it can't tie with the original on `order`
+    const siblings = await siblingIds(this.projectId, parentId, source.pageId);
+    this.before = { parentId, ids: siblings };
+
+    const rows = await db.block.findMany({ where: { projectId: this.projectId, archived: false } });
+    const clones = cloneBlockTree(new Map(rows.map(b => [b.id, b])), this.blockId, this.idMap);
+    for (const clone of clones) {
+      await db.block.create({ data: clone });
+    }
+    this.rootId = clones[0].id;
+
+    const index = siblings.indexOf(this.blockId);
+    await writeSiblings({ parentId, ids: reorderChildren(siblings, this.rootId, index === -1 ? siblings.length : index + 1) });
+  }
+
+  async undo() {
+    await db.block.deleteMany({ where: { id: { in: [...this.idMap.values()] } } });
+    if (this.before) await writeSiblings(this.before);
+  }
+}
+
+/** The block and every descendant reachable through `children`, root first */
+export function subtreeIds(blocks: Map<string, Block>, rootId: string): string[] {
+  const ids: string[] = [];
+  const visit = (id: string) => {
+    if (!blocks.has(id) || ids.includes(id)) return;
+    ids.push(id);
+    for (const child of parseJsonValue<string[]>(blocks.get(id)?.children, [])) visit(child);
+  };
+  visit(rootId);
+  return ids;
+}
+
+/**
+ * Turn a block subtree into a reusable component. The subtree is re-parented under a new
+ * `component` block (components live outside pages) and an `instance` block pointing at it
+ * via `properties.component_id` takes the original's place.
+ */
+export class ExtractComponentCommand implements Command {
+  public readonly opKind = 'extract_component';
+  public readonly description: string;
+  public readonly projectId: string;
+  public readonly componentId = new ObjectId().toHexString();
+  public readonly instanceId = new ObjectId().toHexString();
+  private blockId: string;
+  private name: string;
+  private before: SiblingList | null = null;
+  private pageIds = new Map<string, string | null>();
+
+  constructor(projectId: string, blockId: string, name: string) {
+    this.projectId = projectId;
+    this.blockId = blockId;
+    this.name = name;
+    this.description = `Extract block ${blockId} into component "${name}"`;
+  }
+
+  async execute() {
+    const source = await db.block.findUnique({ where: { id: this.blockId } });
+    if (!source) throw new Error(`Block ${this.blockId} not found`);
+    if (source.blockType === 'component') throw new Error(`Block ${this.blockId} is already a component`);
+
+    const parentId = source.parentId || null;
+    const siblings = await siblingIds(this.projectId, parentId, source.pageId);
+    this.before = { parentId, ids: siblings };
+
+    const rows = await db.block.findMany({ where: { projectId: this.projectId, archived: false } });
+    const blocks = new Map(rows.map(b => [b.id, b]));
+    const subtree = subtreeIds(blocks, this.blockId);
+    this.pageIds = new Map(subtree.map(id => [id, blocks.get(id)?.pageId || null]));
+
+    await db.block.create({
+      data: { id: this.componentId, projectId: this.projectId, blockType: 'component', name: this.name, children: JSON.stringify([this.blockId]) },
+    });
+    await db.block.create({
+      data: {
+        id: this.instanceId,
+        projectId: this.projectId,
+        pageId: source.pageId,
+        parentId,
+        blockType: 'instance',
+        name: this.name,
+        properties: JSON.stringify({ component_id: this.componentId }),
+        order: source.order,
+      },
+    });
+
+    await db.block.updateMany({ where: { id: { in: subtree } }, data: { pageId: null } });
+    await writeSiblings({ parentId: this.componentId, ids: [this.blockId] });
+    await writeSiblings({ parentId, ids: siblings.map(id => (id === this.blockId ? this.instanceId : id)) });
+  }
+
+  async undo() {
+    for (const [id, pageId] of this.pageIds) {
+      await db.block.update({ where: { id }, data: { pageId } });
+    }
+    if (this.before) await writeSiblings(this.before);
+    await db.block.deleteMany({ where: { id: { in: [this.instanceId, this.componentId] } } });
+  }
+}
+
+export interface BulkBlockResult {
+  id: string;
+  success: boolean;
+  error?: string;
+}
+
+/**
+ * Archive or delete many blocks, each with its descendants, as one undoable step. An id that
+ * isn't a block of the project, or a page's root block when deleting, fails on its own
+ * without stopping the rest. Removed blocks leave their parent's `children`; archived ones
+ * can be restored from the trash.
+ */
+export class RemoveBlocksCommand implements Command {
+  public readonly opKind: string;
+  public readonly description: string;
+  public readonly projectId: string;
+  public results: BulkBlockResult[] = [];
+  private blockIds: string[];
+  private mode: 'archive' | 'delete';
+  private removed: Block[] = [];
+  private parentChildren = new Map<string, string[]>();
+
+  constructor(projectId: string, blockIds: string[], mode: 'archive' | 'delete') {
+    this.projectId = projectId;
+    this.blockIds = blockIds;
+    this.mode = mode;
+    this.opKind = `${mode}_blocks`;
+    this.description = `${mode === 'archive' ? 'Archive' : 'Delete'} ${blockIds.length} block${blockIds.length === 1 ? '' : 's'}`;
+  }
+
+  async execute() {
+    const rows = await db.block.findMany({ where: { projectId: this.projectId } });
+    const blocks = new Map(rows.map(b => [b.id, b]));
+    const removed = new Set<string>();
+    // A page's meta keeps pointing at its root blocks, so those can only be archived
+    const pageRoots = new Set(this.mode === 'delete'
+      ? (await db.page.findMany({ where: { projectId: this.projectId }, select: { meta: true } }))
+        .flatMap(page => pageRootIds(parseJsonValue<Record<string, any>>(page.meta, {})))
+      : []);
+
+    this.results = this.blockIds.map(id => {
+      const block = blocks.get(id);
+      if (!block) return { id, success: false, error: 'Block not found' };
+      if (this.mode === 'archive' && block.archived) return { id, success: false, error: 'Block is already archived' };
+      if (pageRoots.has(id)) return { id, success: false, error: 'A page\'s root block cannot be deleted' };
+      for (const blockId of subtreeIds(blocks, id)) removed.add(blockId);
+      return { id, success: true };
+    });
+
+    // Descendants trashed earlier stay archived on undo
+    this.removed = [...removed].map(id => blocks.get(id) as Block).filter(b => this.mode === 'delete' || !b.archived);
+    this.parentChildren = new Map();
+    for (const block of this.removed) {
+      const parent = block.parentId ? blocks.get(block.parentId) : undefined;
+      if (parent && !removed.has(parent.id)) this.parentChildren.set(parent.id, parseJsonValue<string[]>(parent.children, []));
+    }
+
+    const ids = this.removed.map(b => b.id);
+    if (this.mode === 'archive') {
+      await db.block.updateMany({ where: { id: { in: ids } }, data: { archived: true } });
+    } else {
+      await db.block.deleteMany({ where: { id: { in: ids } } });
+    }
+    for (const [parentId, children] of this.parentChildren) {
+      await db.block.update({ where: { id: parentId }, data: { children: JSON.stringify(children.filter(id => !removed.has(id))) } });
+    }
+  }
+
+  async undo() {
+    if (this.mode === 'archive') {
+      await db.block.updateMany({ where: { id: { in: this.removed.map(b => b.id) } }, data: { archived: false } });
+    } else {
+      for (const block of this.removed) {
+        await db.block.create({ data: block });
+      }
+    }
+    for (const [parentId, children] of this.parentChildren) {
+      await db.block.update({ where: { id: parentId }, data: { children: JSON.stringify(children) } });
+    }
+  }
+}
@@ -0,0 +1,26 @@
+import { test } from 'node:test';
+import assert from 'node:assert/strict';
+import { ComposeGeneratorService } from './composeGenerator.js';
+import type { ProjectSchema } from './projectSchema.js';
+
+const project = (overrides: Record<string, unknown> = {}) =>
+  ({ id: 'p', name: 'My Shop', settings: {}, data_models: [], ...overrides }) as unknown as ProjectSchema;
+
+test('compose builds the api and frontend from the bundle directories', () => {
+  const { path, content } = new ComposeGeneratorService(project()).generate();
+  assert.equal(path, 'docker-compose.yml');
+  assert.match(content, /build: \.\/backend/);
+  assert.match(content, /context: \.\/frontend/);
+  assert.match(content, /POSTGRES_DB: my_shop/);
+  assert.doesNotMatch(content, /JWT_SECRET/);
+});
+
+test('compose requires JWT_SECRET instead of defaulting it when the backend has auth', () => {
+  const owned = project({ data_models: [{ id: 'm', name: 'Note', owner_field: ' userId ', fields: [], relations: [] }] });
+  const { content } = new ComposeGeneratorService(owned).generate();
+  assert.match(content, /JWT_SECRET: \$\{JWT_SECRET:\?JWT_SECRET must be set\}/);
+  assert.doesNotMatch(content, /change-me/);
+
+  const blank = project({ data_models: [{ id: 'm', name: 'Note', owner_field: '  ', fields: [], relations: [] }] });
+  assert.doesNotMatch(new ComposeGeneratorService(blank).generate().content, /JWT_SECRET/);
+});
@@ -0,0 +1,82 @@
+import { ownerField } from './backendGenerator.js';
+import { apiPrefix, type GeneratedFile, type ProjectSchema } from './projectSchema.js';
+
+const API_PORT = 3000;
+const FRONTEND_PORT = 8080;
+
+/**
+ * A root `docker-compose.yml` running the whole stack: Postgres (`db`), the generated backend
+ * (`api`, built from `./backend`) and the frontend (`frontend`, built from `./frontend`), on
+ * one network. The api waits for the database to accept connections. Those directories are
+ * the layout of the downloadable bundle (`bundle.ts`), whose root the file is written to.
+ */
+export class ComposeGeneratorService {
+  private project: ProjectSchema;
+
+  constructor(project: ProjectSchema) {
+    this.project = project;
+  }
+
+  public generate(): GeneratedFile {
+    const database = this.project.name.toLowerCase().replace(/[^a-z0-9]+/g, '_').replace(/^_+|_+$/g, '') || 'app';
+    const prefix = apiPrefix(this.project);
+    // The SPA runs in the browser, which reaches the api through its published port
+    const apiUrl = `http://localhost:${API_PORT}${prefix ? `/${prefix}` : ''}`;
+    // Same condition the backend generator uses to add its auth module
+    const auth = this.project.settings.auth?.enabled === true || this.project.data_models.some(m => ownerField(m));
+
+    return {
+      path: 'docker-compose.yml',
+      content: `# Run from the root of the generated project bundle, next to backend/ and frontend/${auth ? '\n# JWT_SECRET must be set, e.g. JWT_SECRET=$(openssl rand -hex 32) docker compose up' : ''}
+services:
+  db:
+    image: postgres:16-alpine
+    environment:
+      POSTGRES_USER: app
+      POSTGRES_PASSWORD: app
+      POSTGRES_DB: ${database}
+    volumes:
+      - db-data:/var/lib/postgresql/data
+    healthcheck:
+      test: ["CMD-SHELL", "pg_isready -U app -d ${database}"]
+      interval: 5s
+      timeout: 5s
+      retries: 10
+    networks:
+      - app
+
+  api:
+    build: ./backend
+    environment:
+      DATABASE_URL: postgresql://app:app@db:5432/${database}
+      PORT: "${API_PORT}"
+      CORS_ORIGINS: http://localhost:${FRONTEND_PORT}
+${auth ? `      JWT_SECRET: \${JWT_SECRET:?JWT_SECRET must be set}\n` : ''}    ports:
+      - "${API_PORT}:${API_PORT}"
+    depends_on:
+      db:
+        condition: service_healthy
+    networks:
+      - app
+
+  frontend:
+    build:
+      context: ./frontend
+      args:
+        VITE_API_URL: ${apiUrl}
+    ports:
+      - "${FRONTEND_PORT}:80"
+    depends_on:
+      - api
+    networks:
+      - app
+
+networks:
+  app: {}
+
+volumes:
+  db-data: {}
+`,
+    };
+  }
+}
@@ -0,0 +1,68 @@
+import { test } from 'node:test';
+import assert from 'node:assert/strict';
+import { DatabaseGeneratorService } from './databaseGenerator.js';
+import type { DataModelSchema, FieldSchema, ProjectSchema } from './projectSchema.js';
+
+const field = (name: string, overrides: Partial<FieldSchema> = {}): FieldSchema => ({
+  id: name, name, field_type: 'string', required: false, unique: false, primary_key: false, ...overrides,
+});
+
+const model = (name: string, overrides: Partial<DataModelSchema> = {}): DataModelSchema => ({
+  id: name, name, fields: [field('id', { primary_key: true })], relations: [], timestamps: false, soft_delete: false, archived: false, ...overrides,
+});
+
+const generator = (models: DataModelSchema[]) =>
+  new DatabaseGeneratorService({ name: 'Shop', data_models: models } as unknown as ProjectSchema);
+
+test('snapshot names tables, key and owner columns the way the Prisma schema maps them', () => {
+  const order = model('OrderItem', {
+    fields: [field('id', { primary_key: true }), field('unitPrice', { field_type: 'decimal' })],
+    relations: [{ id: 'r', name: 'product', target_model_id: 'Product', relation_type: 'many_to_one', optional: false }],
+    owner_field: 'ownerId',
+  });
+  const { tables } = generator([order, model('Product')]).snapshot();
+
+  assert.deepEqual(Object.keys(tables), ['order_item', 'product']);
+  assert.deepEqual(tables.order_item!.columns, {
+    id: 'UUID PRIMARY KEY DEFAULT gen_random_uuid()',
+    unit_price: 'DECIMAL(10,2)',
+    owner_id: 'VARCHAR(255) NOT NULL',
+    product_id: 'UUID NOT NULL',
+  });
+  assert.deepEqual(tables.order_item!.indexes, { order_item_owner_id_idx: 'owner_id' });
+  assert.equal(tables.order_item!.foreign_keys.order_item_product_id_fkey!.references, 'product');
+});
+
+test('diff alters only the clauses of a column that changed', () => {
+  const before = generator([model('Item', { fields: [field('status', { default: 'new' }), field('price', { field_type: 'decimal' })] })]).snapshot();
+  const after = generator([model('Item', {
+    fields: [field('status', { required: true, unique: true }), field('price', { field_type: 'decimal', precision: 12, default: '0' })],
+  })]).snapshot();
+  const service = generator([]);
+
+  assert.deepEqual(service.diff(before, after).filter(s => !s.startsWith('--')), [
+    'ALTER TABLE "item" ALTER COLUMN "status" DROP DEFAULT;',
+    'ALTER TABLE "item" ALTER COLUMN "status" SET NOT NULL;',
+    'ALTER TABLE "item" ADD CONSTRAINT "item_status_key" UNIQUE ("status");',
+    'ALTER TABLE "item" ALTER COLUMN "price" TYPE DECIMAL(12,2) USING "price"::DECIMAL(12,2);',
+    'ALTER TABLE "item" ALTER COLUMN "price" SET DEFAULT 0;',
+  ]);
+  assert.deepEqual(service.diff(after, before).filter(s => !s.startsWith('--')), [
+    'ALTER TABLE "item" ALTER COLUMN "status" SET DEFAULT \'new\';',
+    'ALTER TABLE "item" ALTER COLUMN "status" DROP NOT NULL;',
+    'ALTER TABLE "item" DROP CONSTRAINT IF EXISTS "item_status_key";',
+    'ALTER TABLE "item" ALTER COLUMN "price" DROP DEFAULT;',
+    'ALTER TABLE "item" ALTER COLUMN "price" TYPE DECIMAL(10,2) USING "price"::DECIMAL(10,2);',
+  ]);
+});
+
+test('diff replaces an enum column\'s CHECK when its values change', () => {
+  const enumField = (values: string[]) => field('size', { field_type: 'enum', enum_values: values });
+  const before = generator([model('Shirt', { fields: [enumField(['S', 'M'])] })]).snapshot();
+  const after = generator([model('Shirt', { fields: [enumField(['S', 'M', "X'L"])] })]).snapshot();
+
+  assert.deepEqual(generator([]).diff(before, after).filter(s => !s.startsWith('--')), [
+    'ALTER TABLE "shirt" DROP CONSTRAINT IF EXISTS "shirt_size_check";',
+    'ALTER TABLE "shirt" ADD CONSTRAINT "shirt_size_check" CHECK ("size" IN (\'S\', \'M\', \'X\'\'L\'));',
+  ]);
+});
@@ -0,0 +1,287 @@
+import { onDeleteAction, ownerField, resolveForeignKeys } from './backendGenerator.js';
+import { snakeCase } from '../utils/string.js';
+import { decimalDigits, indexedFields, type DataModelSchema, type FieldSchema, type GeneratedFile, type ProjectSchema } from './projectSchema.js';
+
+const SQL_TYPES: Record<string, string> = {
+  string: 'VARCHAR(255)',
+  email: 'VARCHAR(255)',
+  url: 'VARCHAR(2048)',
+  file: 'VARCHAR(2048)',
+  text: 'TEXT',
+  uuid: 'UUID',
+  int: 'INTEGER',
+  float: 'DOUBLE PRECISION',
+  boolean: 'BOOLEAN',
+  datetime: 'TIMESTAMP(3)',
+  date: 'TIMESTAMP(3)',
+  json: 'JSONB',
+};
+
+export interface ForeignKeySnapshot {
+  column: string;
+  references: string;
+  /** SQL delete action; missing in snapshots from before it was configurable, which used SET NULL */
+  on_delete?: string;
+}
+
+const SQL_DELETE_ACTIONS = { Cascade: 'CASCADE', SetNull: 'SET NULL', Restrict: 'RESTRICT' };
+
+const sameForeignKey = (a: ForeignKeySnapshot | undefined, b: ForeignKeySnapshot) =>
+  !!a && a.references === b.references && (a.on_delete || 'SET NULL') === (b.on_delete || 'SET NULL');
+
+export interface TableSnapshot {
+  /** column name → full SQL column definition */
+  columns: Record<string, string>;
+  /** constraint name → column and referenced table */
+  foreign_keys: Record<string, ForeignKeySnapshot>;
+  /** index name → indexed column; missing in snapshots from before indexes were supported */
+  indexes?: Record<string, string>;
+}
+
+/** The database shape a migration set produces; stored so the next run can diff against it */
+export interface SchemaSnapshot {
+  tables: Record<string, TableSnapshot>;
+}
+
+export interface MigrationResult {
+  name: string;
+  files: GeneratedFile[];
+  snapshot: SchemaSnapshot;
+}
+
+function quote(identifier: string): string {
+  return `"${identifier.replace(/"/g, '""')}"`;
+}
+
+interface ColumnClauses {
+  type: string;
+  /** Parenthesised CHECK condition */
+  check: string | null;
+  notNull: boolean;
+  unique: boolean;
+  default: string | null;
+}
+
+/** A column definition as `columnDefinition` writes it, split back into its clauses */
+function columnClauses(definition: string): ColumnClauses {
+  const type = definition.match(/^(DOUBLE PRECISION|[A-Z]+(\(\d+(,\d+)?\))?)/)?.[0] || definition;
+  let rest = definition.slice(type.length);
+
+  let check: string | null = null;
+  if (rest.startsWith(' CHECK (')) {
+    // Scan to the closing parenthesis, skipping quoted enum values
+    let depth = 0;
+    let quoted = false;
+    let end = ' CHECK '.length;
+    for (; end < rest.length; end++) {
+      if (rest[end] === "'") quoted = !quoted;
+      else if (!quoted && rest[end] === '(') depth++;
+      else if (!quoted && rest[end] === ')' && --depth === 0) break;
+    }
+    check = rest.slice(' CHECK '.length, end + 1);
+    rest = rest.slice(end + 1);
+  }
+
+  const [, notNull, unique, value] = rest.match(/^(?: PRIMARY KEY)?( NOT NULL)?( UNIQUE)?(?: DEFAULT (.*))?$/) || [];
+  return { type, check, notNull: !!notNull, unique: !!unique, default: value ?? null };
+}
+
+/** YYYYMMDDHHMMSS, the prefix Prisma and most SQL migration tools sort by */
+export function migrationTimestamp(date: Date = new Date()): string {
+  return date.toISOString().replace(/[-:T]/g, '').slice(0, 14);
+}
+
+/**
+ * Generates versioned PostgreSQL migrations for a project's data models.
+ * Each run diffs the current models against the snapshot left by the previous run,
+ * so only the delta is emitted. Tables and columns are snake_case (`<relation>_id` for keys);
+ * the generated Prisma schema maps its models onto the same names with `@@map` and `@map`.
+ */
+export class DatabaseGeneratorService {
+  private project: ProjectSchema;
+
+  constructor(project: ProjectSchema) {
+    this.project = project;
+  }
+
+  private tableName(model: DataModelSchema): string {
+    return snakeCase(model.name);
+  }
+
+  private columnDefinition(field: FieldSchema): string {
+    if (field.primary_key) return 'UUID PRIMARY KEY DEFAULT gen_random_uuid()';
+
+    const { precision, scale } = decimalDigits(field);
+    const type = field.field_type === 'decimal' ? `DECIMAL(${precision},${scale})` : SQL_TYPES[field.field_type] || 'VARCHAR(255)';
+    const parts = [type];
+    if (field.field_type === 'enum' && field.enum_values?.length) {
+      const values = field.enum_values.map(v => `'${v.replace(/'/g, "''")}'`).join(', ');
+      parts.push(`CHECK (${quote(snakeCase(field.name))} IN (${values}))`);
+    }
+    if (field.required && field.field_type !== 'file') parts.push('NOT NULL');
+    if (field.unique) parts.push('UNIQUE');
+    if (field.default !== undefined && field.default !== '') {
+      const numeric = ['INTEGER', 'DOUBLE PRECISION', 'BOOLEAN'].includes(type) || field.field_type === 'decimal';
+      parts.push(`DEFAULT ${numeric ? field.default : `'${String(field.default).replace(/'/g, "''")}'`}`);
+    }
+    return parts.join(' ');
+  }
+
+  /**
+   * Describe the database the current models call for
+   */
+  public snapshot(): SchemaSnapshot {
+    const tables: Record<string, TableSnapshot> = {};
+
+    for (const model of this.project.data_models) {
+      const columns: Record<string, string> = {};
+      for (const field of model.fields) {
+        columns[snakeCase(field.name)] = this.columnDefinition(field);
+      }
+      if (model.timestamps) {
+        columns.created_at = 'TIMESTAMP(3) NOT NULL DEFAULT CURRENT_TIMESTAMP';
+        columns.updated_at = 'TIMESTAMP(3) NOT NULL DEFAULT CURRENT_TIMESTAMP';
+      }
+      if (model.soft_delete) columns.deleted_at = 'TIMESTAMP(3)';
+      // Owner-scoped models get the owner column even when it isn't one of their fields
+      const owner = ownerField(model);
+      if (owner && !model.fields.some(f => f.name === owner)) columns[snakeCase(owner)] = 'VARCHAR(255) NOT NULL';
+
+      const indexes: Record<string, string> = {};
+      const indexed = indexedFields(model).map(field => snakeCase(field.name));
+      if (owner) indexed.push(snakeCase(owner));
+      for (const column of new Set(indexed)) {
+        indexes[`${this.tableName(model)}_${column}_idx`] = column;
+      }
+      tables[this.tableName(model)] = { columns, foreign_keys: {}, indexes };
+    }
+
+    for (const key of resolveForeignKeys(this.project.data_models)) {
+      const table = tables[this.tableName(key.holder)];
+      const column = `${snakeCase(key.field)}_id`;
+      table.columns[column] = `UUID${key.optional ? '' : ' NOT NULL'}${key.many ? '' : ' UNIQUE'}`;
+      table.foreign_keys[`${this.tableName(key.holder)}_${column}_fkey`] = {
+        column,
+        references: this.tableName(key.target),
+        on_delete: SQL_DELETE_ACTIONS[onDeleteAction(key)],
+      };
+    }
+
+    return { tables };
+  }
+
+  /**
+   * SQL that turns `previous` into `next`. Tables are created before any index or foreign key
+   * is added, so models can reference each other in any order.
+   */
+  public diff(previous: SchemaSnapshot, next: SchemaSnapshot): string[] {
+    const statements: string[] = [];
+    const indexes: string[] = [];
+    const constraints: string[] = [];
+
+    for (const [table, spec] of Object.entries(next.tables)) {
+      const before = previous.tables[table];
+
+      if (!before) {
+        const columns = Object.entries(spec.columns).map(([name, def]) => `  ${quote(name)} ${def}`);
+        statements.push(`CREATE TABLE ${quote(table)} (\n${columns.join(',\n')}\n);`);
+      } else {
+        for (const [name, def] of Object.entries(spec.columns)) {
+          if (!(name in before.columns)) {
+            statements.push(`ALTER TABLE ${quote(table)} ADD COLUMN ${quote(name)} ${def};`);
+          } else if (before.columns[name] !== def) {
+            statements.push(`-- Column definition changed from: ${before.columns[name]}`);
+            statements.push(...this.alterColumn(table, name, columnClauses(before.columns[name]), columnClauses(def)));
+          }
+        }
+        // Removed or changed keys are dropped; changed ones are re-added below
+        for (const [name, fk] of Object.entries(before.foreign_keys)) {
+          if (!spec.foreign_keys[name] || !sameForeignKey(fk, spec.foreign_keys[name])) {
+            statements.push(`ALTER TABLE ${quote(table)} DROP CONSTRAINT ${quote(name)};`);
+          }
+        }
+        // Before the columns go, since dropping a column takes its indexes with it
+        for (const [name, column] of Object.entries(before.indexes || {})) {
+          if (spec.indexes?.[name] !== column) statements.push(`DROP INDEX IF EXISTS ${quote(name)};`);
+        }
+        for (const name of Object.keys(before.columns)) {
+          if (!(name in spec.columns)) {
+            statements.push(`ALTER TABLE ${quote(table)} DROP COLUMN ${quote(name)};`);
+          }
+        }
+      }
+
+      for (const [name, column] of Object.entries(spec.indexes || {})) {
+        if (before?.indexes?.[name] === column) continue;
+        indexes.push(`CREATE INDEX ${quote(name)} ON ${quote(table)}(${quote(column)});`);
+      }
+
+      for (const [name, fk] of Object.entries(spec.foreign_keys)) {
+        if (sameForeignKey(before?.foreign_keys[name], fk)) continue;
+        constraints.push(
+          `ALTER TABLE ${quote(table)} ADD CONSTRAINT ${quote(name)} FOREIGN KEY (${quote(fk.column)}) REFERENCES ${quote(fk.references)}("id") ON DELETE ${fk.on_delete || 'SET NULL'} ON UPDATE CASCADE;`
+        );
+      }
+    }
+
+    for (const table of Object.keys(previous.tables)) {
+      if (!(table in next.tables)) statements.push(`DROP TABLE ${quote(table)} CASCADE;`);
+    }
+
+    return [...statements, ...indexes, ...constraints];
+  }
+
+  /**
+   * Statements for the clauses that differ between two definitions of a column. Inline
+   * UNIQUE and CHECK constraints are addressed by the names PostgreSQL gives them.
+   */
+  private alterColumn(table: string, column: string, from: ColumnClauses, to: ColumnClauses): string[] {
+    const alter = `ALTER TABLE ${quote(table)}`;
+    const statements: string[] = [];
+    const checkName = quote(`${table}_${column}_check`);
+    const uniqueName = quote(`${table}_${column}_key`);
+
+    if (from.check !== to.check) statements.push(`${alter} DROP CONSTRAINT IF EXISTS ${checkName};`);
+    // The old default may not cast to the new type, so it goes first
+    if (from.default !== null && (from.default !== to.default || from.type !== to.type)) {
+      statements.push(`${alter} ALTER COLUMN ${quote(column)} DROP DEFAULT;`);
+    }
+    if (from.type !== to.type) {
+      statements.push(`${alter} ALTER COLUMN ${quote(column)} TYPE ${to.type} USING ${quote(column)}::${to.type};`);
+    }
+    if (to.default !== null && (from.default !== to.default || from.type !== to.type)) {
+      statements.push(`${alter} ALTER COLUMN ${quote(column)} SET DEFAULT ${to.default};`);
+    }
+    if (from.notNull !== to.notNull) {
+      statements.push(`${alter} ALTER COLUMN ${quote(column)} ${to.notNull ? 'SET' : 'DROP'} NOT NULL;`);
+    }
+    if (from.unique !== to.unique) {
+      statements.push(to.unique
+        ? `${alter} ADD CONSTRAINT ${uniqueName} UNIQUE (${quote(column)});`
+        : `${alter} DROP CONSTRAINT IF EXISTS ${uniqueName};`);
+    }
+    if (to.check && from.check !== to.check) statements.push(`${alter} ADD CONSTRAINT ${checkName} CHECK ${to.check};`);
+    return statements;
+  }
+
+  /**
+   * Emit the migration that brings a database at `previous` up to the current models.
+   * Returns no files when nothing changed.
+   */
+  public generateMigrations(previous: SchemaSnapshot | null, date: Date = new Date()): MigrationResult {
+    const snapshot = this.snapshot();
+    const statements = this.diff(previous || { tables: {} }, snapshot);
+    const name = `${migrationTimestamp(date)}_${previous ? 'update' : 'init'}`;
+
+    if (statements.length === 0) return { name, files: [], snapshot };
+
+    return {
+      name,
+      snapshot,
+      files: [{
+        path: `database/migrations/${name}.sql`,
+        content: `-- Generated for project "${this.project.name}"\n\n${statements.join('\n\n')}\n`,
+      }],
+    };
+  }
+}
@@ -0,0 +1,14 @@
+import { test } from 'node:test';
+import assert from 'node:assert/strict';
+import { parseDevServerPort } from './devServer.js';
+
+test('parseDevServerPort reads the port from Vite\'s startup banner', () => {
+  // As captured from `npm run dev`, colour codes included
+  const banner = '  \u001b[32m➜\u001b[39m  \u001b[1mLocal\u001b[22m:   \u001b[36mhttp://localhost:\u001b[1m5174\u001b[22m/\u001b[39m';
+  assert.equal(parseDevServerPort(banner), 5174);
+  assert.equal(parseDevServerPort('  ➜  Local:   http://127.0.0.1:3000/'), 3000);
+
+  assert.equal(parseDevServerPort('  ➜  Network: use --host to expose'), null);
+  assert.equal(parseDevServerPort('  VITE v5.0.0  ready in 312 ms'), null);
+  assert.equal(parseDevServerPort('  ➜  Local:   http://localhost:99999/'), null);
+});
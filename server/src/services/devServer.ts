@@ -0,0 +1,92 @@
+import { spawn, type ChildProcess } from 'child_process';
+import { outputLineWriter } from './processOutput.js';
+import { stopProcess } from './installer.js';
+
+/** How long the readiness probe waits for the dev server to answer */
+const PROBE_TIMEOUT_MS = 1_000;
+
+export interface DevServerStatus {
+  running: boolean;
+  pid: number | null;
+  /** Null until the server prints the address it listens on */
+  port: number | null;
+  url: string | null;
+  /** Whether an HTTP request to the port got any response */
+  ready: boolean;
+}
+
+interface RunningDevServer {
+  child: ChildProcess;
+  port: number | null;
+}
+
+const servers = new Map<string, RunningDevServer>();
+
+/**
+ * The port from a dev server's startup banner, e.g. Vite's `➜  Local:   http://localhost:5173/`.
+ * Colour codes are stripped first; returns null for any other line.
+ */
+export function parseDevServerPort(line: string): number | null {
+  const plain = line.replace(/\u001b\[[0-9;]*m/g, '');
+  const match = plain.match(/Local:\s+https?:\/\/[^\s/:]+:(\d+)/i);
+  if (!match) return null;
+  const port = Number(match[1]);
+  return port > 0 && port < 65536 ? port : null;
+}
+
+/** Run `npm run dev` in the project root; output is published as `dev` process output */
+export function startDevServer(projectId: string, rootPath: string) {
+  if (servers.has(projectId)) return;
+
+  const child = spawn('npm', ['run', 'dev'], { cwd: rootPath, shell: process.platform === 'win32' });
+  const server: RunningDevServer = { child, port: null };
+  servers.set(projectId, server);
+
+  const detectPort = (line: string) => {
+    if (server.port === null) server.port = parseDevServerPort(line);
+  };
+  const outLines = outputLineWriter(projectId, 'dev', 'client', 'stdout', detectPort);
+  const errLines = outputLineWriter(projectId, 'dev', 'client', 'stderr');
+  child.stdout?.on('data', chunk => outLines.write(chunk));
+  child.stderr?.on('data', chunk => errLines.write(chunk));
+
+  const finish = () => {
+    outLines.flush();
+    errLines.flush();
+    if (servers.get(projectId) === server) servers.delete(projectId);
+  };
+  child.on('error', finish);
+  child.on('close', finish);
+}
+
+/** Returns false when no dev server was running for the project */
+export function stopDevServer(projectId: string): boolean {
+  const server = servers.get(projectId);
+  if (!server) return false;
+  servers.delete(projectId);
+  stopProcess(server.child);
+  return true;
+}
+
+async function probe(port: number): Promise<boolean> {
+  try {
+    await fetch(`http://localhost:${port}/`, { signal: AbortSignal.timeout(PROBE_TIMEOUT_MS) });
+    return true;
+  } catch {
+    return false;
+  }
+}
+
+export async function getDevServerStatus(projectId: string): Promise<DevServerStatus> {
+  const server = servers.get(projectId);
+  if (!server) return { running: false, pid: null, port: null, url: null, ready: false };
+
+  const { port } = server;
+  return {
+    running: true,
+    pid: server.child.pid ?? null,
+    port,
+    url: port ? `http://localhost:${port}/` : null,
+    ready: port ? await probe(port) : false,
+  };
+}
@@ -0,0 +1,34 @@
+import { test } from 'node:test';
+import assert from 'node:assert/strict';
+import { DocsGeneratorService } from './docsGenerator.js';
+import type { ProjectSchema } from './projectSchema.js';
+
+const project = {
+  id: 'p1', name: 'Shop', description: 'Sells things', settings: {}, root_path: '', logic_flows: [], blocks: [], variables: [],
+  data_models: [
+    {
+      id: 'm1', name: 'Product', relations: [{ id: 'r1', name: 'category', target_model_id: 'm2', relation_type: 'many_to_one' }],
+      fields: [{ id: 'f1', name: 'title', field_type: 'string', required: true, unique: true, primary_key: false, description: 'Shown | in lists' }],
+      timestamps: true, soft_delete: false, archived: false,
+    },
+    { id: 'm2', name: 'Category', fields: [], relations: [], timestamps: false, soft_delete: false, archived: false },
+  ],
+  pages: [
+    { id: 'home', idRoot: '', name: 'Home', path: '/', is_dynamic: false, layout: 'default', meta: {}, archived: false },
+    { id: 'item', idRoot: '', name: 'Item', path: '/products/:id', is_dynamic: true, layout: 'default', meta: { title: 'Product' }, archived: false },
+  ],
+  apis: [{ id: 'a1', method: 'get', path: '/api/products/top', name: 'topProducts', permissions: [], archived: false }],
+} as unknown as ProjectSchema;
+
+test('DocsGeneratorService lists every model, page route and endpoint', () => {
+  const readme = new DocsGeneratorService(project).generate();
+  assert.equal(readme.path, 'README.md');
+  assert.match(readme.content, /^# Shop\n\nSells things\n/);
+  assert.match(readme.content, /### Product\n\n\| Field \| Type \| Required \| Notes \|/);
+  assert.match(readme.content, /\| title \| string \| yes \| unique, Shown \\\| in lists \|/);
+  assert.match(readme.content, /\| category \| many to one \| Category \|/);
+  assert.match(readme.content, /### Category/);
+  assert.match(readme.content, /\| Home \| `\/` \|/);
+  assert.match(readme.content, /\| Item \| `\/products\/:id` \| Product \|/);
+  assert.match(readme.content, /\| GET \| `\/api\/products\/top` \| topProducts \|/);
+});
@@ -0,0 +1,102 @@
+import { apiPrefix, type DataModelSchema, type FieldSchema, type GeneratedFile, type ProjectSchema } from './projectSchema.js';
+
+/** A value safe inside a markdown table cell */
+function cell(value: unknown): string {
+  const text = value === undefined || value === null || value === '' ? '' : String(value);
+  return text.replace(/\|/g, '\\|').replace(/\r?\n/g, ' ');
+}
+
+function table(headers: string[], rows: unknown[][]): string {
+  return [
+    `| ${headers.join(' | ')} |`,
+    `| ${headers.map(() => '---').join(' | ')} |`,
+    ...rows.map(row => `| ${row.map(cell).join(' | ')} |`),
+  ].join('\n');
+}
+
+function fieldType(field: FieldSchema): string {
+  if (field.field_type === 'enum' && field.enum_values?.length) return `enum (${field.enum_values.join(', ')})`;
+  return field.field_type;
+}
+
+function fieldNotes(field: FieldSchema): string {
+  return [
+    field.primary_key && 'primary key',
+    field.unique && 'unique',
+    field.indexed && 'indexed',
+    field.default !== undefined && field.default !== '' && `default \`${field.default}\``,
+    field.description,
+  ].filter(Boolean).join(', ');
+}
+
+/**
+ * A top-level `README.md` describing the project from its schema: each data model with its
+ * fields and relations, the pages with their routes, and the API endpoints, as markdown
+ * tables. Archived items are already left out of the schema.
+ */
+export class DocsGeneratorService {
+  private project: ProjectSchema;
+
+  constructor(project: ProjectSchema) {
+    this.project = project;
+  }
+
+  public generate(): GeneratedFile {
+    const sections = [`# ${this.project.name}`];
+    if (this.project.description) sections.push(this.project.description);
+    sections.push(this.modelsSection(), this.pagesSection(), this.endpointsSection());
+    return { path: 'README.md', content: `${sections.join('\n\n')}\n` };
+  }
+
+  private modelsSection(): string {
+    if (this.project.data_models.length === 0) return '## Data models\n\nNo data models yet.';
+
+    const models = this.project.data_models.map(model => {
+      const parts = [
+        `### ${model.name}`,
+        table(['Field', 'Type', 'Required', 'Notes'], model.fields.map(f => [f.name, fieldType(f), f.required ? 'yes' : 'no', fieldNotes(f)])),
+      ];
+      const relations = this.relations(model);
+      if (relations.length) parts.push(table(['Relation', 'Type', 'Target'], relations));
+
+      const traits = [
+        model.timestamps && '`createdAt`/`updatedAt` timestamps',
+        model.soft_delete && 'soft delete (`deletedAt`)',
+        model.owner_field && `owned by the signed-in user through \`${model.owner_field}\``,
+      ].filter(Boolean);
+      if (traits.length) parts.push(`Also: ${traits.join('; ')}.`);
+      return parts.join('\n\n');
+    });
+    return `## Data models\n\n${models.join('\n\n')}`;
+  }
+
+  private relations(model: DataModelSchema): unknown[][] {
+    return model.relations.map(relation => {
+      const target = this.project.data_models.find(m => m.id === relation.target_model_id);
+      return [relation.name, relation.relation_type.replace(/_/g, ' '), target?.name ?? 'unknown model'];
+    });
+  }
+
+  private pagesSection(): string {
+    if (this.project.pages.length === 0) return '## Pages\n\nNo pages yet.';
+
+    const rows = this.project.pages.map(page => [
+      page.name,
+      `\`/${page.path.replace(/^\/+|\/+$/g, '')}\``,
+      page.meta?.title ?? '',
+    ]);
+    return `## Pages\n\n${table(['Page', 'Route', 'Title'], rows)}`;
+  }
+
+  private endpointsSection(): string {
+    if (this.project.apis.length === 0) return '## API endpoints\n\nNo custom endpoints yet.';
+
+    const prefix = apiPrefix(this.project);
+    const rows = this.project.apis.map(api => {
+      // Mounted the way the backend generator mounts it, under the API prefix
+      const route = api.path.replace(/^\/+/, '').replace(/^api\//, '');
+      return [api.method.toUpperCase(), `\`/${[prefix, route].filter(Boolean).join('/')}\``, api.name, api.description ?? ''];
+    });
+    return `## API endpoints\n\n${table(['Method', 'Path', 'Name', 'Description'], rows)}`;
+  }
+}
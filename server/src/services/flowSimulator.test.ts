@@ -0,0 +1,70 @@
+import { test } from 'node:test';
+import assert from 'node:assert/strict';
+import { FlowSimulator, jsonSafe } from './flowSimulator.js';
+import type { LogicFlow, LogicNode } from './logicCompiler.js';
+
+const flow = (nodes: LogicNode[]): LogicFlow => ({ id: 'flow', name: 'Flow', trigger: { type: 'manual' }, nodes });
+
+test('FlowSimulator runs variables, conditions and returns against the input', () => {
+  const result = new FlowSimulator().simulate(flow([
+    { id: 'start', node_type: 'Start', next_nodes: ['total'] },
+    { id: 'total', node_type: 'SetVariable', data: { name: 'total', value: 'input.price * input.qty' }, next_nodes: ['check'] },
+    { id: 'check', node_type: 'Condition', data: { left: 'state.total', operator: '>', right: '100' }, next_nodes: ['big'], else_nodes: ['small'] },
+    { id: 'big', node_type: 'Return', data: { value: '{ total: state.total, tier: "big" }' } },
+    { id: 'small', node_type: 'Return', data: { value: '"small"' } },
+  ]), { price: 30, qty: 4 });
+
+  assert.deepEqual(result.output, { total: 120, tier: 'big' });
+  assert.deepEqual(result.state, { total: 120 });
+  assert.deepEqual(result.steps.map(s => s.node_id), ['start', 'total', 'check', 'big']);
+  assert.equal(result.error, undefined);
+});
+
+test('FlowSimulator skips nodes with side effects', () => {
+  const result = new FlowSimulator().simulate(flow([
+    { id: 'fetch', node_type: 'ApiCall', next_nodes: ['done'] },
+    { id: 'done', node_type: 'End' },
+  ]));
+  assert.match(result.steps[0]!.skipped!, /not run in a simulation/);
+  assert.deepEqual(result.output, {});
+});
+
+test('FlowSimulator reports expressions that reach outside plain data', () => {
+  for (const expression of [
+    'this.constructor.constructor("return process")()',
+    'input.constructor',
+    'input.name.constructor.constructor("return 1")()',
+    'process.exit()',
+    '(() => 1)()',
+  ]) {
+    const result = new FlowSimulator().simulate(flow([
+      { id: 'x', node_type: 'Transform', label: 'Escape', data: { expression } },
+    ]), { name: 'n' });
+    assert.match(result.error!, /^Transform node "Escape": /, expression);
+  }
+});
+
+test('FlowSimulator refuses prototype keys as variable names', () => {
+  const result = new FlowSimulator().simulate(flow([
+    { id: 'x', node_type: 'SetVariable', data: { name: '__proto__', value: '{ admin: true }' } },
+  ]));
+  assert.match(result.error!, /"__proto__" is not a valid variable name/);
+  assert.equal(({} as Record<string, unknown>).admin, undefined);
+});
+
+test('jsonSafe writes cycles, BigInts and functions as plain JSON', () => {
+  const cyclic: Record<string, unknown> = { n: 1n, f: () => 1, inf: Infinity };
+  cyclic.self = cyclic;
+  assert.deepEqual(jsonSafe(cyclic), { n: '1', inf: null, self: '[Circular]' });
+  assert.deepEqual(jsonSafe([1, undefined, () => 1]), [1, null, null]);
+});
+
+test('FlowSimulator collects the last result of each Parallel branch', () => {
+  const result = new FlowSimulator().simulate(flow([
+    { id: 'split', node_type: 'Parallel', data: { result_var: 'both' }, next_nodes: ['a1', 'b1'] },
+    { id: 'a1', node_type: 'SetVariable', data: { name: 'a', value: '1' }, next_nodes: ['a2'] },
+    { id: 'a2', node_type: 'Transform', data: { result_var: 'a2', expression: 'state.a + 1' } },
+    { id: 'b1', node_type: 'SetVariable', data: { name: 'b', value: '"b"' } },
+  ]));
+  assert.deepEqual(result.state.both, [2, 'b']);
+});
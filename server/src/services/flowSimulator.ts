@@ -0,0 +1,181 @@
+import type { LogicFlow, LogicNode } from './logicCompiler.js';
+import { evaluateExpression, FORBIDDEN_KEYS } from './safeExpression.js';
+
+/** Node visits allowed per run, so Parallel branches re-walking shared nodes stay bounded */
+const MAX_STEPS = 500;
+
+export interface SimulationStep {
+  node_id: string;
+  node_type: string;
+  /** Value the node wrote or returned; absent for nodes that only route */
+  value?: unknown;
+  /** Set when the node was skipped, e.g. it talks to the network or database */
+  skipped?: string;
+}
+
+export interface SimulationResult {
+  /** What the flow returns: the Return node's value, or the final state */
+  output: unknown;
+  state: Record<string, any>;
+  steps: SimulationStep[];
+  error?: string;
+}
+
+class ReturnSignal {
+  constructor(public value: unknown) {}
+}
+
+/**
+ * A copy of `value` that `JSON.stringify` can always write: cycles and over-deep nesting are
+ * replaced by markers, BigInts become strings, functions and symbols are dropped
+ */
+export function jsonSafe(value: unknown, seen: unknown[] = []): unknown {
+  if (typeof value === 'bigint') return value.toString();
+  if (typeof value === 'function' || typeof value === 'symbol') return undefined;
+  if (typeof value === 'number' && !Number.isFinite(value)) return null;
+  if (!value || typeof value !== 'object') return value;
+  if (seen.includes(value)) return '[Circular]';
+  if (seen.length >= 50) return '[Too deep]';
+
+  const path = [...seen, value];
+  if (Array.isArray(value)) return value.map(item => jsonSafe(item, path) ?? null);
+  const copy: Record<string, unknown> = {};
+  for (const [key, item] of Object.entries(value)) {
+    const safe = jsonSafe(item, path);
+    if (safe !== undefined) copy[key] = safe;
+  }
+  return copy;
+}
+
+/**
+ * Dry-runs a logic flow against sample input without generating code. Interprets the same
+ * node graph the `LogicCompiler` walks, evaluating SetVariable, Condition, Transform and
+ * Return expressions with `input` and `state` in scope. Expressions go through the
+ * `safeExpression` interpreter, never `eval`, since flows and input come from API callers.
+ * Nodes with side effects (API calls, DB writes) are recorded as skipped rather than run.
+ */
+export class FlowSimulator {
+  private nodes = new Map<string, LogicNode>();
+  private steps: SimulationStep[] = [];
+  private input: unknown = {};
+  private state: Record<string, any> = {};
+  /** Last value the current Parallel branch wrote, which becomes that branch's result */
+  private branchResult: unknown;
+
+  public simulate(flow: LogicFlow, input: unknown = {}, state: Record<string, any> = {}): SimulationResult {
+    this.nodes = new Map(flow.nodes.map(n => [n.id, n]));
+    this.steps = [];
+    this.input = input;
+    this.state = state;
+    const entry = this.findEntryNode(flow);
+
+    let result: SimulationResult;
+    try {
+      if (entry) this.walkNode(entry.id, new Set<string>());
+      result = { output: state, state, steps: this.steps };
+    } catch (error) {
+      result = error instanceof ReturnSignal
+        ? { output: error.value, state, steps: this.steps }
+        : { output: undefined, state, steps: this.steps, error: (error as Error).message };
+    }
+    return jsonSafe(result) as SimulationResult;
+  }
+
+  private findEntryNode(flow: LogicFlow): LogicNode | undefined {
+    if (flow.entry_node_id && this.nodes.has(flow.entry_node_id)) {
+      return this.nodes.get(flow.entry_node_id);
+    }
+    const start = flow.nodes.find(n => n.node_type === 'Start');
+    if (start) return start;
+
+    const targets = new Set(flow.nodes.flatMap(n => [...(n.next_nodes || []), ...(n.else_nodes || [])]));
+    return flow.nodes.find(n => !targets.has(n.id)) || flow.nodes[0];
+  }
+
+  /** Evaluate a node's expression; a missing one falls back the way the compiler's does */
+  private evaluate(node: LogicNode, expression: unknown, fallback: string): unknown {
+    const source = expression === undefined || expression === null || expression === '' ? fallback : String(expression);
+    try {
+      return evaluateExpression(source, { input: this.input, state: this.state });
+    } catch (error) {
+      throw new Error(`${node.node_type} node "${node.label || node.id}": ${(error as Error).message}`);
+    }
+  }
+
+  /** Write a node's result into state; prototype keys would let a flow reshape `state` itself */
+  private assign(node: LogicNode, key: string, value: unknown) {
+    if (FORBIDDEN_KEYS.has(key)) throw new Error(`${node.node_type} node "${node.label || node.id}": "${key}" is not a valid variable name`);
+    this.state[key] = value;
+    this.branchResult = value;
+  }
+
+  private record(node: LogicNode, step: Omit<SimulationStep, 'node_id' | 'node_type'> = {}) {
+    if (this.steps.length >= MAX_STEPS) throw new Error(`Flow ran more than ${MAX_STEPS} steps`);
+    this.steps.push({ node_id: node.id, node_type: node.node_type, ...step });
+  }
+
+  private walkChildren(ids: string[] | undefined, visited: Set<string>) {
+    for (const id of ids || []) this.walkNode(id, visited);
+  }
+
+  /** Run a node, then its successors; `visited` stops cycles just as it does when compiling */
+  private walkNode(nodeId: string, visited: Set<string>) {
+    const node = this.nodes.get(nodeId);
+    if (!node || visited.has(nodeId)) return;
+    visited.add(nodeId);
+
+    const data = node.data || {};
+
+    switch (node.node_type) {
+      case 'Start':
+      case 'End':
+        this.record(node);
+        break;
+
+      case 'SetVariable': {
+        const value = this.evaluate(node, data.value, 'undefined');
+        this.assign(node, data.name || node.id, value);
+        this.record(node, { value });
+        break;
+      }
+
+      case 'Condition': {
+        const passed = !!this.evaluate(node, `${data.left ?? 'true'} ${data.operator ?? '==='} ${data.right ?? 'true'}`, 'true');
+        this.record(node, { value: passed });
+        this.walkChildren(passed ? node.next_nodes : node.else_nodes, visited);
+        return;
+      }
+
+      case 'Transform': {
+        const value = this.evaluate(node, data.expression, 'input');
+        this.assign(node, data.result_var || node.id, value);
+        this.record(node, { value });
+        break;
+      }
+
+      case 'Return': {
+        const value = this.evaluate(node, data.value, 'state');
+        this.record(node, { value });
+        throw new ReturnSignal(value);
+      }
+
+      case 'Parallel': {
+        // Branches run one after another here; each gets its own visited set as in the compiler,
+        // and a Return inside one ends the flow straight away
+        const results = (node.next_nodes || []).map(branchId => {
+          this.branchResult = undefined;
+          this.walkNode(branchId, new Set(visited));
+          return this.branchResult;
+        });
+        this.assign(node, data.result_var || node.id, results);
+        this.record(node, { value: results });
+        return;
+      }
+
+      default:
+        this.record(node, { skipped: `${node.node_type} nodes are not run in a simulation` });
+    }
+
+    this.walkChildren(node.next_nodes, visited);
+  }
+}
@@ -0,0 +1,70 @@
+import { test } from 'node:test';
+import assert from 'node:assert/strict';
+import { generationReport, generationWarnings } from './generationReport.js';
+import type { ApiSchema, BlockSchema, ProjectSchema } from './projectSchema.js';
+
+const api = (overrides: Partial<ApiSchema>): ApiSchema => ({
+  id: 'a1', method: 'GET', path: '/items', name: 'items', permissions: [], archived: false, ...overrides,
+});
+
+const project = (overrides: Partial<ProjectSchema>) => ({
+  id: 'p1', name: 'Shop', settings: {}, root_path: '', data_models: [], apis: [], logic_flows: [], pages: [], blocks: [], variables: [],
+  ...overrides,
+} as unknown as ProjectSchema);
+
+test('backend warnings name an endpoint whose model is missing or that has no flow', () => {
+  const orphan = api({ id: 'orphan', path: '/orders', response_body: { shape_type: 'model', model_ref: 'Order' } });
+  const warnings = generationWarnings(project({ apis: [orphan] }), 'backend');
+
+  assert.ok(warnings.some(w => w.entity_id === 'orphan' && /GET \/orders references missing model "Order"/.test(w.message)));
+  assert.ok(warnings.some(w => w.entity_id === 'orphan' && /501 Not Implemented/.test(w.message)));
+});
+
+test('frontend warnings flag empty pages and forms bound to endpoints without a body', () => {
+  const form = {
+    id: 'f1', block_type: 'Form', name: 'Search', parent_id: null, page_id: 'home', properties: {}, styles: {}, classes: [],
+    event_handlers: [], bindings: { submit: { type: 'api', value: 'a1' } }, children: [], order: 0, archived: false,
+  } as unknown as BlockSchema;
+  const warnings = generationWarnings(project({
+    apis: [api({ method: 'GET' })],
+    pages: [
+      { id: 'home', name: 'Home', path: '/' },
+      { id: 'about', name: 'About', path: '/about' },
+    ] as ProjectSchema['pages'],
+    blocks: [form],
+  }), 'frontend');
+
+  assert.deepEqual(warnings.map(w => w.entity_id), ['about', 'f1']);
+  assert.match(warnings[1]!.message, /Form "Search" is bound to GET \/items/);
+});
+
+test('frontend warnings flag bindings to variables, models and endpoints that can\'t be resolved', () => {
+  const block = (id: string, block_type: string, bindings: Record<string, unknown>) => ({
+    id, block_type, name: id, parent_id: null, page_id: 'home', properties: {}, styles: {}, classes: [],
+    event_handlers: [], bindings, children: [], order: 0, archived: false,
+  } as unknown as BlockSchema);
+  const warnings = generationWarnings(project({
+    pages: [{ id: 'home', name: 'Home', path: '/' }] as ProjectSchema['pages'],
+    variables: [{ id: 'v1', name: 'title' }] as ProjectSchema['variables'],
+    data_models: [{ id: 'm1', name: 'User', fields: [] }] as unknown as ProjectSchema['data_models'],
+    blocks: [
+      block('bound', 'Text', { text: { type: 'variable', value: 'v1' }, alt: { type: 'model', value: 'User.name' } }),
+      block('lost', 'Text', { text: { type: 'variable', value: 'gone' } }),
+      block('field', 'Text', { text: { type: 'model', value: 'Order.total' } }),
+      block('endpoint', 'Button', { text: { type: 'api', value: 'a1' } }),
+      block('table', 'Table', { data: { type: 'model', value: 'Order' } }),
+    ],
+  }), 'frontend');
+
+  assert.deepEqual(warnings.map(w => [w.entity_id, w.message]), [
+    ['lost', 'Block "lost" binds "text" to unknown variable gone'],
+    ['field', 'Block "field" binds "text" to Order.total, which isn\'t a field of a known model'],
+    ['endpoint', 'Block "endpoint" binds "text" to endpoint a1; only form submits can use endpoints'],
+    ['table', 'Block "table" is bound to unknown model Order, so it isn\'t wired up'],
+  ]);
+});
+
+test('generationReport counts the files it returns', () => {
+  const report = generationReport(project({}), 'backend', [{ path: 'a.ts', content: 'é' }, { path: 'b.ts', content: 'ab' }]);
+  assert.deepEqual(report.stats, { file_count: 2, total_bytes: 4 });
+});
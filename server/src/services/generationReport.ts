@@ -0,0 +1,105 @@
+import { modelBindingField, type GeneratedFile, type ProjectSchema } from './projectSchema.js';
+import { validateProject, type ProjectIssue } from './projectValidation.js';
+
+export type GenerationTarget = 'frontend' | 'backend';
+
+export interface GenerationStats {
+  file_count: number;
+  total_bytes: number;
+}
+
+/** What a generate route returns: the files, plus problems the UI should flag */
+export interface GenerationReport {
+  files: GeneratedFile[];
+  warnings: ProjectIssue[];
+  stats: GenerationStats;
+}
+
+/** Validation categories that affect each target's output */
+const TARGET_CATEGORIES: Record<GenerationTarget, Set<ProjectIssue['category']>> = {
+  frontend: new Set(['block', 'page', 'route', 'component']),
+  backend: new Set(['api', 'model', 'logic_flow', 'route']),
+};
+
+/**
+ * Bindings the frontend can't resolve and so renders the property's literal instead: unknown
+ * variables, models or model fields, endpoints bound to anything but a form's submit, and
+ * tables and forms pointing at a model or endpoint that doesn't exist
+ */
+function bindingWarnings(project: ProjectSchema): ProjectIssue[] {
+  const warnings: ProjectIssue[] = [];
+  const findModel = (ref: unknown) => project.data_models.find(m => m.id === ref || m.name === ref);
+  const findApi = (ref: unknown) => project.apis.find(a => a.id === ref || a.name === ref);
+  const warn = (blockId: string, message: string) => warnings.push({ category: 'block', severity: 'warning', entity_id: blockId, message });
+
+  for (const block of project.blocks.filter(b => !b.archived)) {
+    for (const [key, binding] of Object.entries(block.bindings || {})) {
+      if (!binding || binding.value === undefined || binding.value === null || binding.value === '') continue;
+      const value = String(binding.value);
+      const label = `Block "${block.name}"`;
+
+      if ((block.block_type === 'Form' && key === 'submit') || (block.block_type === 'Table' && key === 'data')) {
+        const found = binding.type === 'api' ? findApi(value) : binding.type === 'model' ? findModel(value) : true;
+        if (!found) warn(block.id, `${label} is bound to unknown ${binding.type === 'api' ? 'endpoint' : 'model'} ${value}, so it isn't wired up`);
+        continue;
+      }
+      if (binding.type === 'variable' && !project.variables.some(v => v.id === value || v.name === value)) {
+        warn(block.id, `${label} binds "${key}" to unknown variable ${value}`);
+      } else if (binding.type === 'model' && !modelBindingField(project.data_models, value)) {
+        warn(block.id, `${label} binds "${key}" to ${value}, which isn't a field of a known model`);
+      } else if (binding.type === 'api') {
+        warn(block.id, `${label} binds "${key}" to endpoint ${value}; only form submits can use endpoints`);
+      }
+    }
+  }
+  return warnings;
+}
+
+/**
+ * Problems that still let generation finish but leave gaps in its output: the project's
+ * validation issues for the target (info-level ones left out), and for the frontend pages
+ * that render nothing, bindings that can't be resolved and forms bound to endpoints that
+ * take no body, for the backend endpoints that only throw and models with no fields.
+ */
+export function generationWarnings(project: ProjectSchema, target: GenerationTarget): ProjectIssue[] {
+  const warnings = validateProject(project)
+    .filter(issue => issue.severity !== 'info' && TARGET_CATEGORIES[target].has(issue.category));
+
+  if (target === 'frontend') {
+    const pagesWithBlocks = new Set(project.blocks.map(b => b.page_id));
+    for (const page of project.pages.filter(p => !pagesWithBlocks.has(p.id))) {
+      warnings.push({ category: 'page', severity: 'warning', entity_id: page.id, message: `Page "${page.name}" has no blocks and renders empty` });
+    }
+    for (const block of project.blocks.filter(b => b.block_type === 'Form' && b.bindings.submit?.type === 'api')) {
+      const api = project.apis.find(a => a.id === block.bindings.submit.value || a.name === block.bindings.submit.value);
+      if (api && !['POST', 'PUT', 'PATCH'].includes(api.method.toUpperCase())) {
+        warnings.push({ category: 'block', severity: 'warning', entity_id: block.id, message: `Form "${block.name}" is bound to ${api.method} ${api.path}, which takes no body, so it isn't wired up` });
+      }
+    }
+    warnings.push(...bindingWarnings(project));
+    return warnings;
+  }
+
+  for (const api of project.apis) {
+    // Endpoints wired to a flow that no longer exists are already reported by validation
+    const flow = project.logic_flows.find(f => f.id === api.logic_flow_id);
+    if (!api.logic_flow_id || (flow && flow.context !== 'backend')) {
+      warnings.push({ category: 'api', severity: 'warning', entity_id: api.id, message: `${api.method} ${api.path} has no backend logic flow, so it responds 501 Not Implemented` });
+    }
+  }
+  for (const model of project.data_models.filter(m => m.fields.every(f => f.primary_key))) {
+    warnings.push({ category: 'model', severity: 'warning', entity_id: model.id, message: `Model "${model.name}" has no fields besides its id` });
+  }
+  return warnings;
+}
+
+export function generationReport(project: ProjectSchema, target: GenerationTarget, files: GeneratedFile[]): GenerationReport {
+  return {
+    files,
+    warnings: generationWarnings(project, target),
+    stats: {
+      file_count: files.length,
+      total_bytes: files.reduce((sum, f) => sum + Buffer.byteLength(f.content, 'utf-8'), 0),
+    },
+  };
+}
@@ -0,0 +1,170 @@
+import { test } from 'node:test';
+import assert from 'node:assert/strict';
+import fs from 'fs-extra';
+import os from 'os';
+import path from 'path';
+import { codeStyle } from './codeStyle.js';
+import { GeneratorService } from './generator.js';
+import { useMemoryDb } from './memoryDb.js';
+import { getProjectStore, setProjectStore, type ProjectStore } from './projectStore.js';
+import type { ApiSchema, DataModelSchema, ProjectSchema } from './projectSchema.js';
+import { SyncService } from './sync.js';
+
+const api = (overrides: Partial<ApiSchema>): ApiSchema => ({
+  id: 'a1', method: 'GET', path: '/items', name: 'items', permissions: [], archived: false, ...overrides,
+});
+
+const endpoints = (apis: ApiSchema[]) => {
+  const project = { id: 'p1', name: 'Shop', settings: {}, data_models: [], apis, logic_flows: [], pages: [], blocks: [], variables: [] } as unknown as ProjectSchema;
+  return new GeneratorService().genApiClient(project).find(f => f.path === 'src/lib/endpoints.ts')?.content ?? '';
+};
+
+test('genApiClient keeps endpoint functions and their arguments valid identifiers', () => {
+  const content = endpoints([
+    api({ id: 'a1', method: 'DELETE', path: '/items/:id', name: 'delete' }),
+    api({ id: 'a2', method: 'PUT', path: '/things/:body/:query/:class', name: 'request' }),
+  ]);
+
+  assert.match(content, /export function callDelete\(id: string \| number\)/);
+  assert.match(content, /export function callRequest\(body_: string \| number, query_: string \| number, class_: string \| number, body\?: unknown\)/);
+  assert.match(content, /`\/things\/\$\{encodeURIComponent\(body_\)\}\/\$\{encodeURIComponent\(query_\)\}\/\$\{encodeURIComponent\(class_\)\}`/);
+});
+
+test('genModelHook passes includeDeleted to the list route for soft-delete models', () => {
+  const model = (soft_delete: boolean) => ({ id: 'm1', name: 'Order', fields: [], relations: [], timestamps: true, soft_delete, archived: false });
+  const hook = new GeneratorService().genModelHook(model(true), 'http://api.test').content;
+
+  assert.match(hook, /export function useOrders\(options: \{ includeDeleted\?: boolean \} = \{\}\)/);
+  assert.match(hook, /if \(options\.includeDeleted\) params\.set\('includeDeleted', 'true'\);/);
+  assert.match(hook, /fetch\(`\$\{API_URL\}\/orders\$\{query\}`\)/);
+  assert.match(hook, /\}, \[options\.includeDeleted\]\);/);
+
+  const plain = new GeneratorService().genModelHook(model(false)).content;
+  assert.match(plain, /export function useOrders\(\)/);
+  assert.doesNotMatch(plain, /includeDeleted/);
+});
+
+test('a persisted global variable gets a VariablesContext provider wrapping App.tsx', async t => {
+  const root = await fs.mkdtemp(path.join(os.tmpdir(), 'akasha-generator-'));
+  const previousStore = getProjectStore();
+  t.after(async () => {
+    setProjectStore(previousStore);
+    await fs.remove(root);
+  });
+  setProjectStore({ getProjectById: async (id: string) => ({ id, settings: '{}' }) } as unknown as ProjectStore);
+  useMemoryDb(t, { variables: [{ id: 'v1', projectId: 'p1', name: 'theme', scope: 'global', value: '"light"', persist: true }] });
+
+  assert.equal(await new SyncService(root).syncVariablesContext('p1'), true);
+  await new GeneratorService().generateAppTsx([{ name: 'Home', path: '/', meta: '{}' }], root, true, codeStyle({ settings: {} }));
+
+  const context = await fs.readFile(path.join(root, 'src', 'context', 'VariablesContext.tsx'), 'utf-8');
+  assert.match(context, /export function VariablesProvider\(/);
+  assert.match(context, /const \[theme, setTheme\] = usePersistentState.*\('global\.theme', "light"\);/);
+  assert.ok(await fs.pathExists(path.join(root, 'src', 'hooks', 'usePersistentState.ts')));
+
+  const app = await fs.readFile(path.join(root, 'src', 'App.tsx'), 'utf-8');
+  assert.match(app, /import \{ VariablesProvider \} from '\.\/context\/VariablesContext';/);
+  assert.match(app, /<VariablesProvider>\s*<BrowserRouter>[\s\S]*<\/BrowserRouter>\s*<\/VariablesProvider>/);
+});
+
+test('genZodSchema maps each field of the model into a z.object', () => {
+  const field = (name: string, field_type: string, required: boolean, primary_key = false) => ({ id: name, name, field_type, required, unique: false, primary_key });
+  const model = {
+    id: 'm1', name: 'user', relations: [], timestamps: false, soft_delete: false, archived: false,
+    fields: [field('id', 'uuid', true, true), field('email', 'email', true), field('age', 'int', false)],
+  };
+  const schema = new GeneratorService().genZodSchema(model as unknown as DataModelSchema);
+
+  assert.equal(schema.path, 'src/schemas/User.ts');
+  assert.match(schema.content, /^import \{ z \} from 'zod';$/m);
+  assert.match(schema.content, /export const UserSchema = z\s*\.object\(\{\n {4}id: z\.string\(\),\n {4}email: z\.string\(\)\.email\(\),\n {4}age: z\.number\(\)\.int\(\)\.nullable\(\)\.optional\(\),\n {2}\}\)/);
+  assert.match(schema.content, /export type User = z\.infer<typeof UserSchema>;/);
+});
+
+test('generateAppTsx wraps a page that requires a role in RoleRoute', async t => {
+  const root = await fs.mkdtemp(path.join(os.tmpdir(), 'akasha-generator-'));
+  t.after(() => fs.remove(root));
+  const pages = [
+    { name: 'Home', path: '/', meta: '{}' },
+    { name: 'Admin', path: '/admin', meta: JSON.stringify({ required_role: 'admin' }) },
+  ];
+
+  await new GeneratorService().generateAppTsx(pages, root, false, codeStyle({ settings: {} }));
+
+  const app = await fs.readFile(path.join(root, 'src', 'App.tsx'), 'utf-8');
+  assert.match(app, /import RoleRoute from '\.\/components\/RoleRoute';/);
+  assert.match(app, /<Route path="\/admin" element=\{<RoleRoute role="admin"><Admin \/><\/RoleRoute>\} \/>/);
+  assert.match(app, /<Route path="\/" element=\{<Home \/>\} \/>/);
+  assert.match(await fs.readFile(path.join(root, 'src', 'components', 'RoleRoute.tsx'), 'utf-8'), /user\.role/);
+});
+
+test('generateAppTsx routes a blank-layout page outside <Layout />', async t => {
+  const root = await fs.mkdtemp(path.join(os.tmpdir(), 'akasha-generator-'));
+  t.after(() => fs.remove(root));
+  const pages = [
+    { name: 'Home', path: '/', meta: '{}' },
+    { name: 'Landing', path: '/landing', meta: JSON.stringify({ layout: 'blank' }) },
+  ];
+
+  await new GeneratorService().generateAppTsx(pages, root, false, codeStyle({ settings: {} }));
+
+  const app = await fs.readFile(path.join(root, 'src', 'App.tsx'), 'utf-8');
+  const layout = app.match(/<Route element=\{<Layout \/>\}>([\s\S]*?)<\/Route>\n/)![1]!;
+  assert.match(layout, /<Route path="\/" element=\{<Home \/>\} \/>/);
+  assert.doesNotMatch(layout, /Landing/);
+  assert.match(app, /<\/Route>\n\s*<Route path="\/landing" element=\{<Landing \/>\} \/>\n\s*<\/Routes>/);
+  assert.doesNotMatch(await fs.readFile(path.join(root, 'src', 'components', 'navLinks.ts'), 'utf-8'), /landing/);
+});
+
+test('genSeoFiles lists each public page in the sitemap and leaves out /login', () => {
+  const page = (id: string, path: string, meta: Record<string, unknown> = {}) => ({ id, name: id, path, meta, is_dynamic: false, archived: false });
+  const project = {
+    id: 'p1', name: 'Shop', settings: { base_url: 'https://shop.example.com/' }, data_models: [], apis: [], logic_flows: [], blocks: [], variables: [],
+    pages: [page('home', '/'), page('about', '/about'), page('login', '/login'), page('product', '/products/:id'), page('admin', '/admin', { required_role: 'admin' })],
+  } as unknown as ProjectSchema;
+
+  const files = new GeneratorService().genSeoFiles(project);
+  const sitemap = files.find(f => f.path === 'public/sitemap.xml')?.content ?? '';
+  assert.deepEqual([...sitemap.matchAll(/<loc>(.*)<\/loc>/g)].map(m => m[1]), ['https://shop.example.com/', 'https://shop.example.com/about']);
+  assert.doesNotMatch(sitemap, /login/);
+  assert.match(files.find(f => f.path === 'public/robots.txt')?.content ?? '', /^Sitemap: https:\/\/shop\.example\.com\/sitemap\.xml$/m);
+});
+
+test('genZodSchema types a model\'s relations as optional references to the related types', () => {
+  const model = (id: string, name: string, relations: unknown[] = []) => ({
+    id, name, relations, fields: [], timestamps: false, soft_delete: false, archived: false,
+  } as unknown as DataModelSchema);
+  const user = model('m1', 'User', [
+    { id: 'r1', name: 'posts', target_model_id: 'm2', relation_type: 'one_to_many' },
+    { id: 'r2', name: 'profile', target_model_id: 'm3', relation_type: 'one_to_one' },
+  ]);
+  const models = [user, model('m2', 'Post'), model('m3', 'Profile')];
+
+  const schema = new GeneratorService().genZodSchema(user, models).content;
+  assert.match(schema, /^import type \{ Post \} from '\.\/Post';\nimport type \{ Profile \} from '\.\/Profile';$/m);
+  assert.match(schema, /export type User = z\.infer<typeof UserSchema> & \{\n {2}posts\?: Post\[\];\n {2}profile\?: Profile;\n\};/);
+});
+
+test('generateFrontend writes a Dockerfile serving the build from nginx with an SPA fallback', async t => {
+  const root = await fs.mkdtemp(path.join(os.tmpdir(), 'akasha-generator-'));
+  const page = { id: 'home', projectId: 'p1', idRoot: 'root-home', name: 'Home', path: '/', meta: '{}' };
+  useMemoryDb(t, { pages: [page] });
+  const project = { id: 'p1', name: 'Shop', settings: '{}', rootPath: null };
+  const previous = getProjectStore();
+  setProjectStore({
+    getProjectById: async () => project,
+    getProjectWithEntities: async () => ({ ...project, pages: [page], blocks: [], dataModels: [], apis: [], logicFlows: [], variables: [] }),
+  } as unknown as ProjectStore);
+  t.after(async () => {
+    setProjectStore(previous);
+    await fs.remove(root);
+  });
+
+  await new GeneratorService().generateFrontend('p1', root);
+
+  const dockerfile = await fs.readFile(path.join(root, 'Dockerfile'), 'utf-8');
+  assert.match(dockerfile, /^FROM node:\S+ AS build$/m);
+  assert.match(dockerfile, /^COPY --from=build \/app\/dist \/usr\/share\/nginx\/html$/m);
+  assert.match(await fs.readFile(path.join(root, 'nginx.conf'), 'utf-8'), /try_files \$uri \$uri\/ \/index\.html;/);
+  assert.match(await fs.readFile(path.join(root, '.dockerignore'), 'utf-8'), /^node_modules$/m);
+});
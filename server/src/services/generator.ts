@@ -1,17 +1,79 @@
 
 import fs from 'fs-extra';
 import path from 'path';
-import prisma from '../lib/prisma.js';
+import { getCommandDb } from './commands.js';
+import { getProjectStore } from './projectStore.js';
 import { SyncService } from './sync.js';
-import { pascalCase } from '../utils/string.js';
+import { codeStyle, formatFile, styleFiles, type CodeStyle } from './codeStyle.js';
+import { apiBaseUrl, clientRoute, loadProjectSchema, pageLayout, parseJsonValue, type ApiSchema, type DataModelSchema, type FieldSchema, type GeneratedFile, type ProjectSchema } from './projectSchema.js';
+import { camelCase, kebabCase, pascalCase } from '../utils/string.js';
+import { RESERVED_WORDS } from '../utils/identifiers.js';
+
+const ZOD_TYPES: Record<string, string> = {
+  string: 'z.string()',
+  text: 'z.string()',
+  email: 'z.string().email()',
+  url: 'z.string().url()',
+  uuid: 'z.string()',
+  int: 'z.number().int()',
+  float: 'z.number()',
+  // Prisma serializes Decimal as a string to avoid float rounding
+  decimal: 'z.string().regex(/^-?\\d+(\\.\\d+)?$/)',
+  boolean: 'z.boolean()',
+  datetime: 'z.string().datetime()',
+  date: 'z.string().datetime()',
+  json: 'z.any()',
+};
+
+/** A function `genApiClient` declares in `src/lib/endpoints.ts` for one endpoint */
+export interface EndpointFunction {
+  api: ApiSchema;
+  name: string;
+  /** Path as the client requests it, with `:param` placeholders */
+  route: string;
+  /** Route params in order; the function takes them first, then the body and query */
+  pathParams: string[];
+  /** The function's argument for each route param, renamed where it would clash */
+  pathArgs: string[];
+}
+
+/** Names `src/lib/endpoints.ts` already uses: the transport it imports and the trailing arguments */
+const ENDPOINT_NAMES = new Set(['request', 'body', 'query']);
+
+/** Name and route of each endpoint's client function, deduplicated the way the client declares them */
+export function endpointFunctions(project: ProjectSchema): EndpointFunction[] {
+  const usedNames = new Set<string>();
+  return project.apis.map(api => {
+    let name = camelCase(api.name || `${api.method.toLowerCase()} ${api.path}`) || 'callEndpoint';
+    if (/^[0-9]/.test(name) || RESERVED_WORDS.has(name) || ENDPOINT_NAMES.has(name)) name = `call${pascalCase(name)}`;
+    while (usedNames.has(name)) name += '_';
+    usedNames.add(name);
+
+    const route = clientRoute(project, api.path);
+    const pathParams = route.split('/').filter(s => s.startsWith(':')).map(s => s.slice(1));
+    const usedArgs = new Set(ENDPOINT_NAMES);
+    const pathArgs = pathParams.map(param => {
+      let arg = camelCase(param) || 'param';
+      if (/^[0-9]/.test(arg)) arg = `param${pascalCase(arg)}`;
+      while (usedArgs.has(arg) || RESERVED_WORDS.has(arg)) arg += '_';
+      usedArgs.add(arg);
+      return arg;
+    });
+    return { api, name, route, pathParams, pathArgs };
+  });
+}
+
+/** Sign-in and account pages, which don't belong in a sitemap */
+const AUTH_PATHS = new Set(['/login', '/logout', '/signin', '/sign-in', '/signup', '/sign-up', '/register', '/forgot-password', '/reset-password']);
 
 export class GeneratorService {
 
-  public async generateFrontend(projectId: string, outputDir: string) {
-    const project = await prisma.project.findUnique({ where: { id: projectId } });
+  public async generateFrontend(projectId: string, outputDir: string, options: { recordState?: boolean } = {}) {
+    const project = await getProjectStore().getProjectById(projectId);
     if (!project) throw new Error("Project not found");
 
     const syncService = new SyncService(outputDir);
+    const style = codeStyle({ settings: parseJsonValue(project.settings, {}) });
 
     // 1. Init Structure
     await fs.ensureDir(path.join(outputDir, 'src'));
@@ -20,50 +82,518 @@ export class GeneratorService {
     await fs.ensureDir(path.join(outputDir, 'public'));
 
     // 2. Generate Pages
-    const pages = await prisma.page.findMany({ where: { projectId } });
+    const pages = await getCommandDb().page.findMany({ where: { projectId }, orderBy: { path: 'asc' } });
     for (const page of pages) {
       // Re-use sync service logic to write page files to the output dir
       // We need to temporarily point SyncService to this output dir
-      await syncService.syncPageToDisk(page.id, projectId);
+      // An export is an explicit regeneration, so it overwrites rather than reporting conflicts
+      await syncService.syncPageToDisk(page.id, projectId, { force: true, recordState: options.recordState });
     }
 
-    // 3. Generate App.tsx with Routing
-    await this.generateAppTsx(pages, outputDir);
+    // 3. Generate global variables context and App.tsx with Routing
+    const hasGlobals = await syncService.syncVariablesContext(projectId);
+    await this.generateAppTsx(pages, outputDir, hasGlobals, style);
+
+    // 4. Generate Zod schemas and data hooks for each model
+    const schema = await loadProjectSchema(projectId);
+    const baseUrl = schema ? apiBaseUrl(schema) : undefined;
+    const files: GeneratedFile[] = [];
+    for (const model of schema?.data_models || []) {
+      files.push(this.genZodSchema(model, schema?.data_models), this.genModelHook(model, baseUrl));
+    }
+    if (schema) files.push(...this.genApiClient(schema), ...this.genSeoFiles(schema));
 
-    // 4. Generate Main.tsx, Index.html, package.json etc.
-    await this.generateBoilerplate(project.name, outputDir);
+    // 5. Generate Main.tsx, Index.html, package.json etc.
+    files.push(...this.boilerplateFiles(project.name), ...this.genDockerFiles());
+    for (const file of styleFiles(files, style)) {
+      await fs.outputFile(path.join(outputDir, file.path), file.content);
+    }
 
     return { success: true, path: outputDir };
   }
 
-  private async generateAppTsx(pages: any[], outputDir: string) {
+  /**
+   * Runtime schema for a model's API records, mirroring the backend DTO rules. The exported
+   * type also carries the model's relations as optional references to the related types,
+   * present when the API includes them.
+   */
+  public genZodSchema(model: DataModelSchema, models: DataModelSchema[] = []): GeneratedFile {
+    const name = pascalCase(model.name);
+    const zodType = (field: FieldSchema) => field.field_type === 'enum' && field.enum_values?.length
+      ? `z.enum([${field.enum_values.map(v => `'${v}'`).join(', ')}])`
+      : ZOD_TYPES[field.field_type] || 'z.string()';
+
+    const fields = model.fields.map(f => `  ${f.name}: ${zodType(f)}${(f.required && f.field_type !== 'file') || f.primary_key ? '' : '.nullable().optional()'},`);
+    if (model.timestamps) {
+      fields.push(`  createdAt: z.string().datetime(),`, `  updatedAt: z.string().datetime(),`);
+    }
+    if (model.soft_delete) fields.push(`  deletedAt: z.string().datetime().nullable().optional(),`);
+
+    const related = new Set<string>();
+    const relations = model.relations.flatMap(relation => {
+      const target = models.find(m => m.id === relation.target_model_id);
+      if (!target) return [];
+      const type = pascalCase(target.name);
+      if (type !== name) related.add(type);
+      const many = relation.relation_type === 'one_to_many' || relation.relation_type === 'many_to_many';
+      return [`  ${relation.name}?: ${type}${many ? '[]' : ''};`];
+    });
+    const typeImports = [...related].sort().map(type => `import type { ${type} } from './${type}';\n`).join('');
+    const type = relations.length
+      ? `z.infer<typeof ${name}Schema> & {\n${relations.join('\n')}\n}`
+      : `z.infer<typeof ${name}Schema>`;
+
+    return {
+      path: `src/schemas/${name}.ts`,
+      content: `import { z } from 'zod';
+${typeImports}
+// Unknown keys (relation ids, included relations) pass through untouched
+export const ${name}Schema = z
+  .object({
+${fields.map(f => `  ${f}`).join('\n')}
+  })
+  .passthrough();
+
+export type ${name} = ${type};
+`,
+    };
+  }
+
+  /**
+   * `src/lib/api.ts` is a small fetch transport; `src/lib/endpoints.ts` has one function per
+   * endpoint, named after it, with path params as arguments and return types taken from
+   * linked models.
+   */
+  public genApiClient(project: ProjectSchema): GeneratedFile[] {
+    const transport = `const API_URL = import.meta.env.VITE_API_URL || '${apiBaseUrl(project)}';
+
+export type QueryParams = Record<string, string | number | boolean | undefined>;
+
+export interface RequestOptions {
+  query?: QueryParams;
+  body?: unknown;
+  headers?: Record<string, string>;
+}
+
+export async function request<T = unknown>(method: string, path: string, options: RequestOptions = {}): Promise<T> {
+  const params = new URLSearchParams();
+  for (const [key, value] of Object.entries(options.query || {})) {
+    if (value !== undefined) params.set(key, String(value));
+  }
+  const query = params.toString() ? \`?\${params.toString()}\` : '';
+
+  const res = await fetch(\`\${API_URL}\${path}\${query}\`, {
+    method,
+    headers: {
+      ...(options.body !== undefined && { 'Content-Type': 'application/json' }),
+      ...options.headers,
+    },
+    body: options.body !== undefined ? JSON.stringify(options.body) : undefined,
+  });
+  if (!res.ok) throw new Error(\`\${method} \${path} failed with status \${res.status}\`);
+  if (res.status === 204) return undefined as T;
+  return res.json() as Promise<T>;
+}
+
+export const api = {
+  get: <T = unknown>(path: string, options?: RequestOptions) => request<T>('GET', path, options),
+  post: <T = unknown>(path: string, body?: unknown, options?: RequestOptions) => request<T>('POST', path, { ...options, body }),
+  put: <T = unknown>(path: string, body?: unknown, options?: RequestOptions) => request<T>('PUT', path, { ...options, body }),
+  patch: <T = unknown>(path: string, body?: unknown, options?: RequestOptions) => request<T>('PATCH', path, { ...options, body }),
+  delete: <T = unknown>(path: string, options?: RequestOptions) => request<T>('DELETE', path, options),
+};
+`;
+
+    const usedModels = new Set<string>();
+    const shapeType = (shape: any): string => {
+      if (!shape) return 'unknown';
+      if (shape.shape_type === 'array') return `${shapeType(shape.item_shape)}[]`;
+      if (shape.shape_type === 'model') {
+        const model = project.data_models.find(m => m.id === shape.model_ref || m.name === shape.model_ref);
+        if (!model) return 'unknown';
+        usedModels.add(pascalCase(model.name));
+        return pascalCase(model.name);
+      }
+      return ({ string: 'string', number: 'number', boolean: 'boolean' } as Record<string, string>)[shape.shape_type] || 'Record<string, unknown>';
+    };
+
+    const functions = endpointFunctions(project).map(({ api, name, route, pathParams, pathArgs }) => {
+      const method = api.method.toUpperCase();
+      const url = pathParams.length
+        ? `\`${route.replace(/:([A-Za-z0-9_]+)/g, (_m, p) => `\${encodeURIComponent(${pathArgs[pathParams.indexOf(p)]})}`)}\``
+        : `'${route}'`;
+
+      const args = pathArgs.map(arg => `${arg}: string | number`);
+      const hasBody = method !== 'GET' && method !== 'DELETE';
+      if (hasBody) {
+        const body = api.request_body?.shape_type === 'model' ? `Partial<${shapeType(api.request_body)}>` : shapeType(api.request_body);
+        args.push(`body${api.request_body ? '' : '?'}: ${body}`);
+      }
+      const queryKeys = (api.query_params || []).filter(q => q?.name).map(q => `${JSON.stringify(q.name)}${q.required ? '' : '?'}: string | number | boolean`);
+      if (queryKeys.length) args.push(`query${(api.query_params || []).some(q => q?.required) ? '' : '?'}: { ${queryKeys.join('; ')} }`);
+
+      const options = [hasBody && 'body', queryKeys.length && 'query'].filter(Boolean).join(', ');
+      const returnType = shapeType(api.response_body);
+      const doc = api.description ? `/** ${api.description.replace(/\*\//g, '')} */\n` : `/** ${method} ${route} */\n`;
+
+      return `${doc}export function ${name}(${args.join(', ')}): Promise<${returnType}> {
+  return request<${returnType}>('${method}', ${url}${options ? `, { ${options} }` : ''});
+}`;
+    });
+
+    const typeImports = [...usedModels].sort().map(m => `import type { ${m} } from '../schemas/${m}';`);
+    const endpoints = `import { request } from './api';
+${typeImports.map(i => `${i}\n`).join('')}
+${functions.join('\n\n')}
+`;
+
+    const files: GeneratedFile[] = [{ path: 'src/lib/api.ts', content: transport }];
+    if (functions.length) files.push({ path: 'src/lib/endpoints.ts', content: endpoints });
+    return files;
+  }
+
+  /**
+   * `src/lib/formValues.ts`, which wired forms use to turn their fields into a request body.
+   * FormData only holds strings, so fields backed by numeric, boolean or JSON model fields
+   * are converted; decimals stay strings, as the generated DTOs expect.
+   */
+  public genFormValues(): GeneratedFile {
+    return {
+      path: 'src/lib/formValues.ts',
+      content: `export type FieldKind = 'number' | 'boolean' | 'json';
+
+/** The form's fields as a request body; empty optional inputs are left out */
+export function formValues(form: HTMLFormElement, kinds: Record<string, FieldKind> = {}): Record<string, any> {
+  const values: Record<string, any> = {};
+  for (const [key, raw] of new FormData(form).entries()) {
+    const kind = kinds[key];
+    if (typeof raw !== 'string' || !kind) values[key] = raw;
+    else if (kind === 'boolean') values[key] = raw === 'on' || raw === 'true' || raw === '1';
+    else if (raw.trim() === '') continue;
+    else if (kind === 'number') values[key] = Number(raw);
+    else values[key] = JSON.parse(raw);
+  }
+  // Unchecked checkboxes aren't submitted at all
+  for (const [key, kind] of Object.entries(kinds)) {
+    if (kind === 'boolean' && !(key in values) && form.elements.namedItem(key)) values[key] = false;
+  }
+  return values;
+}
+
+/** A path parameter from the form's own field or else the page route */
+export function pathParam(name: string, ...sources: Array<Record<string, unknown>>): string {
+  for (const source of sources) {
+    const value = source[name];
+    if (value !== undefined && value !== null && value !== '') return String(value);
+  }
+  throw new Error(\`Missing \${name}\`);
+}
+`,
+    };
+  }
+
+  /**
+   * `public/robots.txt` and `public/sitemap.xml`. The sitemap lists every page a crawler can
+   * reach: dynamic routes, pages with a required role and sign-in pages are left out. URLs
+   * are built on `settings.base_url`.
+   */
+  public genSeoFiles(project: ProjectSchema): GeneratedFile[] {
+    const baseUrl = (typeof project.settings.base_url === 'string' && project.settings.base_url.trim()
+      ? project.settings.base_url.trim()
+      : 'http://localhost:5173').replace(/\/+$/, '');
+    const escapeXml = (value: string) => value.replace(/[<>&'"]/g, c => `&#${c.charCodeAt(0)};`);
+
+    const paths = new Set<string>();
+    for (const page of project.pages) {
+      const route = `/${page.path.replace(/^\/+|\/+$/g, '')}`;
+      if (page.is_dynamic || route.includes(':') || page.meta.required_role) continue;
+      if (AUTH_PATHS.has(route.toLowerCase())) continue;
+      paths.add(route);
+    }
+
+    const urls = [...paths].sort().map(route => `  <url>\n    <loc>${escapeXml(baseUrl + route)}</loc>\n  </url>`);
+    return [
+      {
+        path: 'public/robots.txt',
+        content: `User-agent: *\nAllow: /\n\nSitemap: ${baseUrl}/sitemap.xml\n`,
+      },
+      {
+        path: 'public/sitemap.xml',
+        content: `<?xml version="1.0" encoding="UTF-8"?>\n<urlset xmlns="http://www.sitemaps.org/schemas/sitemap/0.9">\n${urls.map(u => `${u}\n`).join('')}</urlset>\n`,
+      },
+    ];
+  }
+
+  /**
+   * A multi-stage Dockerfile that builds the SPA with node and serves `dist/` from nginx.
+   * `VITE_API_URL` is a build argument because Vite inlines it at build time; nginx falls
+   * back to index.html so client-side routes survive a reload.
+   */
+  public genDockerFiles(): GeneratedFile[] {
+    return [
+      {
+        path: 'Dockerfile',
+        content: `FROM node:20-alpine AS build
+WORKDIR /app
+COPY package*.json ./
+RUN npm install
+COPY . .
+ARG VITE_API_URL
+ENV VITE_API_URL=\${VITE_API_URL}
+RUN npm run build
+
+FROM nginx:1.27-alpine
+COPY nginx.conf /etc/nginx/conf.d/default.conf
+COPY --from=build /app/dist /usr/share/nginx/html
+EXPOSE 80
+CMD ["nginx", "-g", "daemon off;"]
+`,
+      },
+      {
+        path: 'nginx.conf',
+        content: `server {
+  listen 80;
+  root /usr/share/nginx/html;
+  index index.html;
+
+  location /assets/ {
+    expires 1y;
+    add_header Cache-Control "public, immutable";
+  }
+
+  location / {
+    try_files $uri $uri/ /index.html;
+  }
+}
+`,
+      },
+      { path: '.dockerignore', content: `node_modules
+dist
+.env
+*.log
+` },
+    ];
+  }
+
+  /**
+   * `use{Model}s()` fetches the model's list route from the generated backend.
+   * Soft-delete models also accept `{ includeDeleted: true }`. In dev builds responses are
+   * checked against the model's Zod schema.
+   */
+  public genModelHook(model: DataModelSchema, baseUrl = 'http://localhost:3000/api'): GeneratedFile {
+    const name = pascalCase(model.name);
+    const hookName = `use${name}s`;
+    const options = model.soft_delete ? `options: { includeDeleted?: boolean } = {}` : '';
+    const query = model.soft_delete
+      ? `
+    const params = new URLSearchParams();
+    if (options.includeDeleted) params.set('includeDeleted', 'true');
+    const query = params.toString() ? \`?\${params.toString()}\` : '';`
+      : `
+    const query = '';`;
+
+    return {
+      path: `src/hooks/${hookName}.ts`,
+      content: `import { useCallback, useEffect, useState } from 'react';
+import { ${name}Schema, type ${name} } from '../schemas/${name}';
+
+const API_URL = import.meta.env.VITE_API_URL || '${baseUrl}';
+
+export function ${hookName}(${options}) {
+  const [data, setData] = useState<${name}[]>([]);
+  const [loading, setLoading] = useState(true);
+  const [error, setError] = useState<Error | null>(null);
+
+  const refresh = useCallback(async () => {${query}
+    setLoading(true);
+    try {
+      const res = await fetch(\`\${API_URL}/${kebabCase(model.name)}s\${query}\`);
+      if (!res.ok) throw new Error(\`Request failed with status \${res.status}\`);
+      const json = await res.json();
+      setData(import.meta.env.DEV ? ${name}Schema.array().parse(json) : json);
+      setError(null);
+    } catch (err) {
+      setError(err as Error);
+    } finally {
+      setLoading(false);
+    }
+  }, [${model.soft_delete ? 'options.includeDeleted' : ''}]);
+
+  useEffect(() => {
+    refresh();
+  }, [refresh]);
+
+  return { data, loading, error, refresh };
+}
+`,
+    };
+  }
+
+  /** Write src/App.tsx routing every page, wrapped in VariablesProvider when the project has globals */
+  public async generateAppTsx(pages: any[], outputDir: string, hasGlobals: boolean, style: CodeStyle) {
     const imports = pages.map(p => `import ${pascalCase(p.name)} from './pages/${pascalCase(p.name)}';`).join('\n');
-    const routes = pages.map(p => {
-      const pathStr = p.path || (p.name === 'Home' ? '/' : `/${p.name.toLowerCase()}`);
-      return `<Route path="${pathStr}" element={<${pascalCase(p.name)} />} />`;
-    }).join('\n          ');
+    const requiredRole = (page: any): string | null => {
+      try {
+        const role = JSON.parse(page.meta || '{}').required_role;
+        return typeof role === 'string' && role ? role : null;
+      } catch {
+        return null;
+      }
+    };
+
+    const routePath = (page: any): string => page.path || (page.name === 'Home' ? '/' : `/${page.name.toLowerCase()}`);
+    const route = (p: any) => {
+      const role = requiredRole(p);
+      const element = role
+        ? `<RoleRoute role=${JSON.stringify(role)}><${pascalCase(p.name)} /></RoleRoute>`
+        : `<${pascalCase(p.name)} />`;
+      return `<Route path="${routePath(p)}" element={${element}} />`;
+    };
+
+    // Default pages nest under the layout with the nav bar, auth pages under the centered one;
+    // blank pages sit at the top level so nothing wraps them
+    const byLayout = (layout: string) => pages.filter(p => pageLayout(parseJsonValue(p.meta, {})) === layout);
+    const defaultPages = byLayout('default');
+    const authPages = byLayout('auth');
+    const nested = (layoutName: string, group: any[]) => group.length
+      ? [`<Route element={<${layoutName} />}>`, ...group.map(p => `  ${route(p)}`), '</Route>']
+      : [];
+    const routes = [
+      ...nested('Layout', defaultPages),
+      ...nested('AuthLayout', authPages),
+      ...byLayout('blank').map(route),
+    ].join('\n        ');
+
+    const guarded = pages.some(p => requiredRole(p));
+    if (guarded) {
+      const file = path.join(outputDir, 'src', 'components', 'RoleRoute.tsx');
+      await fs.outputFile(file, formatFile(file, this.genRoleRoute(), style));
+    }
+    // The layouts are written once and then left to the user; only the nav links are regenerated
+    if (defaultPages.length) {
+      const linksFile = path.join(outputDir, 'src', 'components', 'navLinks.ts');
+      // Dynamic routes need params, so only static pages get a nav link
+      const links = defaultPages.filter(p => !routePath(p).includes(':')).map(p => ({ to: routePath(p), label: p.name }));
+      await fs.outputFile(linksFile, formatFile(linksFile, this.genNavLinks(links), style));
+      const file = path.join(outputDir, 'src', 'components', 'Layout.tsx');
+      if (!await fs.pathExists(file)) await fs.outputFile(file, formatFile(file, this.genLayout(), style));
+    }
+    if (authPages.length) {
+      const file = path.join(outputDir, 'src', 'components', 'AuthLayout.tsx');
+      if (!await fs.pathExists(file)) await fs.outputFile(file, formatFile(file, this.genAuthLayout(), style));
+    }
+
+    const router = `<BrowserRouter>
+      <Routes>
+        ${routes}
+      </Routes>
+    </BrowserRouter>`;
+
+    // Global variables live in a context wrapping every route
+    const tree = hasGlobals
+      ? `<VariablesProvider>\n    ${router.replace(/\n/g, '\n  ')}\n    </VariablesProvider>`
+      : router;
+    const providerImport = (hasGlobals ? `\nimport { VariablesProvider } from './context/VariablesContext';` : '')
+      + (guarded ? `\nimport RoleRoute from './components/RoleRoute';` : '')
+      + (defaultPages.length ? `\nimport Layout from './components/Layout';` : '')
+      + (authPages.length ? `\nimport AuthLayout from './components/AuthLayout';` : '');
 
-    const content = `import { BrowserRouter, Routes, Route } from 'react-router-dom';
+    const content = `import { BrowserRouter, Routes, Route } from 'react-router-dom';${providerImport}
 ${imports}
 
 function App() {
   return (
-    <BrowserRouter>
-      <div className="min-h-screen bg-slate-50">
-        <Routes>
-          ${routes}
-        </Routes>
-      </div>
-    </BrowserRouter>
+    ${tree}
   );
 }
 
 export default App;
 `;
-    await fs.writeFile(path.join(outputDir, 'src', 'App.tsx'), content);
+    await fs.writeFile(path.join(outputDir, 'src', 'App.tsx'), formatFile('App.tsx', content, style));
+  }
+
+  /**
+   * Wrapper for pages with a required role. The signed-in user is read from
+   * `localStorage.user`, which the app's login flow is expected to set.
+   */
+  private genRoleRoute(): string {
+    return `import type { ReactNode } from 'react';
+import { Navigate } from 'react-router-dom';
+
+interface CurrentUser {
+  role?: string;
+}
+
+function currentUser(): CurrentUser | null {
+  try {
+    return JSON.parse(localStorage.getItem('user') || 'null');
+  } catch {
+    return null;
+  }
+}
+
+export default function RoleRoute({ role, children }: { role: string; children: ReactNode }) {
+  const user = currentUser();
+  if (!user) return <Navigate to="/login" replace />;
+  if (user.role !== role) return <Navigate to="/" replace />;
+  return <>{children}</>;
+}
+`;
+  }
+
+  /** The static pages the default layout's nav bar links to */
+  private genNavLinks(links: Array<{ to: string; label: string }>): string {
+    return `export const links = [
+${links.map(l => `  { to: ${JSON.stringify(l.to)}, label: ${JSON.stringify(l.label)} },`).join('\n')}
+];
+`;
+  }
+
+  /** The shell of default-layout pages: a nav bar linking the static pages above the route */
+  private genLayout(): string {
+    return `import { NavLink, Outlet } from 'react-router-dom';
+import { links } from './navLinks';
+
+export default function Layout() {
+  return (
+    <div className="min-h-screen bg-slate-50">
+      <nav className="flex gap-4 border-b border-slate-200 bg-white px-6 py-3">
+        {links.map((link) => (
+          <NavLink
+            key={link.to}
+            to={link.to}
+            end
+            className={({ isActive }) => (isActive ? 'font-semibold text-slate-900' : 'text-slate-600 hover:text-slate-900')}
+          >
+            {link.label}
+          </NavLink>
+        ))}
+      </nav>
+      <Outlet />
+    </div>
+  );
+}
+`;
   }
 
-  private async generateBoilerplate(projectName: string, outputDir: string) {
+  /** The shell of auth-layout pages: the page centered in a card, without the nav bar */
+  private genAuthLayout(): string {
+    return `import { Outlet } from 'react-router-dom';
+
+export default function AuthLayout() {
+  return (
+    <div className="flex min-h-screen items-center justify-center bg-slate-50 p-4">
+      <div className="w-full max-w-md rounded-lg bg-white p-8 shadow">
+        <Outlet />
+      </div>
+    </div>
+  );
+}
+`;
+  }
+
+  /** The Vite, Tailwind and entry-point files every generated frontend starts from */
+  public boilerplateFiles(projectName: string): GeneratedFile[] {
     // package.json
     const packageJson = {
       name: projectName.toLowerCase().replace(/\s+/g, '-'),
@@ -78,7 +608,8 @@ export default App;
       dependencies: {
         "react": "^18.2.0",
         "react-dom": "^18.2.0",
-        "react-router-dom": "^6.21.0"
+        "react-router-dom": "^6.21.0",
+        "zod": "^3.22.4"
       },
       devDependencies: {
         "@types/react": "^18.2.43",
@@ -91,7 +622,6 @@ export default App;
         "vite": "^5.0.8"
       }
     };
-    await fs.writeJson(path.join(outputDir, 'package.json'), packageJson, { spaces: 2 });
 
     // vite.config.ts
     const viteConfig = `import { defineConfig } from 'vite';
@@ -101,7 +631,6 @@ export default defineConfig({
   plugins: [react()],
 });
 `;
-    await fs.writeFile(path.join(outputDir, 'vite.config.ts'), viteConfig);
 
     // index.html
     const indexHtml = `<!DOCTYPE html>
@@ -117,7 +646,6 @@ export default defineConfig({
   </body>
 </html>
 `;
-    await fs.writeFile(path.join(outputDir, 'index.html'), indexHtml);
 
     // main.tsx
     const mainTsx = `import React from 'react';
@@ -131,14 +659,12 @@ ReactDOM.createRoot(document.getElementById('root')!).render(
   </React.StrictMode>,
 );
 `;
-    await fs.writeFile(path.join(outputDir, 'src', 'main.tsx'), mainTsx);
 
     // index.css (Tailwind)
     const indexCss = `@tailwind base;
 @tailwind components;
 @tailwind utilities;
 `;
-    await fs.writeFile(path.join(outputDir, 'src', 'index.css'), indexCss);
 
     // tailwind.config.js
     const tailwindConfig = `/** @type {import('tailwindcss').Config} */
@@ -153,7 +679,6 @@ export default {
   plugins: [],
 };
 `;
-    await fs.writeFile(path.join(outputDir, 'tailwind.config.js'), tailwindConfig);
 
     // postcss.config.js
     const postcssConfig = `export default {
@@ -163,6 +688,15 @@ export default {
   },
 };
 `;
-    await fs.writeFile(path.join(outputDir, 'postcss.config.js'), postcssConfig);
+
+    return [
+      { path: 'package.json', content: `${JSON.stringify(packageJson, null, 2)}\n` },
+      { path: 'vite.config.ts', content: viteConfig },
+      { path: 'index.html', content: indexHtml },
+      { path: 'src/main.tsx', content: mainTsx },
+      { path: 'src/index.css', content: indexCss },
+      { path: 'tailwind.config.js', content: tailwindConfig },
+      { path: 'postcss.config.js', content: postcssConfig },
+    ];
   }
 }
@@ -0,0 +1,40 @@
+import { test } from 'node:test';
+import assert from 'node:assert/strict';
+import { HtmlGeneratorService } from './htmlGenerator.js';
+import type { BlockSchema, ProjectSchema } from './projectSchema.js';
+
+const block = (overrides: Partial<BlockSchema>) => ({
+  id: 'b1', block_type: 'Text', name: 'Block', parent_id: null, page_id: 'home', properties: {}, styles: {}, classes: [],
+  event_handlers: [], bindings: {}, children: [], order: 0, archived: false, ...overrides,
+} as BlockSchema);
+
+test('generated home page is index.html with its block text, escaped, and the shared stylesheet', () => {
+  const project = {
+    id: 'p1', name: 'Shop', settings: {}, data_models: [], apis: [], logic_flows: [], variables: [],
+    pages: [{ id: 'home', name: 'Home', path: '/', root_block_ids: ['root'], meta: {}, archived: false }],
+    blocks: [
+      block({ id: 'root', block_type: 'Section', name: 'Hero', children: ['title'] }),
+      block({ id: 'title', parent_id: 'root', properties: { text: 'Fish & chips <today>' }, styles: { fontSize: '2rem' } }),
+    ],
+  } as unknown as ProjectSchema;
+
+  const files = new HtmlGeneratorService(project).generate();
+  assert.deepEqual(files.map(f => f.path), ['index.html', 'styles.css']);
+
+  const html = files[0]!.content;
+  assert.match(html, /<title>Home \| Shop<\/title>/);
+  assert.match(html, /<script src="https:\/\/cdn\.tailwindcss\.com"><\/script>\n {2}<link rel="stylesheet" href="styles\.css" \/>/);
+  assert.match(html, /<section data-block="root" class="py-12 px-4">\n\s*<p data-block="title" class="text-gray-600">Fish &amp; chips &lt;today&gt;<\/p>\n\s*<\/section>/);
+  assert.match(files[1]!.content, /\[data-block="title"\] \{\n {2}font-size: 2rem;\n\}/);
+});
+
+test('an absolutely positioned block carries its position classes', () => {
+  const project = {
+    id: 'p1', name: 'Shop', settings: {}, data_models: [], apis: [], logic_flows: [], variables: [],
+    pages: [{ id: 'home', name: 'Home', path: '/', root_block_ids: ['badge'], meta: {}, archived: false }],
+    blocks: [block({ id: 'badge', properties: { text: 'New', position: { type: 'absolute', top: 2, right: 2, z_index: 50 } } })],
+  } as unknown as ProjectSchema;
+
+  const html = new HtmlGeneratorService(project).generate()[0]!.content;
+  assert.match(html, /<p data-block="badge" class="text-gray-600 absolute top-2 right-2 z-50">New<\/p>/);
+});
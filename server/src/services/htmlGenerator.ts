@@ -0,0 +1,205 @@
+import { kebabCase } from '../utils/string.js';
+import { positionClasses } from './positioning.js';
+import { normalizeClasses } from './tailwindClasses.js';
+import { ariaAttributes } from './accessibility.js';
+import { customBlockName, type BlockSchema, type GeneratedFile, type PageSchema, type ProjectSchema } from './projectSchema.js';
+
+/** Block type → element, mirroring the components the React sync writes */
+export const HTML_TAGS: Record<string, string> = {
+  Page: 'main',
+  Section: 'section',
+  Container: 'div',
+  Card: 'div',
+  Flex: 'div',
+  Grid: 'div',
+  Columns: 'div',
+  Column: 'div',
+  Text: 'p',
+  Paragraph: 'p',
+  Button: 'button',
+  Image: 'img',
+  Link: 'a',
+  Form: 'form',
+  Input: 'input',
+  TextArea: 'textarea',
+  Select: 'select',
+  List: 'ul',
+  Table: 'table',
+  Video: 'video',
+};
+
+/** Default Tailwind classes, matching the React component templates */
+export const DEFAULT_CLASSES: Record<string, string> = {
+  Section: 'py-12 px-4',
+  Container: 'w-full',
+  Card: 'bg-white rounded-xl shadow-md p-6',
+  Flex: 'flex',
+  Grid: 'grid',
+  Heading: 'font-bold text-gray-900',
+  Text: 'text-gray-600',
+  Paragraph: 'text-gray-600',
+  Button: 'px-6 py-2.5 rounded-lg font-medium transition-all duration-200 bg-indigo-600 text-white hover:bg-indigo-700 shadow-md',
+  Image: 'max-w-full rounded-lg',
+};
+
+export const VOID_TAGS = new Set(['img', 'input']);
+
+/** `custom:ImageCarousel` → `image-carousel`; custom element names need a hyphen, so `Carousel` → `x-carousel` */
+export function customElementTag(name: string): string {
+  const tag = kebabCase(name);
+  return tag.includes('-') ? tag : `x-${tag}`;
+}
+
+export function escapeHtml(value: unknown): string {
+  return String(value)
+    .replace(/&/g, '&amp;')
+    .replace(/</g, '&lt;')
+    .replace(/>/g, '&gt;')
+    .replace(/"/g, '&quot;');
+}
+
+/** `/` → index.html, `/blog/posts` → blog/posts.html; dynamic pages fall back to their name */
+export function pageFileName(page: PageSchema): string {
+  const route = page.path.replace(/^\/+|\/+$/g, '');
+  if (!route) return 'index.html';
+  if (page.is_dynamic || /[:*[]/.test(route)) return `${kebabCase(page.name) || 'page'}.html`;
+  return `${route}.html`;
+}
+
+/**
+ * Generates a plain static site: one HTML file per page with Tailwind from the CDN, plus a
+ * shared styles.css carrying each block's own styles. Bindings and logic are not evaluated;
+ * bound properties render their literal value.
+ */
+export class HtmlGeneratorService {
+  private project: ProjectSchema;
+  private blocks: Map<string, BlockSchema>;
+  private styleRules: string[] = [];
+
+  constructor(project: ProjectSchema) {
+    this.project = project;
+    this.blocks = new Map(project.blocks.map(b => [b.id, b]));
+  }
+
+  public generate(): GeneratedFile[] {
+    this.styleRules = [];
+    const files = this.project.pages.map(page => this.genPage(page));
+    files.push({ path: 'styles.css', content: this.genStylesheet() });
+    return files;
+  }
+
+  private rootBlocks(page: PageSchema): BlockSchema[] {
+    const roots = (page.root_block_ids ?? []).map(id => this.blocks.get(id)).filter((b): b is BlockSchema => !!b);
+    if (roots.length) return roots;
+    return this.project.blocks
+      .filter(b => b.page_id === page.id && !b.parent_id)
+      .sort((a, b) => a.order - b.order);
+  }
+
+  private genPage(page: PageSchema): GeneratedFile {
+    const path = pageFileName(page);
+    const depth = path.split('/').length - 1;
+    const body = this.rootBlocks(page).map(b => this.renderBlock(b, 2, {}, new Set())).join('');
+    const title = typeof page.meta.title === 'string' && page.meta.title ? page.meta.title : `${page.name} | ${this.project.name}`;
+    const description = typeof page.meta.description === 'string' ? page.meta.description : this.project.description;
+
+    return {
+      path,
+      content: `<!DOCTYPE html>
+<html lang="en">
+<head>
+  <meta charset="UTF-8" />
+  <meta name="viewport" content="width=device-width, initial-scale=1.0" />
+  <title>${escapeHtml(title)}</title>
+${description ? `  <meta name="description" content="${escapeHtml(description)}" />\n` : ''}  <script src="https://cdn.tailwindcss.com"></script>
+  <link rel="stylesheet" href="${'../'.repeat(depth)}styles.css" />
+</head>
+<body class="min-h-screen bg-white">
+${body}</body>
+</html>
+`,
+    };
+  }
+
+  /**
+   * Render a block and its children. Instances are expanded in place from their component,
+   * with `props` supplying values to `prop` bindings inside it.
+   */
+  private renderBlock(block: BlockSchema, indent: number, props: Record<string, unknown>, expanding: Set<string>): string {
+    const pad = '  '.repeat(indent);
+
+    if (block.block_type === 'Instance' || block.block_type === 'instance') {
+      const componentId = block.properties.component_id;
+      const component = componentId ? this.blocks.get(componentId) : undefined;
+      if (!component || expanding.has(component.id)) return '';
+      const next = new Set(expanding).add(component.id);
+      return this.children(component).map(c => this.renderBlock(c, indent, block.properties.prop_values || {}, next)).join('');
+    }
+
+    const value = (key: string) => {
+      const binding = block.bindings[key];
+      if (binding?.type === 'prop' && binding.value !== undefined && props[binding.value] !== undefined) return props[binding.value];
+      return block.properties[key];
+    };
+
+    const custom = customBlockName(block.block_type);
+    const tag = custom
+      ? customElementTag(custom)
+      : block.block_type === 'Heading'
+        ? `h${Math.min(Math.max(Number(value('level')) || 1, 1), 6)}`
+        : HTML_TAGS[block.block_type] || 'div';
+
+    const classes = normalizeClasses(DEFAULT_CLASSES[block.block_type], block.classes, positionClasses(block.properties));
+    const attributes: string[] = [`data-block="${escapeHtml(block.id)}"`];
+    if (classes) attributes.push(`class="${escapeHtml(classes)}"`);
+
+    if (tag === 'img') {
+      // An empty alt is kept, marking the image as decorative
+      attributes.push(`src="${escapeHtml(value('src') || '')}"`, `alt="${escapeHtml(value('alt') ?? block.name)}"`);
+    } else if (tag === 'a') {
+      attributes.push(`href="${escapeHtml(value('href') || '#')}"`);
+    } else if (tag === 'input' || tag === 'textarea') {
+      if (value('placeholder')) attributes.push(`placeholder="${escapeHtml(value('placeholder'))}"`);
+      if (value('name')) attributes.push(`name="${escapeHtml(value('name'))}"`);
+    } else if (tag === 'video' && value('src')) {
+      attributes.push(`src="${escapeHtml(value('src'))}"`, 'controls');
+    }
+
+    for (const [name, attr] of ariaAttributes(block.properties)) attributes.push(`${name}="${escapeHtml(attr)}"`);
+
+    this.addStyleRule(block);
+
+    const open = `${pad}<${tag} ${attributes.join(' ')}`;
+    if (VOID_TAGS.has(tag)) return `${open} />\n`;
+
+    const text = value('text') ?? value('content');
+    const children = this.children(block);
+    if (!children.length) {
+      return `${open}>${text !== undefined && text !== null ? escapeHtml(text) : ''}</${tag}>\n`;
+    }
+
+    let html = `${open}>\n`;
+    if (text !== undefined && text !== null && text !== '') html += `${pad}  ${escapeHtml(text)}\n`;
+    for (const child of children) html += this.renderBlock(child, indent + 1, props, expanding);
+    return `${html}${pad}</${tag}>\n`;
+  }
+
+  private children(block: BlockSchema): BlockSchema[] {
+    return block.children.map(id => this.blocks.get(id)).filter((b): b is BlockSchema => !!b);
+  }
+
+  private addStyleRule(block: BlockSchema) {
+    const declarations = Object.entries(block.styles)
+      .filter(([, v]) => v !== '' && v !== null && v !== undefined)
+      .map(([k, v]) => `  ${kebabCase(k)}: ${v};`);
+    if (declarations.length) {
+      this.styleRules.push(`[data-block="${block.id}"] {\n${declarations.join('\n')}\n}`);
+    }
+  }
+
+  private genStylesheet(): string {
+    return `/* Generated for "${this.project.name}". Block styles are keyed by data-block id. */
+*, *::before, *::after { box-sizing: border-box; }
+${this.styleRules.length ? `\n${this.styleRules.join('\n\n')}\n` : ''}`;
+  }
+}
@@ -0,0 +1,39 @@
+import { test, type TestContext } from 'node:test';
+import assert from 'node:assert/strict';
+import fs from 'fs-extra';
+import os from 'os';
+import path from 'path';
+import { cancelInstall, installDependencies } from './installer.js';
+
+/** `npm` on PATH replaced by a script that records its pid and then hangs */
+async function useHangingNpm(t: TestContext, dir: string) {
+  const bin = path.join(dir, 'bin');
+  await fs.outputFile(path.join(bin, 'npm'), `#!/bin/sh\necho $$ > "${path.join(dir, 'npm.pid')}"\nexec sleep 30\n`, { mode: 0o755 });
+  const previous = process.env.PATH;
+  process.env.PATH = `${bin}${path.delimiter}${previous}`;
+  t.after(() => {
+    process.env.PATH = previous;
+  });
+}
+
+test('cancelInstall stops the running npm and skips the remaining targets', { skip: process.platform === 'win32' }, async t => {
+  const root = await fs.mkdtemp(path.join(os.tmpdir(), 'akasha-install-'));
+  t.after(() => fs.remove(root));
+  await useHangingNpm(t, root);
+  await fs.outputJson(path.join(root, 'package.json'), {});
+  await fs.outputJson(path.join(root, 'backend', 'package.json'), {});
+
+  assert.equal(cancelInstall('p1'), false);
+  const install = installDependencies('p1', root);
+  const pidFile = path.join(root, 'npm.pid');
+  while (!(await fs.pathExists(pidFile))) await new Promise(resolve => setTimeout(resolve, 10));
+  const pid = Number((await fs.readFile(pidFile, 'utf-8')).trim());
+
+  assert.equal(cancelInstall('p1'), true);
+  const result = await install;
+
+  assert.deepEqual([result.success, result.cancelled], [false, true]);
+  assert.deepEqual(result.steps.map(s => [s.target, s.status]), [['client', 'cancelled'], ['server', 'skipped']]);
+  assert.throws(() => process.kill(pid, 0), { code: 'ESRCH' });
+  assert.equal(cancelInstall('p1'), false);
+});
@@ -0,0 +1,139 @@
+import { spawn, type ChildProcess } from 'child_process';
+import fs from 'fs-extra';
+import path from 'path';
+import { outputLineWriter } from './processOutput.js';
+
+export const INSTALL_TIMEOUT_MS = 300_000;
+
+/** Grace period between SIGTERM and SIGKILL when an install is stopped */
+const KILL_GRACE_MS = 5_000;
+
+export interface InstallStep {
+  target: string;
+  success: boolean;
+  timed_out: boolean;
+  cancelled: boolean;
+  duration_ms: number;
+  stdout: string;
+  stderr: string;
+  status: 'success' | 'failed' | 'timed_out' | 'cancelled' | 'skipped';
+}
+
+export interface InstallResult {
+  success: boolean;
+  cancelled: boolean;
+  steps: InstallStep[];
+}
+
+export class InstallInProgressError extends Error {
+  constructor() {
+    super('Dependencies are already being installed for this project');
+    this.name = 'InstallInProgressError';
+  }
+}
+
+interface RunningInstall {
+  child: ChildProcess | null;
+  cancelled: boolean;
+}
+
+/** The npm process of each project's install, so it can be cancelled from another request */
+const running = new Map<string, RunningInstall>();
+
+/** Generated frontend at the project root, generated backend under `backend/` */
+function installTargets(rootPath: string) {
+  return [
+    { target: 'client', dir: rootPath },
+    { target: 'server', dir: path.join(rootPath, 'backend') },
+  ];
+}
+
+/** SIGTERM, then SIGKILL if the process is still alive after the grace period */
+export function stopProcess(child: ChildProcess) {
+  if (child.exitCode !== null || child.signalCode !== null) return;
+  child.kill('SIGTERM');
+  setTimeout(() => {
+    if (child.exitCode === null && child.signalCode === null) child.kill('SIGKILL');
+  }, KILL_GRACE_MS).unref();
+}
+
+function runStep(projectId: string, target: string, dir: string, state: RunningInstall): Promise<InstallStep> {
+  const started = Date.now();
+
+  return new Promise(resolve => {
+    const child = spawn('npm', ['install'], { cwd: dir, shell: process.platform === 'win32' });
+    state.child = child;
+
+    let stdout = '';
+    let stderr = '';
+    let timedOut = false;
+    // Lines also go out live over the change socket
+    const outLines = outputLineWriter(projectId, 'install', target, 'stdout');
+    const errLines = outputLineWriter(projectId, 'install', target, 'stderr');
+    child.stdout?.on('data', chunk => { stdout += chunk; outLines.write(chunk); });
+    child.stderr?.on('data', chunk => { stderr += chunk; errLines.write(chunk); });
+
+    const timer = setTimeout(() => {
+      timedOut = true;
+      stopProcess(child);
+    }, INSTALL_TIMEOUT_MS);
+
+    const finish = (code: number | null, error?: Error) => {
+      clearTimeout(timer);
+      outLines.flush();
+      errLines.flush();
+      state.child = null;
+      const success = !error && code === 0 && !timedOut && !state.cancelled;
+      resolve({
+        target,
+        success,
+        timed_out: timedOut,
+        cancelled: state.cancelled,
+        duration_ms: Date.now() - started,
+        stdout,
+        stderr: error ? `${stderr}${error.message}\n` : stderr,
+        status: state.cancelled ? 'cancelled' : timedOut ? 'timed_out' : success ? 'success' : 'failed',
+      });
+    };
+    child.on('error', error => finish(null, error));
+    child.on('close', code => finish(code));
+  });
+}
+
+/**
+ * Run `npm install` for each target that has a package.json, one after another. Only one
+ * install per project runs at a time; `cancelInstall` stops it and skips the remaining targets.
+ */
+export async function installDependencies(projectId: string, rootPath: string): Promise<InstallResult> {
+  if (running.has(projectId)) throw new InstallInProgressError();
+
+  const state: RunningInstall = { child: null, cancelled: false };
+  running.set(projectId, state);
+
+  try {
+    const steps: InstallStep[] = [];
+    for (const { target, dir } of installTargets(rootPath)) {
+      if (!(await fs.pathExists(path.join(dir, 'package.json')))) continue;
+
+      if (state.cancelled) {
+        steps.push({ target, success: false, timed_out: false, cancelled: true, duration_ms: 0, stdout: '', stderr: '', status: 'skipped' });
+        continue;
+      }
+      steps.push(await runStep(projectId, target, dir, state));
+    }
+
+    return { success: !state.cancelled && steps.every(s => s.success), cancelled: state.cancelled, steps };
+  } finally {
+    running.delete(projectId);
+  }
+}
+
+/** Stop the project's running install. Returns false when nothing was installing. */
+export function cancelInstall(projectId: string): boolean {
+  const state = running.get(projectId);
+  if (!state) return false;
+
+  state.cancelled = true;
+  if (state.child) stopProcess(state.child);
+  return true;
+}
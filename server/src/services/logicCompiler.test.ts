@@ -0,0 +1,76 @@
+import { test } from 'node:test';
+import assert from 'node:assert/strict';
+import { LogicCompiler, type LogicFlow, type LogicNode } from './logicCompiler.js';
+
+const flow = (nodes: LogicNode[]): LogicFlow => ({ id: 'flow', name: 'Flow', trigger: { type: 'manual' }, nodes });
+
+/** Compile a flow and load the generated function; its only annotations are `any` types */
+const load = (source: LogicFlow) => {
+  const compiler = new LogicCompiler();
+  const code = compiler.compileFlow(source).replace(/^export /m, '').replace(/: (any|Record<string, any>)/g, '');
+  return new Function(`${code}\nreturn ${compiler.functionName(source)};`)() as (input?: unknown) => Promise<unknown>;
+};
+
+test('compiled Parallel stores the last result each branch wrote', async () => {
+  const run = load(flow([
+    { id: 'split', node_type: 'Parallel', data: { result_var: 'both' }, next_nodes: ['a1', 'b1'] },
+    { id: 'a1', node_type: 'SetVariable', data: { name: 'a', value: '1' }, next_nodes: ['a2'] },
+    { id: 'a2', node_type: 'Transform', data: { result_var: 'a2', expression: 'state.a + 1' } },
+    { id: 'b1', node_type: 'SetVariable', data: { name: 'b', value: '"b"' } },
+  ]));
+  assert.deepEqual(await run(), { a: 1, a2: 2, b: 'b', both: [2, 'b'] });
+});
+
+test('a Return inside a Parallel branch ends the whole flow', async () => {
+  const run = load(flow([
+    { id: 'split', node_type: 'Parallel', next_nodes: ['a', 'inner'] },
+    { id: 'a', node_type: 'SetVariable', data: { name: 'a', value: '1' } },
+    { id: 'inner', node_type: 'Parallel', next_nodes: ['done'] },
+    { id: 'done', node_type: 'Return', data: { value: 'input.x * 2' } },
+    { id: 'after', node_type: 'SetVariable', data: { name: 'after', value: 'true' } },
+  ]));
+  assert.equal(await run({ x: 21 }), 42);
+});
+
+test('a scheduled backend flow becomes a @Cron method in schedule.service.ts', () => {
+  const files = new LogicCompiler().genScheduleRunner([
+    { ...flow([]), id: 'cleanup', name: 'Cleanup', context: 'backend', trigger: { type: 'schedule', cron: '*/10 * * * *' } },
+    { ...flow([]), id: 'ui', name: 'Ui', context: 'frontend', trigger: { type: 'schedule', cron: '* * * * *' } },
+  ]);
+  const service = files.find(f => f.path === 'src/schedule/schedule.service.ts')?.content ?? '';
+
+  assert.match(service, /@Cron\('\*\/10 \* \* \* \*'\)\n\s+async \w+\(\) \{[^}]*await runFlow\('cleanup', \{ trigger: 'schedule' \}\);/);
+  assert.equal(service.match(/@Cron\(/g)?.length, 1);
+  assert.ok(files.some(f => f.path === 'src/schedule/schedule.module.ts'));
+  assert.deepEqual(new LogicCompiler().genScheduleRunner([flow([])]), []);
+});
+
+test('validateFlow reports a Condition missing its operator and passes a complete flow', () => {
+  const compiler = new LogicCompiler();
+  assert.deepEqual(compiler.validateFlow(flow([
+    { id: 'check', node_type: 'Condition', data: { left: 'input.age', right: '18' } },
+  ])), [{ node_id: 'check', node_type: 'Condition', message: 'Condition node is missing "operator"' }]);
+
+  assert.deepEqual(compiler.validateFlow(flow([
+    { id: 'check', node_type: 'Condition', data: { left: 'input.age', operator: '>=', right: '18' }, next_nodes: ['adult'], else_nodes: ['minor'] },
+    { id: 'adult', node_type: 'SetVariable', data: { name: 'adult', value: 'true' } },
+    { id: 'minor', node_type: 'FetchApi', data: { url: '/api/guardians' } },
+  ])), []);
+});
+
+test('a transactional flow runs its DB nodes on the transaction client', () => {
+  const code = new LogicCompiler().compileFlow({
+    ...flow([
+      { id: 'user', node_type: 'DbCreate', data: { model: 'User', data: '{ email: input.email }', result_var: 'user' }, next_nodes: ['log'] },
+      { id: 'log', node_type: 'SetVariable', data: { name: 'created', value: 'true' }, next_nodes: ['audit'] },
+      { id: 'audit', node_type: 'DbCreate', data: { model: 'AuditLog', data: '{ userId: state.user.id }' } },
+    ]),
+    transactional: true,
+  });
+
+  assert.match(code, /return prisma\.\$transaction\(async \(tx\) => \{/);
+  const body = code.slice(code.indexOf('$transaction'));
+  assert.match(body, /tx\.user\.create\(/);
+  assert.match(body, /tx\.auditLog\.create\(/);
+  assert.doesNotMatch(body.slice(body.indexOf('=>')), /prisma\./);
+});
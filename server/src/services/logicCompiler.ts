@@ -0,0 +1,350 @@
+import { camelCase, pascalCase } from '../utils/string.js';
+import type { GeneratedFile } from './projectSchema.js';
+
+export interface LogicNode {
+  id: string;
+  node_type: string;
+  data?: Record<string, any>;
+  label?: string;
+  next_nodes?: string[];
+  else_nodes?: string[];
+}
+
+export interface LogicFlow {
+  id: string;
+  name: string;
+  trigger: { type: string; [key: string]: any };
+  nodes: LogicNode[];
+  /** The editor's canvas connections; compiling follows `next_nodes`, these only round-trip through export */
+  edges?: unknown[];
+  entry_node_id?: string;
+  context?: string;
+  /** Run the flow inside one Prisma interactive transaction so its DB writes are atomic */
+  transactional?: boolean;
+}
+
+export interface FlowIssue {
+  node_id: string;
+  node_type: string;
+  message: string;
+}
+
+/** `data` keys each node type must define to compile into meaningful code */
+const REQUIRED_NODE_DATA: Record<string, string[]> = {
+  SetVariable: ['name', 'value'],
+  Condition: ['left', 'operator', 'right'],
+  FetchApi: ['url'],
+  DbCreate: ['model', 'data'],
+  DbRead: ['model'],
+  DbUpdate: ['model', 'where', 'data'],
+  DbDelete: ['model', 'where'],
+  Transform: ['expression'],
+};
+
+const DB_NODE_TYPES = new Set(['DbCreate', 'DbRead', 'DbUpdate', 'DbDelete']);
+
+/**
+ * Hydrate a LogicFlow row (JSON string columns) into the schema shape the compiler works on
+ */
+export function toLogicFlow(record: any): LogicFlow {
+  const parse = (value: unknown, fallback: any) => {
+    if (typeof value !== 'string') return value ?? fallback;
+    try { return JSON.parse(value); } catch { return fallback; }
+  };
+
+  return {
+    id: record.id,
+    name: record.name,
+    trigger: parse(record.trigger, { type: 'manual' }),
+    nodes: parse(record.nodes, []),
+    edges: parse(record.edges, []),
+    entry_node_id: record.entry_node_id,
+    context: record.context,
+    transactional: !!record.transactional,
+  };
+}
+
+/**
+ * Compiles visual logic flows (node graphs) into plain async TypeScript functions.
+ * Each node reads and writes a shared `state` object; the function returns `state`
+ * unless a Return node short-circuits it.
+ */
+export class LogicCompiler {
+  private nodes = new Map<string, LogicNode>();
+  /** Client the DB nodes call; `tx` inside a transactional flow */
+  private db = 'prisma';
+  /** How many Parallel branch closures enclose the node being emitted */
+  private branchDepth = 0;
+
+  public functionName(flow: LogicFlow): string {
+    const name = camelCase(flow.name || 'flow') || 'flow';
+    return /^[0-9]/.test(name) ? `flow${name}` : name;
+  }
+
+  /**
+   * Compile a single flow into an exported async function
+   */
+  public compileFlow(flow: LogicFlow): string {
+    this.nodes = new Map(flow.nodes.map(n => [n.id, n]));
+    const entry = this.findEntryNode(flow);
+    const header = `// Generated from logic flow "${flow.name}" (${flow.id})
+export async function ${this.functionName(flow)}(input: any = {}, state: Record<string, any> = {}) {
+`;
+
+    // Every node runs inside the callback so Return still ends the flow with its value
+    if (flow.transactional && flow.nodes.some(n => DB_NODE_TYPES.has(n.node_type))) {
+      this.db = 'tx';
+      const body = entry ? this.walkNode(entry.id, 2, new Set<string>()) : '';
+      this.db = 'prisma';
+      return `${header}  return prisma.$transaction(async (tx) => {
+${body}    return state;
+  });
+}
+`;
+    }
+
+    const body = entry ? this.walkNode(entry.id, 1, new Set<string>()) : '';
+    return `${header}${body}  return state;
+}
+`;
+  }
+
+  /**
+   * Check each node for the data keys it needs. The compiler falls back to placeholders
+   * for missing values, so this is the only place such gaps surface.
+   */
+  public validateFlow(flow: LogicFlow): FlowIssue[] {
+    const issues: FlowIssue[] = [];
+    const ids = new Set(flow.nodes.map(n => n.id));
+
+    for (const node of flow.nodes) {
+      const data = node.data || {};
+      for (const key of REQUIRED_NODE_DATA[node.node_type] || []) {
+        const value = data[key];
+        if (value === undefined || value === null || (typeof value === 'string' && !value.trim())) {
+          issues.push({ node_id: node.id, node_type: node.node_type, message: `${node.node_type} node is missing "${key}"` });
+        }
+      }
+
+      for (const target of [...(node.next_nodes || []), ...(node.else_nodes || [])]) {
+        if (!ids.has(target)) {
+          issues.push({ node_id: node.id, node_type: node.node_type, message: `Connects to unknown node "${target}"` });
+        }
+      }
+    }
+
+    return issues;
+  }
+
+  /**
+   * Compile every flow for one context into a bundle: one file per flow plus a
+   * `flow-runner.ts` that dispatches by flow id.
+   */
+  public compileBundle(flows: LogicFlow[], context: 'frontend' | 'backend', baseDir: string): GeneratedFile[] {
+    const scoped = flows.filter(f => (f.context || 'frontend') === context);
+    const files: GeneratedFile[] = [];
+
+    for (const flow of scoped) {
+      const header = context === 'backend' ? `import { prisma } from '../../prisma/client';\n\n` : '';
+      files.push({
+        path: `${baseDir}/flows/${this.functionName(flow)}.ts`,
+        content: header + this.compileFlow(flow),
+      });
+    }
+
+    const imports = scoped.map(f => `import { ${this.functionName(f)} } from './flows/${this.functionName(f)}';`).join('\n');
+    const entries = scoped.map(f => `  ${JSON.stringify(f.id)}: ${this.functionName(f)},`).join('\n');
+    files.push({
+      path: `${baseDir}/flow-runner.ts`,
+      content: `${imports}
+
+type FlowFn = (input?: any, state?: Record<string, any>) => Promise<any>;
+
+const flows: Record<string, FlowFn> = {
+${entries}
+};
+
+export async function runFlow(flowId: string, payload: any = {}) {
+  const flow = flows[flowId];
+  if (!flow) throw new Error(\`Unknown logic flow: \${flowId}\`);
+  return flow(payload);
+}
+`,
+    });
+
+    return files;
+  }
+
+  /**
+   * Backend flows with a schedule trigger become @Cron methods on a Nest provider.
+   * Returns no files when nothing is scheduled.
+   */
+  public genScheduleRunner(flows: LogicFlow[]): GeneratedFile[] {
+    const scheduled = flows.filter(f => f.context === 'backend' && f.trigger?.type === 'schedule' && f.trigger.cron);
+    if (scheduled.length === 0) return [];
+
+    const methods = scheduled.map(f => `  @Cron('${String(f.trigger.cron).replace(/'/g, '')}')
+  async run${pascalCase(this.functionName(f))}() {
+    this.logger.log('Running scheduled flow "${f.name.replace(/"/g, '')}"');
+    await runFlow('${f.id}', { trigger: 'schedule' });
+  }`).join('\n\n');
+
+    return [
+      {
+        path: 'src/schedule/schedule.service.ts',
+        content: `import { Injectable, Logger } from '@nestjs/common';
+import { Cron } from '@nestjs/schedule';
+import { runFlow } from '../logic/flow-runner';
+
+@Injectable()
+export class ScheduleService {
+  private readonly logger = new Logger(ScheduleService.name);
+
+${methods}
+}
+`,
+      },
+      {
+        path: 'src/schedule/schedule.module.ts',
+        content: `import { Module } from '@nestjs/common';
+import { ScheduleModule } from '@nestjs/schedule';
+import { ScheduleService } from './schedule.service';
+
+@Module({
+  imports: [ScheduleModule.forRoot()],
+  providers: [ScheduleService],
+})
+export class FlowScheduleModule {}
+`,
+      },
+    ];
+  }
+
+  private findEntryNode(flow: LogicFlow): LogicNode | undefined {
+    if (flow.entry_node_id && this.nodes.has(flow.entry_node_id)) {
+      return this.nodes.get(flow.entry_node_id);
+    }
+    const start = flow.nodes.find(n => n.node_type === 'Start');
+    if (start) return start;
+
+    // Fall back to the first node nothing points at
+    const targets = new Set(flow.nodes.flatMap(n => [...(n.next_nodes || []), ...(n.else_nodes || [])]));
+    return flow.nodes.find(n => !targets.has(n.id)) || flow.nodes[0];
+  }
+
+  private resultKey(node: LogicNode): string {
+    return JSON.stringify(node.data?.result_var || node.id);
+  }
+
+  /** Write a node's result into state; inside a Parallel branch it also becomes the branch's result */
+  private assign(pad: string, key: string, expression: string): string {
+    return `${pad}${this.branchDepth > 0 ? 'result = ' : ''}state[${key}] = ${expression};\n`;
+  }
+
+  /** What a Return hands back: the value itself, or a marker the enclosing Parallel passes on */
+  private returned(expression: string): string {
+    return this.branchDepth > 0 ? `{ returned: true, value: ${expression} }` : expression;
+  }
+
+  private walkChildren(ids: string[] | undefined, indent: number, visited: Set<string>): string {
+    let out = '';
+    for (const id of ids || []) {
+      out += this.walkNode(id, indent, visited);
+    }
+    return out;
+  }
+
+  /**
+   * Emit the statements for a node, then recurse into its successors.
+   * `visited` guards against cycles in the graph.
+   */
+  private walkNode(nodeId: string, indent: number, visited: Set<string>): string {
+    const pad = '  '.repeat(indent);
+    const node = this.nodes.get(nodeId);
+    if (!node) return `${pad}// Missing node ${nodeId}\n`;
+    if (visited.has(nodeId)) return `${pad}// Cycle detected: ${nodeId} already visited\n`;
+    visited.add(nodeId);
+
+    const data = node.data || {};
+    let out = '';
+
+    switch (node.node_type) {
+      case 'Start':
+      case 'End':
+        break;
+
+      case 'SetVariable':
+        out += this.assign(pad, JSON.stringify(data.name || node.id), data.value ?? 'undefined');
+        break;
+
+      case 'Condition': {
+        out += `${pad}if (${data.left ?? 'true'} ${data.operator ?? '==='} ${data.right ?? 'true'}) {\n`;
+        out += this.walkChildren(node.next_nodes, indent + 1, visited);
+        out += `${pad}} else {\n`;
+        out += this.walkChildren(node.else_nodes, indent + 1, visited);
+        out += `${pad}}\n`;
+        return out;
+      }
+
+      case 'FetchApi': {
+        const method = String(data.method || 'GET').toUpperCase();
+        const init = data.body
+          ? `{ method: '${method}', headers: { 'Content-Type': 'application/json' }, body: JSON.stringify(${data.body}) }`
+          : `{ method: '${method}' }`;
+        out += this.assign(pad, this.resultKey(node), `await fetch(${JSON.stringify(data.url || '/api/data')}, ${init}).then(r => r.json())`);
+        break;
+      }
+
+      case 'DbCreate':
+      case 'DbRead':
+      case 'DbUpdate':
+      case 'DbDelete': {
+        const model = camelCase(data.model || 'record');
+        const op = { DbCreate: 'create', DbRead: 'findMany', DbUpdate: 'update', DbDelete: 'delete' }[node.node_type];
+        const args = [data.where && `where: ${data.where}`, data.data && `data: ${data.data}`].filter(Boolean).join(', ');
+        out += this.assign(pad, this.resultKey(node), `await ${this.db}.${model}.${op}(${args ? `{ ${args} }` : ''})`);
+        break;
+      }
+
+      case 'Transform':
+        out += this.assign(pad, this.resultKey(node), data.expression ?? 'input');
+        break;
+
+      case 'Log':
+        out += `${pad}console.log(${data.message ?? JSON.stringify(node.label || node.id)});\n`;
+        break;
+
+      case 'Return':
+        out += `${pad}return ${this.returned(data.value ?? 'state')};\n`;
+        return out;
+
+      case 'Parallel': {
+        // Each outgoing branch runs as its own async closure and hands back the last result it
+        // wrote; a Return inside any branch ends the flow once every branch has settled
+        out += `${pad}{\n`;
+        out += `${pad}  const results = await Promise.all([\n`;
+        this.branchDepth++;
+        for (const branchId of node.next_nodes || []) {
+          out += `${pad}    (async () => {\n`;
+          out += `${pad}      let result: any;\n`;
+          out += this.walkNode(branchId, indent + 3, new Set(visited));
+          out += `${pad}      return { returned: false, value: result };\n`;
+          out += `${pad}    })(),\n`;
+        }
+        this.branchDepth--;
+        out += `${pad}  ]);\n`;
+        out += `${pad}  const returned = results.find(r => r.returned);\n`;
+        out += `${pad}  if (returned) return ${this.returned('returned.value')};\n`;
+        out += this.assign(`${pad}  `, this.resultKey(node), 'results.map(r => r.value)');
+        out += `${pad}}\n`;
+        return out;
+      }
+
+      default:
+        out += `${pad}// Unsupported node type: ${node.node_type}\n`;
+    }
+
+    out += this.walkChildren(node.next_nodes, indent, visited);
+    return out;
+  }
+}
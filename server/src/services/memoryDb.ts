@@ -0,0 +1,135 @@
+import type { TestContext } from 'node:test';
+import { ObjectId } from 'mongodb';
+import { getCommandDb, setCommandDb, type CommandDb } from './commands.js';
+
+type Row = Record<string, any>;
+
+interface FindArgs {
+  where?: Row;
+  orderBy?: Row | Row[];
+  skip?: number;
+  take?: number;
+  select?: Record<string, boolean>;
+}
+
+/** Prisma's filter semantics for the shapes the commands send: equality, `{ in }` and ignored `undefined` */
+function matches(row: Row, where: Row = {}): boolean {
+  return Object.entries(where).every(([key, condition]) => {
+    if (condition === undefined) return true;
+    if (condition && typeof condition === 'object' && Array.isArray(condition.in)) return condition.in.includes(row[key]);
+    return (row[key] ?? null) === condition;
+  });
+}
+
+function pick(row: Row, select?: Record<string, boolean>): Row {
+  if (!select) return { ...row };
+  return Object.fromEntries(Object.keys(select).filter(key => select[key]).map(key => [key, row[key]]));
+}
+
+function compareBy(orderBy: Row | Row[] = []) {
+  const keys = (Array.isArray(orderBy) ? orderBy : [orderBy]).flatMap(entry => Object.entries(entry));
+  return (a: Row, b: Row) => {
+    for (const [key, direction] of keys) {
+      if (a[key] === b[key]) continue;
+      const order = a[key] < b[key] ? -1 : 1;
+      return direction === 'desc' ? -order : order;
+    }
+    return 0;
+  };
+}
+
+/**
+ * An in-memory stand-in for one Prisma collection. Rows are copied in and out, so callers
+ * can't change stored rows except through `create`/`update`/`delete`.
+ */
+export class MemoryCollection {
+  public rows: Row[];
+  private defaults: () => Row;
+
+  constructor(defaults: () => Row, rows: Row[] = []) {
+    this.defaults = defaults;
+    this.rows = rows.map(row => ({ ...defaults(), ...row }));
+  }
+
+  async findUnique({ where, select }: FindArgs) {
+    const row = this.rows.find(r => matches(r, where));
+    return row ? pick(row, select) : null;
+  }
+
+  async findUniqueOrThrow(args: FindArgs) {
+    const row = await this.findUnique(args);
+    if (!row) throw new Error('No record found');
+    return row;
+  }
+
+  async findFirst(args: FindArgs = {}) {
+    return (await this.findMany({ ...args, take: 1 }))[0] ?? null;
+  }
+
+  async findMany({ where, orderBy, skip = 0, take, select }: FindArgs = {}) {
+    const rows = this.rows.filter(r => matches(r, where)).sort(compareBy(orderBy));
+    return rows.slice(skip, take === undefined ? undefined : skip + take).map(row => pick(row, select));
+  }
+
+  async count({ where }: FindArgs = {}) {
+    return this.rows.filter(r => matches(r, where)).length;
+  }
+
+  async create({ data }: { data: Row }) {
+    const row = { ...this.defaults(), ...data };
+    if (this.rows.some(r => r.id === row.id)) throw new Error(`Unique constraint failed on id ${row.id}`);
+    this.rows.push(row);
+    return { ...row };
+  }
+
+  async update({ where, data }: { where: Row; data: Row }) {
+    const row = this.rows.find(r => matches(r, where));
+    if (!row) throw new Error('Record to update not found');
+    Object.assign(row, data);
+    return { ...row };
+  }
+
+  async updateMany({ where, data }: { where?: Row; data: Row }) {
+    const rows = this.rows.filter(r => matches(r, where));
+    for (const row of rows) Object.assign(row, data);
+    return { count: rows.length };
+  }
+
+  async delete({ where }: { where: Row }) {
+    const index = this.rows.findIndex(r => matches(r, where));
+    if (index === -1) throw new Error('Record to delete does not exist');
+    return this.rows.splice(index, 1)[0];
+  }
+
+  async deleteMany({ where }: { where?: Row } = {}) {
+    const before = this.rows.length;
+    this.rows = this.rows.filter(r => !matches(r, where));
+    return { count: before - this.rows.length };
+  }
+}
+
+const newId = () => new ObjectId().toHexString();
+
+/** Block, page, variable, data model and command log collections with the schema's defaults, for tests that run commands without MongoDB */
+export function memoryCommandDb(seed: { blocks?: Row[]; pages?: Row[]; variables?: Row[]; dataModels?: Row[] } = {}) {
+  const db = {
+    block: new MemoryCollection(() => ({
+      id: newId(), pageId: null, parentId: null, name: '', properties: '{}', styles: '{}', responsiveStyles: '{}',
+      classes: '[]', events: '{}', bindings: '{}', children: '[]', order: 0, archived: false,
+    }), seed.blocks),
+    page: new MemoryCollection(() => ({ idRoot: newId(), isDynamic: false, meta: '{}', archived: false }), seed.pages),
+    variable: new MemoryCollection(() => ({ id: newId(), scope: 'global', pageId: null, persist: false, value: null }), seed.variables),
+    dataModel: new MemoryCollection(() => ({ id: newId(), schema: '{}', archived: false }), seed.dataModels),
+    commandLog: new MemoryCollection(() => ({ id: newId(), createdAt: new Date() })),
+  };
+  return db as unknown as typeof db & CommandDb;
+}
+
+/** Run commands against in-memory collections for the rest of the test */
+export function useMemoryDb(t: TestContext, seed: Parameters<typeof memoryCommandDb>[0] = {}) {
+  const previous = getCommandDb();
+  const db = memoryCommandDb(seed);
+  setCommandDb(db);
+  t.after(() => setCommandDb(previous));
+  return db;
+}
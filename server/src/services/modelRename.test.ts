@@ -0,0 +1,51 @@
+import { test } from 'node:test';
+import assert from 'node:assert/strict';
+import { renameModelReferences, type ModelRenameDb } from './modelRename.js';
+
+/** An in-memory collection with the two calls a rename makes */
+function collection(rows: Array<Record<string, any>>) {
+  return {
+    rows,
+    findMany: async () => rows.map(row => ({ ...row })),
+    update: async ({ where, data }: { where: { id: string }; data: Record<string, any> }) => {
+      const row = rows.find(r => r.id === where.id)!;
+      Object.assign(row, data);
+      return row;
+    },
+  };
+}
+
+test('renameModelReferences rewrites relations, shapes, flow nodes and bindings that use the old name', async () => {
+  const db = {
+    dataModel: collection([
+      { id: 'm1', schema: JSON.stringify({ relations: [] }) },
+      { id: 'm2', schema: JSON.stringify({ relations: [{ target_model_id: 'Item' }, { target_model_id: 'm9' }] }) },
+    ]),
+    apiEndpoint: collection([
+      { id: 'a1', config: JSON.stringify({ response_body: { shape_type: 'array', item_shape: { shape_type: 'model', model_ref: 'Item' } } }) },
+      { id: 'a2', config: JSON.stringify({ request_body: { shape_type: 'model', model_ref: 'Other' } }) },
+    ]),
+    logicFlow: collection([
+      { id: 'f1', nodes: JSON.stringify([{ node_type: 'DbRead', data: { model: 'Item' } }, { node_type: 'Transform', data: { model: 'Item' } }]) },
+    ]),
+    block: collection([
+      { id: 'b1', bindings: JSON.stringify({ data: { type: 'model', value: 'Item' }, text: { type: 'variable', value: 'Item' } }) },
+    ]),
+  };
+
+  const summary = await renameModelReferences('p1', 'm1', 'Item', 'Product', db as unknown as ModelRenameDb);
+
+  assert.deepEqual(summary, { relations: 1, apis: 1, logic_flows: 1, blocks: 1 });
+  assert.deepEqual(JSON.parse(db.dataModel.rows[1]!.schema).relations, [{ target_model_id: 'm1' }, { target_model_id: 'm9' }]);
+  assert.equal(JSON.parse(db.apiEndpoint.rows[0]!.config).response_body.item_shape.model_ref, 'Product');
+  assert.deepEqual(JSON.parse(db.logicFlow.rows[0]!.nodes).map((n: any) => n.data.model), ['Product', 'Item']);
+  assert.deepEqual(JSON.parse(db.block.rows[0]!.bindings), { data: { type: 'model', value: 'Product' }, text: { type: 'variable', value: 'Item' } });
+});
+
+test('renameModelReferences does nothing when the name is unchanged', async () => {
+  const db = { dataModel: collection([]), apiEndpoint: collection([]), logicFlow: collection([]), block: collection([]) };
+  assert.deepEqual(
+    await renameModelReferences('p1', 'm1', 'Item', 'Item', db as unknown as ModelRenameDb),
+    { relations: 0, apis: 0, logic_flows: 0, blocks: 0 },
+  );
+});
@@ -0,0 +1,80 @@
+import prisma from '../lib/prisma.js';
+import { parseJsonValue } from './projectSchema.js';
+
+/** The collections a rename reads and rewrites; the Prisma client unless a test passes its own */
+export type ModelRenameDb = Pick<typeof prisma, 'dataModel' | 'apiEndpoint' | 'logicFlow' | 'block'>;
+
+/** How many records a model rename rewrote, by kind */
+export interface ModelRenameSummary {
+  relations: number;
+  apis: number;
+  logic_flows: number;
+  blocks: number;
+}
+
+/** Point `model_ref`s in a request/response shape at the new name; true when any changed */
+function renameShapeRefs(shape: any, from: string, to: string): boolean {
+  if (!shape || typeof shape !== 'object') return false;
+  let changed = false;
+  if (shape.model_ref === from) {
+    shape.model_ref = to;
+    changed = true;
+  }
+  if (renameShapeRefs(shape.item_shape, from, to)) changed = true;
+  for (const field of shape.fields || []) {
+    if (renameShapeRefs(field.nested, from, to)) changed = true;
+  }
+  return changed;
+}
+
+/**
+ * Rewrite everything in a project that refers to a model by its old name: relations on other
+ * models (moved to the model's id), `model_ref`s in endpoint shapes, the `model` of Db* flow
+ * nodes and `model` bindings on blocks. References by id are left alone since they still
+ * resolve. Archived records are updated too, so restoring one doesn't bring a stale name back.
+ */
+export async function renameModelReferences(
+  projectId: string, modelId: string, from: string, to: string, db: ModelRenameDb = prisma,
+): Promise<ModelRenameSummary> {
+  const summary: ModelRenameSummary = { relations: 0, apis: 0, logic_flows: 0, blocks: 0 };
+  if (from === to) return summary;
+
+  for (const model of await db.dataModel.findMany({ where: { projectId } })) {
+    const schema = parseJsonValue<Record<string, any>>(model.schema, {});
+    const relations: any[] = schema.relations || [];
+    const stale = relations.filter(r => r.target_model_id === from);
+    if (stale.length === 0) continue;
+    for (const relation of stale) relation.target_model_id = modelId;
+    await db.dataModel.update({ where: { id: model.id }, data: { schema: JSON.stringify(schema) } });
+    summary.relations += stale.length;
+  }
+
+  for (const api of await db.apiEndpoint.findMany({ where: { projectId } })) {
+    const config = parseJsonValue<Record<string, any>>(api.config, {});
+    const request = renameShapeRefs(config.request_body, from, to);
+    const response = renameShapeRefs(config.response_body, from, to);
+    if (!request && !response) continue;
+    await db.apiEndpoint.update({ where: { id: api.id }, data: { config: JSON.stringify(config) } });
+    summary.apis++;
+  }
+
+  for (const flow of await db.logicFlow.findMany({ where: { projectId } })) {
+    const nodes = parseJsonValue<any[]>(flow.nodes, []);
+    const stale = nodes.filter(n => /^Db[A-Z]/.test(n?.node_type) && n.data?.model === from);
+    if (stale.length === 0) continue;
+    for (const node of stale) node.data.model = to;
+    await db.logicFlow.update({ where: { id: flow.id }, data: { nodes: JSON.stringify(nodes) } });
+    summary.logic_flows++;
+  }
+
+  for (const block of await db.block.findMany({ where: { projectId } })) {
+    const bindings = parseJsonValue<Record<string, any>>(block.bindings, {});
+    const stale = Object.values(bindings).filter(b => b?.type === 'model' && b.value === from);
+    if (stale.length === 0) continue;
+    for (const binding of stale) binding.value = to;
+    await db.block.update({ where: { id: block.id }, data: { bindings: JSON.stringify(bindings) } });
+    summary.blocks++;
+  }
+
+  return summary;
+}
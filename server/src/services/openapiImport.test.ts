@@ -0,0 +1,28 @@
+import { test } from 'node:test';
+import assert from 'node:assert/strict';
+import { importOpenApi } from './openapiImport.js';
+
+test('importOpenApi turns each operation into an endpoint with its method, route and permissions', () => {
+  const spec = JSON.stringify({
+    openapi: '3.0.0',
+    security: [{ bearerAuth: [] }],
+    paths: {
+      '/users': {
+        get: { operationId: 'listUsers', security: [], responses: { 200: { description: 'ok' } } },
+      },
+      '/users/{id}': {
+        parameters: [{ name: 'id', in: 'path', required: true, schema: { type: 'integer' } }],
+        delete: { operationId: 'deleteUser', responses: { 204: { description: 'gone' } } },
+      },
+    },
+  });
+
+  const apis = importOpenApi(spec);
+
+  assert.deepEqual(apis.map(a => [a.method, a.path, a.name, a.permissions]), [
+    ['GET', '/users', 'listUsers', []],
+    ['DELETE', '/users/:id', 'deleteUser', ['bearerAuth']],
+  ]);
+  assert.deepEqual(apis[1]!.path_params, [{ name: 'id', param_type: 'number', required: true }]);
+  assert.equal(apis[1]!.success_status, 204);
+});
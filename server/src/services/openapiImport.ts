@@ -0,0 +1,177 @@
+import { camelCase, pascalCase } from '../utils/string.js';
+import type { ApiSchema } from './projectSchema.js';
+
+const HTTP_METHODS = ['get', 'post', 'put', 'patch', 'delete'];
+
+const SHAPE_TYPES: Record<string, string> = {
+  object: 'object',
+  array: 'array',
+  string: 'string',
+  number: 'number',
+  integer: 'number',
+  boolean: 'boolean',
+};
+
+/** An endpoint read from a spec, before it has been stored and given an id */
+export type ImportedApi = Omit<ApiSchema, 'id' | 'archived'>;
+
+export class OpenApiImportError extends Error {
+  constructor(message: string) {
+    super(message);
+    this.name = 'OpenApiImportError';
+  }
+}
+
+/** `/users/{id}` → `/users/:id` */
+export function toRoutePath(openApiPath: string): string {
+  return openApiPath.replace(/\{([^}]+)\}/g, ':$1');
+}
+
+/** Method and route used to decide whether an endpoint already exists */
+export function endpointKey(method: string, path: string): string {
+  return `${method.toUpperCase()} ${path.replace(/\/+$/, '') || '/'}`;
+}
+
+class SpecReader {
+  private spec: any;
+
+  constructor(spec: any) {
+    this.spec = spec;
+  }
+
+  /** Follow a local `#/components/...` reference; anything else resolves to null */
+  private resolve(value: any, seen: Set<string>): any {
+    if (!value || typeof value.$ref !== 'string') return value;
+    const ref: string = value.$ref;
+    if (!ref.startsWith('#/') || seen.has(ref)) return null;
+    seen.add(ref);
+    const target = ref.slice(2).split('/').reduce((node: any, key) => node?.[key.replace(/~1/g, '/').replace(/~0/g, '~')], this.spec);
+    return this.resolve(target, seen);
+  }
+
+  /** JSON Schema → DataShape. Recursive references stop at a plain object. */
+  public shape(schema: any, seen: Set<string> = new Set()): any {
+    const resolved = this.resolve(schema, new Set(seen));
+    if (!resolved) return { shape_type: 'object' };
+    if (typeof schema?.$ref === 'string') seen = new Set(seen).add(schema.$ref);
+
+    const type = SHAPE_TYPES[resolved.type] || (resolved.properties ? 'object' : resolved.items ? 'array' : 'object');
+    if (type === 'array') return { shape_type: 'array', item_shape: this.shape(resolved.items, seen) };
+    if (type !== 'object') return { shape_type: type };
+
+    const required: string[] = Array.isArray(resolved.required) ? resolved.required : [];
+    const fields = Object.entries<any>(resolved.properties || {}).map(([name, property]) => {
+      const nested = this.shape(property, seen);
+      return {
+        name,
+        field_type: nested.shape_type,
+        required: required.includes(name),
+        ...((nested.fields || nested.item_shape) && { nested }),
+      };
+    });
+    return { shape_type: 'object', fields };
+  }
+
+  public params(parameters: any[], location: 'path' | 'query') {
+    return parameters
+      .map(p => this.resolve(p, new Set()))
+      .filter(p => p && p.in === location)
+      .map(p => {
+        const schema = this.resolve(p.schema, new Set()) || {};
+        return {
+          name: p.name,
+          param_type: SHAPE_TYPES[schema.type] || 'string',
+          required: location === 'path' || !!p.required,
+          ...(schema.default !== undefined && { default: String(schema.default) }),
+        };
+      });
+  }
+
+  public requestBody(operation: any) {
+    const body = this.resolve(operation.requestBody, new Set());
+    const schema = body?.content?.['application/json']?.schema;
+    return schema ? this.shape(schema) : undefined;
+  }
+
+  /** Lowest documented 2xx response; its JSON schema becomes the response body */
+  public success(operation: any, method: string) {
+    const codes = Object.keys(operation.responses || {}).filter(code => /^2\d\d$/.test(code)).sort();
+    const code = codes[0];
+    const response = code ? this.resolve(operation.responses[code], new Set()) : null;
+    const schema = response?.content?.['application/json']?.schema;
+    const status = code ? Number(code) : undefined;
+    const defaultStatus = method === 'POST' ? 201 : 200;
+
+    return {
+      response_body: schema ? this.shape(schema) : undefined,
+      success_status: status && status !== defaultStatus ? status : undefined,
+    };
+  }
+
+  /** Names of the security schemes an operation requires; an empty `security: []` opts out */
+  public permissions(operation: any): string[] {
+    const requirements = operation.security ?? this.spec.security ?? [];
+    if (!Array.isArray(requirements)) return [];
+    return [...new Set(requirements.flatMap((r: Record<string, unknown>) => Object.keys(r || {})))];
+  }
+}
+
+/** operationId when present, otherwise derived from the verb and the static path segments */
+function operationName(method: string, path: string, operation: any): string {
+  if (typeof operation.operationId === 'string' && operation.operationId.trim()) return operation.operationId.trim();
+  const segments = path.split('/').filter(s => s && !s.startsWith('{'));
+  return camelCase(`${method} ${segments.map(pascalCase).join(' ')}`) || method;
+}
+
+/**
+ * Parse an OpenAPI 3 document (JSON text or an already-parsed object) into one endpoint per
+ * operation. Path templates become `:param` routes, `operationId` becomes the name, and the
+ * security requirements become permissions.
+ */
+export function importOpenApi(spec: string | Record<string, any>): ImportedApi[] {
+  let doc: any = spec;
+  if (typeof spec === 'string') {
+    try {
+      doc = JSON.parse(spec);
+    } catch (error) {
+      throw new OpenApiImportError(`Spec is not valid JSON: ${(error as Error).message}`);
+    }
+  }
+  if (!doc || typeof doc !== 'object' || !doc.paths || typeof doc.paths !== 'object') {
+    throw new OpenApiImportError('Spec has no "paths" object');
+  }
+
+  const reader = new SpecReader(doc);
+  const apis: ImportedApi[] = [];
+
+  for (const [path, item] of Object.entries<any>(doc.paths)) {
+    if (!item || typeof item !== 'object') continue;
+    const shared: any[] = Array.isArray(item.parameters) ? item.parameters : [];
+
+    for (const verb of HTTP_METHODS) {
+      const operation = item[verb];
+      if (!operation) continue;
+
+      const method = verb.toUpperCase();
+      // Operation-level parameters override path-level ones with the same name and location
+      const own: any[] = Array.isArray(operation.parameters) ? operation.parameters : [];
+      const parameters = [...shared.filter(s => !own.some(o => o.name === s.name && o.in === s.in)), ...own];
+      const pathParams = reader.params(parameters, 'path');
+      const queryParams = reader.params(parameters, 'query');
+
+      apis.push({
+        method,
+        path: toRoutePath(path),
+        name: operationName(verb, path, operation),
+        description: operation.summary || operation.description || undefined,
+        request_body: reader.requestBody(operation),
+        ...reader.success(operation, method),
+        ...(pathParams.length > 0 && { path_params: pathParams }),
+        ...(queryParams.length > 0 && { query_params: queryParams }),
+        permissions: reader.permissions(operation),
+      });
+    }
+  }
+
+  return apis;
+}
@@ -0,0 +1,14 @@
+import { test } from 'node:test';
+import assert from 'node:assert/strict';
+import { positionClasses, validatePosition } from './positioning.js';
+
+test('positionClasses turns an absolute position into Tailwind utilities', () => {
+  assert.deepEqual(positionClasses({ position: { type: 'absolute', top: 4, left: 0, z_index: 50 } }), ['absolute', 'top-4', 'left-0', 'z-50']);
+  assert.deepEqual(positionClasses({ position: { type: 'fixed', bottom: -2, right: 13, z_index: 75 } }), ['fixed', 'right-[3.25rem]', '-bottom-2', 'z-[75]']);
+  // Offsets are dropped on a statically positioned block
+  assert.deepEqual(positionClasses({ position: { type: 'static', top: 4 } }), ['static']);
+
+  assert.deepEqual(positionClasses({}), []);
+  assert.equal(validatePosition({ type: 'sticky' }), 'position.type must be one of static, relative, absolute, fixed');
+  assert.deepEqual(positionClasses({ position: { type: 'sticky' } }), []);
+});
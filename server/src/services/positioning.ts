@@ -0,0 +1,73 @@
+/**
+ * Structured positioning for blocks, kept in `properties.position`:
+ * `{ type: 'absolute', top: 4, left: 0, z_index: 50 }`. Offsets are steps on Tailwind's
+ * spacing scale (1 = 0.25rem) and the generators turn the whole thing into utility classes.
+ */
+export type PositionType = 'static' | 'relative' | 'absolute' | 'fixed';
+
+export interface BlockPosition {
+  type: PositionType;
+  top?: number;
+  right?: number;
+  bottom?: number;
+  left?: number;
+  z_index?: number;
+}
+
+export const POSITION_TYPES: PositionType[] = ['static', 'relative', 'absolute', 'fixed'];
+const OFFSET_KEYS = ['top', 'right', 'bottom', 'left'] as const;
+
+export const MAX_OFFSET = 96;
+export const MAX_Z_INDEX = 9999;
+
+/** Steps with a named Tailwind utility (`top-4`); anything else becomes an arbitrary value */
+const SPACING_SCALE = new Set([0, 0.5, 1, 1.5, 2, 2.5, 3, 3.5, 4, 5, 6, 7, 8, 9, 10, 11, 12, 14, 16, 20, 24, 28, 32, 36, 40, 44, 48, 52, 56, 60, 64, 72, 80, 96]);
+const Z_SCALE = new Set([0, 10, 20, 30, 40, 50]);
+
+/** Returns an error message, or null when `value` is a usable position (or unset) */
+export function validatePosition(value: unknown): string | null {
+  if (value === undefined || value === null) return null;
+  if (typeof value !== 'object' || Array.isArray(value)) return 'position must be an object';
+
+  const position = value as Record<string, unknown>;
+  if (!POSITION_TYPES.includes(position.type as PositionType)) {
+    return `position.type must be one of ${POSITION_TYPES.join(', ')}`;
+  }
+  for (const key of OFFSET_KEYS) {
+    const offset = position[key];
+    if (offset === undefined || offset === null) continue;
+    if (typeof offset !== 'number' || !Number.isFinite(offset) || Math.abs(offset) > MAX_OFFSET) {
+      return `position.${key} must be a number from -${MAX_OFFSET} to ${MAX_OFFSET}`;
+    }
+  }
+  const z = position.z_index;
+  if (z !== undefined && z !== null && (!Number.isInteger(z) || Math.abs(z as number) > MAX_Z_INDEX)) {
+    return `position.z_index must be an integer from -${MAX_Z_INDEX} to ${MAX_Z_INDEX}`;
+  }
+  return null;
+}
+
+function offsetClass(key: string, offset: number): string {
+  const sign = offset < 0 ? '-' : '';
+  const step = Math.abs(offset);
+  return SPACING_SCALE.has(step) ? `${sign}${key}-${step}` : `${sign}${key}-[${step * 0.25}rem]`;
+}
+
+/** Tailwind classes for a block's `properties.position`, e.g. `absolute top-4 left-0 z-50` */
+export function positionClasses(properties: Record<string, any> | undefined): string[] {
+  const position = properties?.position;
+  if (!position || validatePosition(position)) return [];
+
+  const classes: string[] = [position.type];
+  // Offsets do nothing on statically positioned elements
+  if (position.type !== 'static') {
+    for (const key of OFFSET_KEYS) {
+      if (typeof position[key] === 'number') classes.push(offsetClass(key, position[key]));
+    }
+  }
+  if (typeof position.z_index === 'number') {
+    const z = position.z_index;
+    classes.push(Z_SCALE.has(z) ? `z-${z}` : z < 0 && Z_SCALE.has(-z) ? `-z-${-z}` : `z-[${z}]`);
+  }
+  return classes;
+}
@@ -0,0 +1,31 @@
+import { test } from 'node:test';
+import assert from 'node:assert/strict';
+import { PostmanGeneratorService } from './postmanGenerator.js';
+import type { ApiSchema, ProjectSchema } from './projectSchema.js';
+
+const api = (overrides: Partial<ApiSchema>): ApiSchema => ({
+  id: 'a1', method: 'GET', path: '/items', name: 'items', permissions: [], archived: false, ...overrides,
+});
+
+test('generated collection has a request per endpoint, grouped by resource, with its method and URL', () => {
+  const project = {
+    id: 'p1', name: 'My Shop', settings: {}, data_models: [], logic_flows: [], pages: [], blocks: [], variables: [],
+    apis: [
+      api({ id: 'a1', method: 'GET', path: '/api/orders/:id', name: 'getOrder', path_params: [{ name: 'id', param_type: 'string', required: true }] }),
+      api({ id: 'a2', method: 'post', path: '/api/orders', name: 'createOrder', request_body: { shape_type: 'object', fields: [{ name: 'total', field_type: 'number', required: true }] } }),
+      api({ id: 'a3', method: 'DELETE', path: '/api/users/:id', name: 'deleteUser' }),
+    ],
+  } as unknown as ProjectSchema;
+
+  const file = new PostmanGeneratorService(project).generate();
+  assert.equal(file.path, 'my-shop.postman_collection.json');
+
+  const collection = JSON.parse(file.content);
+  assert.equal(collection.auth.type, 'bearer');
+  const requests = collection.item.map((folder: any) => [folder.name, folder.item.map((i: any) => [i.name, i.request.method, i.request.url.raw])]);
+  assert.deepEqual(requests, [
+    ['orders', [['getOrder', 'GET', '{{baseUrl}}/orders/:id'], ['createOrder', 'POST', '{{baseUrl}}/orders']]],
+    ['users', [['deleteUser', 'DELETE', '{{baseUrl}}/users/:id']]],
+  ]);
+  assert.equal(collection.item[0].item[1].request.body.raw, JSON.stringify({ total: 0 }, null, 2));
+});
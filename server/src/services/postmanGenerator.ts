@@ -0,0 +1,156 @@
+import crypto from 'crypto';
+import { apiBaseUrl, apiPrefix, type ApiSchema, type GeneratedFile, type ProjectSchema } from './projectSchema.js';
+
+const COLLECTION_SCHEMA = 'https://schema.getpostman.com/json/collection/v2.1.0/collection.json';
+
+/** Placeholder request values for each DataShape primitive */
+const EXAMPLE_VALUES: Record<string, unknown> = {
+  string: 'string',
+  number: 0,
+  boolean: false,
+};
+
+/** First static path segment after the API prefix, used to group endpoints into folders */
+export function resourceOf(path: string, prefix = 'api'): string {
+  const prefixSegments = new Set(prefix.split('/').filter(Boolean));
+  return path.split('/').find(segment => segment && !segment.startsWith(':') && !prefixSegments.has(segment)) || 'root';
+}
+
+/**
+ * Builds a Postman v2.1 collection from the project's endpoints. Requests are grouped into
+ * one folder per resource, share `{{baseUrl}}`, and authenticate with a collection-level
+ * bearer token read from `{{token}}`.
+ */
+export class PostmanGeneratorService {
+  private project: ProjectSchema;
+
+  constructor(project: ProjectSchema) {
+    this.project = project;
+  }
+
+  public generate(): GeneratedFile {
+    const name = this.project.name.toLowerCase().replace(/[^a-z0-9]+/g, '-') || 'akasha-project';
+    return {
+      path: `${name}.postman_collection.json`,
+      content: JSON.stringify(this.collection(), null, 2) + '\n',
+    };
+  }
+
+  public collection() {
+    const folders = new Map<string, ApiSchema[]>();
+    for (const api of this.project.apis) {
+      const resource = resourceOf(api.path, apiPrefix(this.project));
+      folders.set(resource, [...(folders.get(resource) || []), api]);
+    }
+
+    return {
+      info: {
+        // Stable per project so re-imports update the existing collection
+        _postman_id: this.uuidFor(this.project.id),
+        name: this.project.name,
+        description: this.project.description || undefined,
+        schema: COLLECTION_SCHEMA,
+      },
+      auth: {
+        type: 'bearer',
+        bearer: [{ key: 'token', value: '{{token}}', type: 'string' }],
+      },
+      variable: [
+        { key: 'baseUrl', value: apiBaseUrl(this.project), type: 'string' },
+        { key: 'token', value: '', type: 'string' },
+      ],
+      item: [...folders.entries()]
+        .sort(([a], [b]) => a.localeCompare(b))
+        .map(([resource, apis]) => ({ name: resource, item: apis.map(api => this.requestItem(api)) })),
+    };
+  }
+
+  private uuidFor(seed: string): string {
+    const hex = crypto.createHash('sha1').update(seed).digest('hex');
+    return `${hex.slice(0, 8)}-${hex.slice(8, 12)}-5${hex.slice(13, 16)}-a${hex.slice(17, 20)}-${hex.slice(20, 32)}`;
+  }
+
+  private requestItem(api: ApiSchema) {
+    // Postman reads `:name` segments as path variables, the same syntax the routes use.
+    // `{{baseUrl}}` already ends in the API prefix, so a path that repeats it drops it.
+    const prefix = apiPrefix(this.project).split('/').filter(Boolean);
+    let segments = api.path.split('/').filter(Boolean);
+    if (prefix.length && prefix.every((segment, i) => segments[i] === segment)) segments = segments.slice(prefix.length);
+    const query = (api.query_params || []).map(p => ({
+      key: p.name,
+      value: p.default ?? '',
+      disabled: !p.required,
+    }));
+    const pathVariables = (api.path_params || []).map(p => ({ key: p.name, value: p.default ?? '' }));
+    const rawQuery = query.filter(q => !q.disabled).map(q => `${q.key}=${q.value}`).join('&');
+
+    const request: Record<string, unknown> = {
+      method: api.method.toUpperCase(),
+      header: [],
+      url: {
+        raw: `{{baseUrl}}/${segments.join('/')}${rawQuery ? `?${rawQuery}` : ''}`,
+        host: ['{{baseUrl}}'],
+        path: segments,
+        ...(query.length > 0 && { query }),
+        ...(pathVariables.length > 0 && { variable: pathVariables }),
+      },
+      ...(api.description && { description: api.description }),
+    };
+
+    if (api.request_body && !['GET', 'DELETE'].includes(api.method.toUpperCase())) {
+      request.header = [{ key: 'Content-Type', value: 'application/json' }];
+      request.body = {
+        mode: 'raw',
+        raw: JSON.stringify(this.example(api.request_body), null, 2),
+        options: { raw: { language: 'json' } },
+      };
+    }
+
+    return { name: api.name, request, response: [] };
+  }
+
+  /** Example JSON for a DataShape; model refs expand to the model's fields */
+  private example(shape: any, depth = 0): unknown {
+    if (!shape || depth > 5) return null;
+
+    switch (shape.shape_type) {
+      case 'array':
+        return [this.example(shape.item_shape, depth + 1)];
+      case 'model': {
+        const model = this.project.data_models.find(m => m.id === shape.model_ref || m.name === shape.model_ref);
+        if (!model) return {};
+        return Object.fromEntries(model.fields.filter(f => !f.primary_key && f.field_type !== 'file').map(f => [f.name, f.enum_values?.[0] ?? this.fieldExample(f.field_type)]));
+      }
+      case 'object':
+        return Object.fromEntries((shape.fields || []).map((f: any) => [
+          f.name,
+          f.nested ? this.example(f.nested, depth + 1) : f.field_type === 'array' ? [] : f.field_type === 'object' ? {} : EXAMPLE_VALUES[f.field_type] ?? null,
+        ]));
+      default:
+        return EXAMPLE_VALUES[shape.shape_type] ?? null;
+    }
+  }
+
+  private fieldExample(fieldType: string): unknown {
+    switch (fieldType) {
+      case 'int':
+      case 'float':
+        return 0;
+      case 'decimal':
+        return '0.00';
+      case 'boolean':
+        return false;
+      case 'datetime':
+      case 'date':
+        return new Date(0).toISOString();
+      case 'email':
+        return 'user@example.com';
+      case 'url':
+        return 'https://example.com';
+      case 'json':
+        return {};
+      default:
+        return 'string';
+    }
+  }
+}
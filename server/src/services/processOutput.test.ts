@@ -0,0 +1,25 @@
+import { test } from 'node:test';
+import assert from 'node:assert/strict';
+import { spawn } from 'child_process';
+import { outputLineWriter, processOutput, type ProcessOutputLine } from './processOutput.js';
+
+test('outputLineWriter publishes a short-lived command\'s lines in order', { skip: process.platform === 'win32' }, async t => {
+  const published: ProcessOutputLine[] = [];
+  const onLine = (line: ProcessOutputLine) => {
+    if (line.project_id === 'p-output') published.push(line);
+  };
+  processOutput.on('line', onLine);
+  t.after(() => processOutput.off('line', onLine));
+
+  const seen: string[] = [];
+  const lines = outputLineWriter('p-output', 'install', 'client', 'stdout', line => seen.push(line));
+  const child = spawn('sh', ['-c', 'echo one; printf "two\\nthr"; sleep 0.05; printf "ee\\nfour"']);
+  child.stdout.on('data', chunk => lines.write(chunk));
+  await new Promise(resolve => child.on('close', resolve));
+  lines.flush();
+
+  assert.deepEqual(seen, ['one', 'two', 'three', 'four']);
+  assert.deepEqual(published.map(l => [l.line, l.source, l.target, l.stream]), seen.map(line => [line, 'install', 'client', 'stdout']));
+  assert.deepEqual(published.map(l => l.seq), [1, 2, 3, 4]);
+  assert.deepEqual(processOutput.tail('p-output').map(l => l.line), seen);
+});
@@ -0,0 +1,73 @@
+import { EventEmitter } from 'events';
+
+/** Lines kept per project so a client that connects mid-run still sees recent output */
+export const OUTPUT_TAIL_LINES = 500;
+
+export interface ProcessOutputLine {
+  project_id: string;
+  /** The process the line came from: `install` or `dev` */
+  source: string;
+  /** Which part of the project it ran for, e.g. `client` or `server` */
+  target: string;
+  stream: 'stdout' | 'stderr';
+  line: string;
+  /** Increases per project, so clients can drop lines they already have from the tail */
+  seq: number;
+  timestamp: string;
+}
+
+class ProcessOutputFeed extends EventEmitter {
+  private tails = new Map<string, ProcessOutputLine[]>();
+  private seqs = new Map<string, number>();
+
+  public publish(line: Omit<ProcessOutputLine, 'seq' | 'timestamp'>) {
+    const seq = (this.seqs.get(line.project_id) || 0) + 1;
+    this.seqs.set(line.project_id, seq);
+
+    const entry: ProcessOutputLine = { ...line, seq, timestamp: new Date().toISOString() };
+    const tail = this.tails.get(line.project_id) || [];
+    tail.push(entry);
+    if (tail.length > OUTPUT_TAIL_LINES) tail.splice(0, tail.length - OUTPUT_TAIL_LINES);
+    this.tails.set(line.project_id, tail);
+
+    this.emit('line', entry);
+  }
+
+  /** The project's most recent lines, oldest first */
+  public tail(projectId: string): ProcessOutputLine[] {
+    return [...(this.tails.get(projectId) || [])];
+  }
+}
+
+export const processOutput = new ProcessOutputFeed();
+
+/**
+ * A sink for one of a child process's streams: chunks are split into lines and published in
+ * order, holding back a trailing partial line until its newline (or `flush`) arrives.
+ * `onLine` sees each line as it is published.
+ */
+export function outputLineWriter(
+  projectId: string,
+  source: string,
+  target: string,
+  stream: ProcessOutputLine['stream'],
+  onLine?: (line: string) => void,
+) {
+  let pending = '';
+  const emit = (line: string) => {
+    processOutput.publish({ project_id: projectId, source, target, stream, line });
+    onLine?.(line);
+  };
+
+  return {
+    write(chunk: Buffer | string) {
+      const lines = (pending + chunk.toString()).split(/\r?\n/);
+      pending = lines.pop() ?? '';
+      lines.forEach(emit);
+    },
+    flush() {
+      if (pending) emit(pending);
+      pending = '';
+    },
+  };
+}
@@ -0,0 +1,48 @@
+import { test } from 'node:test';
+import assert from 'node:assert/strict';
+import { parseProjectDocument, repairProject } from './projectImport.js';
+
+test('repairProject keeps unique constraints over foreign keys and drops ones naming unknown columns', () => {
+  const schema = parseProjectDocument({
+    name: 'Blog',
+    data_models: [
+      { id: 'user', name: 'User', fields: [{ name: 'email' }] },
+      {
+        id: 'vote', name: 'Vote', fields: [{ name: 'postId' }],
+        relations: [{ id: 'r1', name: 'user', target_model_id: 'user', relation_type: 'many_to_one' }],
+        unique_constraints: [['postId', 'userId'], ['postId', 'authorId'], ['postId']],
+      },
+    ],
+  });
+
+  const report = repairProject(schema);
+  assert.deepEqual(schema.data_models[1]!.unique_constraints, [['postId', 'userId']]);
+  assert.deepEqual(report.actions.map(a => [a.kind, a.entity_id]), [
+    ['dropped_unique_constraint', 'vote'],
+    ['dropped_unique_constraint', 'vote'],
+  ]);
+});
+
+test('repairProject drops a block whose parent is missing, along with its subtree, and reports it', () => {
+  const schema = parseProjectDocument(JSON.stringify({
+    name: 'Landing',
+    pages: [{ id: 'home', name: 'Home', path: '/', root_block_id: 'root' }],
+    blocks: [
+      { id: 'root', block_type: 'canvas', page_id: 'home', children: ['hero', 'stray'] },
+      { id: 'hero', block_type: 'Section', name: 'Hero', page_id: 'home', parent_id: 'root' },
+      { id: 'stray', block_type: 'Card', name: 'Stray', page_id: 'home', parent_id: 'gone', children: ['label'] },
+      { id: 'label', block_type: 'Text', name: 'Label', page_id: 'home', parent_id: 'stray' },
+    ],
+  }));
+
+  const report = repairProject(schema);
+
+  assert.deepEqual(schema.blocks.map(b => b.id), ['root', 'hero']);
+  assert.deepEqual(schema.blocks[0]!.children, ['hero']);
+  assert.equal(report.repaired, true);
+  assert.deepEqual(report.actions, [
+    { kind: 'dropped_block', entity_id: 'stray', message: 'Block "Stray" has a missing parent gone' },
+    { kind: 'dropped_block', entity_id: 'label', message: 'Block "Label" has a missing parent stray' },
+    { kind: 'removed_child', entity_id: 'root', message: 'Block "canvas" listed stray, which isn\'t its child' },
+  ]);
+});
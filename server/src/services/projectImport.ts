@@ -0,0 +1,353 @@
+import { randomUUID } from 'crypto';
+import { ObjectId } from 'mongodb';
+import prisma from '../lib/prisma.js';
+import { getProjectStore } from './projectStore.js';
+import { foreignKeyColumns } from './backendGenerator.js';
+import { pageLayout, type BlockSchema, type PageSchema, type ProjectSchema } from './projectSchema.js';
+
+export class ProjectImportError extends Error {}
+
+export interface RepairAction {
+  kind: 'dropped_page' | 'dropped_block' | 'removed_child' | 'fixed_page_root' | 'dropped_unique_constraint';
+  entity_id: string;
+  message: string;
+}
+
+export interface RepairReport {
+  repaired: boolean;
+  actions: RepairAction[];
+}
+
+const asArray = (value: unknown): any[] => (Array.isArray(value) ? value : []);
+const asObject = (value: unknown): Record<string, any> =>
+  value && typeof value === 'object' && !Array.isArray(value) ? (value as Record<string, any>) : {};
+
+/**
+ * Shape-check an exported project (a JSON string or an already parsed object) and fill in
+ * defaults for missing collections. References between entities are left for
+ * `repairProject` to check.
+ */
+export function parseProjectDocument(input: unknown): ProjectSchema {
+  let raw: unknown = input;
+  if (typeof input === 'string') {
+    try {
+      raw = JSON.parse(input);
+    } catch (error) {
+      throw new ProjectImportError(`Project file is not valid JSON: ${(error as Error).message}`);
+    }
+  }
+  if (!raw || typeof raw !== 'object' || Array.isArray(raw)) throw new ProjectImportError('Project file must be a JSON object');
+
+  const doc = raw as Record<string, any>;
+  if (typeof doc.name !== 'string' || !doc.name.trim()) throw new ProjectImportError('Project file has no name');
+
+  return {
+    id: String(doc.id || ''),
+    name: doc.name.trim(),
+    description: typeof doc.description === 'string' ? doc.description : '',
+    settings: asObject(doc.settings),
+    root_path: '',
+    pages: asArray(doc.pages)
+      .filter(p => typeof p?.id === 'string' && typeof p?.path === 'string')
+      .map(p => ({
+        id: p.id,
+        idRoot: '',
+        name: typeof p.name === 'string' ? p.name : 'Page',
+        path: p.path,
+        root_block_id: typeof p.root_block_id === 'string' ? p.root_block_id : undefined,
+        root_block_ids: Array.isArray(p.root_block_ids) ? p.root_block_ids.filter((id: unknown) => typeof id === 'string') : undefined,
+        is_dynamic: !!p.is_dynamic,
+        layout: pageLayout({ layout: p.layout ?? asObject(p.meta).layout }),
+        meta: asObject(p.meta),
+        archived: !!p.archived,
+      })),
+    blocks: asArray(doc.blocks)
+      .filter(b => typeof b?.id === 'string' && typeof b?.block_type === 'string')
+      .map(b => ({
+        id: b.id,
+        block_type: b.block_type,
+        name: typeof b.name === 'string' ? b.name : b.block_type,
+        parent_id: typeof b.parent_id === 'string' ? b.parent_id : null,
+        page_id: typeof b.page_id === 'string' ? b.page_id : null,
+        properties: asObject(b.properties),
+        styles: asObject(b.styles),
+        responsive_styles: asObject(b.responsive_styles),
+        classes: asArray(b.classes).filter(c => typeof c === 'string'),
+        event_handlers: asArray(b.event_handlers).filter(h => typeof h?.event === 'string'),
+        bindings: asObject(b.bindings),
+        children: asArray(b.children).filter(c => typeof c === 'string'),
+        order: Number(b.order) || 0,
+        archived: !!b.archived,
+      })),
+    data_models: asArray(doc.data_models).filter(m => typeof m?.id === 'string' && typeof m?.name === 'string'),
+    apis: asArray(doc.apis).filter(a => typeof a?.method === 'string' && typeof a?.path === 'string'),
+    logic_flows: asArray(doc.logic_flows).filter(f => typeof f?.id === 'string'),
+    variables: asArray(doc.variables).filter(v => typeof v?.name === 'string'),
+  };
+}
+
+/**
+ * Fix dangling references in place so the project can be stored: pages with a duplicate
+ * path are dropped, blocks whose parent or page is missing are dropped along with their
+ * subtrees, child lists only keep blocks that point back at their parent, page roots
+ * that don't resolve fall back to the page's top-level block, and unique constraints
+ * naming columns the model doesn't have are dropped.
+ */
+export function repairProject(project: ProjectSchema): RepairReport {
+  const actions: RepairAction[] = [];
+
+  const paths = new Set<string>();
+  project.pages = project.pages.filter(page => {
+    if (paths.has(page.path)) {
+      actions.push({ kind: 'dropped_page', entity_id: page.id, message: `Page "${page.name}" repeats the path ${page.path}` });
+      return false;
+    }
+    paths.add(page.path);
+    return true;
+  });
+  const pageIds = new Set(project.pages.map(p => p.id));
+
+  // Dropping a block orphans its children, so repeat until nothing else goes
+  let blocks = project.blocks.filter((b, i, all) => all.findIndex(o => o.id === b.id) === i);
+  for (;;) {
+    const ids = new Set(blocks.map(b => b.id));
+    const kept: BlockSchema[] = [];
+    for (const block of blocks) {
+      if (block.parent_id && !ids.has(block.parent_id)) {
+        actions.push({ kind: 'dropped_block', entity_id: block.id, message: `Block "${block.name}" has a missing parent ${block.parent_id}` });
+      } else if (block.page_id && !pageIds.has(block.page_id)) {
+        actions.push({ kind: 'dropped_block', entity_id: block.id, message: `Block "${block.name}" belongs to a missing page ${block.page_id}` });
+      } else {
+        kept.push(block);
+      }
+    }
+    if (kept.length === blocks.length) break;
+    blocks = kept;
+  }
+  project.blocks = blocks;
+
+  const byId = new Map(blocks.map(b => [b.id, b]));
+  for (const block of blocks) {
+    const children = block.children.filter(id => byId.get(id)?.parent_id === block.id);
+    for (const id of block.children.filter(id => !children.includes(id))) {
+      actions.push({ kind: 'removed_child', entity_id: block.id, message: `Block "${block.name}" listed ${id}, which isn't its child` });
+    }
+    block.children = children;
+  }
+
+  for (const page of project.pages) {
+    if (page.root_block_ids?.length) {
+      const roots = page.root_block_ids.filter(id => byId.get(id)?.page_id === page.id && !byId.get(id)?.parent_id);
+      if (roots.length !== page.root_block_ids.length) {
+        actions.push({ kind: 'fixed_page_root', entity_id: page.id, message: `Page "${page.name}" dropped roots that aren't top-level blocks of the page` });
+      }
+      page.root_block_ids = roots.length ? roots : undefined;
+      if (roots.length) {
+        page.root_block_id = roots[0];
+        continue;
+      }
+    }
+    const root = page.root_block_id ? byId.get(page.root_block_id) : undefined;
+    if (root && root.page_id === page.id && !root.parent_id) continue;
+
+    const fallback = blocks.find(b => b.page_id === page.id && !b.parent_id && b.block_type === 'canvas')
+      || blocks.find(b => b.page_id === page.id && !b.parent_id);
+    if (!fallback && !page.root_block_id) continue;
+
+    page.root_block_id = fallback?.id;
+    actions.push({
+      kind: 'fixed_page_root',
+      entity_id: page.id,
+      message: fallback ? `Page "${page.name}" root now points at ${fallback.id}` : `Page "${page.name}" root was cleared`,
+    });
+  }
+
+  for (const model of project.data_models) {
+    model.fields = asArray(model.fields);
+    model.relations = asArray(model.relations);
+  }
+  for (const model of project.data_models) {
+    if (model.unique_constraints === undefined) continue;
+    const columns = new Set([
+      ...model.fields.map(f => f?.name),
+      ...(model.owner_field ? [model.owner_field] : []),
+      ...foreignKeyColumns(model, project.data_models),
+    ]);
+    const constraints = asArray(model.unique_constraints);
+    model.unique_constraints = constraints.filter(c =>
+      Array.isArray(c) && c.length >= 2 && new Set(c).size === c.length && c.every(column => columns.has(column)));
+    for (const dropped of constraints.filter(c => !model.unique_constraints!.includes(c))) {
+      actions.push({
+        kind: 'dropped_unique_constraint',
+        entity_id: model.id,
+        message: `Model "${model.name}" dropped the unique constraint ${JSON.stringify(dropped)}, which doesn't name two or more of its columns`,
+      });
+    }
+  }
+
+  return { repaired: actions.length > 0, actions };
+}
+
+/** Binding types whose value is the id of another entity, by the id map it's remapped with */
+type IdMaps = Record<'variable' | 'model' | 'api', Map<string, string>>;
+
+/** `bindings` with every value that is an imported entity's id pointed at its new id */
+function remapBindings(bindings: Record<string, any>, ids: IdMaps): Record<string, any> {
+  return Object.fromEntries(Object.entries(bindings).map(([key, binding]) => {
+    const map = ids[binding?.type as keyof IdMaps];
+    const value = map?.get(binding.value);
+    return [key, value ? { ...binding, value } : binding];
+  }));
+}
+
+/**
+ * Store a parsed and repaired project as a new project. Every entity gets a fresh id and
+ * references between them (block tree, page roots, relations, bindings, event handlers and
+ * flow triggers) are remapped. A failure part way deletes the partly imported project.
+ */
+export async function createProjectFromSchema(schema: ProjectSchema): Promise<string> {
+  const project = await getProjectStore().createProject({
+    name: schema.name, description: schema.description, settings: JSON.stringify(schema.settings),
+  });
+  try {
+    await importContent(project.id, schema);
+  } catch (error) {
+    await getProjectStore().deleteProject(project.id).catch(cleanup => {
+      console.error(`[Import] Failed to remove partly imported project ${project.id}:`, cleanup);
+    });
+    throw error;
+  }
+  return project.id;
+}
+
+/** Create every entity of `schema` under the new project `projectId` */
+async function importContent(projectId: string, schema: ProjectSchema) {
+  const newObjectId = () => new ObjectId().toHexString();
+  const modelIds = new Map(schema.data_models.map(m => [m.id, newObjectId()]));
+  const flowIds = new Map(schema.logic_flows.map(f => [f.id, newObjectId()]));
+  const blockIds = new Map(schema.blocks.map(b => [b.id, newObjectId()]));
+  const pageIds = new Map(schema.pages.map(p => [p.id, randomUUID()]));
+  const apiIds = new Map(schema.apis.filter(a => typeof a.id === 'string').map(a => [a.id, newObjectId()]));
+  const variableIds = new Map(schema.variables.filter(v => typeof v.id === 'string').map(v => [v.id, newObjectId()]));
+  const bindingIds: IdMaps = { variable: variableIds, model: modelIds, api: apiIds };
+
+  for (const model of schema.data_models as any[]) {
+    const relations = asArray(model.relations)
+      .filter(r => modelIds.has(r?.target_model_id))
+      .map(r => ({ ...r, target_model_id: modelIds.get(r.target_model_id) }));
+    await prisma.dataModel.create({
+      data: {
+        id: modelIds.get(model.id),
+        projectId,
+        name: model.name,
+        schema: JSON.stringify({
+          fields: asArray(model.fields),
+          relations,
+          timestamps: model.timestamps ?? true,
+          soft_delete: model.soft_delete ?? false,
+          ...(model.owner_field && { owner_field: model.owner_field }),
+          ...(Array.isArray(model.unique_constraints) && { unique_constraints: model.unique_constraints }),
+        }),
+      },
+    });
+  }
+
+  for (const flow of schema.logic_flows as any[]) {
+    const trigger = { ...asObject(flow.trigger) };
+    if (!trigger.type) trigger.type = 'manual';
+    if (trigger.component_id) trigger.component_id = blockIds.get(trigger.component_id) || trigger.component_id;
+    if (trigger.api_id) trigger.api_id = apiIds.get(trigger.api_id) || trigger.api_id;
+    await prisma.logicFlow.create({
+      data: {
+        id: flowIds.get(flow.id),
+        projectId,
+        name: typeof flow.name === 'string' ? flow.name : 'Flow',
+        trigger: JSON.stringify(trigger),
+        context: flow.context === 'backend' ? 'backend' : 'frontend',
+        nodes: JSON.stringify(asArray(flow.nodes)),
+        edges: JSON.stringify(asArray(flow.edges)),
+        transactional: !!flow.transactional,
+      },
+    });
+  }
+
+  for (const { id, method, path, name, logic_flow_id, archived: _archived, ...config } of schema.apis as any[]) {
+    await prisma.apiEndpoint.create({
+      data: {
+        id: apiIds.get(id),
+        projectId,
+        method: String(method).toUpperCase(),
+        path,
+        name: typeof name === 'string' ? name : `${method} ${path}`,
+        config: JSON.stringify(config),
+        logicFlowId: (logic_flow_id && flowIds.get(logic_flow_id)) || null,
+      },
+    });
+  }
+
+  const pageRoots = new Map<string, string>();
+  for (const page of schema.pages as PageSchema[]) {
+    const meta = { ...page.meta };
+    delete meta.root_block_id;
+    delete meta.root_block_ids;
+    delete meta.layout;
+    if (page.layout && page.layout !== 'default') meta.layout = page.layout;
+    if (page.root_block_id && blockIds.has(page.root_block_id)) meta.root_block_id = blockIds.get(page.root_block_id);
+    const rootIds = (page.root_block_ids ?? []).filter(id => blockIds.has(id)).map(id => blockIds.get(id)!);
+    if (rootIds.length) meta.root_block_ids = rootIds;
+
+    const created = await prisma.page.create({
+      data: {
+        id: pageIds.get(page.id)!,
+        projectId,
+        name: page.name,
+        path: page.path,
+        isDynamic: page.is_dynamic,
+        meta: JSON.stringify(meta),
+        archived: page.archived,
+      },
+    });
+    pageRoots.set(page.id, created.idRoot);
+  }
+
+  for (const block of schema.blocks) {
+    const properties = { ...block.properties };
+    if (typeof properties.component_id === 'string') {
+      properties.component_id = blockIds.get(properties.component_id) || properties.component_id;
+    }
+    await prisma.block.create({
+      data: {
+        id: blockIds.get(block.id),
+        projectId,
+        pageId: block.page_id ? pageRoots.get(block.page_id) || null : null,
+        parentId: block.parent_id ? blockIds.get(block.parent_id) || null : null,
+        blockType: block.block_type,
+        name: block.name,
+        properties: JSON.stringify(properties),
+        styles: JSON.stringify(block.styles),
+        responsiveStyles: JSON.stringify(asObject((block as any).responsive_styles)),
+        classes: JSON.stringify(block.classes),
+        events: JSON.stringify(block.event_handlers.map(h => ({ ...h, logic_flow_id: flowIds.get(h.logic_flow_id) || h.logic_flow_id }))),
+        bindings: JSON.stringify(remapBindings(block.bindings, bindingIds)),
+        children: JSON.stringify(block.children.map(id => blockIds.get(id))),
+        order: block.order,
+        archived: block.archived,
+      },
+    });
+  }
+
+  for (const variable of schema.variables as any[]) {
+    await prisma.variable.create({
+      data: {
+        id: variableIds.get(variable.id),
+        projectId,
+        name: variable.name,
+        type: typeof variable.variable_type === 'string' ? variable.variable_type : 'string',
+        value: JSON.stringify(variable.default_value ?? null),
+        scope: variable.scope === 'page' ? 'page' : 'global',
+        pageId: variable.page_id ? pageIds.get(variable.page_id) || null : null,
+        persist: !!variable.persist,
+      },
+    });
+  }
+}
@@ -0,0 +1,35 @@
+import { test } from 'node:test';
+import assert from 'node:assert/strict';
+import { BackendGeneratorService } from './backendGenerator.js';
+import { GeneratorService } from './generator.js';
+import { PostmanGeneratorService } from './postmanGenerator.js';
+import { apiPrefix, pageRootIds, type ProjectSchema } from './projectSchema.js';
+
+test('pageRootIds reads root_block_ids without duplicates', () => {
+  assert.deepEqual(pageRootIds({ root_block_ids: ['a', 'b', 'a', '', 7], root_block_id: 'c' }), ['a', 'b']);
+});
+
+test('pageRootIds falls back to the singular root_block_id', () => {
+  assert.deepEqual(pageRootIds({ root_block_ids: [], root_block_id: 'c' }), ['c']);
+  assert.deepEqual(pageRootIds({}), []);
+  assert.deepEqual(pageRootIds(null as unknown as Record<string, any>), []);
+});
+
+test('api_prefix is shared by the backend, the frontend client and the Postman collection', () => {
+  const project = {
+    id: 'p1', name: 'Shop', settings: { api_prefix: '/v1/' }, data_models: [], logic_flows: [], pages: [], blocks: [], variables: [],
+    apis: [{ id: 'a1', method: 'GET', path: '/v1/orders', name: 'listOrders', permissions: [], archived: false }],
+  } as unknown as ProjectSchema;
+  assert.equal(apiPrefix(project), 'v1');
+
+  const main = new BackendGeneratorService(project).generate().find(f => f.path === 'src/main.ts')?.content ?? '';
+  assert.match(main, /app\.setGlobalPrefix\('v1'\);/);
+
+  const client = new GeneratorService().genApiClient(project);
+  assert.match(client.find(f => f.path === 'src/lib/api.ts')?.content ?? '', /VITE_API_URL \|\| 'http:\/\/localhost:3000\/v1';/);
+  assert.match(client.find(f => f.path === 'src/lib/endpoints.ts')?.content ?? '', /request<unknown>\('GET', '\/orders'\);/);
+
+  const collection = JSON.parse(new PostmanGeneratorService(project).generate().content);
+  assert.equal(collection.variable.find((v: { key: string }) => v.key === 'baseUrl').value, 'http://localhost:3000/v1');
+  assert.equal(collection.item[0].item[0].request.url.raw, '{{baseUrl}}/orders');
+});
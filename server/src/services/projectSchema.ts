@@ -0,0 +1,348 @@
+import { toLogicFlow, type LogicFlow } from './logicCompiler.js';
+import { getProjectStore } from './projectStore.js';
+
+export interface GeneratedFile {
+  path: string;
+  content: string;
+}
+
+export interface FieldSchema {
+  id: string;
+  name: string;
+  field_type: string;
+  required: boolean;
+  unique: boolean;
+  primary_key: boolean;
+  default?: string;
+  description?: string;
+  /** Allowed values when `field_type` is `enum`, each a plain identifier */
+  enum_values?: string[];
+  /** Total digits and digits after the point when `field_type` is `decimal` */
+  precision?: number;
+  scale?: number;
+  /** Index the column; unique and primary key fields already have one */
+  indexed?: boolean;
+}
+
+/** Fields that get an index of their own */
+export function indexedFields(model: DataModelSchema): FieldSchema[] {
+  return model.fields.filter(f => f.indexed && !f.unique && !f.primary_key);
+}
+
+/** Precision and scale for a decimal field, defaulting to DECIMAL(10,2) */
+export function decimalDigits(field: FieldSchema): { precision: number; scale: number } {
+  return { precision: field.precision ?? 10, scale: field.scale ?? 2 };
+}
+
+export interface RelationSchema {
+  id: string;
+  name: string;
+  target_model_id: string;
+  relation_type: string;
+  /** Whether the foreign key may be empty; unset means optional */
+  optional?: boolean;
+  /** What deleting the referenced record does to this one; Prisma's default when unset */
+  on_delete?: 'Cascade' | 'SetNull' | 'Restrict';
+}
+
+export interface DataModelSchema {
+  id: string;
+  name: string;
+  fields: FieldSchema[];
+  relations: RelationSchema[];
+  timestamps: boolean;
+  soft_delete: boolean;
+  /** Field holding the owning user's id; scopes the generated service to the signed-in user */
+  owner_field?: string;
+  /** Groups of field names that must be unique together (`@@unique([userId, slug])`) */
+  unique_constraints?: string[][];
+  archived: boolean;
+}
+
+/** A typed path or query parameter of an endpoint; `param_type` is string, number or boolean */
+export interface ParamSchema {
+  name: string;
+  param_type: string;
+  required: boolean;
+  default?: string;
+}
+
+export interface ApiSchema {
+  id: string;
+  method: string;
+  path: string;
+  name: string;
+  description?: string;
+  request_body?: any;
+  response_body?: any;
+  query_params?: ParamSchema[];
+  path_params?: ParamSchema[];
+  logic_flow_id?: string;
+  success_status?: number;
+  rate_limit?: { requests: number; window_seconds: number };
+  permissions: string[];
+  archived: boolean;
+}
+
+export interface PageSchema {
+  id: string;
+  idRoot: string;
+  name: string;
+  path: string;
+  root_block_id?: string;
+  /** Top-level blocks rendered side by side, in order; `root_block_id` is the first of them */
+  root_block_ids?: string[];
+  is_dynamic: boolean;
+  /** Which shell the page renders in; kept in `meta.layout` */
+  layout: PageLayout;
+  meta: Record<string, any>;
+  archived: boolean;
+}
+
+/** Block types added as `custom:<Name>` render as a user-supplied component of that name */
+export const CUSTOM_BLOCK_PREFIX = 'custom:';
+
+/** `custom:Carousel` → `Carousel`; null for built-in block types */
+export function customBlockName(blockType: string): string | null {
+  if (!blockType.toLowerCase().startsWith(CUSTOM_BLOCK_PREFIX)) return null;
+  return blockType.slice(CUSTOM_BLOCK_PREFIX.length).trim() || null;
+}
+
+export interface BlockSchema {
+  id: string;
+  block_type: string;
+  name: string;
+  parent_id: string | null;
+  page_id: string | null;
+  properties: Record<string, any>;
+  styles: Record<string, string | number | boolean>;
+  classes: string[];
+  event_handlers: any[];
+  bindings: Record<string, any>;
+  children: string[];
+  order: number;
+  archived: boolean;
+}
+
+export interface VariableSchema {
+  id: string;
+  name: string;
+  variable_type: string;
+  scope: 'global' | 'page';
+  page_id?: string;
+  persist: boolean;
+  default_value: unknown;
+}
+
+export interface ProjectSchema {
+  id: string;
+  name: string;
+  description: string;
+  settings: Record<string, any>;
+  root_path: string;
+  pages: PageSchema[];
+  blocks: BlockSchema[];
+  data_models: DataModelSchema[];
+  apis: ApiSchema[];
+  logic_flows: LogicFlow[];
+  variables: VariableSchema[];
+}
+
+export const DEFAULT_API_PREFIX = 'api';
+
+/**
+ * Path the generated backend mounts its routes under, from `settings.api_prefix` (`api` when
+ * unset, none when empty). The backend, frontend clients and Postman collection all use it.
+ */
+export function apiPrefix(project: Pick<ProjectSchema, 'settings'>): string {
+  const raw = project.settings?.api_prefix;
+  if (typeof raw !== 'string') return DEFAULT_API_PREFIX;
+  const segments = raw.split('/').filter(Boolean);
+  return segments.every(s => /^[A-Za-z0-9._~-]+$/.test(s)) ? segments.join('/') : DEFAULT_API_PREFIX;
+}
+
+/**
+ * Shells a generated page can render in: `default` inside the app layout with its nav bar,
+ * `auth` centered on its own without the nav, as sign-in pages are, and `blank` bare.
+ */
+export const PAGE_LAYOUTS = ['default', 'blank', 'auth'] as const;
+export type PageLayout = typeof PAGE_LAYOUTS[number];
+
+export function pageLayout(meta: Record<string, any>): PageLayout {
+  return PAGE_LAYOUTS.includes(meta?.layout) ? meta.layout : 'default';
+}
+
+/**
+ * Ids of a page's top-level blocks from its meta: `root_block_ids` when set, otherwise the
+ * single `root_block_id` pages have always stored. Empty when neither is set.
+ */
+export function pageRootIds(meta: Record<string, any>): string[] {
+  const ids = Array.isArray(meta?.root_block_ids) ? meta.root_block_ids.filter((id: unknown) => typeof id === 'string' && id) : [];
+  if (ids.length) return [...new Set<string>(ids)];
+  return typeof meta?.root_block_id === 'string' && meta.root_block_id ? [meta.root_block_id] : [];
+}
+
+/**
+ * The model and field path a `model` property binding reads, from its `Model.field` value
+ * (the model by name or id). Null when the first segment isn't one of `models` or no field
+ * follows it.
+ */
+export function modelBindingField(models: DataModelSchema[], value: string): { model: DataModelSchema; path: string[] } | null {
+  const [ref, ...path] = value.split('.').filter(Boolean);
+  const model = models.find(m => m.id === ref || m.name === ref);
+  return model && path.length ? { model, path } : null;
+}
+
+/** Head metadata for a generated page */
+export interface PageHeadMeta {
+  title?: string;
+  description?: string;
+  image?: string;
+}
+
+/**
+ * A page's title, description and Open Graph image from its `meta`, with `settings.seo`
+ * adding the title suffix and filling in a missing description or image. Null when the
+ * page sets none of them, so pages without metadata are generated as before.
+ */
+export function pageHeadMeta(meta: Record<string, any>, settings: Record<string, any>): PageHeadMeta | null {
+  const text = (value: unknown) => (typeof value === 'string' && value.trim() ? value.trim() : undefined);
+  if (!text(meta?.title) && !text(meta?.description) && !text(meta?.og_image)) return null;
+
+  const seo = settings?.seo || {};
+  const title = text(meta.title);
+  const head: PageHeadMeta = {
+    title: title && `${title}${typeof seo.title_suffix === 'string' ? seo.title_suffix : ''}`,
+    description: text(meta.description) ?? text(seo.default_description),
+    image: text(meta.og_image) ?? text(seo.default_og_image),
+  };
+  return Object.fromEntries(Object.entries(head).filter(([, value]) => value)) as PageHeadMeta;
+}
+
+/** Origins from `settings.cors_origins` allowed to call the generated API; anything that isn't an origin URL is dropped */
+export function corsOrigins(project: Pick<ProjectSchema, 'settings'>): string[] {
+  const raw = project.settings?.cors_origins;
+  if (!Array.isArray(raw)) return [];
+  return [...new Set(raw.flatMap(value => {
+    if (typeof value !== 'string') return [];
+    try {
+      const url = new URL(value.trim());
+      return /^https?:$/.test(url.protocol) ? [url.origin] : [];
+    } catch {
+      return [];
+    }
+  }))];
+}
+
+/** Default base URL of the generated API, e.g. `http://localhost:3000/api` */
+export function apiBaseUrl(project: Pick<ProjectSchema, 'settings'>): string {
+  const prefix = apiPrefix(project);
+  return `http://localhost:3000${prefix ? `/${prefix}` : ''}`;
+}
+
+/** An endpoint's path relative to `apiBaseUrl`, which already ends in the API prefix */
+export function clientRoute(project: Pick<ProjectSchema, 'settings'>, apiPath: string): string {
+  const prefix = apiPrefix(project);
+  const relative = apiPath.replace(/^\/+/, '');
+  return `/${prefix && (relative === prefix || relative.startsWith(`${prefix}/`)) ? relative.slice(prefix.length + 1) : relative}`;
+}
+
+export function parseJsonValue<T>(value: unknown, fallback: T): T {
+  if (typeof value !== 'string') return (value as T) ?? fallback;
+  try {
+    return JSON.parse(value) as T;
+  } catch {
+    return fallback;
+  }
+}
+
+export function toDataModelSchema(m: any): DataModelSchema {
+  const schema = parseJsonValue<Record<string, any>>(m.schema, {});
+  return {
+    id: m.id,
+    name: m.name,
+    fields: schema.fields || [],
+    relations: schema.relations || [],
+    timestamps: schema.timestamps ?? true,
+    soft_delete: schema.soft_delete ?? false,
+    owner_field: typeof schema.owner_field === 'string' && schema.owner_field ? schema.owner_field : undefined,
+    unique_constraints: Array.isArray(schema.unique_constraints) ? schema.unique_constraints : undefined,
+    archived: m.archived || false,
+  };
+}
+
+export function toApiSchema(a: any): ApiSchema {
+  const config = parseJsonValue<Record<string, any>>(a.config, {});
+  return {
+    ...config,
+    id: a.id,
+    method: a.method,
+    path: a.path,
+    name: a.name,
+    logic_flow_id: a.logicFlowId || undefined,
+    permissions: config.permissions || [],
+    archived: a.archived || false,
+  };
+}
+
+/**
+ * Load a project and every related entity into the snake_case schema used by the generators.
+ * Archived entities are left out. Every collection comes back in a fixed order, so generating
+ * the same project twice emits the same files in the same order.
+ */
+export async function loadProjectSchema(projectId: string): Promise<ProjectSchema | null> {
+  const project = await getProjectStore().getProjectWithEntities(projectId);
+  if (!project) return null;
+
+  const pageIdByInternalId = new Map(project.pages.map(p => [p.idRoot, p.id]));
+
+  return {
+    id: project.id,
+    name: project.name,
+    description: project.description || '',
+    settings: parseJsonValue<Record<string, any>>(project.settings, {}),
+    root_path: project.rootPath || '',
+    pages: project.pages.filter(p => !p.archived).map(p => {
+      const meta = parseJsonValue<Record<string, any>>(p.meta, {});
+      return {
+        id: p.id,
+        idRoot: p.idRoot,
+        name: p.name,
+        path: p.path,
+        root_block_id: pageRootIds(meta)[0],
+        root_block_ids: pageRootIds(meta),
+        is_dynamic: p.isDynamic,
+        layout: pageLayout(meta),
+        meta,
+        archived: p.archived,
+      };
+    }),
+    blocks: project.blocks.filter(b => !b.archived).map(b => ({
+      id: b.id,
+      block_type: b.blockType,
+      name: b.name,
+      parent_id: b.parentId,
+      page_id: b.pageId ? pageIdByInternalId.get(b.pageId) || null : null,
+      properties: parseJsonValue<Record<string, any>>(b.properties, {}),
+      styles: parseJsonValue<Record<string, string | number | boolean>>(b.styles, {}),
+      classes: parseJsonValue<string[]>(b.classes, []),
+      event_handlers: parseJsonValue<any[]>(b.events, []),
+      bindings: parseJsonValue<Record<string, any>>(b.bindings, {}),
+      children: parseJsonValue<string[]>(b.children, []),
+      order: b.order,
+      archived: b.archived,
+    })),
+    data_models: project.dataModels.filter(m => !m.archived).map(toDataModelSchema),
+    apis: project.apis.filter(a => !a.archived).map(toApiSchema),
+    logic_flows: project.logicFlows.filter(f => !f.archived).map(toLogicFlow),
+    variables: project.variables.map(v => ({
+      id: v.id,
+      name: v.name,
+      variable_type: v.type,
+      scope: v.scope === 'page' ? 'page' as const : 'global' as const,
+      page_id: v.pageId || undefined,
+      persist: v.persist,
+      default_value: parseJsonValue<unknown>(v.value, null),
+    })),
+  };
+}
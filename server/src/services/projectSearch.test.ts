@@ -0,0 +1,28 @@
+import { test } from 'node:test';
+import assert from 'node:assert/strict';
+import { searchProject } from './projectSearch.js';
+import type { ProjectSchema } from './projectSchema.js';
+
+const project = {
+  id: 'p1', name: 'Shop', settings: {}, logic_flows: [], variables: [],
+  blocks: [{ id: 'b1', name: 'Hero', properties: { text: 'Browse our Invoice templates' } }],
+  pages: [{ id: 'home', name: 'Home', path: '/' }, { id: 'billing', name: 'Billing', path: '/invoices' }],
+  data_models: [{ id: 'm1', name: 'Invoice', fields: [{ id: 'f1', name: 'invoiceNumber' }] }],
+  apis: [{ id: 'a1', name: 'listOrders', path: '/orders' }],
+} as unknown as ProjectSchema;
+
+test('searchProject matches case-insensitively across entity kinds', () => {
+  assert.deepEqual(searchProject(project, 'INVOICE').map(h => [h.kind, h.entity_id, h.matched]), [
+    ['block', 'b1', 'properties.text'],
+    ['page', 'billing', 'path'],
+    ['data_model', 'm1', 'name'],
+    ['field', 'f1', 'name'],
+  ]);
+  assert.equal(searchProject(project, 'invoice')[3]!.parent_id, 'm1');
+  assert.equal(searchProject(project, 'invoice')[0]!.snippet, 'Browse our Invoice templates');
+});
+
+test('searchProject stops at the limit and ignores a blank query', () => {
+  assert.equal(searchProject(project, 'invoice', 2).length, 2);
+  assert.deepEqual(searchProject(project, '   '), []);
+});
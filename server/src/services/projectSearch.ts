@@ -0,0 +1,79 @@
+import type { ProjectSchema } from './projectSchema.js';
+
+export type SearchHitKind = 'block' | 'page' | 'data_model' | 'field' | 'api' | 'logic_flow' | 'variable';
+
+export interface SearchHit {
+  kind: SearchHitKind;
+  entity_id: string;
+  /** Owning entity for hits that live inside another one (a field's model) */
+  parent_id?: string;
+  /** Which attribute matched, e.g. `name`, `path` or `properties.text` */
+  matched: string;
+  snippet: string;
+}
+
+export const DEFAULT_SEARCH_LIMIT = 50;
+export const MAX_SEARCH_LIMIT = 200;
+
+const SNIPPET_CONTEXT = 30;
+
+/** The match with up to SNIPPET_CONTEXT characters either side, ellipsised where cut */
+export function matchSnippet(text: string, index: number, length: number): string {
+  const start = Math.max(0, index - SNIPPET_CONTEXT);
+  const end = Math.min(text.length, index + length + SNIPPET_CONTEXT);
+  const body = text.slice(start, end).replace(/\s+/g, ' ');
+  return `${start > 0 ? '…' : ''}${body}${end < text.length ? '…' : ''}`;
+}
+
+/**
+ * Case-insensitive substring search over the names and text of every entity in a project.
+ * Hits come back grouped by entity kind in a stable order, capped at `limit`.
+ */
+export function searchProject(project: ProjectSchema, query: string, limit: number = DEFAULT_SEARCH_LIMIT): SearchHit[] {
+  const needle = query.trim().toLowerCase();
+  if (!needle) return [];
+
+  const cap = Math.max(1, Math.min(Math.trunc(limit) || DEFAULT_SEARCH_LIMIT, MAX_SEARCH_LIMIT));
+  const hits: SearchHit[] = [];
+
+  const check = (kind: SearchHitKind, entityId: string, matched: string, value: unknown, parentId?: string) => {
+    if (hits.length >= cap || typeof value !== 'string') return;
+    const index = value.toLowerCase().indexOf(needle);
+    if (index === -1) return;
+    hits.push({ kind, entity_id: entityId, ...(parentId && { parent_id: parentId }), matched, snippet: matchSnippet(value, index, needle.length) });
+  };
+
+  for (const block of project.blocks) {
+    check('block', block.id, 'name', block.name);
+    for (const [key, value] of Object.entries(block.properties)) {
+      check('block', block.id, `properties.${key}`, value);
+    }
+  }
+
+  for (const page of project.pages) {
+    check('page', page.id, 'name', page.name);
+    check('page', page.id, 'path', page.path);
+  }
+
+  for (const model of project.data_models) {
+    check('data_model', model.id, 'name', model.name);
+    for (const field of model.fields) {
+      check('field', field.id, 'name', field.name, model.id);
+    }
+  }
+
+  for (const api of project.apis) {
+    check('api', api.id, 'name', api.name);
+    check('api', api.id, 'path', api.path);
+  }
+
+  for (const flow of project.logic_flows) {
+    check('logic_flow', flow.id, 'name', flow.name);
+  }
+
+  for (const variable of project.variables) {
+    check('variable', variable.id, 'name', variable.name);
+  }
+
+  return hits;
+}
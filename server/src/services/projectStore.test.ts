@@ -0,0 +1,153 @@
+import { test } from 'node:test';
+import assert from 'node:assert/strict';
+import type { Project } from '@prisma/client';
+import type { Request, Response } from 'express';
+import {
+  compareWorkspaceOrder, getProjectStore, setProjectStore, type ProjectStore, type ProjectWithEntities, type WorkspaceSettings,
+} from './projectStore.js';
+import { claimRevision, StaleRevisionError, touchProject } from './revision.js';
+import { loadProjectSchema } from './projectSchema.js';
+import { getWorkspace, pinProject } from '../controllers/workspaceController.js';
+import { search } from '../controllers/searchController.js';
+
+type Entities = Omit<ProjectWithEntities, keyof Project>;
+
+/** A store that keeps everything in memory, standing in for another database */
+class MemoryProjectStore implements ProjectStore {
+  public projects = new Map<string, Project>();
+  public entities = new Map<string, Entities>();
+  private workspace: WorkspaceSettings = {};
+
+  async getAllProjects() { return [...this.projects.values()].sort(compareWorkspaceOrder); }
+  async getProjectById(id: string) { return this.projects.get(id) ?? null; }
+  async createProject(data: Partial<Project> & { name: string }) {
+    const now = new Date();
+    const project = {
+      id: `p${this.projects.size + 1}`, revision: 0, pinned: false, lastOpenedAt: null, settings: '{}',
+      createdAt: now, updatedAt: now, ...data,
+    } as Project;
+    this.projects.set(project.id, project);
+    return project;
+  }
+  async saveProject(id: string, data: Partial<Project>) {
+    const project = { ...this.projects.get(id)!, ...data };
+    this.projects.set(id, project);
+    return project;
+  }
+  async deleteProject(id: string) { this.projects.delete(id); }
+  async setPinned(id: string, pinned: boolean) { return this.saveProject(id, { pinned }); }
+  async markOpened(id: string) { return this.saveProject(id, { lastOpenedAt: new Date() }); }
+  async getProjectContent(id: string) {
+    const { pages = [], blocks = [] } = this.entities.get(id) ?? {};
+    return { pages, blocks };
+  }
+  async getProjectWithEntities(id: string) {
+    const project = this.projects.get(id);
+    if (!project) return null;
+    return { pages: [], blocks: [], dataModels: [], apis: [], logicFlows: [], variables: [], ...project, ...this.entities.get(id) };
+  }
+  async getRevision(id: string) { return this.projects.get(id)?.revision ?? null; }
+  async claimRevision(id: string, expected: number) {
+    if (this.projects.get(id)?.revision !== expected) return false;
+    await this.saveProject(id, { revision: expected + 1 });
+    return true;
+  }
+  async releaseRevision(id: string, claimed: number) {
+    if (this.projects.get(id)?.revision !== claimed) return false;
+    await this.saveProject(id, { revision: claimed - 1 });
+    return true;
+  }
+  async bumpRevision(id: string) {
+    if (!this.projects.has(id)) return null;
+    return (await this.saveProject(id, { revision: this.projects.get(id)!.revision + 1 })).revision;
+  }
+  async getWorkspaceSettings() { return this.workspace; }
+  async saveWorkspaceSettings(settings: WorkspaceSettings) { return (this.workspace = settings); }
+}
+
+test('project revisions go through whichever store is installed', async () => {
+  const previous = getProjectStore();
+  const store = new MemoryProjectStore();
+  setProjectStore(store);
+  try {
+    const { id } = await store.createProject({ name: 'Shop' });
+    assert.equal(await claimRevision(id, 0), 1);
+    await assert.rejects(claimRevision(id, 0), StaleRevisionError);
+    assert.equal(await touchProject(id), 2);
+    assert.equal(await claimRevision('missing', 0), null);
+    assert.equal(store.projects.get(id)!.revision, 2);
+  } finally {
+    setProjectStore(previous);
+  }
+});
+
+/** Enough of an Express response to read back what a handler answered */
+class Reply {
+  public statusCode = 200;
+  public body: any;
+
+  status(code: number) {
+    this.statusCode = code;
+    return this;
+  }
+
+  json(body: any) {
+    this.body = body;
+    return this;
+  }
+}
+
+test('the workspace, schema loading and search run against whichever store is installed', async t => {
+  const previous = getProjectStore();
+  const store = new MemoryProjectStore();
+  setProjectStore(store);
+  t.after(() => setProjectStore(previous));
+
+  const shop = await store.createProject({ name: 'Shop' });
+  const blog = await store.createProject({ name: 'Blog' });
+  store.entities.set(shop.id, {
+    pages: [{ idRoot: 'r1', id: 'home', projectId: shop.id, name: 'Home', path: '/', isDynamic: false, meta: '{"root_block_ids":["b1"]}', archived: false }],
+    blocks: [{
+      id: 'b1', projectId: shop.id, pageId: 'r1', parentId: null, blockType: 'Text', name: 'Hero', properties: '{"text":"Summer sale"}',
+      styles: '{}', responsiveStyles: '{}', classes: '[]', events: '[]', bindings: '{}', children: '[]', order: 0, archived: false,
+    }],
+    dataModels: [{ id: 'm1', projectId: shop.id, name: 'Product', schema: '{"fields":[],"relations":[]}', archived: false }],
+    apis: [],
+    logicFlows: [],
+    variables: [],
+  });
+
+  const pinned = new Reply();
+  await pinProject({ params: { id: blog.id }, body: { pinned: true } } as unknown as Request, pinned as unknown as Response);
+  assert.deepEqual(pinned.body, { id: blog.id, pinned: true });
+
+  const workspace = new Reply();
+  await getWorkspace({} as Request, workspace as unknown as Response);
+  assert.deepEqual(workspace.body.projects.map((p: { name: string }) => p.name), ['Blog', 'Shop']);
+
+  const schema = await loadProjectSchema(shop.id);
+  assert.deepEqual(schema?.pages.map(p => [p.path, p.root_block_ids]), [['/', ['b1']]]);
+  assert.deepEqual(schema?.blocks.map(b => [b.id, b.page_id]), [['b1', 'home']]);
+  assert.deepEqual(schema?.data_models.map(m => m.name), ['Product']);
+
+  const found = new Reply();
+  await search({ query: { projectId: shop.id, q: 'sale' } } as unknown as Request, found as unknown as Response);
+  assert.deepEqual(found.body.hits.map((h: { entity_id: string }) => h.entity_id), ['b1']);
+
+  const missing = new Reply();
+  await search({ query: { projectId: 'nope', q: 'sale' } } as unknown as Request, missing as unknown as Response);
+  assert.equal(missing.statusCode, 404);
+});
+
+test('compareWorkspaceOrder puts a pinned project ahead of a more recently updated or opened one', () => {
+  const project = (name: string, pinned: boolean, updatedAt: string, lastOpenedAt: string | null = null) =>
+    ({ name, pinned, updatedAt: new Date(updatedAt), lastOpenedAt: lastOpenedAt && new Date(lastOpenedAt) }) as Project;
+  const projects = [
+    project('Fresh', false, '2026-03-01T00:00:00Z'),
+    project('Opened', false, '2026-01-01T00:00:00Z', '2026-02-15T00:00:00Z'),
+    project('Pinned', true, '2026-01-01T00:00:00Z'),
+    project('Stale', false, '2025-12-01T00:00:00Z'),
+  ];
+
+  assert.deepEqual(projects.sort(compareWorkspaceOrder).map(p => p.name), ['Pinned', 'Opened', 'Fresh', 'Stale']);
+});
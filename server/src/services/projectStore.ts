@@ -0,0 +1,192 @@
+import type { ApiEndpoint, Block, DataModel, LogicFlow, Page, Project, Variable } from '@prisma/client';
+import prisma from '../lib/prisma.js';
+
+/** Project columns a caller can set; `settings` is the JSON-encoded ProjectSettings */
+export interface ProjectFields {
+  name?: string;
+  description?: string | null;
+  settings?: string;
+  rootPath?: string | null;
+}
+
+/** A project with every entity it owns, archived ones included */
+export type ProjectWithEntities = Project & {
+  pages: Page[];
+  blocks: Block[];
+  dataModels: DataModel[];
+  apis: ApiEndpoint[];
+  logicFlows: LogicFlow[];
+  variables: Variable[];
+};
+
+/** Settings for the workspace as a whole rather than any one project */
+export interface WorkspaceSettings {
+  /** Where the editor says the workspace lives */
+  workspace_path?: string;
+}
+
+/**
+ * Where project records are kept. Controllers reach projects through `getProjectStore()`
+ * instead of Prisma, so a deployment can plug in another database with `setProjectStore`.
+ */
+export interface ProjectStore {
+  /** Pinned first, then most recently opened, then most recently updated */
+  getAllProjects(): Promise<Project[]>;
+  getProjectById(id: string): Promise<Project | null>;
+  createProject(data: ProjectFields & { name: string }): Promise<Project>;
+  saveProject(id: string, data: ProjectFields): Promise<Project>;
+  /** Also removes everything the project owns */
+  deleteProject(id: string): Promise<void>;
+  /** Pinning and opening don't count as edits, so neither moves `updatedAt` */
+  setPinned(id: string, pinned: boolean): Promise<Project>;
+  markOpened(id: string): Promise<Project>;
+  /** The project's pages and blocks, unordered */
+  getProjectContent(id: string): Promise<{ pages: Page[]; blocks: Block[] }>;
+  /**
+   * Everything the generators read, each collection in a fixed order: pages by path, blocks by
+   * id, endpoints by path then method, the rest by name, ties broken by id
+   */
+  getProjectWithEntities(id: string): Promise<ProjectWithEntities | null>;
+  /** The revision If-Match headers are checked against; null when the project doesn't exist */
+  getRevision(id: string): Promise<number | null>;
+  /** Bump the revision in one conditional write, only while it still equals `expected` */
+  claimRevision(id: string, expected: number): Promise<boolean>;
+  /** Hand back a claim whose request failed, only while the revision is still `claimed` */
+  releaseRevision(id: string, claimed: number): Promise<boolean>;
+  /** Bump the revision unconditionally; null when the project doesn't exist */
+  bumpRevision(id: string): Promise<number | null>;
+  getWorkspaceSettings(): Promise<WorkspaceSettings>;
+  saveWorkspaceSettings(settings: WorkspaceSettings): Promise<WorkspaceSettings>;
+}
+
+/** The workspace order: pinned projects, then recently opened ones, then by last edit */
+export function compareWorkspaceOrder(a: Project, b: Project): number {
+  if (a.pinned !== b.pinned) return a.pinned ? -1 : 1;
+  const opened = (b.lastOpenedAt?.getTime() ?? 0) - (a.lastOpenedAt?.getTime() ?? 0);
+  if (opened !== 0) return opened;
+  return b.updatedAt.getTime() - a.updatedAt.getTime();
+}
+
+export class PrismaProjectStore implements ProjectStore {
+  async getAllProjects() {
+    // MongoDB can't order nulls last, so projects never opened are placed here
+    const projects = await prisma.project.findMany({ orderBy: { updatedAt: 'desc' } });
+    return projects.sort(compareWorkspaceOrder);
+  }
+
+  async getProjectById(id: string) {
+    return prisma.project.findUnique({ where: { id } });
+  }
+
+  async createProject(data: ProjectFields & { name: string }) {
+    return prisma.project.create({ data });
+  }
+
+  async saveProject(id: string, data: ProjectFields) {
+    return prisma.project.update({ where: { id }, data });
+  }
+
+  async deleteProject(id: string) {
+    // Relations cascade on delete
+    await prisma.project.delete({ where: { id } });
+  }
+
+  async setPinned(id: string, pinned: boolean) {
+    const project = await prisma.project.findUniqueOrThrow({ where: { id } });
+    return prisma.project.update({ where: { id }, data: { pinned, updatedAt: project.updatedAt } });
+  }
+
+  async markOpened(id: string) {
+    const project = await prisma.project.findUniqueOrThrow({ where: { id } });
+    return prisma.project.update({ where: { id }, data: { lastOpenedAt: new Date(), updatedAt: project.updatedAt } });
+  }
+
+  async getProjectContent(id: string) {
+    const [pages, blocks] = await Promise.all([
+      prisma.page.findMany({ where: { projectId: id } }),
+      prisma.block.findMany({ where: { projectId: id } }),
+    ]);
+    return { pages, blocks };
+  }
+
+  async getProjectWithEntities(id: string) {
+    return prisma.project.findUnique({
+      where: { id },
+      include: {
+        pages: { orderBy: [{ path: 'asc' }, { idRoot: 'asc' }] },
+        blocks: { orderBy: { id: 'asc' } },
+        dataModels: { orderBy: [{ name: 'asc' }, { id: 'asc' }] },
+        apis: { orderBy: [{ path: 'asc' }, { method: 'asc' }, { id: 'asc' }] },
+        logicFlows: { orderBy: [{ name: 'asc' }, { id: 'asc' }] },
+        variables: { orderBy: [{ name: 'asc' }, { id: 'asc' }] },
+      },
+    });
+  }
+
+  async getRevision(id: string) {
+    const project = await prisma.project.findUnique({ where: { id }, select: { revision: true } });
+    return project ? project.revision : null;
+  }
+
+  async claimRevision(id: string, expected: number) {
+    const { count } = await prisma.project.updateMany({
+      where: { id, revision: expected },
+      data: { revision: { increment: 1 } },
+    });
+    return count > 0;
+  }
+
+  async releaseRevision(id: string, claimed: number) {
+    const { count } = await prisma.project.updateMany({
+      where: { id, revision: claimed },
+      data: { revision: { decrement: 1 } },
+    });
+    return count > 0;
+  }
+
+  async bumpRevision(id: string) {
+    try {
+      const project = await prisma.project.update({
+        where: { id },
+        data: { revision: { increment: 1 } },
+        select: { revision: true },
+      });
+      return project.revision;
+    } catch {
+      return null;
+    }
+  }
+
+  async getWorkspaceSettings() {
+    const workspace = await prisma.workspace.findFirst();
+    return parseSettings(workspace?.settings);
+  }
+
+  async saveWorkspaceSettings(settings: WorkspaceSettings) {
+    const workspace = await prisma.workspace.findFirst();
+    const data = { settings: JSON.stringify(settings) };
+    const saved = workspace
+      ? await prisma.workspace.update({ where: { id: workspace.id }, data })
+      : await prisma.workspace.create({ data });
+    return parseSettings(saved.settings);
+  }
+}
+
+function parseSettings(value: string | undefined): WorkspaceSettings {
+  try {
+    const settings = JSON.parse(value || '{}');
+    return settings && typeof settings === 'object' && !Array.isArray(settings) ? settings : {};
+  } catch {
+    return {};
+  }
+}
+
+let store: ProjectStore = new PrismaProjectStore();
+
+export function getProjectStore(): ProjectStore {
+  return store;
+}
+
+export function setProjectStore(next: ProjectStore) {
+  store = next;
+}
@@ -0,0 +1,28 @@
+import { test } from 'node:test';
+import assert from 'node:assert/strict';
+import { useMemoryDb } from './memoryDb.js';
+import { applyProjectTemplate, findTemplate } from './projectTemplates.js';
+
+test('the Blog template creates a Post model and a home page whose root holds its blocks', async t => {
+  const db = useMemoryDb(t);
+
+  await applyProjectTemplate('p1', findTemplate('Blog')!);
+
+  assert.deepEqual(db.dataModel.rows.map(m => m.name), ['Author', 'Post', 'Comment']);
+  const post = JSON.parse(db.dataModel.rows.find(m => m.name === 'Post')!.schema);
+  assert.deepEqual(post.fields.map((f: { name: string }) => f.name), ['id', 'title', 'slug', 'body', 'status', 'publishedAt']);
+
+  const home = db.page.rows.find(p => p.path === '/')!;
+  assert.equal(home.name, 'Home');
+  const rootId = JSON.parse(home.meta).root_block_id;
+  const root = db.block.rows.find(b => b.id === rootId)!;
+  assert.deepEqual([root.blockType, root.pageId], ['canvas', home.idRoot]);
+  const sections = JSON.parse(root.children).map((id: string) => db.block.rows.find(b => b.id === id)!.name);
+  assert.deepEqual(sections, ['Hero', 'Latest Posts']);
+  assert.ok(db.block.rows.every(b => b.projectId === 'p1'));
+});
+
+test('findTemplate is case-insensitive and unknown ids find nothing', () => {
+  assert.equal(findTemplate('BLANK')?.id, 'blank');
+  assert.equal(findTemplate('shop'), undefined);
+});
@@ -0,0 +1,279 @@
+import { randomUUID } from 'crypto';
+import { getCommandDb } from './commands.js';
+
+export interface TemplateField {
+  name: string;
+  field_type: string;
+  required?: boolean;
+  unique?: boolean;
+  enum_values?: string[];
+}
+
+export interface TemplateBlock {
+  block_type: string;
+  name: string;
+  properties?: Record<string, unknown>;
+  classes?: string[];
+  children?: TemplateBlock[];
+}
+
+export interface TemplatePage {
+  name: string;
+  path: string;
+  is_dynamic?: boolean;
+  blocks: TemplateBlock[];
+}
+
+export interface ProjectTemplate {
+  id: string;
+  name: string;
+  description: string;
+  data_models: Array<{ name: string; fields: TemplateField[] }>;
+  pages: TemplatePage[];
+}
+
+export const DEFAULT_TEMPLATE_ID = 'blank';
+
+export const PROJECT_TEMPLATES: ProjectTemplate[] = [
+  {
+    id: 'blank',
+    name: 'Blank',
+    description: 'An empty home page',
+    data_models: [],
+    pages: [{ name: 'Home', path: '/', blocks: [] }],
+  },
+  {
+    id: 'blog',
+    name: 'Blog',
+    description: 'Posts with authors and comments, a post list and a post page',
+    data_models: [
+      {
+        name: 'Author',
+        fields: [
+          { name: 'name', field_type: 'string', required: true },
+          { name: 'email', field_type: 'email', required: true, unique: true },
+          { name: 'bio', field_type: 'text' },
+        ],
+      },
+      {
+        name: 'Post',
+        fields: [
+          { name: 'title', field_type: 'string', required: true },
+          { name: 'slug', field_type: 'string', required: true, unique: true },
+          { name: 'body', field_type: 'text', required: true },
+          { name: 'status', field_type: 'enum', required: true, enum_values: ['draft', 'published'] },
+          { name: 'publishedAt', field_type: 'datetime' },
+        ],
+      },
+      {
+        name: 'Comment',
+        fields: [
+          { name: 'author', field_type: 'string', required: true },
+          { name: 'body', field_type: 'text', required: true },
+        ],
+      },
+    ],
+    pages: [
+      {
+        name: 'Home',
+        path: '/',
+        blocks: [
+          {
+            block_type: 'Section',
+            name: 'Hero',
+            children: [
+              { block_type: 'Heading', name: 'Title', properties: { text: 'My Blog', level: 1 } },
+              { block_type: 'Text', name: 'Tagline', properties: { text: 'Notes, stories and updates.' } },
+            ],
+          },
+          {
+            block_type: 'Section',
+            name: 'Latest Posts',
+            children: [
+              { block_type: 'Heading', name: 'Latest Heading', properties: { text: 'Latest posts', level: 2 } },
+              { block_type: 'List', name: 'Post List', properties: { model: 'Post' } },
+            ],
+          },
+        ],
+      },
+      {
+        name: 'Post',
+        path: '/posts/:slug',
+        is_dynamic: true,
+        blocks: [
+          {
+            block_type: 'Container',
+            name: 'Article',
+            children: [
+              { block_type: 'Heading', name: 'Post Title', properties: { text: 'Post title', level: 1 } },
+              { block_type: 'Paragraph', name: 'Post Body', properties: { text: 'Post body' } },
+            ],
+          },
+        ],
+      },
+    ],
+  },
+  {
+    id: 'crm',
+    name: 'CRM',
+    description: 'Companies, contacts and deals with a contacts dashboard',
+    data_models: [
+      {
+        name: 'Company',
+        fields: [
+          { name: 'name', field_type: 'string', required: true },
+          { name: 'website', field_type: 'url' },
+        ],
+      },
+      {
+        name: 'Contact',
+        fields: [
+          { name: 'firstName', field_type: 'string', required: true },
+          { name: 'lastName', field_type: 'string', required: true },
+          { name: 'email', field_type: 'email', required: true, unique: true },
+          { name: 'phone', field_type: 'string' },
+        ],
+      },
+      {
+        name: 'Deal',
+        fields: [
+          { name: 'title', field_type: 'string', required: true },
+          { name: 'amount', field_type: 'decimal', required: true },
+          { name: 'stage', field_type: 'enum', required: true, enum_values: ['lead', 'qualified', 'won', 'lost'] },
+        ],
+      },
+    ],
+    pages: [
+      {
+        name: 'Home',
+        path: '/',
+        blocks: [
+          {
+            block_type: 'Section',
+            name: 'Dashboard',
+            children: [
+              { block_type: 'Heading', name: 'Title', properties: { text: 'Contacts', level: 1 } },
+              { block_type: 'Table', name: 'Contacts Table', properties: { model: 'Contact' } },
+            ],
+          },
+        ],
+      },
+      {
+        name: 'Deals',
+        path: '/deals',
+        blocks: [
+          {
+            block_type: 'Section',
+            name: 'Pipeline',
+            children: [
+              { block_type: 'Heading', name: 'Title', properties: { text: 'Deals', level: 1 } },
+              { block_type: 'Table', name: 'Deals Table', properties: { model: 'Deal' } },
+            ],
+          },
+        ],
+      },
+    ],
+  },
+];
+
+export function findTemplate(id: string): ProjectTemplate | undefined {
+  return PROJECT_TEMPLATES.find(t => t.id === id.toLowerCase());
+}
+
+/** Create a block and its subtree; returns the new block's id */
+async function createBlockTree(projectId: string, pageIdRoot: string, parentId: string, block: TemplateBlock, order: number): Promise<string> {
+  const db = getCommandDb();
+  const created = await db.block.create({
+    data: {
+      projectId,
+      pageId: pageIdRoot,
+      parentId,
+      blockType: block.block_type,
+      name: block.name,
+      properties: JSON.stringify(block.properties || {}),
+      styles: JSON.stringify({}),
+      responsiveStyles: JSON.stringify({}),
+      classes: JSON.stringify(block.classes || []),
+      events: JSON.stringify([]),
+      bindings: JSON.stringify({}),
+      children: JSON.stringify([]),
+      order,
+    },
+  });
+
+  const children: string[] = [];
+  for (const [index, child] of (block.children || []).entries()) {
+    children.push(await createBlockTree(projectId, pageIdRoot, created.id, child, index));
+  }
+  if (children.length) {
+    await db.block.update({ where: { id: created.id }, data: { children: JSON.stringify(children) } });
+  }
+  return created.id;
+}
+
+/**
+ * Populate a freshly created project with a template's data models, pages and blocks.
+ * Each page gets a canvas root block holding the template's blocks.
+ */
+export async function applyProjectTemplate(projectId: string, template: ProjectTemplate) {
+  const db = getCommandDb();
+  for (const model of template.data_models) {
+    const fields = [
+      { id: 'id', name: 'id', field_type: 'uuid', required: true, unique: true, primary_key: true },
+      ...model.fields.map(f => ({
+        id: randomUUID(),
+        required: false,
+        unique: false,
+        primary_key: false,
+        ...f,
+        ...(f.field_type === 'decimal' && { precision: 10, scale: 2 }),
+      })),
+    ];
+    await db.dataModel.create({
+      data: { projectId, name: model.name, schema: JSON.stringify({ fields, relations: [] }) },
+    });
+  }
+
+  for (const templatePage of template.pages) {
+    const page = await db.page.create({
+      data: {
+        id: randomUUID(),
+        projectId,
+        name: templatePage.name,
+        path: templatePage.path,
+        isDynamic: templatePage.is_dynamic || false,
+      },
+    });
+
+    const root = await db.block.create({
+      data: {
+        projectId,
+        pageId: page.idRoot,
+        parentId: null,
+        blockType: 'canvas',
+        name: 'Page Root',
+        properties: JSON.stringify({}),
+        styles: JSON.stringify({}),
+        responsiveStyles: JSON.stringify({}),
+        classes: JSON.stringify([]),
+        events: JSON.stringify([]),
+        bindings: JSON.stringify({}),
+        children: JSON.stringify([]),
+        order: 0,
+      },
+    });
+
+    const children: string[] = [];
+    for (const [index, block] of templatePage.blocks.entries()) {
+      children.push(await createBlockTree(projectId, page.idRoot, root.id, block, index));
+    }
+    if (children.length) {
+      await db.block.update({ where: { id: root.id }, data: { children: JSON.stringify(children) } });
+    }
+
+    await db.page.update({
+      where: { id: page.id },
+      data: { meta: JSON.stringify({ root_block_id: root.id }) },
+    });
+  }
+}
@@ -0,0 +1,40 @@
+import { test } from 'node:test';
+import assert from 'node:assert/strict';
+import { validateProject } from './projectValidation.js';
+import type { ApiSchema, PageSchema, ProjectSchema } from './projectSchema.js';
+
+test('validateProject reports a dangling page root and an API referencing a missing model', () => {
+  const project = {
+    id: 'p1', name: 'Shop', settings: {}, data_models: [], logic_flows: [], blocks: [], variables: [],
+    pages: [{ id: 'home', name: 'Home', path: '/', root_block_id: 'gone', archived: false } as PageSchema],
+    apis: [{
+      id: 'a1', method: 'GET', path: '/orders', name: 'orders', permissions: [], archived: false,
+      response_body: { shape_type: 'array', item_shape: { shape_type: 'model', model_ref: 'Order' } },
+    } as ApiSchema],
+  } as unknown as ProjectSchema;
+
+  assert.deepEqual(validateProject(project), [
+    { category: 'page', severity: 'error', entity_id: 'home', message: 'Page "Home" has a root block gone that does not exist' },
+    { category: 'api', severity: 'error', entity_id: 'a1', message: 'GET /orders references missing model "Order"' },
+  ]);
+});
+
+test('validateProject warns about an image without alt text and a button without a label', () => {
+  const block = (id: string, block_type: string, properties: Record<string, unknown>) => ({
+    id, block_type, name: id, parent_id: null, properties, styles: {}, classes: [], event_handlers: [], bindings: {}, children: [], order: 0, archived: false,
+  });
+  const project = {
+    id: 'p1', name: 'Shop', settings: {}, data_models: [], logic_flows: [], apis: [], pages: [], variables: [],
+    blocks: [
+      block('Hero', 'Image', { src: '/hero.png' }),
+      block('Divider', 'Image', { src: '/line.png', alt: '' }),
+      block('Close', 'Button', {}),
+      block('Menu', 'Button', { aria_label: 'Open menu' }),
+    ],
+  } as unknown as ProjectSchema;
+
+  assert.deepEqual(validateProject(project).map(i => [i.entity_id, i.severity, i.message]), [
+    ['Hero', 'warning', 'Image "Hero" has no alt text; set an empty alt if it is decorative'],
+    ['Close', 'warning', 'Button "Close" has no text or aria label'],
+  ]);
+});
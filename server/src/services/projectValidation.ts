@@ -0,0 +1,103 @@
+import type { ProjectSchema } from './projectSchema.js';
+import { accessibilityIssue } from './accessibility.js';
+
+export type IssueCategory = 'block' | 'page' | 'api' | 'model' | 'logic_flow' | 'route' | 'component';
+export type IssueSeverity = 'info' | 'warning' | 'error';
+
+export interface ProjectIssue {
+  category: IssueCategory;
+  severity: IssueSeverity;
+  entity_id: string;
+  message: string;
+}
+
+/** Every `model_ref` inside a request/response shape, including nested fields and array items */
+function collectModelRefs(shape: any, refs: string[] = []): string[] {
+  if (!shape || typeof shape !== 'object') return refs;
+  if (shape.model_ref) refs.push(String(shape.model_ref));
+  collectModelRefs(shape.item_shape, refs);
+  for (const field of shape.fields || []) collectModelRefs(field.nested, refs);
+  return refs;
+}
+
+/**
+ * Run every cross-entity consistency check on a loaded project. Archived entities are
+ * already filtered out by loadProjectSchema, so references to them count as dangling.
+ */
+export function validateProject(project: ProjectSchema): ProjectIssue[] {
+  const issues: ProjectIssue[] = [];
+  const blockIds = new Set(project.blocks.map(b => b.id));
+  const modelKeys = new Set(project.data_models.flatMap(m => [m.id, m.name]));
+
+  for (const block of project.blocks) {
+    if (block.parent_id && !blockIds.has(block.parent_id)) {
+      issues.push({ category: 'block', severity: 'error', entity_id: block.id, message: `Block "${block.name}" points at missing parent ${block.parent_id}` });
+    }
+    for (const child of block.children) {
+      if (!blockIds.has(child)) {
+        issues.push({ category: 'block', severity: 'warning', entity_id: block.id, message: `Block "${block.name}" lists missing child ${child}` });
+      }
+    }
+    const a11yIssue = accessibilityIssue(block);
+    if (a11yIssue) issues.push({ category: 'block', severity: 'warning', entity_id: block.id, message: a11yIssue });
+  }
+
+  for (const page of project.pages) {
+    const rootIds = page.root_block_ids?.length ? page.root_block_ids : page.root_block_id ? [page.root_block_id] : [];
+    for (const rootId of rootIds) {
+      if (!blockIds.has(rootId)) {
+        issues.push({ category: 'page', severity: 'error', entity_id: page.id, message: `Page "${page.name}" has a root block ${rootId} that does not exist` });
+      }
+    }
+  }
+
+  for (const api of project.apis) {
+    const refs = [...collectModelRefs(api.request_body), ...collectModelRefs(api.response_body)];
+    for (const ref of new Set(refs)) {
+      if (!modelKeys.has(ref)) {
+        issues.push({ category: 'api', severity: 'error', entity_id: api.id, message: `${api.method} ${api.path} references missing model "${ref}"` });
+      }
+    }
+    if (api.logic_flow_id && !project.logic_flows.some(f => f.id === api.logic_flow_id)) {
+      issues.push({ category: 'api', severity: 'warning', entity_id: api.id, message: `${api.method} ${api.path} is wired to missing logic flow ${api.logic_flow_id}` });
+    }
+  }
+
+  for (const flow of project.logic_flows) {
+    if (flow.entry_node_id && !flow.nodes.some(n => n.id === flow.entry_node_id)) {
+      issues.push({ category: 'logic_flow', severity: 'error', entity_id: flow.id, message: `Flow "${flow.name}" starts at unknown node ${flow.entry_node_id}` });
+    }
+  }
+
+  const pageRoutes = new Map<string, string>();
+  for (const page of project.pages) {
+    const route = page.path.replace(/\/+$/, '') || '/';
+    const first = pageRoutes.get(route);
+    if (first) {
+      issues.push({ category: 'route', severity: 'error', entity_id: page.id, message: `Page route "${route}" is also used by page ${first}` });
+    } else {
+      pageRoutes.set(route, page.id);
+    }
+  }
+
+  const apiRoutes = new Map<string, string>();
+  for (const api of project.apis) {
+    const route = `${api.method.toUpperCase()} ${api.path.replace(/\/+$/, '') || '/'}`;
+    const first = apiRoutes.get(route);
+    if (first) {
+      issues.push({ category: 'route', severity: 'error', entity_id: api.id, message: `API route "${route}" is also defined by ${first}` });
+    } else {
+      apiRoutes.set(route, api.id);
+    }
+  }
+
+  // Components are blocks of type "component"; instances point at them via properties.component_id
+  const usedComponents = new Set(project.blocks.map(b => b.properties.component_id).filter(Boolean));
+  for (const component of project.blocks.filter(b => b.block_type === 'component')) {
+    if (!usedComponents.has(component.id)) {
+      issues.push({ category: 'component', severity: 'info', entity_id: component.id, message: `Component "${component.name}" is not used anywhere` });
+    }
+  }
+
+  return issues;
+}
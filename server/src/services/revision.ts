@@ -0,0 +1,79 @@
+import prisma from '../lib/prisma.js';
+import { getProjectStore } from './projectStore.js';
+
+export class StaleRevisionError extends Error {
+  public readonly expected: number;
+  public readonly current: number;
+
+  constructor(expected: number, current: number) {
+    super(`Project revision ${expected} is stale; current revision is ${current}`);
+    this.name = 'StaleRevisionError';
+    this.expected = expected;
+    this.current = current;
+  }
+}
+
+/** Project id of an entity addressed by a route, for routes that don't carry the project id */
+export async function projectIdForEntity(kind: string, id: string): Promise<string | null> {
+  const select = { projectId: true } as const;
+  try {
+    return await lookupProjectId(kind, id, select);
+  } catch {
+    // Malformed ids make Prisma throw; the handler will report those itself
+    return null;
+  }
+}
+
+async function lookupProjectId(kind: string, id: string, select: { projectId: true }): Promise<string | null> {
+  switch (kind) {
+    case 'block': return (await prisma.block.findUnique({ where: { id }, select }))?.projectId ?? null;
+    case 'page': return (await prisma.page.findUnique({ where: { id }, select }))?.projectId ?? null;
+    case 'data_model': return (await prisma.dataModel.findUnique({ where: { id }, select }))?.projectId ?? null;
+    case 'logic_flow': return (await prisma.logicFlow.findUnique({ where: { id }, select }))?.projectId ?? null;
+    case 'variable': return (await prisma.variable.findUnique({ where: { id }, select }))?.projectId ?? null;
+    case 'usecase': return (await prisma.useCase.findUnique({ where: { id }, select }))?.projectId ?? null;
+    case 'project': return id;
+    default: return null;
+  }
+}
+
+/**
+ * Parse an `If-Match` header: a bare revision number, optionally quoted or weak (`W/"3"`).
+ * Returns null when the header is absent or `*`.
+ */
+export function parseIfMatch(header: string | undefined): number | null {
+  if (!header || header.trim() === '*') return null;
+  const value = Number(header.trim().replace(/^W\//, '').replace(/"/g, ''));
+  return Number.isInteger(value) && value >= 0 ? value : null;
+}
+
+export async function projectRevision(projectId: string): Promise<number | null> {
+  return getProjectStore().getRevision(projectId);
+}
+
+/**
+ * Bump the revision only while it still equals the client's expected one, as a single
+ * conditional write so two requests holding the same revision can't both pass. Returns the
+ * new revision, null when the project doesn't exist, or throws StaleRevisionError
+ */
+export async function claimRevision(projectId: string, expected: number): Promise<number | null> {
+  if (await getProjectStore().claimRevision(projectId, expected)) return expected + 1;
+
+  const current = await projectRevision(projectId);
+  if (current === null) return null;
+  throw new StaleRevisionError(expected, current);
+}
+
+/**
+ * Undo a claim made by `claimRevision` when the request it guarded failed, so the client's
+ * revision is current again. Left alone if another write has bumped it since.
+ */
+export async function releaseRevision(projectId: string, claimed: number): Promise<void> {
+  await getProjectStore().releaseRevision(projectId, claimed);
+}
+
+/** Record that the project changed; returns the new revision */
+export async function touchProject(projectId: string): Promise<number | null> {
+  // Null when the project was deleted by this request
+  return getProjectStore().bumpRevision(projectId);
+}
@@ -0,0 +1,386 @@
+/**
+ * A small interpreter for the JavaScript expressions logic-flow nodes carry, for running
+ * flows on the editor server without handing user code to `eval` or `node:vm`. It supports
+ * literals, `input`/`state` lookups, operators, the ternary, array and object literals, and
+ * calls to a fixed set of pure string, array, number and `Math` functions. Everything else
+ * (assignment, functions, `new`, templates) is a syntax error. Only own properties of plain
+ * data can be read, so no expression can reach a prototype or a host constructor.
+ */
+
+/** Longest string or array an expression may build, so repeated doubling can't exhaust memory */
+const MAX_VALUE_LENGTH = 100_000;
+
+/** Names that lead from plain data back to constructors and prototypes */
+export const FORBIDDEN_KEYS = new Set(['__proto__', 'prototype', 'constructor', '__defineGetter__', '__defineSetter__', '__lookupGetter__', '__lookupSetter__']);
+
+const STRING_METHODS = new Set([
+  'at', 'charAt', 'concat', 'endsWith', 'includes', 'indexOf', 'lastIndexOf', 'padEnd', 'padStart',
+  'replace', 'replaceAll', 'slice', 'split', 'startsWith', 'substring', 'toLowerCase', 'toString', 'toUpperCase',
+  'trim', 'trimEnd', 'trimStart',
+]);
+const ARRAY_METHODS = new Set(['at', 'concat', 'includes', 'indexOf', 'join', 'lastIndexOf', 'slice']);
+const NUMBER_METHODS = new Set(['toFixed', 'toPrecision', 'toString']);
+
+const MATH: Record<string, unknown> = Object.assign(Object.create(null), {
+  PI: Math.PI, E: Math.E,
+  abs: Math.abs, ceil: Math.ceil, floor: Math.floor, max: Math.max, min: Math.min, pow: Math.pow,
+  round: Math.round, sign: Math.sign, sqrt: Math.sqrt, trunc: Math.trunc,
+});
+
+/** Identifiers every expression can use besides its scope */
+const GLOBALS: Record<string, unknown> = Object.assign(Object.create(null), {
+  Math: MATH,
+  String: (value: unknown) => String(value),
+  Number: (value: unknown) => Number(value),
+  Boolean: (value: unknown) => Boolean(value),
+  parseInt: (value: unknown, radix?: number) => parseInt(String(value), radix),
+  parseFloat: (value: unknown) => parseFloat(String(value)),
+  isNaN: (value: unknown) => Number.isNaN(Number(value)),
+  undefined,
+  NaN,
+  Infinity,
+});
+const CALLABLE = new Set(Object.values(GLOBALS).concat(Object.values(MATH)).filter(v => typeof v === 'function'));
+
+type Token = { type: 'number' | 'string' | 'name' | 'punct'; value: string };
+
+type Node =
+  | { kind: 'literal'; value: unknown }
+  | { kind: 'name'; name: string }
+  | { kind: 'member'; object: Node; property: Node; optional: boolean }
+  | { kind: 'call'; callee: Node; args: Node[] }
+  | { kind: 'unary'; operator: string; operand: Node }
+  | { kind: 'binary'; operator: string; left: Node; right: Node }
+  | { kind: 'conditional'; test: Node; consequent: Node; alternate: Node }
+  | { kind: 'array'; items: Node[] }
+  | { kind: 'object'; entries: Array<[string, Node]> };
+
+const PUNCTUATORS = ['===', '!==', '**', '==', '!=', '<=', '>=', '&&', '||', '??', '?.', '+', '-', '*', '/', '%', '<', '>', '!', '?', ':', '.', ',', '(', ')', '[', ']', '{', '}'];
+
+/** Binding power of each binary operator; `**` is the only right-associative one */
+const BINARY_PRECEDENCE: Record<string, number> = {
+  '||': 1, '??': 1, '&&': 2,
+  '==': 3, '!=': 3, '===': 3, '!==': 3,
+  '<': 4, '>': 4, '<=': 4, '>=': 4,
+  '+': 5, '-': 5, '*': 6, '/': 6, '%': 6, '**': 7,
+};
+
+function tokenize(source: string): Token[] {
+  const tokens: Token[] = [];
+  let i = 0;
+  while (i < source.length) {
+    const rest = source.slice(i);
+    const space = /^\s+/.exec(rest);
+    if (space) {
+      i += space[0].length;
+      continue;
+    }
+    const number = /^(?:\d+(?:\.\d+)?|\.\d+)(?:[eE][+-]?\d+)?/.exec(rest);
+    if (number) {
+      tokens.push({ type: 'number', value: number[0] });
+      i += number[0].length;
+      continue;
+    }
+    const name = /^[A-Za-z_$][\w$]*/.exec(rest);
+    if (name) {
+      tokens.push({ type: 'name', value: name[0] });
+      i += name[0].length;
+      continue;
+    }
+    if (rest[0] === '"' || rest[0] === "'") {
+      const quote = rest[0];
+      let value = '';
+      let j = 1;
+      for (; j < rest.length && rest[j] !== quote; j++) {
+        if (rest[j] !== '\\') {
+          value += rest[j];
+          continue;
+        }
+        const escaped = rest[++j];
+        if (escaped === 'u' && /^[0-9a-fA-F]{4}$/.test(rest.slice(j + 1, j + 5))) {
+          value += String.fromCharCode(parseInt(rest.slice(j + 1, j + 5), 16));
+          j += 4;
+        } else {
+          value += ({ n: '\n', t: '\t', r: '\r', b: '\b', f: '\f', v: '\v', 0: '\0' } as Record<string, string>)[escaped] ?? escaped;
+        }
+      }
+      if (j >= rest.length) throw new SyntaxError('Unterminated string');
+      tokens.push({ type: 'string', value });
+      i += j + 1;
+      continue;
+    }
+    const punct = PUNCTUATORS.find(p => rest.startsWith(p));
+    if (!punct) throw new SyntaxError(`Unexpected character "${rest[0]}"`);
+    tokens.push({ type: 'punct', value: punct });
+    i += punct.length;
+  }
+  return tokens;
+}
+
+class Parser {
+  private position = 0;
+
+  constructor(private tokens: Token[]) {}
+
+  parse(): Node {
+    const node = this.expression();
+    if (this.peek()) throw new SyntaxError(`Unexpected "${this.peek()!.value}"`);
+    return node;
+  }
+
+  private peek(): Token | undefined {
+    return this.tokens[this.position];
+  }
+
+  private next(): Token {
+    const token = this.tokens[this.position++];
+    if (!token) throw new SyntaxError('Unexpected end of expression');
+    return token;
+  }
+
+  private accept(value: string): boolean {
+    const token = this.peek();
+    if (token?.type === 'punct' && token.value === value) {
+      this.position++;
+      return true;
+    }
+    return false;
+  }
+
+  private expect(value: string) {
+    if (!this.accept(value)) throw new SyntaxError(`Expected "${value}"`);
+  }
+
+  private expression(): Node {
+    const test = this.binary(0);
+    if (!this.accept('?')) return test;
+    const consequent = this.expression();
+    this.expect(':');
+    return { kind: 'conditional', test, consequent, alternate: this.expression() };
+  }
+
+  private binary(minPrecedence: number): Node {
+    let left = this.unary();
+    for (;;) {
+      const token = this.peek();
+      const precedence = token?.type === 'punct' ? BINARY_PRECEDENCE[token.value] : undefined;
+      if (precedence === undefined || precedence <= minPrecedence) return left;
+      this.position++;
+      const right = this.binary(token!.value === '**' ? precedence - 1 : precedence);
+      left = { kind: 'binary', operator: token!.value, left, right };
+    }
+  }
+
+  private unary(): Node {
+    const token = this.peek();
+    if (token?.type === 'punct' && ['!', '-', '+'].includes(token.value)) {
+      this.position++;
+      return { kind: 'unary', operator: token.value, operand: this.unary() };
+    }
+    if (token?.type === 'name' && token.value === 'typeof') {
+      this.position++;
+      return { kind: 'unary', operator: 'typeof', operand: this.unary() };
+    }
+    return this.postfix(this.primary());
+  }
+
+  private postfix(node: Node): Node {
+    for (;;) {
+      if (this.accept('.') || this.accept('?.')) {
+        const optional = this.tokens[this.position - 1].value === '?.';
+        const name = this.next();
+        if (name.type !== 'name') throw new SyntaxError('Expected a property name');
+        node = { kind: 'member', object: node, property: { kind: 'literal', value: name.value }, optional };
+      } else if (this.accept('[')) {
+        const property = this.expression();
+        this.expect(']');
+        node = { kind: 'member', object: node, property, optional: false };
+      } else if (this.accept('(')) {
+        node = { kind: 'call', callee: node, args: this.list(')') };
+      } else {
+        return node;
+      }
+    }
+  }
+
+  private list(close: string): Node[] {
+    const items: Node[] = [];
+    while (!this.accept(close)) {
+      items.push(this.expression());
+      if (!this.accept(',')) {
+        this.expect(close);
+        break;
+      }
+    }
+    return items;
+  }
+
+  private primary(): Node {
+    const token = this.next();
+    switch (token.type) {
+      case 'number':
+        return { kind: 'literal', value: Number(token.value) };
+      case 'string':
+        return { kind: 'literal', value: token.value };
+      case 'name':
+        if (token.value === 'true' || token.value === 'false') return { kind: 'literal', value: token.value === 'true' };
+        if (token.value === 'null') return { kind: 'literal', value: null };
+        return { kind: 'name', name: token.value };
+    }
+    if (token.value === '(') {
+      const node = this.expression();
+      this.expect(')');
+      return node;
+    }
+    if (token.value === '[') return { kind: 'array', items: this.list(']') };
+    if (token.value === '{') return this.object();
+    throw new SyntaxError(`Unexpected "${token.value}"`);
+  }
+
+  private object(): Node {
+    const entries: Array<[string, Node]> = [];
+    while (!this.accept('}')) {
+      const key = this.next();
+      if (key.type === 'punct') throw new SyntaxError(`Unexpected "${key.value}"`);
+      if (FORBIDDEN_KEYS.has(key.value)) throw new SyntaxError(`"${key.value}" is not allowed as a key`);
+      entries.push([key.value, this.accept(':') ? this.expression() : { kind: 'name', name: key.value }]);
+      if (!this.accept(',')) {
+        this.expect('}');
+        break;
+      }
+    }
+    return { kind: 'object', entries };
+  }
+}
+
+function isPlainObject(value: unknown): value is Record<string, unknown> {
+  if (!value || typeof value !== 'object' || Array.isArray(value)) return false;
+  const proto = Object.getPrototypeOf(value);
+  return proto === Object.prototype || proto === null;
+}
+
+function checkSize<T>(value: T): T {
+  if ((typeof value === 'string' || Array.isArray(value)) && value.length > MAX_VALUE_LENGTH) {
+    throw new RangeError(`Value longer than ${MAX_VALUE_LENGTH}`);
+  }
+  return value;
+}
+
+/** `object[key]` for plain data only: own properties, plus string and array length and indexes */
+function readMember(object: unknown, key: unknown): unknown {
+  if (typeof key !== 'string' && typeof key !== 'number') throw new TypeError('Property keys must be strings or numbers');
+  const name = String(key);
+  if (FORBIDDEN_KEYS.has(name)) throw new TypeError(`Reading "${name}" is not allowed`);
+  if (typeof object === 'string' || Array.isArray(object)) {
+    if (name === 'length') return object.length;
+    return /^\d+$/.test(name) ? object[Number(name)] : undefined;
+  }
+  if (object === MATH || isPlainObject(object)) return Object.hasOwn(object, name) ? object[name] : undefined;
+  return undefined;
+}
+
+function callMethod(object: unknown, name: string, args: unknown[]): unknown {
+  if (typeof object === 'string' && STRING_METHODS.has(name)) {
+    // Padding allocates the whole result up front, so its size is checked first
+    if ((name === 'padStart' || name === 'padEnd') && Number(args[0]) > MAX_VALUE_LENGTH) {
+      throw new RangeError(`Value longer than ${MAX_VALUE_LENGTH}`);
+    }
+    return checkSize((String.prototype as any)[name].apply(object, args));
+  }
+  if (Array.isArray(object) && ARRAY_METHODS.has(name)) {
+    return checkSize((Array.prototype as any)[name].apply(object, args));
+  }
+  if (typeof object === 'number' && NUMBER_METHODS.has(name)) {
+    return (Number.prototype as any)[name].apply(object, args);
+  }
+  if (object === MATH && typeof MATH[name] === 'function') return (MATH[name] as (...a: unknown[]) => unknown)(...args);
+  throw new TypeError(`${name}() is not an allowed function`);
+}
+
+function evaluateNode(node: Node, scope: Record<string, unknown>): unknown {
+  switch (node.kind) {
+    case 'literal':
+      return node.value;
+
+    case 'name':
+      if (Object.hasOwn(scope, node.name)) return scope[node.name];
+      if (node.name in GLOBALS) return GLOBALS[node.name];
+      throw new ReferenceError(`${node.name} is not defined`);
+
+    case 'member': {
+      const object = evaluateNode(node.object, scope);
+      if (object === null || object === undefined) {
+        if (node.optional) return undefined;
+        throw new TypeError(`Cannot read properties of ${object}`);
+      }
+      return readMember(object, evaluateNode(node.property, scope));
+    }
+
+    case 'call': {
+      const args = node.args.map(arg => evaluateNode(arg, scope));
+      if (node.callee.kind === 'member') {
+        const object = evaluateNode(node.callee.object, scope);
+        const name = evaluateNode(node.callee.property, scope);
+        if (typeof name !== 'string' || FORBIDDEN_KEYS.has(name)) throw new TypeError('Not an allowed function');
+        return callMethod(object, name, args);
+      }
+      const callee = evaluateNode(node.callee, scope);
+      if (!CALLABLE.has(callee)) throw new TypeError('Not an allowed function');
+      return (callee as (...a: unknown[]) => unknown)(...args);
+    }
+
+    case 'unary': {
+      const operand = evaluateNode(node.operand, scope);
+      switch (node.operator) {
+        case '!': return !operand;
+        case '-': return -(operand as number);
+        case '+': return +(operand as number);
+        default: return typeof operand;
+      }
+    }
+
+    case 'binary': {
+      const left = evaluateNode(node.left, scope);
+      // Short-circuit like JavaScript does
+      if (node.operator === '&&') return left ? evaluateNode(node.right, scope) : left;
+      if (node.operator === '||') return left ? left : evaluateNode(node.right, scope);
+      if (node.operator === '??') return left ?? evaluateNode(node.right, scope);
+      const right = evaluateNode(node.right, scope);
+      const l = left as any;
+      const r = right as any;
+      switch (node.operator) {
+        case '+': return checkSize(l + r);
+        case '-': return l - r;
+        case '*': return l * r;
+        case '/': return l / r;
+        case '%': return l % r;
+        case '**': return l ** r;
+        case '==': return l == r;
+        case '!=': return l != r;
+        case '===': return l === r;
+        case '!==': return l !== r;
+        case '<': return l < r;
+        case '>': return l > r;
+        case '<=': return l <= r;
+        default: return l >= r;
+      }
+    }
+
+    case 'conditional':
+      return evaluateNode(node.test, scope) ? evaluateNode(node.consequent, scope) : evaluateNode(node.alternate, scope);
+
+    case 'array':
+      return checkSize(node.items.map(item => evaluateNode(item, scope)));
+
+    case 'object': {
+      const object: Record<string, unknown> = {};
+      for (const [key, value] of node.entries) object[key] = evaluateNode(value, scope);
+      return object;
+    }
+  }
+}
+
+/** Evaluate `source` with the names in `scope` (e.g. `input`, `state`) defined */
+export function evaluateExpression(source: string, scope: Record<string, unknown>): unknown {
+  return evaluateNode(new Parser(tokenize(source)).parse(), scope);
+}
@@ -0,0 +1,307 @@
+import { test, type TestContext } from 'node:test';
+import assert from 'node:assert/strict';
+import fs from 'fs-extra';
+import os from 'os';
+import path from 'path';
+import { sha256 } from './bundle.js';
+import { AddBlockCommand, RemoveBlocksCommand, getCommandDb, setCommandDb } from './commands.js';
+import { memoryCommandDb } from './memoryDb.js';
+import { getProjectStore, setProjectStore, type ProjectStore } from './projectStore.js';
+import { PageNotFoundError, SyncError, SyncService } from './sync.js';
+import { DEFAULT_WATCH_IGNORE } from './watcher.js';
+
+/** A project rooted in a fresh temp dir whose entities are `seed`, held in memory */
+async function useProject(t: TestContext, seed: Parameters<typeof memoryCommandDb>[0]) {
+  const root = await fs.mkdtemp(path.join(os.tmpdir(), 'akasha-sync-'));
+  const previousDb = getCommandDb();
+  const previousStore = getProjectStore();
+  t.after(async () => {
+    setCommandDb(previousDb);
+    setProjectStore(previousStore);
+    await fs.remove(root);
+  });
+  setProjectStore({
+    getProjectById: async (id: string) => ({ id, rootPath: root, settings: '{}' }),
+    getProjectWithEntities: async () => null,
+  } as unknown as ProjectStore);
+  setCommandDb(memoryCommandDb(seed));
+  return root;
+}
+
+/** Let `useProject`'s project load `entities` (data models, endpoints) for its schema */
+function useSchemaEntities(root: string, entities: Record<string, unknown[]>) {
+  setProjectStore({
+    getProjectById: async (id: string) => ({ id, rootPath: root, settings: '{}' }),
+    getProjectWithEntities: async (id: string) => ({
+      id, name: 'Shop', settings: '{}', rootPath: root, pages: [], blocks: [], dataModels: [], apis: [], logicFlows: [], variables: [], ...entities,
+    }),
+  } as unknown as ProjectStore);
+}
+
+test('detectConflicts reports a page whose file and schema both changed since the last sync', async t => {
+  const root = await fs.mkdtemp(path.join(os.tmpdir(), 'akasha-sync-'));
+  const previous = getCommandDb();
+  t.after(async () => {
+    setCommandDb(previous);
+    await fs.remove(root);
+  });
+
+  // Both pages were synced when their files read "synced"; the schema has moved on since
+  const syncState = { root, schema_hash: 'schema-at-last-sync', file_hash: sha256('synced'), synced_at: '2026-01-01T00:00:00.000Z' };
+  setCommandDb(memoryCommandDb({
+    pages: [
+      { id: 'home', projectId: 'p1', idRoot: 'root-home', name: 'Home', path: '/', meta: JSON.stringify({ sync_state: syncState }) },
+      { id: 'about', projectId: 'p1', idRoot: 'root-about', name: 'About', path: '/about', meta: JSON.stringify({ sync_state: syncState }) },
+    ],
+    blocks: [{ id: 'b1', projectId: 'p1', pageId: 'root-home', blockType: 'Text', name: 'Title', properties: '{"text":"Edited"}' }],
+  }));
+  await fs.outputFile(path.join(root, 'src', 'pages', 'Home.tsx'), 'edited on disk');
+  await fs.outputFile(path.join(root, 'src', 'pages', 'About.tsx'), 'synced');
+
+  const service = new SyncService(root);
+  assert.deepEqual(await service.detectConflicts('p1'), [{
+    page_id: 'home',
+    page_name: 'Home',
+    file: path.join('src', 'pages', 'Home.tsx'),
+    message: '"Home" was edited on disk and in the editor since 2026-01-01T00:00:00.000Z',
+  }]);
+
+  // The page is left as it is on disk rather than overwritten
+  assert.deepEqual(await service.syncPageToDisk('home', 'p1'), (await service.detectConflicts('p1'))[0]);
+  assert.equal(await fs.readFile(path.join(root, 'src', 'pages', 'Home.tsx'), 'utf-8'), 'edited on disk');
+});
+
+test('syncPageToDisk renders a Text block bound to a variable as a reference to it', async t => {
+  const root = await useProject(t, {
+    pages: [{ id: 'home', projectId: 'p1', idRoot: 'root-home', name: 'Home', path: '/' }],
+    blocks: [{
+      id: 'b1', projectId: 'p1', pageId: 'root-home', blockType: 'Text', name: 'Title',
+      properties: '{"text":"Static title"}', bindings: '{"text":{"type":"variable","value":"v1"}}',
+    }],
+    variables: [{ id: 'v1', projectId: 'p1', name: 'page_title', scope: 'page', pageId: 'home', value: '"Welcome"' }],
+  });
+
+  assert.equal(await new SyncService(root).syncPageToDisk('home', 'p1'), null);
+
+  const page = await fs.readFile(path.join(root, 'src', 'pages', 'Home.tsx'), 'utf-8');
+  assert.match(page, /<Text text=\{pageTitle\} \/>/);
+  assert.match(page, /const \[pageTitle, setPageTitle\] = useState.*\("Welcome"\);/);
+  assert.doesNotMatch(page, /Static title/);
+});
+
+test('initProjectStructure writes a .gitignore covering the watcher\'s ignore list and keeps user entries', async t => {
+  const root = await fs.mkdtemp(path.join(os.tmpdir(), 'akasha-sync-'));
+  t.after(() => fs.remove(root));
+  await fs.writeFile(path.join(root, '.gitignore'), 'coverage/\n');
+
+  await new SyncService(root).initProjectStructure([...DEFAULT_WATCH_IGNORE, 'build']);
+
+  const entries = (await fs.readFile(path.join(root, '.gitignore'), 'utf-8')).split('\n');
+  for (const entry of ['coverage/', 'node_modules/', '.git/', 'dist/', '.next/', 'build/']) assert.ok(entries.includes(entry), entry);
+  assert.deepEqual((await fs.readJson(path.join(root, 'akasha.config.json'))).watch_ignore, [...DEFAULT_WATCH_IGNORE, 'build']);
+});
+
+test('syncPageToDisk passes an instance\'s prop values to its component, which declares them', async t => {
+  const root = await useProject(t, {
+    pages: [{ id: 'home', projectId: 'p1', idRoot: 'root-home', name: 'Home', path: '/' }],
+    blocks: [
+      { id: 'b0', projectId: 'p1', pageId: 'root-home', blockType: 'Container', name: 'Root', children: '["i1"]' },
+      { id: 'i1', projectId: 'p1', pageId: 'root-home', parentId: 'b0', blockType: 'instance', name: 'Promo', properties: '{"component_id":"c1","prop_values":{"title":"Summer sale"}}' },
+      {
+        id: 'c1', projectId: 'p1', blockType: 'component', name: 'PromoCard', children: '["t1"]',
+        properties: '{"props":[{"name":"title","prop_type":"string","required":true}]}',
+      },
+      { id: 't1', projectId: 'p1', parentId: 'c1', blockType: 'Text', name: 'Title', bindings: '{"text":{"type":"prop","value":"title"}}' },
+    ],
+  });
+
+  assert.equal(await new SyncService(root).syncPageToDisk('home', 'p1'), null);
+
+  const page = await fs.readFile(path.join(root, 'src', 'pages', 'Home.tsx'), 'utf-8');
+  assert.match(page, /import PromoCard from '\.\.\/components\/PromoCard';/);
+  assert.match(page, /<PromoCard title="Summer sale" \/>/);
+  const component = await fs.readFile(path.join(root, 'src', 'components', 'PromoCard.tsx'), 'utf-8');
+  assert.match(component, /export interface PromoCardProps \{\n {2}title: string;\n\}/);
+  assert.match(component, /export default function PromoCard\(\{ title \}: PromoCardProps\)/);
+});
+
+test('syncPageToDisk renders a page of thousands of blocks from one read, before and after edits', async t => {
+  const count = 3000;
+  const items = Array.from({ length: count }, (_, i) => ({
+    id: `t${i}`, projectId: 'p1', parentId: 'b0', blockType: 'Text', name: `Item ${i}`, properties: JSON.stringify({ text: `Item ${i}` }), order: i,
+  }));
+  const root = await useProject(t, {
+    pages: [{ id: 'home', projectId: 'p1', idRoot: 'root-home', name: 'Home', path: '/' }],
+    blocks: [
+      { id: 'b0', projectId: 'p1', pageId: 'root-home', blockType: 'Container', name: 'Root', children: JSON.stringify(items.map(b => b.id)) },
+      ...items,
+    ],
+  });
+  const findUnique = t.mock.method(getCommandDb().block, 'findUnique');
+  const service = new SyncService(root);
+  const readPage = () => fs.readFile(path.join(root, 'src', 'pages', 'Home.tsx'), 'utf-8');
+  const texts = (source: string) => [...source.matchAll(/<Text text="(Item \d+|Added)" \/>/g)].map(m => m[1]);
+
+  assert.equal(await service.syncPageToDisk('home', 'p1'), null);
+  assert.equal(findUnique.mock.callCount(), 0);
+  assert.deepEqual(texts(await readPage()), items.map(b => `Item ${b.id.slice(1)}`));
+
+  const add = new AddBlockCommand({ projectId: 'p1', blockType: 'Text', name: 'Added', parentId: 'b0', properties: { text: 'Added' } });
+  await add.execute();
+  await new RemoveBlocksCommand('p1', ['t0', 't1500'], 'delete').execute();
+  findUnique.mock.resetCalls();
+
+  assert.equal(await service.syncPageToDisk('home', 'p1'), null);
+  assert.equal(findUnique.mock.callCount(), 0);
+  const after = texts(await readPage());
+  assert.equal(after.length, count - 1);
+  assert.deepEqual([after[0], after[1498], after[1499], after.at(-1)], ['Item 1', 'Item 1499', 'Item 1501', 'Added']);
+});
+
+test('syncPageToDisk emits an image\'s alt and a button\'s aria-label', async t => {
+  const root = await useProject(t, {
+    pages: [{ id: 'home', projectId: 'p1', idRoot: 'root-home', name: 'Home', path: '/' }],
+    blocks: [
+      { id: 'b0', projectId: 'p1', pageId: 'root-home', blockType: 'Container', name: 'Root', children: '["img","btn"]' },
+      { id: 'img', projectId: 'p1', parentId: 'b0', blockType: 'Image', name: 'Hero', properties: '{"src":"/hero.png","alt":"A red bicycle"}' },
+      { id: 'btn', projectId: 'p1', parentId: 'b0', blockType: 'Button', name: 'Close', properties: '{"aria_label":"Close dialog","role":"button"}', order: 1 },
+    ],
+  });
+
+  assert.equal(await new SyncService(root).syncPageToDisk('home', 'p1'), null);
+
+  const page = await fs.readFile(path.join(root, 'src', 'pages', 'Home.tsx'), 'utf-8');
+  assert.match(page, /<Image src="\/hero\.png" alt="A red bicycle" \/>/);
+  assert.match(page, /<Button aria-label="Close dialog" role="button" \/>/);
+});
+
+test('syncPageToDisk renders a model-bound Table as a <table> mapped over the model\'s data', async t => {
+  const root = await useProject(t, {
+    pages: [{ id: 'home', projectId: 'p1', idRoot: 'root-home', name: 'Home', path: '/' }],
+    blocks: [{
+      id: 'tbl', projectId: 'p1', pageId: 'root-home', blockType: 'Table', name: 'Products',
+      properties: '{"columns":["price","name"]}', bindings: '{"data":{"type":"model","value":"m1"}}',
+    }],
+  });
+  const fields = ['id', 'name', 'price', 'sku'].map(name => ({ id: name, name, field_type: 'string', required: true, unique: false, primary_key: name === 'id' }));
+  useSchemaEntities(root, { dataModels: [{ id: 'm1', name: 'Product', schema: JSON.stringify({ fields }), archived: false }] });
+
+  assert.equal(await new SyncService(root).syncPageToDisk('home', 'p1'), null);
+
+  const page = await fs.readFile(path.join(root, 'src', 'pages', 'Home.tsx'), 'utf-8');
+  assert.match(page, /const \{ data: productData \} = useProducts\(\);/);
+  assert.match(page, /\{productData\.map\(row => \(\n\s*<tr key=\{row\.id\}>/);
+  assert.deepEqual([...page.matchAll(/<th[^>]*>(\w+)<\/th>/g)].map(m => m[1]), ['price', 'name']);
+  assert.deepEqual([...page.matchAll(/\{String\(row\.(\w+) \?\? ''\)\}/g)].map(m => m[1]), ['price', 'name']);
+  assert.ok(await fs.pathExists(path.join(root, 'src', 'hooks', 'useProducts.ts')));
+});
+
+test('syncPageToDisk wires a Form bound to an endpoint to submit its inputs through the api client', async t => {
+  const root = await useProject(t, {
+    pages: [{ id: 'signup', projectId: 'p1', idRoot: 'root-signup', name: 'Signup', path: '/signup' }],
+    blocks: [
+      { id: 'f1', projectId: 'p1', pageId: 'root-signup', blockType: 'Form', name: 'Signup form', children: '["i1","i2"]', bindings: '{"submit":{"type":"api","value":"a1"}}' },
+      { id: 'i1', projectId: 'p1', parentId: 'f1', blockType: 'Input', name: 'Email', properties: '{"name":"email","label":"Email"}' },
+      { id: 'i2', projectId: 'p1', parentId: 'f1', blockType: 'Input', name: 'Age', properties: '{"name":"age","label":"Age"}', order: 1 },
+    ],
+  });
+  const fields = [{ id: 'email', name: 'email', field_type: 'string', required: true }, { id: 'age', name: 'age', field_type: 'int', required: false }];
+  useSchemaEntities(root, {
+    dataModels: [{ id: 'm1', name: 'User', schema: JSON.stringify({ fields }), archived: false }],
+    apis: [{ id: 'a1', method: 'POST', path: '/users', name: 'Create User', config: JSON.stringify({ request_body: { shape_type: 'model', model_ref: 'm1' } }), archived: false }],
+  });
+
+  assert.equal(await new SyncService(root).syncPageToDisk('signup', 'p1'), null);
+
+  const page = await fs.readFile(path.join(root, 'src', 'pages', 'Signup.tsx'), 'utf-8');
+  assert.match(page, /import \{ createUser \} from '\.\.\/lib\/endpoints';/);
+  assert.match(page, /<form onSubmit=\{submitSignupForm\}>/);
+  assert.match(page, /const data = formValues\(form, \{"age":"number"\}\);\n\s*await createUser\(data\);/);
+  assert.match(page, /const \[signupFormSubmitting, setSignupFormSubmitting\] = useState\(false\);/);
+  assert.match(await fs.readFile(path.join(root, 'src', 'lib', 'endpoints.ts'), 'utf-8'), /export function createUser\(/);
+});
+
+test('initProjectStructure keeps a user-edited App.tsx when called again, unless forced', async t => {
+  const root = await fs.mkdtemp(path.join(os.tmpdir(), 'akasha-sync-'));
+  t.after(() => fs.remove(root));
+  const service = new SyncService(root);
+  const appPath = path.join(root, 'src', 'App.tsx');
+
+  await service.initProjectStructure(DEFAULT_WATCH_IGNORE, { project: { id: 'p1', name: 'Shop' } });
+  assert.match(await fs.readFile(appPath, 'utf-8'), /export default function App\(\)/);
+  await fs.writeFile(appPath, 'export default function App() { return <h1>Mine</h1>; }\n');
+
+  await service.initProjectStructure(DEFAULT_WATCH_IGNORE, { project: { id: 'p1', name: 'Shop renamed' } });
+  assert.equal(await fs.readFile(appPath, 'utf-8'), 'export default function App() { return <h1>Mine</h1>; }\n');
+  assert.equal((await fs.readJson(path.join(root, 'akasha.config.json'))).name, 'Shop renamed');
+
+  await service.initProjectStructure(DEFAULT_WATCH_IGNORE, { force: true });
+  assert.doesNotMatch(await fs.readFile(appPath, 'utf-8'), /Mine/);
+});
+
+test('syncPageToDisk sets the page title, description and Open Graph tags from its meta', async t => {
+  const meta = { title: 'Summer sale', description: 'Half price on everything "outdoor"' };
+  const root = await useProject(t, {
+    pages: [{ id: 'sale', projectId: 'p1', idRoot: 'root-sale', name: 'Sale', path: '/sale', meta: JSON.stringify(meta) }],
+    blocks: [{ id: 'b1', projectId: 'p1', pageId: 'root-sale', blockType: 'Text', name: 'Title', properties: '{"text":"Sale"}' }],
+  });
+
+  assert.equal(await new SyncService(root).syncPageToDisk('sale', 'p1'), null);
+
+  const page = await fs.readFile(path.join(root, 'src', 'pages', 'Sale.tsx'), 'utf-8');
+  assert.match(page, /import \{ usePageMeta \} from '\.\.\/hooks\/usePageMeta';/);
+  assert.match(page, /usePageMeta\(\{ title: "Summer sale", description: "Half price on everything \\"outdoor\\"" \}\);/);
+  const hook = await fs.readFile(path.join(root, 'src', 'hooks', 'usePageMeta.ts'), 'utf-8');
+  assert.match(hook, /if \(title\) document\.title = title;/);
+  assert.match(hook, /setMeta\('property', 'og:description', description\);/);
+});
+
+test('syncPageToDisk rejects a missing page with PageNotFoundError naming it', async t => {
+  const root = await useProject(t, { pages: [{ id: 'home', projectId: 'p1', idRoot: 'root-home', name: 'Home', path: '/' }] });
+
+  await assert.rejects(new SyncService(root).syncPageToDisk('nope', 'p1'), (error: unknown) => {
+    assert.ok(error instanceof PageNotFoundError && error instanceof SyncError);
+    assert.deepEqual([error.pageId, error.status, error.message], ['nope', 404, 'Page nope not found']);
+    return true;
+  });
+  assert.equal(await fs.pathExists(path.join(root, 'src', 'pages', 'Nope.tsx')), false);
+});
+
+test('syncPageToDisk escapes text that would break the JSX or read as markup', async t => {
+  const root = await useProject(t, {
+    pages: [{ id: 'home', projectId: 'p1', idRoot: 'root-home', name: 'Home', path: '/' }],
+    blocks: [{ id: 'b1', projectId: 'p1', pageId: 'root-home', blockType: 'Text', name: 'Title', properties: '{"text":"<script>{}</script>"}' }],
+  });
+
+  assert.equal(await new SyncService(root).syncPageToDisk('home', 'p1'), null);
+
+  const page = await fs.readFile(path.join(root, 'src', 'pages', 'Home.tsx'), 'utf-8');
+  assert.match(page, /<Text text=\{"\\u003cscript\\u003e\{\}\\u003c\/script\\u003e"\} \/>/);
+  assert.doesNotMatch(page, /<script>|<\/script>/);
+  // The expression decodes back to the text as typed
+  assert.equal(JSON.parse(page.match(/text=\{(".*")\}/)![1]!), '<script>{}</script>');
+});
+
+test('syncPageFromDisk takes the edited text from the file and keeps the block\'s events', async t => {
+  const events = '{"onClick":{"flow_id":"addToCart"}}';
+  const root = await useProject(t, {
+    pages: [{ id: 'home', projectId: 'p1', idRoot: 'root-home', name: 'Home', path: '/' }],
+    blocks: [{
+      id: 'b1', projectId: 'p1', pageId: 'root-home', blockType: 'Button', name: 'Buy',
+      properties: '{"text":"Buy now","size":"lg"}', events,
+    }],
+  });
+  const service = new SyncService(root);
+  assert.equal(await service.syncPageToDisk('home', 'p1'), null);
+
+  const file = path.join(root, 'src', 'pages', 'Home.tsx');
+  const page = await fs.readFile(file, 'utf-8');
+  assert.match(page, /text="Buy now"/);
+  await fs.writeFile(file, page.replace('text="Buy now"', 'text="Add to basket"'));
+
+  assert.deepEqual(await service.syncPageFromDisk('home'), ['b1']);
+  const block = (await getCommandDb().block.findUnique({ where: { id: 'b1' } }))!;
+  assert.deepEqual(JSON.parse(block.properties), { text: 'Add to basket', size: 'lg' });
+  assert.equal(block.events, events);
+  assert.deepEqual(await service.detectConflicts('p1'), []);
+});
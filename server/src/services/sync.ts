@@ -1,16 +1,288 @@
 
 import fs from 'fs-extra';
 import path from 'path';
-import prisma from '../lib/prisma.js';
-import { pascalCase } from '../utils/string.js';
+import type { Block, Page } from '@prisma/client';
+import type { Response } from 'express';
+import { getCommandDb } from './commands.js';
+import { getProjectStore } from './projectStore.js';
+import { camelCase, kebabCase, pascalCase } from '../utils/string.js';
+import { sha256 } from './bundle.js';
+import { DEFAULT_WATCH_IGNORE } from './watcher.js';
+import { apiBaseUrl, customBlockName, loadProjectSchema, modelBindingField, pageHeadMeta, pageRootIds, parseJsonValue, type DataModelSchema, type ProjectSchema } from './projectSchema.js';
+import { GeneratorService, endpointFunctions, type EndpointFunction } from './generator.js';
+import { codeStyle, DEFAULT_CODE_STYLE, formatFile, type CodeStyle } from './codeStyle.js';
+import { positionClasses } from './positioning.js';
+import { normalizeClasses, splitStyles } from './tailwindClasses.js';
+import { ariaAttributes } from './accessibility.js';
+
+/** Why syncing to disk failed, naming what it failed on; `status` is the HTTP status to answer with */
+export class SyncError extends Error {
+  public readonly status: number;
+
+  constructor(message: string, status: number) {
+    super(message);
+    this.name = 'SyncError';
+    this.status = status;
+  }
+}
+
+export class PageNotFoundError extends SyncError {
+  public readonly pageId: string;
+
+  constructor(pageId: string) {
+    super(`Page ${pageId} not found`, 404);
+    this.name = 'PageNotFoundError';
+    this.pageId = pageId;
+  }
+}
+
+export class ComponentNotFoundError extends SyncError {
+  public readonly componentId: string;
+
+  constructor(componentId: string) {
+    super(`Component ${componentId} not found`, 404);
+    this.name = 'ComponentNotFoundError';
+    this.componentId = componentId;
+  }
+}
+
+export class WriteFailedError extends SyncError {
+  public readonly path: string;
+
+  constructor(filePath: string, cause: unknown) {
+    super(`Failed to write ${filePath}: ${(cause as Error)?.message ?? cause}`, 500);
+    this.name = 'WriteFailedError';
+    this.path = filePath;
+  }
+}
+
+/** A page or block column holding JSON that doesn't parse */
+export class ParseFailedError extends SyncError {
+  public readonly pageId: string;
+
+  constructor(pageId: string, cause: unknown) {
+    super(`Page ${pageId} has unreadable stored data: ${(cause as Error)?.message ?? cause}`, 422);
+    this.name = 'ParseFailedError';
+    this.pageId = pageId;
+  }
+}
+
+/** Answer with the error's status when it is a SyncError; returns whether it did */
+export function sendSyncError(res: Response, error: unknown): boolean {
+  if (!(error instanceof SyncError)) return false;
+  res.status(error.status).json({ error: error.message, code: error.name });
+  return true;
+}
+
+/** A prop declared on a component block, stored in its `properties.props` */
+export interface ComponentProp {
+  name: string;
+  prop_type: 'string' | 'number' | 'boolean' | 'object' | 'array' | 'any';
+  required: boolean;
+  default?: unknown;
+}
+
+const PROP_TS_TYPES: Record<string, string> = {
+  string: 'string',
+  number: 'number',
+  boolean: 'boolean',
+  object: 'Record<string, any>',
+  array: 'any[]',
+};
+
+/** What a page looked like the last time it was written to disk, kept in `meta.sync_state` */
+interface SyncState {
+  root: string;
+  schema_hash: string;
+  file_hash: string;
+  synced_at: string;
+}
+
+/** A page whose file and schema have both changed since the last sync */
+export interface SyncConflict {
+  page_id: string;
+  page_name: string;
+  /** File path relative to the project root */
+  file: string;
+  message: string;
+}
+
+export interface SyncPageOptions {
+  /** Overwrite the file even when it was edited on disk since the last sync */
+  force?: boolean;
+  /** Record the page's sync state; off for throwaway output such as previews */
+  recordState?: boolean;
+}
+
+export const AKASHA_CONFIG_FILE = 'akasha.config.json';
+
+/** Written to the project root on every init so tools can tell which project a folder holds */
+export interface AkashaConfig {
+  project_id: string | null;
+  name: string;
+  pages_dir: string;
+  components_dir: string;
+  watch_ignore: string[];
+}
+
+export interface InitProjectOptions {
+  /** Overwrite starter files and the .gitignore even when they already exist */
+  force?: boolean;
+  project?: { id: string; name: string };
+}
+
+/** A Form wired to an endpoint: the handler and state names it gets, and the request it sends */
+interface FormSubmit {
+  name: string;
+  /** Endpoint client function the form calls with its path params and values */
+  endpoint?: EndpointFunction;
+  /** Otherwise the model create route the values are posted to */
+  route?: string;
+  /** Field name → how `formValues` converts its string value */
+  kinds: Record<string, 'number' | 'boolean' | 'json'>;
+}
+
+/** Methods a form can submit to; GET and DELETE requests carry no body */
+const FORM_METHODS = new Set(['POST', 'PUT', 'PATCH']);
+
+/** How a model's fields are converted out of FormData strings; decimals stay strings like their DTOs */
+function fieldKinds(model: DataModelSchema | undefined): FormSubmit['kinds'] {
+  const kinds: FormSubmit['kinds'] = {};
+  for (const field of model?.fields || []) {
+    if (field.primary_key) continue;
+    if (field.field_type === 'int' || field.field_type === 'float') kinds[field.name] = 'number';
+    else if (field.field_type === 'boolean') kinds[field.name] = 'boolean';
+    else if (field.field_type === 'json') kinds[field.name] = 'json';
+  }
+  return kinds;
+}
+
+const isInstance = (blockType: string) => blockType === 'Instance' || blockType === 'instance';
+
+/**
+ * A value as a JS literal for a JSX expression. `<` and `>` are escaped too, so user text
+ * such as `</script>` can't read as markup in the generated file.
+ */
+function jsxLiteral(value: unknown): string {
+  return JSON.stringify(value ?? null).replace(/</g, '\\u003c').replace(/>/g, '\\u003e');
+}
+
+/** A literal as a JSX attribute: plain strings stay quoted, everything else becomes an expression */
+function jsxAttribute(name: string, value: unknown): string {
+  // Quoted JSX attributes have no escapes and decode `&` entities, so anything unusual goes in braces
+  if (typeof value === 'string' && !/["{}<>&\\\n]/.test(value)) return ` ${name}="${value}"`;
+  return ` ${name}={${jsxLiteral(value)}}`;
+}
+
+/** A block's non-Tailwind styles as a JSX `style` object, or nothing when all map to classes */
+function jsxStyle(inline: Array<[string, string | number]>): string {
+  if (inline.length === 0) return '';
+  const entries = inline.map(([name, value]) => {
+    const key = /^[A-Za-z_$][\w$]*$/.test(name) ? name : jsxLiteral(name);
+    const literal = typeof value === 'number'
+      ? String(value)
+      : `'${value.replace(/\\/g, '\\\\').replace(/'/g, "\\'").replace(/\n/g, '\\n').replace(/</g, '\\u003c').replace(/>/g, '\\u003e')}'`;
+    return `${key}: ${literal}`;
+  });
+  return ` style={{ ${entries.join(', ')} }}`;
+}
+
+/** Block properties a page file carries as JSX attributes, by block type: attribute → property */
+const DISK_PROPERTIES: Record<string, Record<string, string>> = {
+  Button: { text: 'text', variant: 'variant' },
+  Heading: { text: 'text', level: 'level' },
+  Text: { text: 'text' },
+  Paragraph: { text: 'text' },
+  Input: { label: 'label', placeholder: 'placeholder', type: 'input_type' },
+  Image: { src: 'src', alt: 'alt' },
+};
+
+/**
+ * The literal attributes of the JSX tag opening at `start` (just past its name). Quoted
+ * strings and JSON-literal expressions are read; other expressions, such as bindings, are
+ * left out since they don't hold a value the schema can store.
+ */
+function readJsxAttributes(source: string, start: number): Record<string, unknown> {
+  const attributes: Record<string, unknown> = {};
+  const attribute = /\s*([A-Za-z_][\w-]*)(?:=("[^"]*"|\{))?/y;
+  let i = start;
+  while (i < source.length) {
+    attribute.lastIndex = i;
+    const match = attribute.exec(source);
+    if (!match) break;
+    const [whole, name, value] = match;
+    i += whole.length;
+    if (value === undefined) {
+      attributes[name] = true;
+    } else if (value.startsWith('"')) {
+      attributes[name] = value.slice(1, -1);
+    } else {
+      // Find the matching brace, skipping over string literals
+      let depth = 1;
+      let j = i;
+      for (; j < source.length && depth > 0; j++) {
+        const char = source[j];
+        if (char === '"' || char === "'" || char === '`') {
+          for (j++; j < source.length && source[j] !== char; j++) if (source[j] === '\\') j++;
+        } else if (char === '{') depth++;
+        else if (char === '}') depth--;
+      }
+      try {
+        attributes[name] = JSON.parse(source.slice(i, j - 1));
+      } catch {
+        // An expression, not a literal
+      }
+      i = j;
+    }
+  }
+  return attributes;
+}
 
 export class SyncService {
   private rootPath: string;
+  /** Variable id (or name) → identifier used in generated JSX, loaded per page sync */
+  private variables = new Map<string, string>();
+  /** Identifiers of global variables referenced by the page being synced */
+  private usedGlobals = new Set<string>();
+  private globalNames = new Set<string>();
+  /** Data hooks (`useTodos`) read by model-bound tables and props, with the model each one serves */
+  private modelHooks = new Map<string, DataModelSchema>();
+  /** Forms with a submit binding in the component being rendered, by handler name */
+  private forms = new Map<string, FormSubmit>();
+  /** The project schema, loaded the first time a page sync needs its models or endpoints */
+  private schema: ProjectSchema | null = null;
+  /** The project's settings and the code style they set, loaded at the start of each sync */
+  private settings: Record<string, any> = {};
+  private style: CodeStyle = DEFAULT_CODE_STYLE;
+  /** Components whose files were written during the current sync, to stop instance cycles */
+  private syncedComponents = new Set<string>();
+  /**
+   * The project's blocks by id, loaded once per page sync so walking the tree doesn't cost a
+   * query per block. Null outside a sync; lookups then go to the database.
+   */
+  private blockIndex: Map<string, Block> | null = null;
 
   constructor(rootPath: string) {
     this.rootPath = rootPath;
   }
 
+  private async loadSettings(projectId: string) {
+    const project = await getProjectStore().getProjectById(projectId);
+    this.settings = parseJsonValue<Record<string, any>>(project?.settings, {});
+    this.style = codeStyle({ settings: this.settings });
+  }
+
+  /** Write a generated source file in the project's code style; returns what was written */
+  private async writeSource(filePath: string, content: string): Promise<string> {
+    const formatted = formatFile(filePath, content, this.style);
+    try {
+      await fs.outputFile(filePath, formatted);
+    } catch (error) {
+      throw new WriteFailedError(path.relative(this.rootPath, filePath), error);
+    }
+    return formatted;
+  }
+
   private get pagesDir() {
     return path.join(this.rootPath, 'src', 'pages');
   }
@@ -19,6 +291,49 @@ export class SyncService {
     return path.join(this.rootPath, 'src', 'components');
   }
 
+  /**
+   * Create the base folder layout for a synced project: a runnable Vite starter, a root
+   * .gitignore covering the same paths the file watcher skips, and `akasha.config.json`.
+   * Safe to call on every open: starter files that already exist are left alone unless
+   * `force` is set, and only `akasha.config.json` is always rewritten.
+   */
+  public async initProjectStructure(ignore: string[] = DEFAULT_WATCH_IGNORE, options: InitProjectOptions = {}) {
+    await fs.ensureDir(this.pagesDir);
+    await fs.ensureDir(this.componentsDir);
+
+    const name = options.project?.name || path.basename(this.rootPath);
+    const starter = [
+      ...new GeneratorService().boilerplateFiles(name),
+      { path: 'src/App.tsx', content: `export default function App() {\n  return <div className="min-h-screen bg-slate-50" />;\n}\n` },
+    ];
+    for (const file of starter) {
+      const filePath = path.join(this.rootPath, file.path);
+      if (!options.force && await fs.pathExists(filePath)) continue;
+      await fs.outputFile(filePath, file.content);
+    }
+
+    const gitignorePath = path.join(this.rootPath, '.gitignore');
+    const entries = [...ignore.map(entry => `${entry}/`), '.env', '*.log'];
+
+    if (!options.force && await fs.pathExists(gitignorePath)) {
+      // Append only what's missing so user entries are kept
+      const existing = (await fs.readFile(gitignorePath, 'utf-8')).split(/\r?\n/);
+      const missing = entries.filter(entry => !existing.includes(entry));
+      if (missing.length) await fs.appendFile(gitignorePath, `\n${missing.join('\n')}\n`);
+    } else {
+      await fs.writeFile(gitignorePath, `${entries.join('\n')}\n`);
+    }
+
+    const config: AkashaConfig = {
+      project_id: options.project?.id ?? null,
+      name,
+      pages_dir: path.relative(this.rootPath, this.pagesDir).split(path.sep).join('/'),
+      components_dir: path.relative(this.rootPath, this.componentsDir).split(path.sep).join('/'),
+      watch_ignore: ignore,
+    };
+    await fs.writeJson(path.join(this.rootPath, AKASHA_CONFIG_FILE), config, { spaces: 2 });
+  }
+
   /**
    * Map BlockType to PascalCase component file name
    */
@@ -57,6 +372,7 @@ export class SyncService {
       default:
         // If it's a custom component or unknown, use pascal case of the type or custom name
         if (customName) return pascalCase(customName);
+        if (customBlockName(blockType)) return pascalCase(customBlockName(blockType)!);
         if (blockType.startsWith('Custom')) return pascalCase(blockType.replace('Custom', ''));
         return pascalCase(blockType);
     }
@@ -172,6 +488,30 @@ export default function Image({ src = 'https://via.placeholder.com/400x300', alt
 `;
     }
 
+    if (name === 'Input') {
+      return `import React from 'react';
+// @akasha-component type="input"
+
+interface InputProps {
+  name?: string;
+  label?: string;
+  type?: string;
+  placeholder?: string;
+  className?: string;
+  [key: string]: any;
+}
+
+export default function Input({ name, label, type = 'text', placeholder, className = '', ...props }: InputProps) {
+  return (
+    <label className={\`flex flex-col gap-1 text-sm text-gray-700 \$\{className\}\`}>
+      {label}
+      <input name={name} type={type} placeholder={placeholder} className="rounded-lg border border-gray-300 px-3 py-2" {...props} />
+    </label>
+  );
+}
+`;
+    }
+
     // Fallback generic
     return `import React from 'react';
 // @akasha-component type="${lowerName}"
@@ -203,7 +543,7 @@ export default function ${name}({ children, className = '', ...props }: ${name}P
     const filePath = path.join(compDir, `${compName}.tsx`);
     if (!await fs.pathExists(filePath)) {
       const template = this.getComponentTemplate(blockType, compName);
-      await fs.writeFile(filePath, template);
+      await this.writeSource(filePath, template);
     }
     return compName;
   }
@@ -211,16 +551,34 @@ export default function ${name}({ children, className = '', ...props }: ${name}P
   /**
    * Recursively collect used component names from a block tree
    */
+  /** A block by id, from the sync's index when one is loaded */
+  private async findBlock(blockId: string): Promise<Block | null> {
+    if (this.blockIndex) return this.blockIndex.get(blockId) ?? null;
+    return getCommandDb().block.findUnique({ where: { id: blockId } });
+  }
+
   private async collectUsedComponents(blockId: string, projectId: string, components: Set<string>) {
-    const block = await prisma.block.findUnique({ where: { id: blockId } });
+    const block = await this.findBlock(blockId);
     if (!block) return;
     if (block.projectId !== projectId) return; // Safety check
 
-    const compName = this.blockTypeToComponentName(block.blockType);
+    // Instances import the component they point at; its subtree is rendered in its own file
+    if (isInstance(block.blockType)) {
+      const component = await this.instanceComponent(block.properties, projectId);
+      if (component) {
+        components.add(await this.syncComponentToDisk(component.id, projectId));
+        return;
+      }
+    }
 
-    // Ensure file exists (Sync Engine logic)
-    await this.ensureComponentFile(block.blockType);
-    components.add(compName);
+    // Model-bound tables and wired forms render plain elements, so they need no component file
+    if (!await this.tableModel(block) && !await this.formSubmit(block)) {
+      const compName = this.blockTypeToComponentName(block.blockType);
+
+      // Ensure file exists (Sync Engine logic)
+      await this.ensureComponentFile(block.blockType);
+      components.add(compName);
+    }
 
     const childrenIds = JSON.parse(block.children || '[]');
     for (const childId of childrenIds) {
@@ -228,37 +586,187 @@ export default function ${name}({ children, className = '', ...props }: ${name}P
     }
   }
 
+  /** The component block an instance's `properties.component_id` points at */
+  private async instanceComponent(properties: string, projectId: string) {
+    const componentId = JSON.parse(properties || '{}').component_id;
+    if (!componentId) return null;
+    const component = await this.findBlock(componentId);
+    return component && component.projectId === projectId ? component : null;
+  }
+
+  /**
+   * Write `src/components/{Name}.tsx` for a component block: a typed `{Name}Props` interface
+   * built from `properties.props`, and the component's children as the rendered body.
+   * Blocks inside it read props through `prop` bindings. Returns the component name.
+   */
+  public async syncComponentToDisk(componentId: string, projectId: string): Promise<string> {
+    const component = await this.findBlock(componentId);
+    if (!component) throw new ComponentNotFoundError(componentId);
+
+    const name = pascalCase(component.name);
+    if (this.syncedComponents.has(componentId)) return name;
+    this.syncedComponents.add(componentId);
+
+    const declared: ComponentProp[] = JSON.parse(component.properties || '{}').props || [];
+    const childrenIds: string[] = JSON.parse(component.children || '[]');
+
+    const usedComponents = new Set<string>();
+    for (const childId of childrenIds) {
+      await this.collectUsedComponents(childId, projectId, usedComponents);
+    }
+    usedComponents.delete(name);
+
+    // Render with a clean binding scope; the page being synced restores its own afterwards
+    const saved = { variables: this.variables, usedGlobals: this.usedGlobals, globalNames: this.globalNames, modelHooks: this.modelHooks, forms: this.forms };
+    const globals = (await getCommandDb().variable.findMany({ where: { projectId } })).filter(v => v.scope !== 'page');
+    this.variables = new Map(globals.flatMap(v => [[v.id, camelCase(v.name)], [v.name, camelCase(v.name)]] as const));
+    this.globalNames = new Set(globals.map(v => camelCase(v.name)));
+    this.usedGlobals = new Set();
+    this.modelHooks = new Map();
+    this.forms = new Map();
+
+    let jsxContent = '';
+    for (const childId of childrenIds) {
+      jsxContent += await this.generateBlockJsx(childId, 3);
+    }
+
+    const imports = Array.from(usedComponents).sort().map(c => `import ${c} from './${c}';`);
+    const declarations: string[] = [];
+    if (this.usedGlobals.size > 0) {
+      imports.push(`import { useVariables } from '../context/VariablesContext';`);
+      declarations.push(`  const { ${[...this.usedGlobals].sort().join(', ')} } = useVariables();`);
+      await this.syncVariablesContext(projectId);
+    }
+    await this.declareModelHooks(projectId, imports, declarations);
+    const reactHooks = await this.declareForms(projectId, imports, declarations);
+    ({ variables: this.variables, usedGlobals: this.usedGlobals, globalNames: this.globalNames, modelHooks: this.modelHooks, forms: this.forms } = saved);
+
+    const fields = declared.map(p => `  ${camelCase(p.name)}${p.required ? '' : '?'}: ${PROP_TS_TYPES[p.prop_type] || 'any'};`);
+    const params = declared.map(p => {
+      const prop = camelCase(p.name);
+      return p.default !== undefined && p.default !== null && !p.required ? `${prop} = ${JSON.stringify(p.default)}` : prop;
+    });
+    const body = declarations.length ? `${declarations.join('\n')}\n\n` : '';
+
+    const reactImport = reactHooks.size ? `import React, { ${[...reactHooks].join(', ')} } from 'react';` : `import React from 'react';`;
+    const fileContent = `${reactImport}
+${imports.join('\n')}${imports.length ? '\n' : ''}
+export interface ${name}Props {
+${fields.length ? `${fields.join('\n')}\n` : ''}}
+
+export default function ${name}(${params.length ? `{ ${params.join(', ')} }` : '_props'}: ${name}Props) {
+${body}  return (
+    <>
+${jsxContent}    </>
+  );
+}
+`;
+
+    await fs.ensureDir(this.componentsDir);
+    await this.writeSource(path.join(this.componentsDir, `${name}.tsx`), fileContent);
+    return name;
+  }
+
+  /**
+   * Turn a property binding into a JSX expression. Variable bindings reference the variable
+   * by name; model bindings (`Model.field`) read the field off the first record the model's
+   * data hook loads. Returns null for unbound properties, for variables and models that no
+   * longer exist and for `api` bindings, which have no data to read, so the literal is used;
+   * `generationWarnings` reports those.
+   */
+  private resolveBinding(binding: { type?: string; value?: unknown } | undefined): string | null {
+    if (!binding || binding.value === undefined || binding.value === null || binding.value === '') return null;
+    const value = String(binding.value);
+
+    switch (binding.type) {
+      case 'variable': {
+        const name = this.variables.get(value);
+        if (!name) return null;
+        if (this.globalNames.has(name)) this.usedGlobals.add(name);
+        return name;
+      }
+      case 'prop':
+        // Inside a component: read the component's own prop
+        return camelCase(value);
+      case 'model': {
+        const bound = modelBindingField(this.schema?.data_models || [], value);
+        if (!bound) return null;
+        this.modelHooks.set(`use${pascalCase(bound.model.name)}s`, bound.model);
+        return `${camelCase(bound.model.name)}Data[0]${bound.path.map(part => `?.${part}`).join('')}`;
+      }
+      default:
+        return null;
+    }
+  }
+
   /**
    * Generate JSX for a block and its children
    */
   private async generateBlockJsx(blockId: string, indent: number): Promise<string> {
-    const block = await prisma.block.findUnique({ where: { id: blockId } });
+    const block = await this.findBlock(blockId);
     if (!block) return '';
 
     const indentStr = '  '.repeat(indent);
+    // Model bindings are resolved against the project's models
+    if (Object.values(parseJsonValue<Record<string, any>>(block.bindings, {})).some(b => b?.type === 'model')) {
+      await this.projectSchema(block.projectId);
+    }
+
+    if (isInstance(block.blockType)) {
+      const component = await this.instanceComponent(block.properties, block.projectId);
+      if (component) return this.generateInstanceJsx(block, component, indentStr);
+    }
+
+    const model = await this.tableModel(block);
+    if (model) return this.generateTableJsx(block, model, indentStr);
+
+    const form = await this.formSubmit(block);
+    if (form) return this.generateFormJsx(block, form, indent);
+
     const compName = this.blockTypeToComponentName(block.blockType);
 
     // Parse properties
     const properties = JSON.parse(block.properties || '{}');
-    const classes = JSON.parse(block.classes || '[]').join(' ');
+    const styles = splitStyles(parseJsonValue<Record<string, unknown>>(block.styles, {}));
+    const classes = normalizeClasses(JSON.parse(block.classes || '[]'), styles.classes, positionClasses(properties));
+
+    const bindings = JSON.parse(block.bindings || '{}');
+
+    // Bound properties become expressions; everything else stays a literal
+    const prop = (key: string, literal: unknown, numeric = false) => {
+      const bound = this.resolveBinding(bindings[key]);
+      if (bound) return ` ${key}={${bound}}`;
+      if (!literal) return '';
+      return numeric && Number.isFinite(Number(literal)) ? ` ${key}={${Number(literal)}}` : jsxAttribute(key, literal);
+    };
 
     let props = '';
-    if (classes) props += ` className="${classes}"`;
+    if (classes) props += jsxAttribute('className', classes);
+    props += jsxStyle(styles.inline);
 
     // Map specific properties to props
     if (block.blockType === 'Button' || block.blockType === 'Heading' || block.blockType === 'Text' || block.blockType === 'Paragraph') {
-      if (properties.text) props += ` text="${properties.text}"`;
+      props += prop('text', properties.text);
     }
-    if (block.blockType === 'Heading' && properties.level) {
-      props += ` level={${properties.level}}`;
+    if (block.blockType === 'Heading') {
+      props += prop('level', properties.level, true);
     }
-    if (block.blockType === 'Button' && properties.variant) {
-      props += ` variant="${properties.variant}"`;
+    if (block.blockType === 'Button') {
+      props += prop('variant', properties.variant);
+    }
+    if (block.blockType === 'Input') {
+      // Forms send their inputs keyed by name; unnamed ones are named after their label
+      props += jsxAttribute('name', properties.name || camelCase(properties.label || block.name));
+      props += prop('label', properties.label);
+      props += prop('placeholder', properties.placeholder);
+      if (properties.input_type) props += jsxAttribute('type', properties.input_type);
     }
     if (block.blockType === 'Image') {
-      if (properties.src) props += ` src="${properties.src}"`;
-      if (properties.alt) props += ` alt="${properties.alt}"`;
+      props += prop('src', properties.src);
+      // An empty alt is deliberate: it marks the image as decorative
+      props += properties.alt === '' && !bindings.alt ? ' alt=""' : prop('alt', properties.alt);
     }
+    for (const [name, value] of ariaAttributes(properties)) props += jsxAttribute(name, value);
 
     const childrenIds = JSON.parse(block.children || '[]');
     const isContainer = ['Container', 'Section', 'Card', 'Flex', 'Grid', 'Page', 'Column', 'Columns'].includes(block.blockType) || childrenIds.length > 0;
@@ -279,20 +787,381 @@ export default function ${name}({ children, className = '', ...props }: ${name}P
     return jsx;
   }
 
+  private async projectSchema(projectId: string): Promise<ProjectSchema | null> {
+    if (!this.schema) this.schema = await loadProjectSchema(projectId);
+    return this.schema;
+  }
+
+  /** The data model a Table block's `data` binding points at, by id or name */
+  private async tableModel(block: Block): Promise<DataModelSchema | null> {
+    if (block.blockType !== 'Table') return null;
+    const binding = parseJsonValue<Record<string, any>>(block.bindings, {}).data;
+    if (binding?.type !== 'model' || !binding.value) return null;
+
+    const model = (await this.projectSchema(block.projectId))?.data_models.find(m => m.id === binding.value || m.name === binding.value);
+    return model ?? null;
+  }
+
+  /**
+   * Render a model-bound Table as a real `<table>`: a header cell per column and a row per
+   * record from the model's data hook. `properties.columns` picks and orders the fields shown;
+   * without it every field is a column.
+   */
+  private generateTableJsx(block: Block, model: DataModelSchema, indentStr: string): string {
+    const properties = parseJsonValue<Record<string, any>>(block.properties, {});
+    const fieldNames = model.fields.map(f => f.name);
+    const columns: string[] = Array.isArray(properties.columns) && properties.columns.length > 0
+      ? properties.columns.filter((c: unknown) => typeof c === 'string' && fieldNames.includes(c))
+      : fieldNames;
+
+    const hook = `use${pascalCase(model.name)}s`;
+    this.modelHooks.set(hook, model);
+    const rows = `${camelCase(model.name)}Data`;
+    // Rows are keyed by the primary key, or by position when the model has none
+    const primary = model.fields.find(f => f.primary_key);
+    const [params, key] = primary ? ['row', `row.${primary.name}`] : ['(row, index)', 'index'];
+
+    const styles = splitStyles(parseJsonValue<Record<string, unknown>>(block.styles, {}));
+    const classes = normalizeClasses('min-w-full divide-y divide-gray-200', parseJsonValue<string[]>(block.classes, []), styles.classes, positionClasses(properties));
+    const aria = ariaAttributes(properties).map(([name, value]) => jsxAttribute(name, value)).join('');
+    const i = indentStr;
+
+    return `${i}/* @akasha-block id="${block.id}" */
+${i}<table className="${classes}"${jsxStyle(styles.inline)}${aria}>
+${i}  <thead>
+${i}    <tr>
+${columns.map(c => `${i}      <th className="px-4 py-2 text-left text-sm font-semibold text-gray-700">${c}</th>\n`).join('')}${i}    </tr>
+${i}  </thead>
+${i}  <tbody>
+${i}    {${rows}.map(${params} => (
+${i}      <tr key={${key}}>
+${columns.map(c => `${i}        <td className="px-4 py-2 text-sm text-gray-600">{String(row.${c} ?? '')}</td>\n`).join('')}${i}      </tr>
+${i}    ))}
+${i}  </tbody>
+${i}</table>
+`;
+  }
+
+  /**
+   * Call each data hook a table used at the top of the component, and write the hook (with
+   * the Zod schema it validates against) so the import resolves without a full export
+   */
+  private async declareModelHooks(projectId: string, imports: string[], declarations: string[]) {
+    if (this.modelHooks.size === 0) return;
+    const schema = await this.projectSchema(projectId);
+    const baseUrl = schema ? apiBaseUrl(schema) : undefined;
+    const generator = new GeneratorService();
+
+    for (const [hook, model] of [...this.modelHooks].sort(([a], [b]) => a.localeCompare(b))) {
+      imports.push(`import { ${hook} } from '../hooks/${hook}';`);
+      declarations.push(`  const { data: ${camelCase(model.name)}Data } = ${hook}();`);
+      for (const file of [generator.genZodSchema(model, schema?.data_models), generator.genModelHook(model, baseUrl)]) {
+        await this.writeSource(path.join(this.rootPath, file.path), file.content);
+      }
+    }
+  }
+
+  /**
+   * Where a Form block's `submit` binding sends it: an endpoint (`api`, by id or name) through
+   * its client function, or a model (`model`), whose create route gets a POST. Values are
+   * converted by the types of the model's fields, or of the endpoint's request body model.
+   * Endpoints that take no body (GET, DELETE) can't be submitted to, so the form stays unwired.
+   */
+  private async formSubmit(block: Block): Promise<Omit<FormSubmit, 'name'> | null> {
+    if (block.blockType !== 'Form') return null;
+    const binding = parseJsonValue<Record<string, any>>(block.bindings, {}).submit;
+    if ((binding?.type !== 'api' && binding?.type !== 'model') || !binding.value) return null;
+
+    const schema = await this.projectSchema(block.projectId);
+    if (!schema) return null;
+    const findModel = (ref: unknown) => schema.data_models.find(m => m.id === ref || m.name === ref);
+    if (binding.type === 'model') {
+      const model = findModel(binding.value);
+      if (model) return { route: `/${kebabCase(model.name)}s`, kinds: fieldKinds(model) };
+    } else {
+      const endpoint = endpointFunctions(schema).find(e => e.api.id === binding.value || e.api.name === binding.value);
+      if (endpoint && !FORM_METHODS.has(endpoint.api.method.toUpperCase())) return null;
+      const body = endpoint?.api.request_body;
+      if (endpoint) return { endpoint, kinds: fieldKinds(body?.shape_type === 'model' ? findModel(body.model_ref) : undefined) };
+    }
+    return null;
+  }
+
+  /**
+   * Render a wired Form as a native `<form>` whose submit handler sends its fields, keyed by
+   * input name, through the api client. Fields are disabled while the request is in flight
+   * and a failed request shows its error under them.
+   */
+  private async generateFormJsx(block: Block, submit: Omit<FormSubmit, 'name'>, indent: number): Promise<string> {
+    const base = camelCase(block.name) || 'form';
+    let name = base;
+    for (let n = 2; this.forms.has(name); n++) name = `${base}${n}`;
+    this.forms.set(name, { name, ...submit });
+
+    const properties = parseJsonValue<Record<string, any>>(block.properties, {});
+    const styles = splitStyles(parseJsonValue<Record<string, unknown>>(block.styles, {}));
+    const classes = normalizeClasses(parseJsonValue<string[]>(block.classes, []), styles.classes, positionClasses(properties));
+    const aria = ariaAttributes(properties).map(([attr, value]) => jsxAttribute(attr, value)).join('');
+    const i = '  '.repeat(indent);
+
+    let fields = '';
+    for (const childId of parseJsonValue<string[]>(block.children, [])) {
+      fields += await this.generateBlockJsx(childId, indent + 2);
+    }
+
+    return `${i}/* @akasha-block id="${block.id}" */
+${i}<form${classes ? ` className="${classes}"` : ''}${jsxStyle(styles.inline)} onSubmit={submit${pascalCase(name)}}${aria}>
+${i}  <fieldset className="contents" disabled={${name}Submitting}>
+${fields}${i}  </fieldset>
+${i}  {${name}Error && <p className="mt-2 text-sm text-red-600" role="alert">{${name}Error}</p>}
+${i}</form>
+`;
+  }
+
+  /**
+   * Declare the submitting/error state and submit handler of each wired form, and write the
+   * api client and `formValues` helper they call. Path params come from the form's own
+   * fields or else the page route. Returns the React hooks the declarations use.
+   */
+  private async declareForms(projectId: string, imports: string[], declarations: string[]): Promise<Set<string>> {
+    const hooks = new Set<string>();
+    if (this.forms.size === 0) return hooks;
+    hooks.add('useState');
+
+    const generator = new GeneratorService();
+    const schema = await this.projectSchema(projectId);
+    if (schema) {
+      for (const file of generator.genApiClient(schema)) {
+        await this.writeSource(path.join(this.rootPath, file.path), file.content);
+      }
+    }
+    const helpers = generator.genFormValues();
+    await this.writeSource(path.join(this.rootPath, helpers.path), helpers.content);
+
+    const forms = [...this.forms.values()];
+    const endpoints = [...new Set(forms.flatMap(f => f.endpoint ? [f.endpoint.name] : []))].sort();
+    const usesParams = forms.some(f => f.endpoint?.pathParams.length);
+    if (forms.some(f => f.route)) imports.push(`import { api } from '../lib/api';`);
+    if (endpoints.length) imports.push(`import { ${endpoints.join(', ')} } from '../lib/endpoints';`);
+    imports.push(`import { formValues${usesParams ? ', pathParam' : ''} } from '../lib/formValues';`);
+    if (usesParams) {
+      imports.push(`import { useParams } from 'react-router-dom';`);
+      declarations.push(`  const params = useParams();`);
+    }
+
+    for (const form of forms) {
+      const pascal = pascalCase(form.name);
+      const call = form.endpoint
+        ? `${form.endpoint.name}(${[...form.endpoint.pathParams.map(p => `pathParam('${p}', data, params)`), 'data'].join(', ')})`
+        : `api.post('${form.route}', data)`;
+      declarations.push(`  const [${form.name}Submitting, set${pascal}Submitting] = useState(false);
+  const [${form.name}Error, set${pascal}Error] = useState<string | null>(null);
+
+  const submit${pascal} = async (event: React.FormEvent<HTMLFormElement>) => {
+    event.preventDefault();
+    const form = event.currentTarget;
+    set${pascal}Submitting(true);
+    set${pascal}Error(null);
+    try {
+      const data = formValues(form, ${JSON.stringify(form.kinds)});
+      await ${call};
+      form.reset();
+    } catch (err) {
+      set${pascal}Error((err as Error).message);
+    } finally {
+      set${pascal}Submitting(false);
+    }
+  };`);
+    }
+    return hooks;
+  }
+
+  /**
+   * Render an instance as `<Component ... />`, passing each declared prop from the instance's
+   * binding for it, or else its literal in `properties.prop_values`
+   */
+  private generateInstanceJsx(block: { id: string; properties: string; bindings: string }, component: { properties: string; name: string }, indentStr: string): string {
+    const declared: ComponentProp[] = JSON.parse(component.properties || '{}').props || [];
+    const values = JSON.parse(block.properties || '{}').prop_values || {};
+    const bindings = JSON.parse(block.bindings || '{}');
+
+    let props = '';
+    for (const p of declared) {
+      const prop = camelCase(p.name);
+      const bound = this.resolveBinding(bindings[p.name]);
+      if (bound) props += ` ${prop}={${bound}}`;
+      else if (values[p.name] !== undefined) props += jsxAttribute(prop, values[p.name]);
+    }
+
+    return `${indentStr}/* @akasha-block id="${block.id}" */\n${indentStr}<${pascalCase(component.name)}${props} />\n`;
+  }
+
+  private pageFile(page: { name: string }) {
+    return path.join(this.pagesDir, `${pascalCase(page.name)}.tsx`);
+  }
+
+  /**
+   * Hash of everything in the schema a page file is generated from: the page itself
+   * (minus sync bookkeeping) and its blocks
+   */
+  private async pageSchemaHash(page: { idRoot: string; name: string; path: string; meta: string }): Promise<string> {
+    const { sync_state: _syncState, ...meta } = JSON.parse(page.meta || '{}');
+    const blocks = await getCommandDb().block.findMany({ where: { pageId: page.idRoot, archived: false }, orderBy: { id: 'asc' } });
+    return sha256(JSON.stringify({
+      name: page.name,
+      path: page.path,
+      meta,
+      blocks: blocks.map(b => [b.id, b.blockType, b.name, b.parentId, b.properties, b.styles, b.classes, b.events, b.bindings, b.children, b.order]),
+    }));
+  }
+
+  /**
+   * A conflict when both the page file and the page schema changed since the last sync into
+   * this root. Pages never synced here, or whose file is gone, have nothing to conflict with.
+   */
+  public async detectPageConflict(page: { id: string; idRoot: string; name: string; path: string; meta: string }): Promise<SyncConflict | null> {
+    let state: SyncState | undefined;
+    try {
+      state = JSON.parse(page.meta || '{}').sync_state;
+    } catch (error) {
+      throw new ParseFailedError(page.id, error);
+    }
+    const filePath = this.pageFile(page);
+    if (!state || state.root !== this.rootPath || !await fs.pathExists(filePath)) return null;
+
+    const diskChanged = sha256(await fs.readFile(filePath, 'utf-8')) !== state.file_hash;
+    const schemaChanged = (await this.pageSchemaHash(page)) !== state.schema_hash;
+    if (!diskChanged || !schemaChanged) return null;
+
+    return {
+      page_id: page.id,
+      page_name: page.name,
+      file: path.relative(this.rootPath, filePath),
+      message: `"${page.name}" was edited on disk and in the editor since ${state.synced_at}`,
+    };
+  }
+
+  /**
+   * Every page of the project whose disk file and schema have diverged
+   */
+  public async detectConflicts(projectId: string): Promise<SyncConflict[]> {
+    const pages = await getCommandDb().page.findMany({ where: { projectId, archived: false } });
+    const conflicts: SyncConflict[] = [];
+    for (const page of pages) {
+      const conflict = await this.detectPageConflict(page);
+      if (conflict) conflicts.push(conflict);
+    }
+    return conflicts;
+  }
+
+  /**
+   * Sync a specific page to disk. Returns the conflict instead of writing when the file
+   * was also edited on disk since the last sync, unless `force` is set.
+   */
+  public async syncPageToDisk(pageId: string, projectId: string, options: SyncPageOptions = {}): Promise<SyncConflict | null> {
+    const page = await getCommandDb().page.findUnique({ where: { id: pageId } });
+    if (!page) throw new PageNotFoundError(pageId);
+
+    if (!options.force) {
+      const conflict = await this.detectPageConflict(page);
+      if (conflict) return conflict;
+    }
+
+    this.blockIndex = new Map((await getCommandDb().block.findMany({ where: { projectId } })).map(b => [b.id, b]));
+    try {
+      await this.writePage(page, projectId, options);
+    } catch (error) {
+      if (error instanceof SyntaxError) throw new ParseFailedError(page.id, error);
+      throw error;
+    } finally {
+      this.blockIndex = null;
+    }
+    return null;
+  }
+
   /**
-   * Sync a specific page to disk
+   * Pull text and other literal properties edited in a page file back into its blocks. The
+   * merge is additive: only properties read from the file are overwritten, so events,
+   * bindings and anything else the file doesn't show are kept. Returns the updated block ids.
    */
-  public async syncPageToDisk(pageId: string, projectId: string) {
-    const page = await prisma.page.findUnique({ where: { id: pageId } });
-    if (!page) throw new Error('Page not found');
+  public async syncPageFromDisk(pageId: string): Promise<string[]> {
+    const page = await getCommandDb().page.findUnique({ where: { id: pageId } });
+    if (!page) throw new PageNotFoundError(pageId);
+
+    const filePath = this.pageFile(page);
+    if (!await fs.pathExists(filePath)) return [];
+    const source = await fs.readFile(filePath, 'utf-8');
+
+    const updated: string[] = [];
+    for (const marker of source.matchAll(/\/\* @akasha-block id="([^"]+)" \*\/\s*<[A-Za-z][\w.]*/g)) {
+      const block = await getCommandDb().block.findUnique({ where: { id: marker[1] } });
+      const mapping = block && block.pageId === page.idRoot ? DISK_PROPERTIES[block.blockType] : undefined;
+      if (!block || !mapping) continue;
+
+      const properties = parseJsonValue<Record<string, any>>(block.properties, {});
+      if (properties.component_id) continue;
+      const attributes = readJsxAttributes(source, marker.index! + marker[0].length);
+      const changes: Record<string, unknown> = {};
+      for (const [attribute, property] of Object.entries(mapping)) {
+        const value = attributes[attribute];
+        if (value !== undefined && String(value) !== String(properties[property] ?? '')) changes[property] = value;
+      }
+      if (Object.keys(changes).length === 0) continue;
+
+      await getCommandDb().block.update({ where: { id: block.id }, data: { properties: JSON.stringify({ ...properties, ...changes }) } });
+      updated.push(block.id);
+    }
+
+    // The schema now matches the file, so the next sync to disk isn't reported as a conflict
+    const syncState: SyncState = {
+      root: this.rootPath,
+      schema_hash: await this.pageSchemaHash(page),
+      file_hash: sha256(source),
+      synced_at: new Date().toISOString(),
+    };
+    await getCommandDb().page.update({
+      where: { id: page.id },
+      data: { meta: JSON.stringify({ ...parseJsonValue<Record<string, any>>(page.meta, {}), sync_state: syncState }) },
+    });
+    return updated;
+  }
 
+  /** `syncPageFromDisk` for every page of the project */
+  public async syncProjectFromDisk(projectId: string): Promise<string[]> {
+    const pages = await getCommandDb().page.findMany({ where: { projectId, archived: false } });
+    const updated: string[] = [];
+    for (const page of pages) updated.push(...await this.syncPageFromDisk(page.id));
+    return updated;
+  }
+
+  /**
+   * The blocks rendered at the top level of a page: its `root_block_ids` (or `root_block_id`)
+   * in order, or every parentless block of the page when none of those exist
+   */
+  private async pageRootBlocks(page: Page): Promise<Block[]> {
+    const declared: Block[] = [];
+    for (const id of pageRootIds(parseJsonValue<Record<string, any>>(page.meta, {}))) {
+      const block = await this.findBlock(id);
+      if (block && !block.archived) declared.push(block);
+    }
+    if (declared.length) return declared;
+    return getCommandDb().block.findMany({ where: { pageId: page.idRoot, parentId: null, archived: false }, orderBy: { order: 'asc' } });
+  }
+
+  /** Write the page file (and the component files it uses) with the block index loaded */
+  private async writePage(page: Page, projectId: string, options: SyncPageOptions) {
     const pageDir = this.pagesDir;
     await fs.ensureDir(pageDir);
 
+    this.syncedComponents = new Set();
+    this.schema = null;
+    await this.loadSettings(projectId);
+    this.modelHooks = new Map();
+    this.forms = new Map();
     const usedComponents = new Set<string>();
-    const rootBlock = await prisma.block.findFirst({ where: { pageId: page.idRoot, parentId: null } });
-    if (rootBlock) {
-      await this.collectUsedComponents(rootBlock.id, projectId, usedComponents);
+    const rootBlocks = await this.pageRootBlocks(page);
+    for (const root of rootBlocks) {
+      await this.collectUsedComponents(root.id, projectId, usedComponents);
     }
 
     const componentImports = Array.from(usedComponents).sort().map(name =>
@@ -301,24 +1170,82 @@ export default function ${name}({ children, className = '', ...props }: ${name}P
 
     const pageName = pascalCase(page.name);
 
+    // Only globals and this page's own variables are in scope for its bindings
+    const variables = (await getCommandDb().variable.findMany({ where: { projectId } }))
+      .filter(v => v.scope !== 'page' || v.pageId === page.id);
+    const pageVariables = variables.filter(v => v.scope === 'page');
+    // Bindings store the variable id; older ones may store its name
+    this.variables = new Map(variables.flatMap(v => [[v.id, camelCase(v.name)], [v.name, camelCase(v.name)]] as const));
+    this.globalNames = new Set(variables.filter(v => v.scope !== 'page').map(v => camelCase(v.name)));
+    this.usedGlobals = new Set();
+
     let jsxContent = '';
-    if (rootBlock) {
-      jsxContent = await this.generateBlockJsx(rootBlock.id, 3);
+    for (const root of rootBlocks) {
+      jsxContent += await this.generateBlockJsx(root.id, 3);
     }
 
-    const fileContent = `import React from 'react';
-${componentImports}
+    const declarations: string[] = [];
+    const imports: string[] = [];
+
+    if (this.usedGlobals.size > 0) {
+      imports.push(`import { useVariables } from '../context/VariablesContext';`);
+      declarations.push(`  const { ${[...this.usedGlobals].sort().join(', ')} } = useVariables();`);
+      await this.syncVariablesContext(projectId);
+    }
+
+    const reactHooks = new Set<string>();
+    for (const v of pageVariables) {
+      const name = camelCase(v.name);
+      const initial = v.value ?? 'null';
+      if (v.persist) {
+        imports.push(`import { usePersistentState } from '../hooks/usePersistentState';`);
+        declarations.push(`  const [${name}, set${pascalCase(v.name)}] = usePersistentState<any>('${pascalCase(page.name)}.${name}', ${initial});`);
+        await this.writeSource(path.join(this.rootPath, 'src', 'hooks', 'usePersistentState.ts'), this.genPersistentStateHook());
+      } else {
+        reactHooks.add('useState');
+        declarations.push(`  const [${name}, set${pascalCase(v.name)}] = useState<any>(${initial});`);
+      }
+    }
+
+    await this.declareModelHooks(projectId, imports, declarations);
+    for (const hook of await this.declareForms(projectId, imports, declarations)) reactHooks.add(hook);
+
+    const head = pageHeadMeta(parseJsonValue(page.meta, {}), this.settings);
+    if (head) {
+      imports.push(`import { usePageMeta } from '../hooks/usePageMeta';`);
+      declarations.push(`  usePageMeta({ ${Object.entries(head).map(([key, value]) => `${key}: ${JSON.stringify(value)}`).join(', ')} });`);
+      await this.writeSource(path.join(this.rootPath, 'src', 'hooks', 'usePageMeta.ts'), this.genPageMetaHook());
+    }
+
+
+    const reactImport = reactHooks.size ? `import React, { ${[...reactHooks].join(', ')} } from 'react';` : `import React from 'react';`;
+    const body = declarations.length ? `${declarations.join('\n')}\n\n` : '';
+
+    const fileContent = `${reactImport}
+${[componentImports, ...new Set(imports)].filter(Boolean).join('\n')}
 
 export default function ${pageName}() {
-  return (
+${body}  return (
     <div className="min-h-screen bg-white">
 ${jsxContent}    </div>
   );
 }
 `;
 
-    const filePath = path.join(pageDir, `${pageName}.tsx`);
-    await fs.writeFile(filePath, fileContent);
+    const written = await this.writeSource(this.pageFile(page), fileContent);
+
+    if (options.recordState !== false) {
+      const syncState: SyncState = {
+        root: this.rootPath,
+        schema_hash: await this.pageSchemaHash(page),
+        file_hash: sha256(written),
+        synced_at: new Date().toISOString(),
+      };
+      await getCommandDb().page.update({
+        where: { id: page.id },
+        data: { meta: JSON.stringify({ ...JSON.parse(page.meta || '{}'), sync_state: syncState }) },
+      });
+    }
 
     // Also update App.tsx routes? 
     // Rust implementation did this in `sync_app_routes_to_disk`.
@@ -326,6 +1253,122 @@ ${jsxContent}    </div>
     await this.syncAppRoutes(projectId);
   }
 
+  /**
+   * Sets the document title, description and Open Graph tags while a page is shown,
+   * creating the meta tags the first time
+   */
+  public genPageMetaHook(): string {
+    return `import { useEffect } from 'react';
+
+export interface PageMeta {
+  title?: string;
+  description?: string;
+  image?: string;
+}
+
+function setMeta(attribute: 'name' | 'property', key: string, content?: string) {
+  if (!content) return;
+  let tag = document.head.querySelector<HTMLMetaElement>(\`meta[\${attribute}="\${key}"]\`);
+  if (!tag) {
+    tag = document.createElement('meta');
+    tag.setAttribute(attribute, key);
+    document.head.appendChild(tag);
+  }
+  tag.content = content;
+}
+
+export function usePageMeta({ title, description, image }: PageMeta) {
+  useEffect(() => {
+    if (title) document.title = title;
+    setMeta('name', 'description', description);
+    setMeta('property', 'og:title', title);
+    setMeta('property', 'og:description', description);
+    setMeta('property', 'og:image', image);
+  }, [title, description, image]);
+}
+`;
+  }
+
+  public genPersistentStateHook(): string {
+    return `import { useEffect, useState } from 'react';
+
+/**
+ * useState that is read from and written back to localStorage under \`key\`
+ */
+export function usePersistentState<T>(key: string, initial: T) {
+  const [value, setValue] = useState<T>(() => {
+    const saved = localStorage.getItem(key);
+    return saved !== null ? (JSON.parse(saved) as T) : initial;
+  });
+
+  useEffect(() => {
+    localStorage.setItem(key, JSON.stringify(value));
+  }, [key, value]);
+
+  return [value, setValue] as const;
+}
+`;
+  }
+
+  /**
+   * Context holding every global variable, with a setter per variable.
+   * Persisted variables are mirrored to localStorage.
+   */
+  public genVariablesContext(globals: Array<{ name: string; value: string | null; persist: boolean }>): string {
+    const anyPersisted = globals.some(v => v.persist);
+    const state = globals.map(v => {
+      const name = camelCase(v.name);
+      const setter = `set${pascalCase(v.name)}`;
+      return v.persist
+        ? `  const [${name}, ${setter}] = usePersistentState<any>('global.${name}', ${v.value ?? 'null'});`
+        : `  const [${name}, ${setter}] = useState<any>(${v.value ?? 'null'});`;
+    });
+    const fields = globals.map(v => `  ${camelCase(v.name)}: any;\n  set${pascalCase(v.name)}: (value: any) => void;`);
+    const values = globals.flatMap(v => [camelCase(v.name), `set${pascalCase(v.name)}`]);
+
+    return `import React, { createContext, useContext, useState } from 'react';
+${anyPersisted ? `import { usePersistentState } from '../hooks/usePersistentState';\n` : ''}
+export interface VariablesContextValue {
+${fields.join('\n')}
+}
+
+const VariablesContext = createContext<VariablesContextValue | null>(null);
+
+export function VariablesProvider({ children }: { children: React.ReactNode }) {
+${state.join('\n')}
+
+  return (
+    <VariablesContext.Provider value={{ ${values.join(', ')} }}>
+      {children}
+    </VariablesContext.Provider>
+  );
+}
+
+export function useVariables() {
+  const context = useContext(VariablesContext);
+  if (!context) throw new Error('useVariables must be used inside VariablesProvider');
+  return context;
+}
+`;
+  }
+
+  /**
+   * Write src/context/VariablesContext.tsx for the project's global variables.
+   * Returns false when there are none.
+   */
+  public async syncVariablesContext(projectId: string): Promise<boolean> {
+    const globals = (await getCommandDb().variable.findMany({ where: { projectId }, orderBy: { name: 'asc' } }))
+      .filter(v => v.scope !== 'page');
+    if (globals.length === 0) return false;
+
+    await this.loadSettings(projectId);
+    await this.writeSource(path.join(this.rootPath, 'src', 'context', 'VariablesContext.tsx'), this.genVariablesContext(globals));
+    if (globals.some(v => v.persist)) {
+      await this.writeSource(path.join(this.rootPath, 'src', 'hooks', 'usePersistentState.ts'), this.genPersistentStateHook());
+    }
+    return true;
+  }
+
   public async syncAppRoutes(projectId: string) {
     // ... (Similar logic to Rust for App.tsx generation)
     // For now, let's just make sure we handle the page syncing.
@@ -0,0 +1,47 @@
+import { test } from 'node:test';
+import assert from 'node:assert/strict';
+import { normalizeClasses, splitStyles } from './tailwindClasses.js';
+
+test('normalizeClasses keeps the last of two utilities setting the same property', () => {
+  assert.equal(normalizeClasses('p-2 p-4 p-2'), 'p-4');
+  assert.equal(normalizeClasses(['text-sm', 'text-gray-500'], 'text-lg text-red-500'), 'text-lg text-red-500');
+  assert.equal(normalizeClasses('flex block'), 'block');
+  assert.equal(normalizeClasses('bg-white bg-black'), 'bg-black');
+  assert.equal(normalizeClasses('border border-2'), 'border-2');
+  assert.equal(normalizeClasses('max-w-lg max-w-screen-xl'), 'max-w-screen-xl');
+});
+
+test('normalizeClasses compares utilities under the same variants only', () => {
+  assert.equal(normalizeClasses('p-2 md:p-4 md:p-6 hover:p-8'), 'p-2 md:p-6 hover:p-8');
+  assert.equal(normalizeClasses('mt-2 -mt-4'), '-mt-4');
+  assert.equal(normalizeClasses('p-2 !p-4'), '!p-4');
+});
+
+test('normalizeClasses keeps utilities that only share a prefix', () => {
+  for (const classes of [
+    'justify-between justify-items-center',
+    'space-x-4 space-x-reverse',
+    'bg-black bg-opacity-50',
+    'border-collapse border-gray-300',
+    'text-[14px] text-red-500',
+    'text-ellipsis text-gray-500',
+    'text-sm/6 text-red-500',
+    'bg-cover bg-center bg-red-500',
+    'content-center content-none',
+    'rounded-t-lg rounded-lg',
+  ]) {
+    assert.equal(normalizeClasses(classes), classes);
+  }
+});
+
+test('normalizeClasses drops duplicates and empty entries', () => {
+  assert.equal(normalizeClasses('  flex  items-center ', undefined, null, ['flex', '']), 'flex items-center');
+});
+
+test('splitStyles turns exact values into utilities and keeps the rest inline', () => {
+  assert.deepEqual(splitStyles({ display: 'flex', fontWeight: 700, backgroundColor: '#fff', '--gap': '4px', color: '' }), {
+    classes: ['flex', 'font-bold'],
+    inline: [['backgroundColor', '#fff'], ['--gap', '4px']],
+  });
+  assert.deepEqual(splitStyles(null), { classes: [], inline: [] });
+});
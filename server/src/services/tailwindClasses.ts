@@ -0,0 +1,174 @@
+/**
+ * Tailwind class lists assembled from a block's default classes, its own `classes` and its
+ * position can repeat a utility or set the same property twice (`p-2 p-4`). The generators
+ * pass them through `normalizeClasses`, which keeps one of each and lets the later of two
+ * conflicting utilities win, the way the block's own classes are meant to override defaults.
+ */
+
+const SPACING = /^(\d+(\.5)?|px)$/;
+const SPACING_OR_AUTO = /^(\d+(\.5)?|px|auto)$/;
+const LENGTH = /^(\d+(\.5)?|px|auto|full|screen|svh|lvh|dvh|min|max|fit|\d+\/\d+)$/;
+const MAX_WIDTH = /^(none|xs|sm|md|lg|xl|[2-7]xl|full|min|max|fit|prose|screen-(sm|md|lg|xl|2xl)|\d+(\.5)?|px)$/;
+const NUMBER = /^\d+$/;
+
+/**
+ * Utilities whose value sets one property, with the values recognised for each; the prefix
+ * is the conflict group. Anything else under a prefix (`space-x-reverse`, `justify-items-*`,
+ * arbitrary `[…]` values) sets a different property or can't be told apart, so it is kept.
+ */
+const PREFIX_GROUPS: Record<string, RegExp> = {
+  ...Object.fromEntries(['p', 'px', 'py', 'pt', 'pr', 'pb', 'pl', 'ps', 'pe', 'gap', 'gap-x', 'gap-y', 'space-x', 'space-y'].map(p => [p, SPACING])),
+  ...Object.fromEntries(['m', 'mx', 'my', 'mt', 'mr', 'mb', 'ml', 'ms', 'me', 'top', 'right', 'bottom', 'left', 'inset', 'inset-x', 'inset-y'].map(p => [p, SPACING_OR_AUTO])),
+  ...Object.fromEntries(['w', 'h', 'size', 'min-w', 'min-h', 'max-h', 'basis'].map(p => [p, LENGTH])),
+  'max-w': MAX_WIDTH,
+  z: /^(\d+|auto)$/,
+  opacity: NUMBER,
+  order: /^(\d+|first|last|none)$/,
+  grow: /^0$/,
+  shrink: /^0$/,
+  'grid-cols': /^(\d+|none|subgrid)$/,
+  'grid-rows': /^(\d+|none|subgrid)$/,
+  'col-span': /^(\d+|full)$/,
+  'row-span': /^(\d+|full)$/,
+  leading: /^(\d+|none|tight|snug|normal|relaxed|loose)$/,
+  tracking: /^(tighter|tight|normal|wide|wider|widest)$/,
+  items: /^(start|end|center|baseline|stretch)$/,
+  justify: /^(normal|start|end|center|between|around|evenly|stretch)$/,
+  self: /^(auto|start|end|center|baseline|stretch)$/,
+  // `content-none` is the `content` property, not `align-content`
+  content: /^(normal|start|end|center|between|around|evenly|baseline|stretch)$/,
+  duration: NUMBER,
+  delay: NUMBER,
+  ease: /^(linear|in|out|in-out)$/,
+};
+
+/** Keywords that make up a group on their own, e.g. one display value replaces another */
+const KEYWORD_GROUPS: Record<string, string> = {
+  ...Object.fromEntries(['block', 'inline-block', 'inline', 'flex', 'inline-flex', 'grid', 'inline-grid', 'table', 'contents', 'hidden'].map(c => [c, 'display'])),
+  ...Object.fromEntries(['static', 'relative', 'absolute', 'fixed', 'sticky'].map(c => [c, 'position'])),
+  ...Object.fromEntries(['flex-row', 'flex-row-reverse', 'flex-col', 'flex-col-reverse'].map(c => [c, 'flex-direction'])),
+  ...Object.fromEntries(['flex-wrap', 'flex-wrap-reverse', 'flex-nowrap'].map(c => [c, 'flex-wrap'])),
+  ...Object.fromEntries(['italic', 'not-italic'].map(c => [c, 'font-style'])),
+  ...Object.fromEntries(['uppercase', 'lowercase', 'capitalize', 'normal-case'].map(c => [c, 'text-transform'])),
+};
+
+const TEXT_SIZES = new Set(['xs', 'sm', 'base', 'lg', 'xl', '2xl', '3xl', '4xl', '5xl', '6xl', '7xl', '8xl', '9xl']);
+const TEXT_ALIGNS = new Set(['left', 'center', 'right', 'justify', 'start', 'end']);
+const FONT_WEIGHTS = new Set(['thin', 'extralight', 'light', 'normal', 'medium', 'semibold', 'bold', 'extrabold', 'black']);
+const FONT_FAMILIES = new Set(['sans', 'serif', 'mono']);
+const SIZES = new Set(['none', 'sm', 'md', 'lg', 'xl', '2xl', '3xl', 'full']);
+const SHADOWS = new Set(['none', 'sm', 'md', 'lg', 'xl', '2xl', 'inner']);
+const BORDER_WIDTHS = new Set(['0', '2', '4', '8']);
+const BORDER_STYLES = new Set(['solid', 'dashed', 'dotted', 'double', 'hidden', 'none']);
+/** Palette colors (`red-500`, with an optional `/50` alpha) and the color keywords */
+const COLOR = /^((slate|gray|zinc|neutral|stone|red|orange|amber|yellow|lime|green|emerald|teal|cyan|sky|blue|indigo|violet|purple|fuchsia|pink|rose)-(50|[1-9]00|950)(\/\d+)?|white|black|transparent|current|inherit)$/;
+
+/**
+ * The property a utility (without variants) sets, or null when it isn't known to conflict.
+ * Only fully recognised values are grouped: `text-ellipsis` isn't a color and `text-[14px]`
+ * could be a size or a color, so guessing would drop a class the block needs.
+ */
+function conflictGroup(utility: string): string | null {
+  if (KEYWORD_GROUPS[utility]) return KEYWORD_GROUPS[utility];
+
+  const [, prefix, value] = utility.match(/^(text|font|bg|border|rounded|shadow)(?:-(.+))?$/) || [];
+  if (prefix) {
+    switch (prefix) {
+      case 'text':
+        if (!value) return null;
+        return TEXT_SIZES.has(value) ? 'text-size' : TEXT_ALIGNS.has(value) ? 'text-align' : COLOR.test(value) ? 'text-color' : null;
+      case 'font':
+        if (!value) return null;
+        return FONT_WEIGHTS.has(value) ? 'font-weight' : FONT_FAMILIES.has(value) ? 'font-family' : null;
+      case 'bg':
+        // Only colors; bg-cover, bg-center and the like set other properties
+        return value && COLOR.test(value) ? 'bg-color' : null;
+      case 'border':
+        if (!value || BORDER_WIDTHS.has(value)) return 'border-width';
+        if (BORDER_STYLES.has(value)) return 'border-style';
+        return COLOR.test(value) ? 'border-color' : null;
+      case 'rounded':
+        return !value || SIZES.has(value) ? 'rounded' : null;
+      case 'shadow':
+        return !value || SHADOWS.has(value) ? 'shadow' : null;
+    }
+  }
+
+  for (let end = utility.lastIndexOf('-'); end > 0; end = utility.lastIndexOf('-', end - 1)) {
+    const head = utility.slice(0, end);
+    const values = Object.hasOwn(PREFIX_GROUPS, head) ? PREFIX_GROUPS[head] : undefined;
+    if (values) return values.test(utility.slice(end + 1)) ? head : null;
+  }
+  return null;
+}
+
+/** Variants (`md:hover:`) and the conflict group of a class, or null for classes left as they are */
+function conflictKey(className: string): string | null {
+  const variantEnd = className.lastIndexOf(':');
+  const variants = className.slice(0, variantEnd + 1);
+  // `!p-4` and `-mt-2` set the same property as `p-4` and `mt-2`
+  const utility = className.slice(variantEnd + 1).replace(/^!/, '').replace(/^-/, '');
+  const group = conflictGroup(utility);
+  return group ? `${variants}${group}` : null;
+}
+
+/**
+ * One class string from class lists in order of precedence: duplicates are dropped and, of
+ * utilities setting the same property under the same variants, only the last is kept.
+ * `['p-2', 'p-4', 'p-2']` becomes `p-4`.
+ */
+export function normalizeClasses(...lists: Array<string | string[] | undefined | null>): string {
+  const unique = [...new Set(lists.flatMap(list => (Array.isArray(list) ? list : [list]))
+    .flatMap(entry => (typeof entry === 'string' ? entry.split(/\s+/) : []))
+    .filter(Boolean))];
+
+  const winners = new Map<string, string>();
+  for (const className of unique) {
+    const key = conflictKey(className);
+    if (key) winners.set(key, className);
+  }
+  return unique.filter(className => {
+    const key = conflictKey(className);
+    return !key || winners.get(key) === className;
+  }).join(' ');
+}
+
+/** Style values a single Tailwind utility sets exactly, by CSS property */
+const STYLE_UTILITIES: Record<string, Record<string, string>> = {
+  display: { block: 'block', 'inline-block': 'inline-block', inline: 'inline', flex: 'flex', 'inline-flex': 'inline-flex', grid: 'grid', 'inline-grid': 'inline-grid', contents: 'contents', none: 'hidden' },
+  position: { static: 'static', relative: 'relative', absolute: 'absolute', fixed: 'fixed', sticky: 'sticky' },
+  'flex-direction': { row: 'flex-row', 'row-reverse': 'flex-row-reverse', column: 'flex-col', 'column-reverse': 'flex-col-reverse' },
+  'flex-wrap': { wrap: 'flex-wrap', 'wrap-reverse': 'flex-wrap-reverse', nowrap: 'flex-nowrap' },
+  'text-align': { left: 'text-left', center: 'text-center', right: 'text-right', justify: 'text-justify', start: 'text-start', end: 'text-end' },
+  'font-weight': {
+    100: 'font-thin', 200: 'font-extralight', 300: 'font-light', 400: 'font-normal', normal: 'font-normal',
+    500: 'font-medium', 600: 'font-semibold', 700: 'font-bold', bold: 'font-bold', 800: 'font-extrabold', 900: 'font-black',
+  },
+  'font-style': { italic: 'italic', normal: 'not-italic' },
+  'text-transform': { uppercase: 'uppercase', lowercase: 'lowercase', capitalize: 'capitalize', none: 'normal-case' },
+};
+
+/**
+ * A block's `styles` split into Tailwind utilities, for values one utility sets exactly, and
+ * the rest as inline style entries keyed the way React expects (`background-color` becomes
+ * `backgroundColor`). Keys may be stored kebab- or camelCase; empty values are dropped.
+ */
+export function splitStyles(styles: Record<string, unknown> | null | undefined): { classes: string[]; inline: Array<[string, string | number]> } {
+  const classes: string[] = [];
+  const inline: Array<[string, string | number]> = [];
+  for (const [key, raw] of Object.entries(styles ?? {})) {
+    if (raw === '' || raw === null || raw === undefined || typeof raw === 'boolean' || typeof raw === 'object') continue;
+    const value = typeof raw === 'number' ? raw : String(raw).trim();
+
+    // Custom properties keep their name; everything else is matched in kebab-case
+    const property = key.startsWith('--') ? key : key.trim().replace(/[A-Z]/g, c => `-${c.toLowerCase()}`);
+    const utility = STYLE_UTILITIES[property]?.[String(value)];
+    if (utility) {
+      classes.push(utility);
+      continue;
+    }
+    const name = property.startsWith('--') ? property : property.replace(/^-ms-/, 'ms-').replace(/-([a-z])/g, (_, c: string) => c.toUpperCase());
+    inline.push([name, value]);
+  }
+  return { classes, inline };
+}
@@ -1,4 +1,4 @@
-import prisma from '../lib/prisma.js';
+import { getProjectStore } from './projectStore.js';
 import { getLLMProvider, type LLMCompletionOptions } from '../lib/llmProvider.js';
 import {
     clamp,
@@ -759,11 +759,15 @@ async function mergeLLMText(
     }
 }
 
+/** The project with its pages and blocks, read through the project store */
+async function loadProjectWithContent(projectId: string) {
+    const store = getProjectStore();
+    const project = await store.getProjectById(projectId);
+    return project ? { ...project, ...await store.getProjectContent(project.id) } : null;
+}
+
 export async function generateUiBuilderResult(request: UiBuilderGenerateRequest): Promise<UiBuilderGenerateResult> {
-    const project = await prisma.project.findUnique({
-        where: { id: request.projectId },
-        include: { pages: true, blocks: true },
-    });
+    const project = await loadProjectWithContent(request.projectId);
 
     if (!project) {
         throw new Error('Project not found');
@@ -850,10 +854,7 @@ export async function generateUiBuilderResult(request: UiBuilderGenerateRequest)
 export async function* generateUiBuilderStream(
     request: UiBuilderGenerateRequest,
 ): AsyncGenerator<string, UiBuilderGenerateResult, undefined> {
-    const project = await prisma.project.findUnique({
-        where: { id: request.projectId },
-        include: { pages: true, blocks: true },
-    });
+    const project = await loadProjectWithContent(request.projectId);
 
     if (!project) throw new Error('Project not found');
 
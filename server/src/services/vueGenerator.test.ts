@@ -0,0 +1,66 @@
+import { test } from 'node:test';
+import assert from 'node:assert/strict';
+import { VueGeneratorService } from './vueGenerator.js';
+import type { BlockSchema, DataModelSchema, ProjectSchema } from './projectSchema.js';
+
+const block = (id: string, overrides: Partial<BlockSchema> = {}): BlockSchema => ({
+  id, block_type: 'Text', name: id, parent_id: null, page_id: 'home', properties: {}, styles: {}, classes: [],
+  event_handlers: [], bindings: {}, children: [], order: 0, archived: false, ...overrides,
+});
+
+const product = {
+  id: 'm1', name: 'Product', fields: [{ id: 'f1', name: 'title', field_type: 'string', required: true, unique: false, primary_key: false }],
+  relations: [], timestamps: false, soft_delete: false, archived: false,
+} as DataModelSchema;
+
+const project = (blocks: BlockSchema[]) => ({
+  id: 'p1', name: 'Shop', settings: {}, root_path: '', data_models: [product], apis: [], logic_flows: [], variables: [],
+  pages: [{ id: 'home', idRoot: '', name: 'Home', path: '/', is_dynamic: false, layout: 'default', meta: {}, archived: false, root_block_ids: blocks.map(b => b.id) }],
+  blocks,
+} as unknown as ProjectSchema);
+
+const page = (blocks: BlockSchema[]) =>
+  new VueGeneratorService(project(blocks)).generate().find(f => f.path === 'src/pages/Home.vue')?.content ?? '';
+
+test('VueGeneratorService writes a page as an SFC with a template', () => {
+  const content = page([block('t1', { properties: { text: 'Hello' } })]);
+  assert.match(content, /<script setup lang="ts">/);
+  assert.match(content, /<template>[\s\S]*Hello[\s\S]*<\/template>/);
+});
+
+test('VueGeneratorService reads model-bound props from the model\'s composable', () => {
+  const content = page([
+    block('t1', { bindings: { text: { type: 'model', value: 'Product.title' } } }),
+    block('t2', { properties: { text: 'Fallback' }, bindings: { text: { type: 'model', value: 'Missing.title' } } }),
+  ]);
+  assert.match(content, /import \{ useProducts \} from '\.\.\/composables\/useProducts';/);
+  assert.match(content, /const \{ data: productData \} = useProducts\(\);/);
+  assert.match(content, /\{\{ productData\[0\]\?\.title \}\}/);
+  assert.match(content, />Fallback</);
+  assert.doesNotMatch(content, /record/);
+});
+
+test('VueGeneratorService nests routes under the layout each page asks for', () => {
+  const schema = project([]);
+  schema.pages.push(
+    { ...schema.pages[0]!, id: 'login', name: 'Login', path: '/login', layout: 'auth' },
+    { ...schema.pages[0]!, id: 'landing', name: 'Landing', path: '/landing', layout: 'blank' },
+  );
+  const files = new VueGeneratorService(schema).generate();
+  const router = files.find(f => f.path === 'src/router/index.ts')?.content ?? '';
+
+  assert.match(router, /component: Layout,\n    children: \[\n      \{ path: '\/', name: 'home', component: Home \},\n    \]/);
+  assert.match(router, /component: AuthLayout,\n    children: \[\n      \{ path: '\/login', name: 'login', component: Login \},/);
+  assert.match(router, /\n  \{ path: '\/landing', name: 'landing', component: Landing \},/);
+  assert.match(files.find(f => f.path === 'src/components/Layout.vue')?.content ?? '', /\{ to: "\/", label: "Home" \}/);
+  assert.ok(files.some(f => f.path === 'src/components/AuthLayout.vue'));
+});
+
+test('VueGeneratorService renders every root block of a page at the top level', () => {
+  const content = page([
+    block('hero', { properties: { text: 'Hero' } }),
+    block('footer', { properties: { text: 'Footer' }, order: 1 }),
+  ]);
+  const template = content.slice(content.indexOf('<template>'));
+  assert.match(template, /<div class="min-h-screen bg-white">\n    <[^>]+>Hero<\/[^>]+>\n    <[^>]+>Footer<\/[^>]+>\n  <\/div>/);
+});
@@ -0,0 +1,607 @@
+import { GeneratorService } from './generator.js';
+import { DEFAULT_CLASSES, HTML_TAGS, VOID_TAGS, escapeHtml } from './htmlGenerator.js';
+import { positionClasses } from './positioning.js';
+import { normalizeClasses } from './tailwindClasses.js';
+import { ariaAttributes } from './accessibility.js';
+import { codeStyle, styleFiles } from './codeStyle.js';
+import type { ComponentProp } from './sync.js';
+import { camelCase, kebabCase, pascalCase } from '../utils/string.js';
+import { apiBaseUrl, customBlockName, modelBindingField, pageHeadMeta, pageLayout, type BlockSchema, type DataModelSchema, type GeneratedFile, type PageSchema, type ProjectSchema } from './projectSchema.js';
+
+const PROP_TS_TYPES: Record<string, string> = {
+  string: 'string',
+  number: 'number',
+  boolean: 'boolean',
+  object: 'Record<string, any>',
+  array: 'any[]',
+};
+
+/** Attributes a block property maps to on its element, beyond the text content */
+const ELEMENT_ATTRIBUTES: Record<string, string[]> = {
+  img: ['src', 'alt'],
+  a: ['href'],
+  input: ['placeholder', 'name'],
+  textarea: ['placeholder', 'name'],
+  video: ['src'],
+};
+
+const isInstance = (block: BlockSchema) => block.block_type === 'Instance' || block.block_type === 'instance';
+
+/** Names that need binding state in a component's `<script setup>` */
+interface ScriptScope {
+  globals: Set<string>;
+  components: Set<string>;
+  /** Composables (`useTodos`) read by model-bound props, with the model each one serves */
+  models: Map<string, DataModelSchema>;
+}
+
+/**
+ * Generates a Vue 3 + Vite + Tailwind frontend: one SFC per page and per component block,
+ * a `use{Model}s` composable per data model, and `src/router/index.ts` from the pages.
+ * Selected with `settings.build.frontend_framework = "vue"`.
+ */
+export class VueGeneratorService {
+  private project: ProjectSchema;
+  private blocks: Map<string, BlockSchema>;
+  /** Variable id (or name) → identifier in the generated script */
+  private variables = new Map<string, string>();
+  private globalNames = new Set<string>();
+
+  constructor(project: ProjectSchema) {
+    this.project = project;
+    this.blocks = new Map(project.blocks.map(b => [b.id, b]));
+  }
+
+  public generate(): GeneratedFile[] {
+    const files: GeneratedFile[] = [...this.genBoilerplate()];
+    const globals = this.project.variables.filter(v => v.scope !== 'page');
+    this.globalNames = new Set(globals.map(v => camelCase(v.name)));
+
+    if (globals.length) files.push(this.genVariablesStore());
+    files.push(...this.genRouter());
+
+    for (const page of this.project.pages) {
+      files.push(this.genPage(page));
+    }
+    if (this.project.pages.some(p => pageHeadMeta(p.meta, this.project.settings))) files.push(this.genPageMetaComposable());
+    for (const component of this.project.blocks.filter(b => b.block_type === 'component')) {
+      files.push(this.genComponent(component));
+    }
+
+    // Custom block types get a placeholder SFC, unless a component block already has the name
+    const componentNames = new Set(this.project.blocks.filter(b => b.block_type === 'component').map(b => pascalCase(b.name)));
+    const customNames = new Set(this.project.blocks.map(b => customBlockName(b.block_type)).filter((n): n is string => !!n).map(pascalCase));
+    for (const name of [...customNames].sort()) {
+      if (!componentNames.has(name)) files.push(this.genCustomStub(name));
+    }
+
+    // Zod schemas are framework-neutral; share them with the React target
+    const react = new GeneratorService();
+    for (const model of this.project.data_models) {
+      files.push(react.genZodSchema(model, this.project.data_models), this.genComposable(model));
+    }
+    files.push(...react.genApiClient(this.project), ...react.genSeoFiles(this.project), ...react.genDockerFiles());
+
+    return styleFiles(files, codeStyle(this.project));
+  }
+
+  private scopeVariables(pageId?: string) {
+    const visible = this.project.variables.filter(v => v.scope !== 'page' || v.page_id === pageId);
+    this.variables = new Map(visible.flatMap(v => [[v.id, camelCase(v.name)], [v.name, camelCase(v.name)]] as const));
+  }
+
+  /**
+   * Template expression for a bound property, or null to fall back to the literal. Model
+   * bindings (`Model.field`) read the field off the first record the model's composable
+   * loads; `api` bindings have no data to read and fall back too.
+   */
+  private resolveBinding(binding: { type?: string; value?: unknown } | undefined, scope: ScriptScope): string | null {
+    if (!binding || binding.value === undefined || binding.value === null || binding.value === '') return null;
+    const value = String(binding.value);
+
+    switch (binding.type) {
+      case 'variable': {
+        const name = this.variables.get(value);
+        if (!name) return null;
+        if (this.globalNames.has(name)) scope.globals.add(name);
+        return name;
+      }
+      case 'prop':
+        return camelCase(value);
+      case 'model': {
+        const bound = modelBindingField(this.project.data_models, value);
+        if (!bound) return null;
+        scope.models.set(`use${pascalCase(bound.model.name)}s`, bound.model);
+        return `${camelCase(bound.model.name)}Data[0]${bound.path.map(part => `?.${part}`).join('')}`;
+      }
+      default:
+        return null;
+    }
+  }
+
+  private children(block: BlockSchema): BlockSchema[] {
+    return block.children.map(id => this.blocks.get(id)).filter((b): b is BlockSchema => !!b);
+  }
+
+  private renderBlock(block: BlockSchema, indent: number, scope: ScriptScope): string {
+    const pad = '  '.repeat(indent);
+
+    if (isInstance(block)) {
+      const component = this.blocks.get(block.properties.component_id);
+      if (!component) return '';
+      const name = pascalCase(component.name);
+      scope.components.add(name);
+
+      const declared: ComponentProp[] = component.properties.props || [];
+      const values = block.properties.prop_values || {};
+      const attrs = declared.flatMap(p => {
+        const bound = this.resolveBinding(block.bindings[p.name], scope);
+        if (bound) return [`:${kebabCase(p.name)}="${escapeHtml(bound)}"`];
+        const literal = values[p.name];
+        if (literal === undefined) return [];
+        return typeof literal === 'string'
+          ? [`${kebabCase(p.name)}="${escapeHtml(literal)}"`]
+          : [`:${kebabCase(p.name)}="${escapeHtml(JSON.stringify(literal))}"`];
+      });
+      return `${pad}<${name}${attrs.map(a => ` ${a}`).join('')} />\n`;
+    }
+
+    const custom = customBlockName(block.block_type);
+    const tag = custom
+      ? pascalCase(custom)
+      : block.block_type === 'Heading'
+        ? `h${Math.min(Math.max(Number(block.properties.level) || 1, 1), 6)}`
+        : HTML_TAGS[block.block_type] || 'div';
+    if (custom) scope.components.add(tag);
+
+    const attrs: string[] = [`data-block="${escapeHtml(block.id)}"`];
+    const classes = normalizeClasses(DEFAULT_CLASSES[block.block_type], block.classes, positionClasses(block.properties));
+    if (classes) attrs.push(`class="${escapeHtml(classes)}"`);
+
+    const styles = Object.entries(block.styles).filter(([, v]) => v !== '' && v !== null && v !== undefined);
+    if (styles.length) attrs.push(`style="${escapeHtml(styles.map(([k, v]) => `${kebabCase(k)}: ${v}`).join('; '))}"`);
+
+    for (const key of ELEMENT_ATTRIBUTES[tag] || []) {
+      const bound = this.resolveBinding(block.bindings[key], scope);
+      if (bound) attrs.push(`:${key}="${escapeHtml(bound)}"`);
+      else if (block.properties[key] !== undefined && block.properties[key] !== '') attrs.push(`${key}="${escapeHtml(block.properties[key])}"`);
+      else if (key === 'alt' && block.properties.alt === '') attrs.push('alt=""');
+    }
+    for (const [name, value] of ariaAttributes(block.properties)) attrs.push(`${name}="${escapeHtml(value)}"`);
+
+    const open = `${pad}<${tag} ${attrs.join(' ')}`;
+    if (VOID_TAGS.has(tag)) return `${open} />\n`;
+
+    const boundText = this.resolveBinding(block.bindings.text, scope);
+    const literal = block.properties.text ?? block.properties.content;
+    const text = boundText ? `{{ ${boundText} }}` : literal !== undefined && literal !== null ? escapeHtml(literal) : '';
+    const children = this.children(block);
+    if (!children.length) return `${open}>${text}</${tag}>\n`;
+
+    let html = `${open}>\n`;
+    if (text) html += `${pad}  ${text}\n`;
+    for (const child of children) html += this.renderBlock(child, indent + 1, scope);
+    return `${html}${pad}</${tag}>\n`;
+  }
+
+  /** Imports and declarations a template's bindings need */
+  private scriptSetup(scope: ScriptScope, extra: string[] = [], importDepth = '..'): string[] {
+    const imports = [...scope.components].sort().map(c => `import ${c} from '${importDepth}/components/${c}.vue';`);
+    const lines: string[] = [];
+    if (scope.globals.size) {
+      imports.push(`import { useVariables } from '${importDepth}/stores/variables';`);
+      lines.push(`const { ${[...scope.globals].sort().join(', ')} } = useVariables();`);
+    }
+    for (const [fn, model] of [...scope.models].sort(([a], [b]) => a.localeCompare(b))) {
+      imports.push(`import { ${fn} } from '${importDepth}/composables/${fn}';`);
+      lines.push(`const { data: ${camelCase(model.name)}Data } = ${fn}();`);
+    }
+    lines.push(...extra);
+    return [...imports, ...(imports.length && lines.length ? [''] : []), ...lines];
+  }
+
+  private genPage(page: PageSchema): GeneratedFile {
+    this.scopeVariables(page.id);
+    const scope: ScriptScope = { globals: new Set(), components: new Set(), models: new Map() };
+    const declared = (page.root_block_ids ?? []).map(id => this.blocks.get(id)).filter((b): b is BlockSchema => !!b);
+    const roots = declared.length
+      ? declared
+      : this.project.blocks.filter(b => b.page_id === page.id && !b.parent_id).sort((a, b) => a.order - b.order);
+    const template = roots.map(b => this.renderBlock(b, 2, scope)).join('');
+
+    const pageVariables = this.project.variables
+      .filter(v => v.scope === 'page' && v.page_id === page.id)
+      .map(v => `const ${camelCase(v.name)} = ref<any>(${JSON.stringify(v.default_value ?? null)});`);
+    const head = pageHeadMeta(page.meta, this.project.settings);
+    if (head) pageVariables.push(`usePageMeta({ ${Object.entries(head).map(([key, value]) => `${key}: ${JSON.stringify(value)}`).join(', ')} });`);
+    const script = this.scriptSetup(scope, pageVariables);
+    if (head) script.unshift(`import { usePageMeta } from '../composables/usePageMeta';`);
+    if (pageVariables.some(line => line.includes('ref<'))) script.unshift(`import { ref } from 'vue';`);
+
+    return {
+      path: `src/pages/${pascalCase(page.name)}.vue`,
+      content: `<script setup lang="ts">
+${script.join('\n')}
+</script>
+
+<template>
+  <div class="min-h-screen bg-white">
+${template}  </div>
+</template>
+`,
+    };
+  }
+
+  /**
+   * A component block as an SFC with typed `defineProps`; blocks inside it read props
+   * through `prop` bindings
+   */
+  private genComponent(component: BlockSchema): GeneratedFile {
+    this.scopeVariables();
+    const name = pascalCase(component.name);
+    const scope: ScriptScope = { globals: new Set(), components: new Set(), models: new Map() };
+    const template = this.children(component).map(b => this.renderBlock(b, 1, scope)).join('');
+    scope.components.delete(name);
+
+    const declared: ComponentProp[] = component.properties.props || [];
+    const fields = declared.map(p => `  ${camelCase(p.name)}${p.required ? '' : '?'}: ${PROP_TS_TYPES[p.prop_type] || 'any'};`);
+    const defaults = declared
+      .filter(p => !p.required && p.default !== undefined && p.default !== null)
+      .map(p => `  ${camelCase(p.name)}: ${JSON.stringify(p.default)},`);
+
+    const props = declared.length
+      ? `export interface ${name}Props {
+${fields.join('\n')}
+}
+
+${defaults.length ? `withDefaults(defineProps<${name}Props>(), {\n${defaults.join('\n')}\n});` : `defineProps<${name}Props>();`}`
+      : '';
+    const script = this.scriptSetup(scope);
+
+    return {
+      path: `src/components/${name}.vue`,
+      content: `<script setup lang="ts">
+${[...script, ...(script.length && props ? [''] : []), ...(props ? [props] : [])].join('\n')}
+</script>
+
+<template>
+${template || '  <div></div>\n'}</template>
+`,
+    };
+  }
+
+  private genCustomStub(name: string): GeneratedFile {
+    return {
+      path: `src/components/${name}.vue`,
+      content: `<script setup lang="ts">
+// Placeholder for the custom "${name}" block; replace it with the real component
+</script>
+
+<template>
+  <div><slot /></div>
+</template>
+`,
+    };
+  }
+
+  /** Sets the document title, description and Open Graph tags while a page is mounted */
+  private genPageMetaComposable(): GeneratedFile {
+    return {
+      path: 'src/composables/usePageMeta.ts',
+      content: `import { onMounted } from 'vue';
+
+export interface PageMeta {
+  title?: string;
+  description?: string;
+  image?: string;
+}
+
+function setMeta(attribute: 'name' | 'property', key: string, content?: string) {
+  if (!content) return;
+  let tag = document.head.querySelector<HTMLMetaElement>(\`meta[\${attribute}="\${key}"]\`);
+  if (!tag) {
+    tag = document.createElement('meta');
+    tag.setAttribute(attribute, key);
+    document.head.appendChild(tag);
+  }
+  tag.content = content;
+}
+
+export function usePageMeta({ title, description, image }: PageMeta) {
+  onMounted(() => {
+    if (title) document.title = title;
+    setMeta('name', 'description', description);
+    setMeta('property', 'og:title', title);
+    setMeta('property', 'og:description', description);
+    setMeta('property', 'og:image', image);
+  });
+}
+`,
+    };
+  }
+
+  /**
+   * `use{Model}s()` mirrors the React hook: reactive data/loading/error refs, fetched on
+   * mount and validated against the Zod schema in dev builds
+   */
+  private genComposable(model: DataModelSchema): GeneratedFile {
+    const name = pascalCase(model.name);
+    const fn = `use${name}s`;
+    const options = model.soft_delete ? `options: { includeDeleted?: boolean } = {}` : '';
+    const query = model.soft_delete
+      ? `options.includeDeleted ? '?includeDeleted=true' : ''`
+      : `''`;
+
+    return {
+      path: `src/composables/${fn}.ts`,
+      content: `import { onMounted, ref } from 'vue';
+import { ${name}Schema, type ${name} } from '../schemas/${name}';
+
+const API_URL = import.meta.env.VITE_API_URL || '${apiBaseUrl(this.project)}';
+
+export function ${fn}(${options}) {
+  const data = ref<${name}[]>([]);
+  const loading = ref(true);
+  const error = ref<Error | null>(null);
+
+  async function refresh() {
+    loading.value = true;
+    try {
+      const res = await fetch(\`\${API_URL}/${kebabCase(model.name)}s\${${query}}\`);
+      if (!res.ok) throw new Error(\`Request failed with status \${res.status}\`);
+      const json = await res.json();
+      data.value = import.meta.env.DEV ? ${name}Schema.array().parse(json) : json;
+      error.value = null;
+    } catch (err) {
+      error.value = err as Error;
+    } finally {
+      loading.value = false;
+    }
+  }
+
+  onMounted(refresh);
+
+  return { data, loading, error, refresh };
+}
+`,
+    };
+  }
+
+  /** Global variables as one reactive store; persisted ones are mirrored to localStorage */
+  private genVariablesStore(): GeneratedFile {
+    const globals = this.project.variables.filter(v => v.scope !== 'page');
+    const persisted = globals.filter(v => v.persist);
+    const initial = (v: typeof globals[number]) => JSON.stringify(v.default_value ?? null);
+
+    const state = globals.map(v => v.persist
+      ? `  ${camelCase(v.name)}: load('global.${camelCase(v.name)}', ${initial(v)}),`
+      : `  ${camelCase(v.name)}: ${initial(v)} as any,`);
+
+    return {
+      path: 'src/stores/variables.ts',
+      content: `import { reactive, toRefs${persisted.length ? ', watch' : ''} } from 'vue';
+${persisted.length ? `
+function load(key: string, initial: any) {
+  const saved = localStorage.getItem(key);
+  return saved !== null ? JSON.parse(saved) : initial;
+}
+` : ''}
+const state = reactive({
+${state.join('\n')}
+});
+${persisted.map(v => `
+watch(() => state.${camelCase(v.name)}, value => localStorage.setItem('global.${camelCase(v.name)}', JSON.stringify(value)), { deep: true });`).join('')}
+
+export function useVariables() {
+  return toRefs(state);
+}
+`,
+    };
+  }
+
+  private genRouter(): GeneratedFile[] {
+    const imports = this.project.pages.map(p => `import ${pascalCase(p.name)} from '../pages/${pascalCase(p.name)}.vue';`);
+    const routePath = (page: PageSchema) => page.path || (page.name === 'Home' ? '/' : `/${page.name.toLowerCase()}`);
+    const route = (page: PageSchema, indent: string) =>
+      `${indent}{ path: '${routePath(page)}', name: '${kebabCase(page.name)}', component: ${pascalCase(page.name)} },`;
+
+    // Default pages nest under the layout with the nav bar, auth pages under the centered one;
+    // blank pages sit at the top level so nothing wraps them
+    const byLayout = (layout: string) => this.project.pages.filter(p => pageLayout({ layout: p.layout }) === layout);
+    const defaultPages = byLayout('default');
+    const authPages = byLayout('auth');
+    const nested = (layoutName: string, group: PageSchema[]) => group.length
+      ? [`  {\n    path: '/',\n    component: ${layoutName},\n    children: [\n${group.map(p => route(p, '      ')).join('\n')}\n    ],\n  },`]
+      : [];
+    const routes = [
+      ...nested('Layout', defaultPages),
+      ...nested('AuthLayout', authPages),
+      ...byLayout('blank').map(p => route(p, '  ')),
+    ];
+    const layoutImports = [
+      ...defaultPages.length ? [`import Layout from '../components/Layout.vue';`] : [],
+      ...authPages.length ? [`import AuthLayout from '../components/AuthLayout.vue';`] : [],
+    ];
+
+    const files: GeneratedFile[] = [{
+      path: 'src/router/index.ts',
+      content: `import { createRouter, createWebHistory } from 'vue-router';
+${[...layoutImports, ...imports].join('\n')}
+
+export const routes = [
+${routes.join('\n')}
+];
+
+export default createRouter({
+  history: createWebHistory(),
+  routes,
+});
+`,
+    }];
+
+    // Dynamic routes need params, so only static pages get a nav link
+    if (defaultPages.length) {
+      const links = defaultPages.filter(p => !routePath(p).includes(':')).map(p => ({ to: routePath(p), label: p.name }));
+      files.push(this.genLayout(links));
+    }
+    if (authPages.length) files.push(this.genAuthLayout());
+    return files;
+  }
+
+  /** The shell of default-layout pages: a nav bar linking the static pages above the route */
+  private genLayout(links: Array<{ to: string; label: string }>): GeneratedFile {
+    return {
+      path: 'src/components/Layout.vue',
+      content: `<script setup lang="ts">
+const links = [
+${links.map(l => `  { to: ${JSON.stringify(l.to)}, label: ${JSON.stringify(l.label)} },`).join('\n')}
+];
+</script>
+
+<template>
+  <div class="min-h-screen bg-slate-50">
+    <nav class="flex gap-4 border-b border-slate-200 bg-white px-6 py-3">
+      <RouterLink
+        v-for="link in links"
+        :key="link.to"
+        :to="link.to"
+        class="text-slate-600 hover:text-slate-900"
+        exact-active-class="font-semibold text-slate-900"
+      >
+        {{ link.label }}
+      </RouterLink>
+    </nav>
+    <RouterView />
+  </div>
+</template>
+`,
+    };
+  }
+
+  /** The shell of auth-layout pages: the page centered in a card, without the nav bar */
+  private genAuthLayout(): GeneratedFile {
+    return {
+      path: 'src/components/AuthLayout.vue',
+      content: `<template>
+  <div class="flex min-h-screen items-center justify-center bg-slate-50 p-4">
+    <div class="w-full max-w-md rounded-lg bg-white p-8 shadow">
+      <RouterView />
+    </div>
+  </div>
+</template>
+`,
+    };
+  }
+
+  private genBoilerplate(): GeneratedFile[] {
+    const packageJson = {
+      name: this.project.name.toLowerCase().replace(/\s+/g, '-'),
+      private: true,
+      version: '0.1.0',
+      type: 'module',
+      scripts: {
+        dev: 'vite',
+        build: 'vue-tsc --noEmit && vite build',
+        preview: 'vite preview',
+      },
+      dependencies: {
+        'vue': '^3.4.0',
+        'vue-router': '^4.2.5',
+        'zod': '^3.22.4',
+      },
+      devDependencies: {
+        '@vitejs/plugin-vue': '^5.0.0',
+        'autoprefixer': '^10.4.16',
+        'postcss': '^8.4.32',
+        'tailwindcss': '^3.4.0',
+        'typescript': '^5.2.2',
+        'vite': '^5.0.8',
+        'vue-tsc': '^1.8.25',
+      },
+    };
+
+    return [
+      { path: 'package.json', content: JSON.stringify(packageJson, null, 2) + '\n' },
+      {
+        path: 'vite.config.ts',
+        content: `import { defineConfig } from 'vite';
+import vue from '@vitejs/plugin-vue';
+
+export default defineConfig({
+  plugins: [vue()],
+});
+`,
+      },
+      {
+        path: 'index.html',
+        content: `<!DOCTYPE html>
+<html lang="en">
+  <head>
+    <meta charset="UTF-8" />
+    <meta name="viewport" content="width=device-width, initial-scale=1.0" />
+    <title>${escapeHtml(this.project.name)}</title>
+  </head>
+  <body>
+    <div id="app"></div>
+    <script type="module" src="/src/main.ts"></script>
+  </body>
+</html>
+`,
+      },
+      {
+        path: 'src/main.ts',
+        content: `import { createApp } from 'vue';
+import App from './App.vue';
+import router from './router';
+import './index.css';
+
+createApp(App).use(router).mount('#app');
+`,
+      },
+      {
+        path: 'src/App.vue',
+        content: `<template>
+  <RouterView />
+</template>
+`,
+      },
+      {
+        path: 'src/env.d.ts',
+        content: `/// <reference types="vite/client" />
+
+declare module '*.vue' {
+  import type { DefineComponent } from 'vue';
+  const component: DefineComponent<object, object, any>;
+  export default component;
+}
+`,
+      },
+      { path: 'src/index.css', content: `@tailwind base;\n@tailwind components;\n@tailwind utilities;\n` },
+      {
+        path: 'tailwind.config.js',
+        content: `/** @type {import('tailwindcss').Config} */
+export default {
+  content: [
+    "./index.html",
+    "./src/**/*.{vue,js,ts}",
+  ],
+  theme: {
+    extend: {},
+  },
+  plugins: [],
+};
+`,
+      },
+      {
+        path: 'postcss.config.js',
+        content: `export default {
+  plugins: {
+    tailwindcss: {},
+    autoprefixer: {},
+  },
+};
+`,
+      },
+    ];
+  }
+}
@@ -0,0 +1,38 @@
+import { test } from 'node:test';
+import assert from 'node:assert/strict';
+import { DEFAULT_WATCH_IGNORE, EventDebouncer, coalesceKind, isIgnoredPath, type FileChangeEvent } from './watcher.js';
+
+test('isIgnoredPath rejects paths under node_modules and accepts client sources', () => {
+  assert.equal(isIgnoredPath('node_modules/react/index.js'), true);
+  assert.equal(isIgnoredPath('client/node_modules/.vite/deps.js'), true);
+  assert.equal(isIgnoredPath('client\\node_modules\\react\\index.js'), true);
+  assert.equal(isIgnoredPath('client/src/App.tsx'), false);
+  assert.equal(isIgnoredPath('src/node_modules_notes.md'), false);
+});
+
+test('isIgnoredPath uses the project\'s own list when given one', () => {
+  assert.equal(isIgnoredPath('build/index.html', [...DEFAULT_WATCH_IGNORE, 'build']), true);
+  assert.equal(isIgnoredPath('node_modules/react/index.js', ['build']), false);
+});
+
+test('EventDebouncer turns three rapid modifies of one path into one event', async () => {
+  const flushed: FileChangeEvent[] = [];
+  const debouncer = new EventDebouncer(event => flushed.push(event), 20);
+  for (let i = 0; i < 3; i++) debouncer.push({ projectId: 'p1', kind: 'modify', path: 'src/App.tsx' });
+  debouncer.push({ projectId: 'p1', kind: 'create', path: 'src/New.tsx' });
+  debouncer.push({ projectId: 'p1', kind: 'modify', path: 'src/New.tsx' });
+
+  assert.deepEqual(flushed, []);
+  await new Promise(resolve => setTimeout(resolve, 60));
+  assert.deepEqual(flushed, [
+    { projectId: 'p1', kind: 'modify', path: 'src/App.tsx' },
+    { projectId: 'p1', kind: 'create', path: 'src/New.tsx' },
+  ]);
+});
+
+test('coalesceKind keeps create, delete and modify apart', () => {
+  assert.equal(coalesceKind('create', 'modify'), 'create');
+  assert.equal(coalesceKind('create', 'delete'), null);
+  assert.equal(coalesceKind('delete', 'create'), 'modify');
+  assert.equal(coalesceKind('modify', 'delete'), 'delete');
+});
@@ -0,0 +1,108 @@
+import fs from 'fs';
+import path from 'path';
+import { EventEmitter } from 'events';
+
+export const DEFAULT_WATCH_IGNORE = ['node_modules', '.git', 'dist', '.next'];
+export const DEFAULT_DEBOUNCE_MS = 300;
+
+export interface FileChangeEvent {
+  projectId: string;
+  kind: 'create' | 'modify' | 'delete';
+  path: string;
+}
+
+/**
+ * True when any segment of the (root-relative) path matches an ignored directory name
+ */
+export function isIgnoredPath(relativePath: string, ignore: string[] = DEFAULT_WATCH_IGNORE): boolean {
+  const segments = relativePath.split(/[\\/]+/).filter(Boolean);
+  return segments.some(segment => ignore.includes(segment));
+}
+
+/**
+ * Merge a burst of events for one path into the kind that describes the net change.
+ * A file created then modified is still a create; deleted then recreated is a modify.
+ * Returns null when the burst cancels out (created then deleted).
+ */
+export function coalesceKind(previous: FileChangeEvent['kind'], next: FileChangeEvent['kind']): FileChangeEvent['kind'] | null {
+  if (previous === 'create') return next === 'delete' ? null : 'create';
+  if (previous === 'delete') return next === 'delete' ? 'delete' : 'modify';
+  return next === 'delete' ? 'delete' : 'modify';
+}
+
+/**
+ * Collects raw events per path and flushes one consolidated event once a path
+ * has been quiet for `windowMs`.
+ */
+export class EventDebouncer {
+  private pending = new Map<string, { event: FileChangeEvent | null; timer: NodeJS.Timeout }>();
+  private windowMs: number;
+  private onFlush: (event: FileChangeEvent) => void;
+
+  constructor(onFlush: (event: FileChangeEvent) => void, windowMs: number = DEFAULT_DEBOUNCE_MS) {
+    this.onFlush = onFlush;
+    this.windowMs = windowMs;
+  }
+
+  public push(event: FileChangeEvent) {
+    const key = `${event.projectId}:${event.path}`;
+    const existing = this.pending.get(key);
+
+    let merged: FileChangeEvent | null = event;
+    if (existing) {
+      clearTimeout(existing.timer);
+      if (existing.event) {
+        const kind = coalesceKind(existing.event.kind, event.kind);
+        merged = kind ? { ...event, kind } : null;
+      }
+    }
+
+    const timer = setTimeout(() => {
+      this.pending.delete(key);
+      if (merged) this.onFlush(merged);
+    }, this.windowMs);
+    this.pending.set(key, { event: merged, timer });
+  }
+
+  public clear() {
+    for (const { timer } of this.pending.values()) clearTimeout(timer);
+    this.pending.clear();
+  }
+}
+
+/**
+ * Watches project roots on disk and re-emits filtered, debounced `change` events.
+ */
+class ProjectWatcherRegistry extends EventEmitter {
+  private watchers = new Map<string, { watcher: fs.FSWatcher; debouncer: EventDebouncer }>();
+
+  /** Number of project roots currently watched */
+  public get size() {
+    return this.watchers.size;
+  }
+
+  public watch(projectId: string, rootPath: string, ignore: string[] = DEFAULT_WATCH_IGNORE, debounceMs: number = DEFAULT_DEBOUNCE_MS) {
+    this.unwatch(projectId);
+
+    const debouncer = new EventDebouncer((event) => this.emit('change', event), debounceMs);
+    const watcher = fs.watch(rootPath, { recursive: true }, (eventType, filename) => {
+      if (!filename) return;
+      const relative = filename.toString();
+      if (isIgnoredPath(relative, ignore)) return;
+
+      const kind = fs.existsSync(path.join(rootPath, relative)) ? (eventType === 'rename' ? 'create' : 'modify') : 'delete';
+      debouncer.push({ projectId, kind, path: relative });
+    });
+    watcher.on('error', (error) => console.error(`[Watcher] ${projectId}:`, error));
+    this.watchers.set(projectId, { watcher, debouncer });
+  }
+
+  public unwatch(projectId: string) {
+    const entry = this.watchers.get(projectId);
+    entry?.watcher.close();
+    entry?.debouncer.clear();
+    this.watchers.delete(projectId);
+  }
+}
+
+export const projectWatcher = new ProjectWatcherRegistry();
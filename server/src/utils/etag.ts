@@ -0,0 +1,24 @@
+import crypto from 'crypto';
+import type { Request, Response } from 'express';
+
+/** Strong ETag for a response body: a quoted, truncated SHA-256 of the content */
+export function contentEtag(content: string): string {
+    return `"${crypto.createHash('sha256').update(content, 'utf-8').digest('hex').slice(0, 32)}"`;
+}
+
+/**
+ * Set `ETag` on the response and, when the request's `If-None-Match` already lists it,
+ * answer 304 with no body. Returns true when the 304 was sent and the caller should stop.
+ */
+export function sendNotModified(req: Request, res: Response, etag: string): boolean {
+    res.setHeader('ETag', etag);
+
+    const header = req.get('If-None-Match');
+    if (!header) return false;
+
+    const tags = header.split(',').map(tag => tag.trim().replace(/^W\//, ''));
+    if (!tags.includes(etag) && !tags.includes('*')) return false;
+
+    res.status(304).end();
+    return true;
+}
@@ -0,0 +1,13 @@
+import { test } from 'node:test';
+import assert from 'node:assert/strict';
+import { validateIdentifier } from './identifiers.js';
+
+test('validateIdentifier rejects reserved words and names with nothing to build an identifier from', () => {
+  assert.equal(validateIdentifier('class', 'field'), 'Field name "class" is a reserved word in TypeScript');
+  assert.equal(validateIdentifier('Default', 'model'), 'Model name "Default" is a reserved word in TypeScript');
+  assert.equal(validateIdentifier('!!!', 'page'), 'Page name "!!!" has no letters or digits to build an identifier from');
+  assert.equal(validateIdentifier('3d view', 'component'), 'Component name "3d view" must start with a letter');
+  assert.equal(validateIdentifier('  ', 'page'), 'Page name is required');
+  assert.equal(validateIdentifier('order items', 'field'), null);
+  assert.equal(validateIdentifier('Pricing & plans', 'page'), null);
+});
@@ -0,0 +1,34 @@
+import { camelCase, pascalCase } from './string.js';
+
+/** JavaScript/TypeScript reserved and strict-mode words that can't be used as identifiers */
+export const RESERVED_WORDS = new Set([
+    'break', 'case', 'catch', 'class', 'const', 'continue', 'debugger', 'default', 'delete',
+    'do', 'else', 'enum', 'export', 'extends', 'false', 'finally', 'for', 'function', 'if',
+    'import', 'in', 'instanceof', 'new', 'null', 'return', 'super', 'switch', 'this', 'throw',
+    'true', 'try', 'typeof', 'var', 'void', 'while', 'with', 'yield', 'let', 'static',
+    'implements', 'interface', 'package', 'private', 'protected', 'public', 'await',
+    'arguments', 'eval', 'undefined',
+]);
+
+export type IdentifierKind = 'model' | 'field' | 'page' | 'component';
+
+/** The identifier the generators derive from a user-facing name */
+export function toIdentifier(name: string, kind: IdentifierKind): string {
+    const cased = kind === 'field' ? camelCase(name) : pascalCase(name);
+    return cased.replace(/[^A-Za-z0-9_$]/g, '');
+}
+
+/**
+ * Check that a name still makes a usable TypeScript identifier once sanitized. Returns an
+ * error message, or null when the name is fine.
+ */
+export function validateIdentifier(name: unknown, kind: IdentifierKind): string | null {
+    const label = kind.charAt(0).toUpperCase() + kind.slice(1);
+    if (typeof name !== 'string' || !name.trim()) return `${label} name is required`;
+
+    const identifier = toIdentifier(name.trim(), kind);
+    if (!identifier) return `${label} name "${name}" has no letters or digits to build an identifier from`;
+    if (/^[0-9]/.test(identifier)) return `${label} name "${name}" must start with a letter`;
+    if (RESERVED_WORDS.has(identifier.toLowerCase())) return `${label} name "${name}" is a reserved word in TypeScript`;
+    return null;
+}
@@ -0,0 +1,20 @@
+import { test } from 'node:test';
+import assert from 'node:assert/strict';
+import fs from 'fs-extra';
+import os from 'os';
+import path from 'path';
+import { PathOutsideRootError, resolveWithinRoot } from './path.js';
+
+test('resolveWithinRoot rejects paths that leave the root and accepts nested ones', async t => {
+  const root = await fs.realpath(await fs.mkdtemp(path.join(os.tmpdir(), 'akasha-root-')));
+  t.after(() => fs.remove(root));
+  await fs.outputFile(path.join(root, 'client/src/App.tsx'), '');
+
+  await assert.rejects(resolveWithinRoot(root, '../../etc/passwd'), PathOutsideRootError);
+  await assert.rejects(resolveWithinRoot(root, path.resolve(root, '..', 'elsewhere.txt')), PathOutsideRootError);
+  await assert.rejects(resolveWithinRoot(root, '/etc/passwd'), PathOutsideRootError);
+
+  assert.equal(await resolveWithinRoot(root, 'client/src/App.tsx'), path.join(root, 'client/src/App.tsx'));
+  assert.equal(await resolveWithinRoot(root, path.join(root, 'client/src/New.tsx')), path.join(root, 'client/src/New.tsx'));
+  assert.equal(await resolveWithinRoot(root), root);
+});
@@ -0,0 +1,40 @@
+import fs from 'fs-extra';
+import path from 'path';
+
+export class PathOutsideRootError extends Error {
+    constructor(userPath: string) {
+        super(`Path "${userPath}" resolves outside the project root`);
+        this.name = 'PathOutsideRootError';
+    }
+}
+
+/**
+ * Resolve the deepest existing ancestor through realpath so symlinks can't be used to escape.
+ */
+async function canonicalize(target: string): Promise<string> {
+    let existing = target;
+    const rest: string[] = [];
+    while (!(await fs.pathExists(existing))) {
+        const parent = path.dirname(existing);
+        if (parent === existing) break;
+        rest.unshift(path.basename(existing));
+        existing = parent;
+    }
+    const real = await fs.realpath(existing).catch(() => existing);
+    return path.join(real, ...rest);
+}
+
+/**
+ * Resolve a user-supplied path (relative or absolute) and make sure it stays inside `root`.
+ * Throws PathOutsideRootError on `../` escapes, absolute paths elsewhere, or symlinks out of the root.
+ */
+export async function resolveWithinRoot(root: string, userPath: string = ''): Promise<string> {
+    const rootReal = await canonicalize(path.resolve(root));
+    const resolved = await canonicalize(path.resolve(rootReal, userPath));
+
+    const relative = path.relative(rootReal, resolved);
+    if (relative === '..' || relative.startsWith(`..${path.sep}`) || path.isAbsolute(relative)) {
+        throw new PathOutsideRootError(userPath);
+    }
+    return resolved;
+}
@@ -0,0 +1,64 @@
+import type { Response } from 'express';
+import fs from 'fs-extra';
+import path from 'path';
+import { getProjectStore } from '../services/projectStore.js';
+
+/** Where projects without a root path of their own keep their files */
+export const WORKSPACE_DIR = path.join(process.cwd(), 'projects');
+
+/** Why a command that works on the project's folder can't run; `status` is the HTTP status to answer with */
+export class ProjectRootError extends Error {
+    public readonly status: number;
+
+    constructor(message: string, status: number) {
+        super(message);
+        this.name = 'ProjectRootError';
+        this.status = status;
+    }
+}
+
+export class ProjectNotFoundError extends ProjectRootError {
+    constructor(projectId: string) {
+        super(`Project ${projectId} not found`, 404);
+        this.name = 'ProjectNotFoundError';
+    }
+}
+
+export class NoProjectRootError extends ProjectRootError {
+    constructor() {
+        super('No project root path set', 409);
+        this.name = 'NoProjectRootError';
+    }
+}
+
+export class ProjectRootMissingError extends ProjectRootError {
+    constructor(rootPath: string) {
+        super(`Project root path "${rootPath}" does not exist; create the folder or sync the project to it first`, 409);
+        this.name = 'ProjectRootMissingError';
+    }
+}
+
+/**
+ * The project's root path, for git, sync and file commands. Throws a ProjectRootError when
+ * the project id is missing or unknown, no root is set, or the folder is gone. With `create`,
+ * as when writing the project out for the first time, a missing folder is made instead.
+ */
+export async function requireRoot(projectId: unknown, options: { create?: boolean } = {}): Promise<string> {
+    if (!projectId || typeof projectId !== 'string') throw new ProjectRootError('Project ID required', 400);
+
+    const project = await getProjectStore().getProjectById(projectId);
+    if (!project) throw new ProjectNotFoundError(projectId);
+    if (!project.rootPath) throw new NoProjectRootError();
+    if (!(await fs.pathExists(project.rootPath))) {
+        if (!options.create) throw new ProjectRootMissingError(project.rootPath);
+        await fs.ensureDir(project.rootPath);
+    }
+    return project.rootPath;
+}
+
+/** Answer with the error's status when it is a ProjectRootError; returns whether it did */
+export function sendRootError(res: Response, error: unknown): boolean {
+    if (!(error instanceof ProjectRootError)) return false;
+    res.status(error.status).json({ error: error.message, code: error.name });
+    return true;
+}
@@ -0,0 +1,57 @@
+import { test } from 'node:test';
+import assert from 'node:assert/strict';
+import { EventEmitter } from 'events';
+import type { Request, Response } from 'express';
+import { REQUEST_ID_HEADER, assignRequestId } from './requestId.js';
+
+class Reply extends EventEmitter {
+  public statusCode = 200;
+  public body: any;
+  public locals: Record<string, any> = {};
+  public headers: Record<string, string> = {};
+
+  setHeader(name: string, value: string) {
+    this.headers[name] = value;
+    return this;
+  }
+
+  status(code: number) {
+    this.statusCode = code;
+    return this;
+  }
+
+  json(body: any) {
+    this.body = body;
+    return this;
+  }
+}
+
+function run(incoming?: string) {
+  const req = { method: 'GET', originalUrl: '/api/projects', get: () => incoming } as unknown as Request;
+  const res = new Reply();
+  let called = false;
+  assignRequestId(req, res as unknown as Response, () => { called = true; });
+  assert.ok(called);
+  return res;
+}
+
+test('assignRequestId sets an X-Request-Id header, honouring a usable incoming one', () => {
+  const fresh = run();
+  assert.match(fresh.headers[REQUEST_ID_HEADER]!, /^[0-9a-f-]{36}$/);
+  assert.equal(fresh.locals.requestId, fresh.headers[REQUEST_ID_HEADER]);
+
+  assert.equal(run('client-42').headers[REQUEST_ID_HEADER], 'client-42');
+  assert.notEqual(run('bad id\nwith newline').headers[REQUEST_ID_HEADER], 'bad id\nwith newline');
+});
+
+test('assignRequestId adds the id to JSON error bodies only', t => {
+  t.mock.method(console, 'log', () => {});
+  const res = run('req-1');
+
+  res.json({ ok: true });
+  assert.deepEqual(res.body, { ok: true });
+
+  res.status(404).json({ error: 'Not found' });
+  assert.deepEqual(res.body, { error: 'Not found', request_id: 'req-1' });
+  res.emit('finish');
+});
@@ -0,0 +1,37 @@
+import { randomUUID } from 'crypto';
+import type { NextFunction, Request, Response } from 'express';
+
+export const REQUEST_ID_HEADER = 'X-Request-Id';
+
+/** Incoming ids are echoed back and logged, so only short token-like values are honored */
+const VALID_REQUEST_ID = /^[A-Za-z0-9._:-]{1,128}$/;
+
+/**
+ * Express middleware giving each request a correlation id: the client's `X-Request-Id` when it
+ * sends a usable one, otherwise a fresh UUID. The id is echoed in the response header, added
+ * as `request_id` to JSON error bodies and logged with the request's outcome.
+ */
+export function assignRequestId(req: Request, res: Response, next: NextFunction) {
+    const incoming = req.get(REQUEST_ID_HEADER);
+    const requestId = incoming && VALID_REQUEST_ID.test(incoming) ? incoming : randomUUID();
+    const started = Date.now();
+
+    res.locals.requestId = requestId;
+    res.setHeader(REQUEST_ID_HEADER, requestId);
+
+    const json = res.json.bind(res);
+    res.json = (body: any) => {
+        if (res.statusCode >= 400 && body && typeof body === 'object' && !Array.isArray(body)) {
+            return json({ ...body, request_id: requestId });
+        }
+        return json(body);
+    };
+
+    res.on('finish', () => {
+        const line = `[Request ${requestId}] ${req.method} ${req.originalUrl} ${res.statusCode} ${Date.now() - started}ms`;
+        if (res.statusCode >= 500) console.error(line);
+        else console.log(line);
+    });
+
+    next();
+}
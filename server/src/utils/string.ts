@@ -10,3 +10,15 @@ export function camelCase(str: string): string {
         .replace(/[^a-zA-Z0-9]+(.)/g, (_m, chr) => chr.toUpperCase())
         .replace(/^[A-Z]/, (c) => c.toLowerCase());
 }
+
+export function kebabCase(str: string): string {
+    return str
+        .replace(/([a-z0-9])([A-Z])/g, '$1-$2')
+        .replace(/[^a-zA-Z0-9]+/g, '-')
+        .replace(/^-+|-+$/g, '')
+        .toLowerCase();
+}
+
+export function snakeCase(str: string): string {
+    return kebabCase(str).replace(/-/g, '_');
+}
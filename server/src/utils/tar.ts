@@ -0,0 +1,65 @@
+import zlib from 'zlib';
+import type { ZipEntry } from './zip.js';
+
+const BLOCK_SIZE = 512;
+const NAME_LENGTH = 100;
+
+function octal(value: number, width: number): string {
+    return value.toString(8).padStart(width - 1, '0') + '\0';
+}
+
+function header(name: Buffer, size: number, type: '0' | 'x'): Buffer {
+    const block = Buffer.alloc(BLOCK_SIZE);
+    name.copy(block, 0, 0, NAME_LENGTH);
+    block.write(octal(0o644, 8), 100, 'ascii');
+    block.write(octal(0, 8), 108, 'ascii');
+    block.write(octal(0, 8), 116, 'ascii');
+    block.write(octal(size, 12), 124, 'ascii');
+    // mtime 0, like the ZIP's fixed DOS epoch, keeps archives byte-for-byte reproducible
+    block.write(octal(0, 12), 136, 'ascii');
+    block.write(type, 156, 'ascii');
+    block.write('ustar\0', 257, 'ascii');
+    block.write('00', 263, 'ascii');
+
+    // The checksum is taken with its own field filled with spaces
+    block.fill(' ', 148, 156);
+    const checksum = block.reduce((sum, byte) => sum + byte, 0);
+    block.write(checksum.toString(8).padStart(6, '0') + '\0 ', 148, 'ascii');
+    return block;
+}
+
+function padded(data: Buffer): Buffer[] {
+    const remainder = data.length % BLOCK_SIZE;
+    return remainder ? [data, Buffer.alloc(BLOCK_SIZE - remainder)] : [data];
+}
+
+/** A PAX `path=` record; its length prefix counts its own digits */
+function paxPath(path: string): Buffer {
+    const body = ` path=${path}\n`;
+    let length = Buffer.byteLength(body) + 1;
+    while (String(length).length + Buffer.byteLength(body) !== length) length += 1;
+    return Buffer.from(`${length}${body}`, 'utf-8');
+}
+
+/**
+ * Build a gzip-compressed ustar archive in memory, entries in the order given. Paths longer
+ * than the 100-byte name field are carried in a PAX extended header.
+ */
+export function buildTarGz(entries: ZipEntry[]): Buffer {
+    const chunks: Buffer[] = [];
+
+    for (const entry of entries) {
+        const name = Buffer.from(entry.path, 'utf-8');
+        const data = Buffer.isBuffer(entry.content) ? entry.content : Buffer.from(entry.content, 'utf-8');
+
+        if (name.length > NAME_LENGTH) {
+            const pax = paxPath(entry.path);
+            chunks.push(header(Buffer.from('PaxHeader', 'ascii'), pax.length, 'x'), ...padded(pax));
+        }
+        chunks.push(header(name, data.length, '0'), ...padded(data));
+    }
+
+    // Two zero blocks mark the end of the archive
+    chunks.push(Buffer.alloc(BLOCK_SIZE * 2));
+    return zlib.gzipSync(Buffer.concat(chunks));
+}
@@ -0,0 +1,60 @@
+import { test } from 'node:test';
+import assert from 'node:assert/strict';
+import { EventEmitter } from 'events';
+import type { Duplex } from 'stream';
+import { WebSocketConnection } from './websocket.js';
+
+/** A socket that records what the connection writes */
+class FakeSocket extends EventEmitter {
+  public written: Buffer[] = [];
+  public ended = false;
+
+  write(data: Buffer) {
+    this.written.push(data);
+    return true;
+  }
+
+  end() {
+    this.ended = true;
+  }
+}
+
+const frame = (opcode: number, payload: Buffer, mask: boolean, declaredLength = payload.length) => {
+  const header = declaredLength < 126
+    ? Buffer.from([0x80 | opcode, (mask ? 0x80 : 0) | declaredLength])
+    : Buffer.from([0x80 | opcode, (mask ? 0x80 : 0) | 127, 0, 0, 0, 0, ...Buffer.from(declaredLength.toString(16).padStart(8, '0'), 'hex')]);
+  if (!mask) return Buffer.concat([header, payload]);
+  const key = Buffer.from([1, 2, 3, 4]);
+  return Buffer.concat([header, key, payload.map((b, i) => b ^ key[i % 4]!)]);
+};
+
+/** Status code of the close frame the connection sent, if any */
+const closeCode = (socket: FakeSocket) => {
+  const close = socket.written.find(f => (f[0]! & 0x0f) === 0x8);
+  return close && close.length >= 4 ? close.readUInt16BE(2) : undefined;
+};
+
+test('WebSocketConnection answers masked pings', () => {
+  const socket = new FakeSocket();
+  const connection = new WebSocketConnection(socket as unknown as Duplex, () => {});
+  socket.emit('data', frame(0x9, Buffer.from('hi'), true));
+  assert.deepEqual(socket.written, [Buffer.from([0x8a, 2, ...Buffer.from('hi')])]);
+  assert.ok(connection.isOpen);
+});
+
+test('WebSocketConnection closes with 1002 on an unmasked frame', () => {
+  const socket = new FakeSocket();
+  let closed = false;
+  const connection = new WebSocketConnection(socket as unknown as Duplex, () => { closed = true; });
+  socket.emit('data', frame(0x9, Buffer.from('hi'), false));
+  assert.equal(closeCode(socket), 1002);
+  assert.ok(socket.ended && closed && !connection.isOpen);
+});
+
+test('WebSocketConnection closes with 1009 before buffering an oversized frame', () => {
+  const socket = new FakeSocket();
+  const connection = new WebSocketConnection(socket as unknown as Duplex, () => {});
+  socket.emit('data', frame(0x1, Buffer.alloc(0), true, 2 ** 31).subarray(0, 10));
+  assert.equal(closeCode(socket), 1009);
+  assert.ok(!connection.isOpen);
+});
@@ -0,0 +1,149 @@
+import crypto from 'crypto';
+import type { IncomingMessage } from 'http';
+import type { Duplex } from 'stream';
+
+const HANDSHAKE_GUID = '258EAFA5-E914-47DA-95CA-C5AB0DC85B11';
+
+const OPCODE_TEXT = 0x1;
+const OPCODE_CLOSE = 0x8;
+const OPCODE_PING = 0x9;
+const OPCODE_PONG = 0xa;
+
+const CLOSE_PROTOCOL_ERROR = 1002;
+const CLOSE_TOO_BIG = 1009;
+
+/** Clients only send control frames here, so anything bigger is refused before it is buffered */
+const MAX_FRAME_BYTES = 64 * 1024;
+
+function encodeFrame(opcode: number, payload: Buffer): Buffer {
+    const length = payload.length;
+    let header: Buffer;
+    if (length < 126) {
+        header = Buffer.from([0x80 | opcode, length]);
+    } else if (length < 0x10000) {
+        header = Buffer.alloc(4);
+        header[0] = 0x80 | opcode;
+        header[1] = 126;
+        header.writeUInt16BE(length, 2);
+    } else {
+        header = Buffer.alloc(10);
+        header[0] = 0x80 | opcode;
+        header[1] = 127;
+        header.writeBigUInt64BE(BigInt(length), 2);
+    }
+    return Buffer.concat([header, payload]);
+}
+
+/**
+ * Minimal server side of RFC 6455: text frames out, control frames (ping/close) in.
+ * Enough for pushing notifications; client data frames are read and dropped. Unmasked
+ * client frames close the connection with 1002 and frames over MAX_FRAME_BYTES with 1009.
+ */
+export class WebSocketConnection {
+    private socket: Duplex;
+    private buffer = Buffer.alloc(0);
+    private closed = false;
+    private onClose: () => void;
+
+    constructor(socket: Duplex, onClose: () => void) {
+        this.socket = socket;
+        this.onClose = onClose;
+        socket.on('data', (chunk: Buffer) => this.read(chunk));
+        socket.on('close', () => this.finish());
+        socket.on('error', () => this.finish());
+    }
+
+    public get isOpen() {
+        return !this.closed;
+    }
+
+    public send(text: string) {
+        if (this.closed) return;
+        this.socket.write(encodeFrame(OPCODE_TEXT, Buffer.from(text, 'utf-8')));
+    }
+
+    public close(code?: number) {
+        if (this.closed) return;
+        const payload = Buffer.alloc(code ? 2 : 0);
+        if (code) payload.writeUInt16BE(code, 0);
+        this.socket.write(encodeFrame(OPCODE_CLOSE, payload));
+        this.socket.end();
+        this.finish();
+    }
+
+    private finish() {
+        if (this.closed) return;
+        this.closed = true;
+        this.onClose();
+    }
+
+    private read(chunk: Buffer) {
+        if (this.closed) return;
+        this.buffer = Buffer.concat([this.buffer, chunk]);
+
+        while (this.buffer.length >= 2) {
+            const opcode = this.buffer[0] & 0x0f;
+            const masked = (this.buffer[1] & 0x80) !== 0;
+            let length = this.buffer[1] & 0x7f;
+            let offset = 2;
+
+            if (length === 126) {
+                if (this.buffer.length < 4) return;
+                length = this.buffer.readUInt16BE(2);
+                offset = 4;
+            } else if (length === 127) {
+                if (this.buffer.length < 10) return;
+                length = Number(this.buffer.readBigUInt64BE(2));
+                offset = 10;
+            }
+
+            // Clients must mask every frame (RFC 6455 §5.1)
+            if (!masked) {
+                this.close(CLOSE_PROTOCOL_ERROR);
+                return;
+            }
+            if (length > MAX_FRAME_BYTES) {
+                this.close(CLOSE_TOO_BIG);
+                return;
+            }
+
+            const maskOffset = offset;
+            offset += 4;
+            if (this.buffer.length < offset + length) return;
+
+            const payload = Buffer.from(this.buffer.subarray(offset, offset + length));
+            for (let i = 0; i < payload.length; i++) payload[i] ^= this.buffer[maskOffset + (i % 4)];
+            this.buffer = this.buffer.subarray(offset + length);
+
+            if (opcode === OPCODE_CLOSE) {
+                this.close();
+                return;
+            }
+            if (opcode === OPCODE_PING) this.socket.write(encodeFrame(OPCODE_PONG, payload));
+        }
+    }
+}
+
+/**
+ * Complete the upgrade handshake. Returns null (after rejecting the socket) when the
+ * request isn't a valid WebSocket upgrade.
+ */
+export function acceptWebSocket(req: IncomingMessage, socket: Duplex, onClose: () => void): WebSocketConnection | null {
+    const key = req.headers['sec-websocket-key'];
+    if (typeof key !== 'string' || req.headers.upgrade?.toLowerCase() !== 'websocket') {
+        socket.end('HTTP/1.1 400 Bad Request\r\n\r\n');
+        return null;
+    }
+
+    const accept = crypto.createHash('sha1').update(key + HANDSHAKE_GUID).digest('base64');
+    socket.write([
+        'HTTP/1.1 101 Switching Protocols',
+        'Upgrade: websocket',
+        'Connection: Upgrade',
+        `Sec-WebSocket-Accept: ${accept}`,
+        '',
+        '',
+    ].join('\r\n'));
+
+    return new WebSocketConnection(socket, onClose);
+}
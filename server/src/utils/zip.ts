@@ -0,0 +1,68 @@
+import zlib from 'zlib';
+
+export interface ZipEntry {
+    path: string;
+    content: string | Buffer;
+}
+
+// 1980-01-01 00:00, the DOS epoch; a fixed timestamp keeps archives byte-for-byte reproducible
+const DOS_TIME = 0;
+const DOS_DATE = (1 << 5) | 1;
+const UTF8_FLAG = 0x0800;
+
+/**
+ * Build a deflate-compressed ZIP archive in memory. Entries are written in the order given.
+ */
+export function buildZip(entries: ZipEntry[]): Buffer {
+    const chunks: Buffer[] = [];
+    const central: Buffer[] = [];
+    let offset = 0;
+
+    for (const entry of entries) {
+        const name = Buffer.from(entry.path, 'utf-8');
+        const data = Buffer.isBuffer(entry.content) ? entry.content : Buffer.from(entry.content, 'utf-8');
+        const compressed = zlib.deflateRawSync(data);
+        const crc = zlib.crc32(data);
+
+        const local = Buffer.alloc(30);
+        local.writeUInt32LE(0x04034b50, 0);
+        local.writeUInt16LE(20, 4);
+        local.writeUInt16LE(UTF8_FLAG, 6);
+        local.writeUInt16LE(8, 8);
+        local.writeUInt16LE(DOS_TIME, 10);
+        local.writeUInt16LE(DOS_DATE, 12);
+        local.writeUInt32LE(crc, 14);
+        local.writeUInt32LE(compressed.length, 18);
+        local.writeUInt32LE(data.length, 22);
+        local.writeUInt16LE(name.length, 26);
+        local.writeUInt16LE(0, 28);
+
+        const header = Buffer.alloc(46);
+        header.writeUInt32LE(0x02014b50, 0);
+        header.writeUInt16LE(20, 4);
+        header.writeUInt16LE(20, 6);
+        header.writeUInt16LE(UTF8_FLAG, 8);
+        header.writeUInt16LE(8, 10);
+        header.writeUInt16LE(DOS_TIME, 12);
+        header.writeUInt16LE(DOS_DATE, 14);
+        header.writeUInt32LE(crc, 16);
+        header.writeUInt32LE(compressed.length, 20);
+        header.writeUInt32LE(data.length, 24);
+        header.writeUInt16LE(name.length, 28);
+        header.writeUInt32LE(offset, 42);
+
+        chunks.push(local, name, compressed);
+        central.push(header, name);
+        offset += local.length + name.length + compressed.length;
+    }
+
+    const centralSize = central.reduce((size, chunk) => size + chunk.length, 0);
+    const end = Buffer.alloc(22);
+    end.writeUInt32LE(0x06054b50, 0);
+    end.writeUInt16LE(entries.length, 8);
+    end.writeUInt16LE(entries.length, 10);
+    end.writeUInt32LE(centralSize, 12);
+    end.writeUInt32LE(offset, 16);
+
+    return Buffer.concat([...chunks, ...central, end]);
+}